@@ -24,6 +24,7 @@ impl GameComponent {
 		}
 	}
 
+	#[allow(clippy::collapsible_if)]
 	fn handle_gamepad(&mut self, event: &CustomInputEvent) {
 		match event.event_type.as_str() {
 			"button_press" => {
@@ -2,7 +2,7 @@
 //!
 //! Demonstrates the layout system with relative units and positioning.
 
-use engage_ux_core::layout::{Layout, Size, Unit};
+use engage_ux_core::layout::{Layout, PositionMode, Size, Unit};
 use engage_ux_oal::{Monitor, MonitorConfiguration, MonitorLayoutMode};
 
 fn main() {
@@ -102,7 +102,8 @@ fn main() {
 		.with_left(Unit::pixels(20.0))
 		.with_right(Unit::pixels(20.0))
 		.with_top(Unit::pixels(30.0))
-		.with_bottom(Unit::pixels(30.0));
+		.with_bottom(Unit::pixels(30.0))
+		.with_position_mode(PositionMode::Absolute);
 
 	println!("   Edge-Based Layout:");
 	println!("   - left=20px, right=20px, top=30px, bottom=30px");
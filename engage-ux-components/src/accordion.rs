@@ -1,9 +1,15 @@
 //! Accordion component for collapsible content panels
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	Paint, clip, fill_rect, inset, restore_clip, stroke_rect, text_color, text_in, text_lines,
+};
+
 /// Accordion panel
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccordionPanel {
@@ -154,6 +160,81 @@ impl Component for Accordion {
 	}
 }
 
+impl Paint for Accordion {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let header_height = font_size * 2.5;
+		let line_height = font_size * theme.typography.line_height;
+		let padding = theme.spacing.medium;
+		let mut commands = vec![clip(bounds)];
+		let mut y = bounds.y;
+
+		for panel in &self.panels {
+			let header = Rect::new(bounds.x, y, bounds.width, header_height);
+			let header_color = if panel.expanded {
+				&self.active_color
+			} else {
+				&self.background_color
+			};
+			let label_color = text_color(
+				&self.color,
+				self.properties.enabled && !panel.disabled,
+				theme,
+			);
+			let label = inset(header, padding);
+			let label = Rect::new(label.x, header.y, label.width, header.height);
+			let indicator = if panel.expanded { "-" } else { "+" };
+
+			commands.push(fill_rect(header, header_color));
+			commands.push(stroke_rect(header, &self.border_color, theme.borders.width));
+			commands.push(text_in(
+				label,
+				panel.title.clone(),
+				font_size,
+				label_color,
+				TextAlign::Left,
+			));
+			commands.push(text_in(
+				label,
+				indicator,
+				font_size,
+				label_color,
+				TextAlign::Right,
+			));
+			y += header_height;
+
+			if panel.expanded {
+				let line_count = panel.content.lines().count().max(1) as f32;
+				let content = Rect::new(
+					bounds.x,
+					y,
+					bounds.width,
+					line_count * line_height + padding * 2.0,
+				);
+				commands.push(fill_rect(content, &theme.colors.surface));
+				commands.push(stroke_rect(
+					content,
+					&self.border_color,
+					theme.borders.width,
+				));
+				commands.extend(text_lines(
+					inset(content, padding),
+					&panel.content,
+					font_size,
+					line_height,
+					&self.color,
+					TextAlign::Left,
+				));
+				y += content.height;
+			}
+		}
+
+		commands.push(restore_clip());
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
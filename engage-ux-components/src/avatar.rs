@@ -1,8 +1,15 @@
 //! Avatar component for user profiles
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_core::rendering::SvgDocument;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::paint::{Paint, circle, fill_rect, stroke_rect, text_in};
 
 /// Avatar shape
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -47,6 +54,9 @@ pub struct Avatar {
 	background_color: Color,
 	border_color: Option<Color>,
 	border_width: f32,
+	/// Vector artwork for the icon, tinted with the text color
+	#[serde(skip)]
+	icon_svg: Option<Arc<SvgDocument>>,
 }
 
 impl Avatar {
@@ -64,6 +74,7 @@ impl Avatar {
 			background_color: Color::from_hex("#757575").unwrap(),
 			border_color: None,
 			border_width: 0.0,
+			icon_svg: None,
 		}
 	}
 
@@ -118,6 +129,16 @@ impl Avatar {
 		self.icon = icon;
 	}
 
+	/// Get the icon's vector artwork, if set
+	pub fn icon_svg(&self) -> Option<&Arc<SvgDocument>> {
+		self.icon_svg.as_ref()
+	}
+
+	/// Set the icon's vector artwork, drawn when there are no initials
+	pub fn set_icon_svg(&mut self, svg: Option<Arc<SvgDocument>>) {
+		self.icon_svg = svg;
+	}
+
 	/// Get shape
 	pub fn shape(&self) -> AvatarShape {
 		self.shape
@@ -191,6 +212,84 @@ impl Component for Avatar {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		let diameter = self.size.pixels() as f32;
+		(diameter, diameter)
+	}
+}
+
+impl Paint for Avatar {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let diameter = (self.size.pixels() as f32)
+			.min(bounds.width)
+			.min(bounds.height);
+		let rect = Rect::new(
+			bounds.x + (bounds.width - diameter) / 2.0,
+			bounds.y + (bounds.height - diameter) / 2.0,
+			diameter,
+			diameter,
+		);
+		let radius = diameter / 2.0;
+		let (cx, cy) = (rect.x + radius, rect.y + radius);
+		let mut commands = Vec::new();
+
+		match self.shape {
+			AvatarShape::Circle => {
+				commands.push(circle(cx, cy, radius, &self.background_color, true));
+				if let Some(border) = &self.border_color
+					&& self.border_width > 0.0
+				{
+					commands.push(circle(cx, cy, radius, border, false));
+				}
+			}
+			AvatarShape::Square | AvatarShape::Rounded => {
+				commands.push(fill_rect(rect, &self.background_color));
+				if let Some(border) = &self.border_color
+					&& self.border_width > 0.0
+				{
+					commands.push(stroke_rect(rect, border, self.border_width));
+				}
+			}
+		}
+
+		if self.initials.is_none()
+			&& let Some(svg) = &self.icon_svg
+		{
+			let inset = diameter * 0.2;
+			commands.push(RenderCommand::Svg {
+				document: Arc::clone(svg),
+				rect: Rect::new(
+					rect.x + inset,
+					rect.y + inset,
+					diameter - inset * 2.0,
+					diameter - inset * 2.0,
+				)
+				.into(),
+				tint: Some(self.color.clone().into()),
+			});
+			return commands;
+		}
+
+		let label = self.initials.clone().or_else(|| {
+			self.alt_text
+				.chars()
+				.next()
+				.map(|c| c.to_uppercase().to_string())
+		});
+		if let Some(label) = label {
+			commands.push(text_in(
+				rect,
+				label,
+				diameter * 0.4,
+				&self.color,
+				TextAlign::Center,
+			));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -223,6 +322,31 @@ mod tests {
 		assert_eq!(avatar.icon(), Some("user"));
 	}
 
+	#[test]
+	fn test_avatar_icon_svg() {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24">
+			<circle cx="12" cy="12" r="10"/>
+		</svg>"#;
+		let mut avatar = Avatar::with_icon(1, "user");
+		avatar.properties_mut().bounds = Rect::new(0.0, 0.0, 48.0, 48.0);
+		avatar.set_icon_svg(Some(Arc::new(
+			engage_ux_core::rendering::SvgParser::new()
+				.parse(svg)
+				.unwrap(),
+		)));
+
+		let commands = avatar.paint(&Theme::default());
+		match commands.last() {
+			Some(RenderCommand::Svg { rect, .. }) => assert!(rect.width < 48.0),
+			command => panic!("unexpected command {command:?}"),
+		}
+
+		// Initials take precedence over the icon
+		avatar.set_initials(Some("JD".to_string()));
+		let commands = avatar.paint(&Theme::default());
+		assert!(matches!(commands.last(), Some(RenderCommand::Text { .. })));
+	}
+
 	#[test]
 	fn test_avatar_shape() {
 		let mut avatar = Avatar::new(1);
@@ -1,9 +1,14 @@
 //! Badge component for notifications and status indicators
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, fill_rect, measure_text, text_in};
+
 /// Badge variant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BadgeVariant {
@@ -191,6 +196,74 @@ impl Component for Badge {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Content with half its height of space at each end, or a dot half
+	/// the font size across
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		if self.dot {
+			return (self.font_size / 2.0, self.font_size / 2.0);
+		}
+		let (width, height) = measure_text(fonts, &self.content, self.font_size);
+		(width + height, height)
+	}
+}
+
+impl Paint for Badge {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let radius = bounds.width.min(bounds.height) / 2.0;
+		let (cx, cy) = (
+			bounds.x + bounds.width / 2.0,
+			bounds.y + bounds.height / 2.0,
+		);
+
+		if self.dot {
+			return vec![circle(cx, cy, radius, &self.background_color, true)];
+		}
+
+		let mut commands = Vec::new();
+		match self.shape {
+			BadgeShape::Circle => {
+				commands.push(circle(cx, cy, radius, &self.background_color, true));
+			}
+			BadgeShape::Pill => {
+				// Capsule: end caps plus the body between them
+				let body = Rect::new(
+					bounds.x + radius,
+					bounds.y,
+					(bounds.width - radius * 2.0).max(0.0),
+					bounds.height,
+				);
+				commands.push(circle(
+					bounds.x + radius,
+					cy,
+					radius,
+					&self.background_color,
+					true,
+				));
+				commands.push(circle(
+					bounds.x + bounds.width - radius,
+					cy,
+					radius,
+					&self.background_color,
+					true,
+				));
+				commands.push(fill_rect(body, &self.background_color));
+			}
+			BadgeShape::Rounded | BadgeShape::Square => {
+				commands.push(fill_rect(bounds, &self.background_color));
+			}
+		}
+		commands.push(text_in(
+			bounds,
+			self.content.clone(),
+			self.font_size,
+			&self.color,
+			TextAlign::Center,
+		));
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -1,10 +1,14 @@
 //! Banner component for important messages
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, text_in};
+
 /// Banner variant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BannerVariant {
@@ -253,6 +257,76 @@ impl Component for Banner {
 	}
 }
 
+impl Paint for Banner {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let content = inset(bounds, padding);
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			fill_rect(
+				Rect::new(bounds.x, bounds.y, theme.spacing.small, bounds.height),
+				&self.border_color,
+			),
+		];
+
+		match &self.title {
+			Some(title) => {
+				let half = content.height / 2.0;
+				commands.push(text_in(
+					Rect::new(content.x, content.y, content.width, half),
+					title.clone(),
+					font_size,
+					&self.color,
+					TextAlign::Left,
+				));
+				commands.push(text_in(
+					Rect::new(content.x, content.y + half, content.width, half),
+					self.message.clone(),
+					theme.typography.font_size_small,
+					&self.color,
+					TextAlign::Left,
+				));
+			}
+			None => commands.push(text_in(
+				content,
+				self.message.clone(),
+				font_size,
+				&self.color,
+				TextAlign::Left,
+			)),
+		}
+
+		let mut right = content;
+		if self.dismissible {
+			commands.push(text_in(
+				right,
+				"×",
+				font_size,
+				&self.color,
+				TextAlign::Right,
+			));
+			right.width -= font_size + padding;
+		}
+		if let Some(action) = &self.action_text {
+			commands.push(text_in(
+				right,
+				action.clone(),
+				font_size,
+				&self.border_color,
+				TextAlign::Right,
+			));
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,10 +1,14 @@
 //! Breadcrumb navigation component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, text_color, text_in, text_width};
+
 /// Breadcrumb item
 #[derive(Clone, Serialize, Deserialize)]
 pub struct BreadcrumbItem {
@@ -171,6 +175,61 @@ impl Component for Breadcrumb {
 	}
 }
 
+impl Paint for Breadcrumb {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let gap = theme.spacing.small;
+
+		// Collapse the middle of long trails to "first … last items"
+		let labels: Vec<&str> = match self.max_items {
+			Some(max) if max >= 2 && self.items.len() > max => {
+				let mut labels = vec![self.items[0].label.as_str(), "…"];
+				labels.extend(
+					self.items[self.items.len() - (max - 1)..]
+						.iter()
+						.map(|item| item.label.as_str()),
+				);
+				labels
+			}
+			_ => self.items.iter().map(|item| item.label.as_str()).collect(),
+		};
+
+		let mut commands = Vec::new();
+		let mut x = bounds.x;
+		let last = labels.len().saturating_sub(1);
+		for (index, label) in labels.iter().enumerate() {
+			let color = if index == last {
+				&self.active_color
+			} else {
+				&self.color
+			};
+			let width = text_width(theme, label, self.font_size);
+			commands.push(text_in(
+				Rect::new(x, bounds.y, width, bounds.height),
+				*label,
+				self.font_size,
+				text_color(color, self.properties.enabled, theme),
+				TextAlign::Left,
+			));
+			x += width + gap;
+
+			if index != last {
+				let width = text_width(theme, &self.separator, self.font_size);
+				commands.push(text_in(
+					Rect::new(x, bounds.y, width, bounds.height),
+					self.separator.clone(),
+					self.font_size,
+					&self.separator_color,
+					TextAlign::Left,
+				));
+				x += width + gap;
+			}
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
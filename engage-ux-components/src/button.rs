@@ -3,9 +3,20 @@
 use engage_ux_core::color::Color;
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::input::keyboard::KeyEventType;
+use engage_ux_core::input::mouse::MouseEventType;
+use engage_ux_core::input::{InputHandler, KeyCode, KeyboardEvent, MouseButton, MouseEvent};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::paint::{
+	MEASURE_FONT_SIZE, MEASURE_MEDIUM_SPACING, MEASURE_SMALL_SPACING, Paint, fill_rect,
+	measure_text, stroke_rect, text_in,
+};
+
 /// Button variants
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ButtonVariant {
@@ -108,6 +119,100 @@ impl Component for Button {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Label at the theme's base font size, padded on every side
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let (width, height) = measure_text(fonts, &self.text, MEASURE_FONT_SIZE);
+		(
+			width + MEASURE_MEDIUM_SPACING * 2.0,
+			height + MEASURE_SMALL_SPACING * 2.0,
+		)
+	}
+}
+
+/// Releasing the left mouse button over an enabled button, or pressing
+/// Enter or Space while it has the keyboard, clicks it
+///
+/// The click handler's event carries the input that activated the button.
+impl InputHandler for Button {
+	fn handle_keyboard(&mut self, event: &KeyboardEvent) -> bool {
+		let activates = event.event_type == KeyEventType::Down
+			&& matches!(event.key_code, KeyCode::Enter | KeyCode::Space);
+		if !activates || !self.properties.enabled {
+			return false;
+		}
+		self.handle_click(&Event::from_input(self.properties.id, event.clone()));
+		true
+	}
+
+	fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+		let released =
+			event.event_type == MouseEventType::ButtonUp && event.button == Some(MouseButton::Left);
+		if !released
+			|| !self.properties.enabled
+			|| !self.properties.bounds.contains_point(event.x, event.y)
+		{
+			return false;
+		}
+		self.handle_click(&Event::from_input(self.properties.id, event.clone()));
+		true
+	}
+}
+
+impl Paint for Button {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let mut commands = Vec::new();
+
+		if !self.properties.enabled {
+			if self.variant != ButtonVariant::Text {
+				commands.push(fill_rect(bounds, &theme.colors.surface));
+				commands.push(stroke_rect(
+					bounds,
+					&theme.colors.text_disabled,
+					theme.borders.width,
+				));
+			}
+			commands.push(text_in(
+				bounds,
+				self.text.clone(),
+				font_size,
+				&theme.colors.text_disabled,
+				TextAlign::Center,
+			));
+			return commands;
+		}
+
+		let text_color = match self.variant {
+			ButtonVariant::Primary => {
+				commands.push(fill_rect(bounds, &self.background_color));
+				&self.color
+			}
+			ButtonVariant::Secondary => {
+				commands.push(fill_rect(bounds, &theme.colors.secondary));
+				&self.color
+			}
+			ButtonVariant::Outlined => {
+				commands.push(stroke_rect(
+					bounds,
+					&self.background_color,
+					theme.borders.width,
+				));
+				&self.background_color
+			}
+			ButtonVariant::Text => &self.background_color,
+		};
+		commands.push(text_in(
+			bounds,
+			self.text.clone(),
+			font_size,
+			text_color,
+			TextAlign::Center,
+		));
+
+		commands
+	}
 }
 
 // Manual Debug implementation due to callback
@@ -127,7 +232,11 @@ impl std::fmt::Debug for Button {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use engage_ux_core::events::EventType;
+	use engage_ux_core::component::{ComponentTree, Rect};
+	use engage_ux_core::events::{EventHandler, EventType};
+	use engage_ux_core::input::KeyModifiers;
+	use engage_ux_core::input::PointerDispatcher;
+	use tokio::sync::RwLock;
 
 	#[test]
 	fn test_button_creation() {
@@ -175,6 +284,80 @@ mod tests {
 		assert!(clicked.load(std::sync::atomic::Ordering::Relaxed));
 	}
 
+	#[test]
+	fn test_button_routed_click() {
+		let mut button = Button::new(2, "Button");
+		button.properties_mut().bounds = Rect::new(10.0, 10.0, 80.0, 30.0);
+		let clicks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+		let clicks_clone = clicks.clone();
+		button.set_on_click(move |event| {
+			assert_eq!(event.target, 2);
+			clicks_clone.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+		});
+
+		let button = Arc::new(RwLock::new(button));
+		let mut tree = ComponentTree::new();
+		let mut dispatcher = PointerDispatcher::new();
+		tree.insert(None, Button::new(1, "Behind")).unwrap();
+		tree.insert_ref(None, button.clone()).unwrap();
+		dispatcher.register_handler(2, button.clone());
+
+		let release = MouseEvent::button_up(MouseButton::Left, 20.0, 20.0);
+		let event = dispatcher.dispatch_mouse(&tree, release.clone()).unwrap();
+		assert_eq!(event.target(), 2);
+		assert!(event.is_propagation_stopped());
+		assert_eq!(clicks.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+		// Pressing, or releasing another button, is not a click
+		dispatcher.dispatch_mouse(
+			&tree,
+			MouseEvent::button_down(MouseButton::Left, 20.0, 20.0),
+		);
+		dispatcher.dispatch_mouse(&tree, MouseEvent::button_up(MouseButton::Right, 20.0, 20.0));
+		assert_eq!(clicks.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+		// Disabled buttons let the click through to what is beneath
+		button.try_write().unwrap().set_enabled(false);
+		let event = dispatcher.dispatch_mouse(&tree, release).unwrap();
+		assert_eq!(event.target(), 1);
+		assert_eq!(clicks.load(std::sync::atomic::Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn test_button_keyboard_activation() {
+		let mut button = Button::new(3, "Button");
+		let keys = Arc::new(std::sync::Mutex::new(Vec::new()));
+		let keys_clone = keys.clone();
+		button.set_on_click(move |event| {
+			if let EventType::Keyboard(key) = &event.event_type {
+				keys_clone.lock().unwrap().push(key.key_code);
+			}
+		});
+
+		// Input from a backend reaches the click handler through the event system
+		let events = EventHandler::new();
+		let mut receiver = events.subscribe();
+		for key in [KeyCode::Enter, KeyCode::Key('a'), KeyCode::Space] {
+			events.emit_input(3, KeyboardEvent::key_down(key, KeyModifiers::empty()));
+		}
+		events.emit_input(
+			3,
+			KeyboardEvent::key_up(KeyCode::Enter, KeyModifiers::empty()),
+		);
+		let mut handled = 0;
+		while let Ok(event) = receiver.try_recv() {
+			if button.handle_event(&event) {
+				handled += 1;
+			}
+		}
+		assert_eq!(handled, 2);
+		assert_eq!(*keys.lock().unwrap(), vec![KeyCode::Enter, KeyCode::Space]);
+
+		button.set_enabled(false);
+		let enter = KeyboardEvent::key_down(KeyCode::Enter, KeyModifiers::empty());
+		assert!(!button.handle_keyboard(&enter));
+	}
+
 	#[test]
 	fn test_button_component_trait() {
 		let mut button = Button::new(1, "Button");
@@ -184,4 +367,29 @@ mod tests {
 		button.set_enabled(false);
 		assert!(!button.is_enabled());
 	}
+
+	#[test]
+	fn test_button_paint() {
+		let theme = Theme::default();
+		let mut button = Button::new(1, "OK");
+		button.properties_mut().bounds = Rect::new(10.0, 10.0, 80.0, 30.0);
+
+		let commands = button.paint(&theme);
+		assert!(matches!(commands[0], RenderCommand::FillRect { .. }));
+		match commands.last() {
+			Some(RenderCommand::Text { text, x, .. }) => {
+				assert_eq!(text, "OK");
+				assert_eq!(*x, 50.0);
+			}
+			_ => panic!("Expected button label"),
+		}
+
+		button.properties_mut().enabled = false;
+		match button.paint(&theme).last() {
+			Some(RenderCommand::Text { color, .. }) => {
+				assert_eq!(*color, (&theme.colors.text_disabled).into());
+			}
+			_ => panic!("Expected button label"),
+		}
+	}
 }
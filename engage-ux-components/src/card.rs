@@ -1,9 +1,13 @@
 //! Card component for grouping content
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rounded_rect, shadow, stroke_rounded_rect, text_in};
+
 /// Card component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Card {
@@ -44,3 +48,48 @@ impl Component for Card {
 		&mut self.properties
 	}
 }
+
+impl Paint for Card {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let mut commands = Vec::new();
+
+		commands.extend(shadow(
+			bounds,
+			self.border_radius,
+			self.elevation as f32,
+			theme,
+		));
+		commands.push(fill_rounded_rect(
+			bounds,
+			self.border_radius,
+			&self.background_color,
+		));
+		commands.push(stroke_rounded_rect(
+			bounds,
+			self.border_radius,
+			&theme.borders.color,
+			theme.borders.width,
+		));
+
+		if let Some(title) = &self.title {
+			let padding = theme.spacing.medium;
+			let font_size = theme.typography.font_size_large;
+			let title_rect = Rect::new(
+				bounds.x + padding,
+				bounds.y + padding,
+				(bounds.width - padding * 2.0).max(0.0),
+				font_size * theme.typography.line_height,
+			);
+			commands.push(text_in(
+				title_rect,
+				title.clone(),
+				font_size,
+				&theme.colors.text_primary,
+				TextAlign::Left,
+			));
+		}
+
+		commands
+	}
+}
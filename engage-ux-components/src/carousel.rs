@@ -1,10 +1,14 @@
 //! Carousel component for cycling through items
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, fill_rect, inset, text_in};
+
 /// Carousel item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CarouselItem {
@@ -242,6 +246,73 @@ impl Component for Carousel {
 	}
 }
 
+impl Paint for Carousel {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let padding = theme.spacing.medium;
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+
+		if let Some(item) = self.items.get(self.current_index) {
+			let half = bounds.height / 2.0;
+			if let Some(title) = &item.title {
+				commands.push(text_in(
+					Rect::new(bounds.x, bounds.y, bounds.width, half),
+					title.clone(),
+					theme.typography.font_size_large,
+					&self.color,
+					TextAlign::Center,
+				));
+			}
+			if let Some(description) = &item.description {
+				commands.push(text_in(
+					Rect::new(bounds.x, bounds.y + half, bounds.width, half / 2.0),
+					description.clone(),
+					theme.typography.font_size_base,
+					&self.color,
+					TextAlign::Center,
+				));
+			}
+		}
+
+		if self.show_controls {
+			let controls = inset(bounds, padding);
+			let font_size = theme.typography.font_size_large;
+			commands.push(text_in(
+				controls,
+				"‹",
+				font_size,
+				&self.color,
+				TextAlign::Left,
+			));
+			commands.push(text_in(
+				controls,
+				"›",
+				font_size,
+				&self.color,
+				TextAlign::Right,
+			));
+		}
+
+		if self.show_indicators && !self.items.is_empty() {
+			let radius = theme.spacing.small / 2.0;
+			let step = radius * 4.0;
+			let total = step * (self.items.len() - 1) as f32;
+			let start = bounds.x + (bounds.width - total) / 2.0;
+			let y = bounds.y + bounds.height - padding;
+			for index in 0..self.items.len() {
+				let color = if index == self.current_index {
+					&self.active_indicator_color
+				} else {
+					&self.indicator_color
+				};
+				commands.push(circle(start + index as f32 * step, y, radius, color, true));
+			}
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,10 +1,15 @@
 //! Checkbox component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, line, measure_labelled, stroke_rect, text_color, text_in};
+
 /// Checkbox state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckboxState {
@@ -159,6 +164,82 @@ impl Component for Checkbox {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Box followed by the label
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		measure_labelled(fonts, (self.size, self.size), &self.label)
+	}
+}
+
+impl Paint for Checkbox {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let enabled = self.properties.enabled && !self.disabled;
+		let box_color = text_color(&self.color, enabled, theme);
+		let size = self.size.min(bounds.height);
+		let check = Rect::new(
+			bounds.x,
+			bounds.y + (bounds.height - size) / 2.0,
+			size,
+			size,
+		);
+		let mut commands = Vec::new();
+
+		match self.state {
+			CheckboxState::Unchecked => {
+				commands.push(fill_rect(check, &theme.colors.surface));
+				commands.push(stroke_rect(check, box_color, 2.0));
+			}
+			CheckboxState::Checked => {
+				commands.push(fill_rect(check, box_color));
+				commands.push(line(
+					check.x + size * 0.2,
+					check.y + size * 0.5,
+					check.x + size * 0.4,
+					check.y + size * 0.7,
+					&self.check_color,
+					2.0,
+				));
+				commands.push(line(
+					check.x + size * 0.4,
+					check.y + size * 0.7,
+					check.x + size * 0.8,
+					check.y + size * 0.3,
+					&self.check_color,
+					2.0,
+				));
+			}
+			CheckboxState::Indeterminate => {
+				commands.push(fill_rect(check, box_color));
+				commands.push(line(
+					check.x + size * 0.25,
+					check.y + size * 0.5,
+					check.x + size * 0.75,
+					check.y + size * 0.5,
+					&self.check_color,
+					2.0,
+				));
+			}
+		}
+
+		if !self.label.is_empty() {
+			let x = check.x + size + theme.spacing.small;
+			commands.push(text_in(
+				Rect::new(
+					x,
+					bounds.y,
+					(bounds.x + bounds.width - x).max(0.0),
+					bounds.height,
+				),
+				self.label.clone(),
+				theme.typography.font_size_base,
+				text_color(&self.label_color, enabled, theme),
+				TextAlign::Left,
+			));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -212,4 +293,15 @@ mod tests {
 		checkbox.set_size(24.0);
 		assert_eq!(checkbox.size(), 24.0);
 	}
+
+	#[test]
+	fn test_checkbox_measure() {
+		let fonts = FontRegistry::new();
+		assert_eq!(Checkbox::new(1, "").measure(&fonts), (20.0, 20.0));
+
+		// Box, spacing, then four approximate 9.6px glyphs
+		let (width, height) = Checkbox::new(1, "Test").measure(&fonts);
+		assert!((width - (20.0 + 6.0 + 38.4)).abs() < 0.01);
+		assert_eq!(height, 20.0);
+	}
 }
@@ -1,9 +1,15 @@
 //! Console view component with ANSI escape code support
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	Paint, baseline_in, clip, fill_rect, inset, line, restore_clip, text_in, text_width,
+};
+
 /// ANSI color codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AnsiColor {
@@ -238,6 +244,56 @@ impl Component for Console {
 	}
 }
 
+impl Paint for Console {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let content = inset(bounds, self.padding);
+		let row_height = self.font_size * self.line_height;
+		let visible_rows = (content.height / row_height).floor().max(0.0) as usize;
+
+		// Auto-scroll keeps the newest lines in view
+		let first = if self.auto_scroll {
+			self.lines.len().saturating_sub(visible_rows)
+		} else {
+			0
+		};
+
+		let mut commands = vec![fill_rect(bounds, &self.background_color), clip(content)];
+		for (row, console_line) in self.lines[first..].iter().take(visible_rows).enumerate() {
+			let row_rect = Rect::new(
+				content.x,
+				content.y + row as f32 * row_height,
+				content.width,
+				row_height,
+			);
+			if let Some(background) = console_line.background {
+				commands.push(fill_rect(row_rect, &background.to_color()));
+			}
+
+			let color = console_line
+				.foreground
+				.map(|foreground| foreground.to_color())
+				.unwrap_or_else(|| self.color.clone());
+			commands.push(text_in(
+				row_rect,
+				console_line.text.clone(),
+				self.font_size,
+				&color,
+				TextAlign::Left,
+			));
+
+			if console_line.underline {
+				let y = baseline_in(row_rect, self.font_size) + 1.0;
+				let width = text_width(theme, &console_line.text, self.font_size);
+				commands.push(line(row_rect.x, y, row_rect.x + width, y, &color, 1.0));
+			}
+		}
+		commands.push(restore_clip());
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -294,6 +350,48 @@ mod tests {
 	}
 
 	#[test]
+	fn test_console_underline_measured_with_theme_fonts() {
+		use engage_ux_core::component::Rect;
+		use engage_ux_core::media::{Font, FontRegistry};
+		use std::sync::Arc;
+
+		let font = Font::load_from_bytes(
+			include_bytes!("../../assets/fonts/Tuffy.ttf").to_vec(),
+			16.0,
+		)
+		.unwrap();
+		let mut fonts = FontRegistry::new();
+		fonts.set_default_family(font.family.name.clone());
+		fonts.register(font);
+		let fonts = Arc::new(fonts);
+		let theme = Theme::default().with_fonts(fonts.clone());
+
+		let mut console = Console::new(1);
+		console.properties_mut().bounds = Rect::new(0.0, 0.0, 400.0, 100.0);
+		let mut underlined = ConsoleLine::new("Warp core online");
+		underlined.underline = true;
+		console.add_line(underlined);
+
+		let expected = fonts
+			.layout_text("Warp core online", console.font_size(), None, None)
+			.width();
+		let underline = console
+			.paint(&theme)
+			.into_iter()
+			.find_map(|command| match command {
+				RenderCommand::Line { x1, x2, .. } => Some(x2 - x1),
+				_ => None,
+			})
+			.unwrap();
+		assert!((underline - expected).abs() < 0.01);
+		assert_ne!(
+			expected,
+			"Warp core online".len() as f32 * console.font_size() * 0.6
+		);
+	}
+
+	#[test]
+	#[allow(clippy::assertions_on_constants)]
 	fn test_ansi_colors() {
 		let red = AnsiColor::Red;
 		let color = red.to_color();
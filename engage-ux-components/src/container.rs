@@ -1,8 +1,15 @@
 //! Container component for layout
 
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::layout::{
+	AlignItems, FlexDirection, FlexLayout, FlexWrap, GridLayout, JustifyContent,
+};
+use engage_ux_oal::backends::renderer::RenderCommand;
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::Paint;
+
 /// Layout direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LayoutDirection {
@@ -18,6 +25,11 @@ pub struct Container {
 	direction: LayoutDirection,
 	padding: f32,
 	gap: f32,
+	justify: JustifyContent,
+	align: AlignItems,
+	wrap: FlexWrap,
+	#[serde(default)]
+	grid: Option<GridLayout>,
 }
 
 impl Container {
@@ -28,6 +40,10 @@ impl Container {
 			direction: LayoutDirection::Column,
 			padding: 0.0,
 			gap: 0.0,
+			justify: JustifyContent::Start,
+			align: AlignItems::Stretch,
+			wrap: FlexWrap::NoWrap,
+			grid: None,
 		}
 	}
 
@@ -38,6 +54,77 @@ impl Container {
 	pub fn children(&self) -> &[ComponentId] {
 		&self.children
 	}
+
+	/// Get layout direction
+	pub fn direction(&self) -> LayoutDirection {
+		self.direction
+	}
+
+	/// Set layout direction
+	pub fn set_direction(&mut self, direction: LayoutDirection) {
+		self.direction = direction;
+	}
+
+	/// Get padding
+	pub fn padding(&self) -> f32 {
+		self.padding
+	}
+
+	/// Set padding
+	pub fn set_padding(&mut self, padding: f32) {
+		self.padding = padding.max(0.0);
+	}
+
+	/// Get gap between children
+	pub fn gap(&self) -> f32 {
+		self.gap
+	}
+
+	/// Set gap between children
+	pub fn set_gap(&mut self, gap: f32) {
+		self.gap = gap.max(0.0);
+	}
+
+	/// Get main axis distribution
+	pub fn justify(&self) -> JustifyContent {
+		self.justify
+	}
+
+	/// Set main axis distribution
+	pub fn set_justify(&mut self, justify: JustifyContent) {
+		self.justify = justify;
+	}
+
+	/// Get cross axis alignment
+	pub fn align(&self) -> AlignItems {
+		self.align
+	}
+
+	/// Set cross axis alignment
+	pub fn set_align(&mut self, align: AlignItems) {
+		self.align = align;
+	}
+
+	/// Get wrapping
+	pub fn wrap(&self) -> FlexWrap {
+		self.wrap
+	}
+
+	/// Set wrapping
+	pub fn set_wrap(&mut self, wrap: FlexWrap) {
+		self.wrap = wrap;
+	}
+
+	/// Get the grid, if children are arranged in one
+	pub fn grid(&self) -> Option<&GridLayout> {
+		self.grid.as_ref()
+	}
+
+	/// Arrange children in a grid instead of a flex line, or pass `None` to
+	/// return to flex layout
+	pub fn set_grid(&mut self, grid: Option<GridLayout>) {
+		self.grid = grid;
+	}
 }
 
 impl Component for Container {
@@ -52,4 +139,120 @@ impl Component for Container {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn children(&self) -> &[ComponentId] {
+		&self.children
+	}
+
+	fn set_children(&mut self, children: Vec<ComponentId>) {
+		self.children = children;
+	}
+
+	fn grid_layout(&self) -> Option<GridLayout> {
+		self.grid.clone()
+	}
+
+	fn flex_layout(&self) -> Option<FlexLayout> {
+		let direction = match self.direction {
+			LayoutDirection::Row => FlexDirection::Row,
+			LayoutDirection::Column => FlexDirection::Column,
+		};
+		Some(
+			FlexLayout::new(direction)
+				.with_padding(self.padding)
+				.with_gap(self.gap)
+				.with_justify(self.justify)
+				.with_align(self.align)
+				.with_wrap(self.wrap),
+		)
+	}
+}
+
+impl Paint for Container {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		// Containers only lay out their children
+		Vec::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::button::Button;
+	use crate::checkbox::Checkbox;
+	use engage_ux_core::component::{ComponentTree, Rect};
+	use engage_ux_core::layout::{FlexItem, GridItem, LayoutSolver, Size, TrackSize, Unit};
+	use engage_ux_core::media::FontRegistry;
+
+	#[test]
+	fn test_container_lays_out_children() {
+		let mut tree = ComponentTree::new();
+		let mut container = Container::new(1);
+		container.set_direction(LayoutDirection::Row);
+		container.set_padding(8.0);
+		container.set_gap(4.0);
+		container.set_bounds(Rect::new(0.0, 0.0, 200.0, 50.0));
+		let root = tree.insert(None, container).unwrap();
+
+		let mut first = Container::new(2);
+		first.properties_mut().flex = FlexItem::new().with_width(Size::Fixed(Unit::pixels(60.0)));
+		let first = tree.insert(Some(root), first).unwrap();
+		let mut second = Container::new(3);
+		second.properties_mut().flex = FlexItem::new().with_grow(1.0);
+		let second = tree.insert(Some(root), second).unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		let bounds = |id| tree.get(id).unwrap().try_read().unwrap().bounds();
+		assert_eq!(bounds(first), Rect::new(8.0, 8.0, 60.0, 34.0));
+		assert_eq!(bounds(second), Rect::new(72.0, 8.0, 120.0, 34.0));
+	}
+
+	#[test]
+	fn test_container_fits_controls_to_content() {
+		let mut tree = ComponentTree::new();
+		let mut container = Container::new(1);
+		container.set_direction(LayoutDirection::Row);
+		container.set_bounds(Rect::new(0.0, 0.0, 400.0, 40.0));
+		let root = tree.insert(None, container).unwrap();
+		let button = tree.insert(Some(root), Button::new(2, "OK")).unwrap();
+		let checkbox = tree.insert(Some(root), Checkbox::new(3, "Accept")).unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		let fonts = FontRegistry::new();
+		let (button_width, _) = Button::new(2, "OK").measure(&fonts);
+		let (checkbox_width, _) = Checkbox::new(3, "Accept").measure(&fonts);
+		assert!(button_width > 0.0 && checkbox_width > 0.0);
+		let bounds = |id| tree.get(id).unwrap().try_read().unwrap().bounds();
+		assert_eq!(bounds(button).width, button_width);
+		assert_eq!(bounds(checkbox).x, button_width);
+		assert_eq!(bounds(checkbox).width, checkbox_width);
+	}
+
+	#[test]
+	fn test_container_grid() {
+		let mut tree = ComponentTree::new();
+		let mut container = Container::new(1);
+		container.set_grid(Some(
+			GridLayout::new(TrackSize::parse_list("100px 1fr").unwrap())
+				.with_rows(TrackSize::parse_list("40px 1fr").unwrap())
+				.with_areas(vec!["header header".into(), "nav main".into()]),
+		));
+		container.set_bounds(Rect::new(0.0, 0.0, 300.0, 200.0));
+		let root = tree.insert(None, container).unwrap();
+
+		let mut header = Container::new(2);
+		header.properties_mut().grid = GridItem::in_area("header");
+		let header = tree.insert(Some(root), header).unwrap();
+		let mut main = Container::new(3);
+		main.properties_mut().grid = GridItem::in_area("main");
+		let main = tree.insert(Some(root), main).unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		let bounds = |id| tree.get(id).unwrap().try_read().unwrap().bounds();
+		assert_eq!(bounds(header), Rect::new(0.0, 0.0, 300.0, 40.0));
+		assert_eq!(bounds(main), Rect::new(100.0, 40.0, 200.0, 160.0));
+	}
 }
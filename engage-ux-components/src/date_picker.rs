@@ -1,10 +1,14 @@
 //! Date picker component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, stroke_rect, text_color, text_in};
+
 /// Date struct
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Date {
@@ -312,6 +316,140 @@ impl Component for DatePicker {
 	}
 }
 
+impl Paint for DatePicker {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let field_height = (font_size * 2.5).min(bounds.height);
+		let field = Rect::new(bounds.x, bounds.y, bounds.width, field_height);
+
+		let label = match self.selected_date {
+			Some(date) => format!("{:04}-{:02}-{:02}", date.year, date.month, date.day),
+			None => "YYYY-MM-DD".to_string(),
+		};
+		let label_color = if self.selected_date.is_some() {
+			text_color(&self.color, self.properties.enabled, theme)
+		} else {
+			&theme.colors.text_secondary
+		};
+		let mut commands = vec![
+			fill_rect(field, &self.background_color),
+			stroke_rect(field, &theme.borders.color, theme.borders.width),
+			text_in(
+				inset(field, padding),
+				label,
+				font_size,
+				label_color,
+				TextAlign::Left,
+			),
+		];
+
+		if !self.open || self.view != DatePickerView::Days {
+			return commands;
+		}
+
+		// Calendar: a heading row, a weekday row and six weeks of days
+		let calendar = Rect::new(
+			bounds.x,
+			bounds.y + field_height,
+			bounds.width,
+			(bounds.height - field_height).max(0.0),
+		);
+		let cell_width = calendar.width / 7.0;
+		let cell_height = calendar.height / 8.0;
+		commands.push(fill_rect(calendar, &self.background_color));
+		commands.push(stroke_rect(
+			calendar,
+			&theme.borders.color,
+			theme.borders.width,
+		));
+		commands.push(text_in(
+			Rect::new(calendar.x, calendar.y, calendar.width, cell_height),
+			format!("{:04}-{:02}", self.viewing_year, self.viewing_month),
+			font_size,
+			&self.color,
+			TextAlign::Center,
+		));
+
+		const WEEKDAYS: [&str; 7] = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+		for column in 0..7 {
+			let weekday = (column + self.first_day_of_week as usize) % 7;
+			commands.push(text_in(
+				Rect::new(
+					calendar.x + column as f32 * cell_width,
+					calendar.y + cell_height,
+					cell_width,
+					cell_height,
+				),
+				WEEKDAYS[weekday],
+				theme.typography.font_size_small,
+				&theme.colors.text_secondary,
+				TextAlign::Center,
+			));
+		}
+
+		let offset = (weekday(self.viewing_year, self.viewing_month, 1) + 7
+			- self.first_day_of_week as usize % 7)
+			% 7;
+		let today = Self::today();
+		for day in 1..=days_in_month(self.viewing_year, self.viewing_month) {
+			let date = Date {
+				year: self.viewing_year,
+				month: self.viewing_month,
+				day,
+			};
+			let slot = offset + day as usize - 1;
+			let cell = Rect::new(
+				calendar.x + (slot % 7) as f32 * cell_width,
+				calendar.y + (slot / 7 + 2) as f32 * cell_height,
+				cell_width,
+				cell_height,
+			);
+			let key = |date: Date| (date.year, date.month, date.day);
+			let out_of_range = self.min_date.is_some_and(|min| key(date) < key(min))
+				|| self.max_date.is_some_and(|max| key(date) > key(max));
+
+			let color = if self.selected_date == Some(date) {
+				commands.push(fill_rect(cell, &self.selected_background));
+				&self.selected_color
+			} else if out_of_range {
+				&self.disabled_color
+			} else if date == today {
+				&self.today_color
+			} else {
+				&self.color
+			};
+			commands.push(text_in(
+				cell,
+				day.to_string(),
+				font_size,
+				color,
+				TextAlign::Center,
+			));
+		}
+
+		commands
+	}
+}
+
+/// Number of days in a month
+fn days_in_month(year: i32, month: u8) -> u8 {
+	(28..=31)
+		.rev()
+		.find(|&day| Date { year, month, day }.is_valid())
+		.unwrap_or(28)
+}
+
+/// Day of the week for a date, 0 = Sunday (Sakamoto's method)
+fn weekday(year: i32, month: u8, day: u8) -> usize {
+	const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+	let year = if month < 3 { year - 1 } else { year };
+	let month = month.clamp(1, 12);
+	(year + year / 4 - year / 100 + year / 400 + OFFSETS[month as usize - 1] + day as i32)
+		.rem_euclid(7) as usize
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -397,4 +535,35 @@ mod tests {
 		picker.set_selected_date(Date::new(2023, 12, 31));
 		assert_eq!(picker.selected_date(), Date::new(2024, 6, 15));
 	}
+
+	#[test]
+	fn test_calendar_helpers() {
+		assert_eq!(days_in_month(2024, 2), 29);
+		assert_eq!(days_in_month(2023, 2), 28);
+		assert_eq!(days_in_month(2024, 4), 30);
+		assert_eq!(days_in_month(2024, 12), 31);
+		// 2024-01-01 was a Monday, 2000-02-29 a Tuesday
+		assert_eq!(weekday(2024, 1, 1), 1);
+		assert_eq!(weekday(2000, 2, 29), 2);
+	}
+
+	#[test]
+	fn test_date_picker_paint_calendar() {
+		let theme = Theme::default();
+		let mut picker = DatePicker::new(1);
+		let closed = picker.paint(&theme).len();
+
+		picker.set_viewing_year(2024);
+		picker.set_viewing_month(2);
+		picker.open();
+		let day_labels = picker
+			.paint(&theme)
+			.iter()
+			.filter(
+				|command| matches!(command, RenderCommand::Text { text, .. } if text.parse::<u8>().is_ok()),
+			)
+			.count();
+		assert_eq!(day_labels, 29);
+		assert!(closed < picker.paint(&theme).len());
+	}
 }
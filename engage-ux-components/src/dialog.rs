@@ -1,9 +1,24 @@
 //! Dialog components (alert, modal, file dialogs)
 
+use engage_ux_core::accessibility::FocusManager;
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, ComponentTree, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::paint::{
+	Paint, backdrop_blur, fill_rect, inset, line, shadow, stroke_rect, text_in, text_lines,
+	text_width,
+};
+
+/// How far a modal dialog is raised above the content behind it
+const MODAL_ELEVATION: f32 = 3.0;
+
+/// Default blur radius of the content behind a modal dialog
+const DEFAULT_BACKDROP_BLUR: f32 = 8.0;
 
 /// Dialog result
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -145,6 +160,33 @@ impl Component for AlertDialog {
 	}
 }
 
+impl Paint for AlertDialog {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let accent = match self.alert_type {
+			AlertType::Info | AlertType::Question => &theme.colors.info,
+			AlertType::Warning => &theme.colors.warning,
+			AlertType::Error => &theme.colors.error,
+			AlertType::Success => &theme.colors.success,
+		};
+		let mut commands = dialog_frame(self.properties.bounds, &self.title, accent, theme);
+		let (body, buttons) = dialog_body(self.properties.bounds, theme);
+		commands.extend(text_lines(
+			body,
+			&self.message,
+			theme.typography.font_size_base,
+			theme.typography.font_size_base * theme.typography.line_height,
+			&theme.colors.text_primary,
+			TextAlign::Left,
+		));
+		commands.extend(dialog_buttons(buttons, &["OK"], accent, theme));
+		commands
+	}
+}
+
 /// Confirm dialog with Yes/No or OK/Cancel buttons
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ConfirmDialog {
@@ -261,6 +303,39 @@ impl Component for ConfirmDialog {
 	}
 }
 
+impl Paint for ConfirmDialog {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+		commands.extend(dialog_frame(bounds, &self.title, &self.color, theme));
+		let (body, buttons) = dialog_body(bounds, theme);
+		commands.extend(text_lines(
+			body,
+			&self.message,
+			theme.typography.font_size_base,
+			theme.typography.font_size_base * theme.typography.line_height,
+			&self.color,
+			TextAlign::Left,
+		));
+		let labels: &[&str] = if self.use_yes_no {
+			&["No", "Yes"]
+		} else {
+			&["Cancel", "OK"]
+		};
+		commands.extend(dialog_buttons(
+			buttons,
+			labels,
+			&theme.colors.primary,
+			theme,
+		));
+		commands
+	}
+}
+
 /// Custom modal dialog
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Modal {
@@ -274,10 +349,16 @@ pub struct Modal {
 	color: Color,
 	background_color: Color,
 	overlay_color: Color,
+	#[serde(default = "default_backdrop_blur")]
+	backdrop_blur: f32,
 	#[serde(skip)]
 	on_close: Option<EventCallback>,
 }
 
+fn default_backdrop_blur() -> f32 {
+	DEFAULT_BACKDROP_BLUR
+}
+
 impl Modal {
 	/// Create a new modal
 	pub fn new(id: ComponentId) -> Self {
@@ -292,6 +373,7 @@ impl Modal {
 			color: Color::from_hex("#000000").unwrap(),
 			background_color: Color::from_hex("#FFFFFF").unwrap(),
 			overlay_color: Color::from_hex("#00000080").unwrap(), // 50% transparent black
+			backdrop_blur: DEFAULT_BACKDROP_BLUR,
 			on_close: None,
 		}
 	}
@@ -326,6 +408,40 @@ impl Modal {
 		self.visible = !self.visible;
 	}
 
+	/// Show a modal from the tree and, when it is modal, trap keyboard focus
+	/// inside it until it is closed
+	///
+	/// The modal is unlocked before focus moves, as locked components cannot
+	/// take focus. Returns `false`, doing nothing, when it is locked
+	/// elsewhere.
+	pub fn open(modal: &RwLock<Self>, focus: &mut FocusManager, tree: &ComponentTree) -> bool {
+		let Ok(mut this) = modal.try_write() else {
+			return false;
+		};
+		this.show();
+		let (id, traps_focus) = (this.properties.id, this.modal);
+		drop(this);
+		if traps_focus {
+			focus.push_scope(tree, id);
+		}
+		true
+	}
+
+	/// Hide a modal from the tree and give focus back to where it was when
+	/// it opened
+	///
+	/// Returns `false`, doing nothing, when the modal is locked elsewhere.
+	pub fn close(modal: &RwLock<Self>, focus: &mut FocusManager, tree: &ComponentTree) -> bool {
+		let Ok(mut this) = modal.try_write() else {
+			return false;
+		};
+		this.hide();
+		let id = this.properties.id;
+		drop(this);
+		focus.pop_scope(tree, id);
+		true
+	}
+
 	/// Check if closable
 	pub fn is_closable(&self) -> bool {
 		self.closable
@@ -381,6 +497,16 @@ impl Modal {
 		self.overlay_color = color;
 	}
 
+	/// Get the blur radius of the content behind the modal
+	pub fn backdrop_blur(&self) -> f32 {
+		self.backdrop_blur
+	}
+
+	/// Set the blur radius of the content behind the modal, 0 for none
+	pub fn set_backdrop_blur(&mut self, blur_radius: f32) {
+		self.backdrop_blur = blur_radius.max(0.0);
+	}
+
 	/// Set close callback
 	pub fn set_on_close(&mut self, callback: impl Fn(&Event) + Send + Sync + 'static) {
 		self.on_close = Some(std::sync::Arc::new(callback));
@@ -408,6 +534,46 @@ impl Component for Modal {
 	}
 }
 
+impl Paint for Modal {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let width = self.width.unwrap_or(bounds.width).min(bounds.width);
+		let height = self.height.unwrap_or(bounds.height).min(bounds.height);
+		let dialog = Rect::new(
+			bounds.x + (bounds.width - width) / 2.0,
+			bounds.y + (bounds.height - height) / 2.0,
+			width,
+			height,
+		);
+
+		let mut commands = Vec::new();
+		if self.modal {
+			if self.backdrop_blur > 0.0 {
+				commands.push(backdrop_blur(bounds, 0.0, self.backdrop_blur));
+			}
+			commands.push(fill_rect(bounds, &self.overlay_color));
+		}
+		commands.extend(shadow(dialog, 0.0, MODAL_ELEVATION, theme));
+		commands.push(fill_rect(dialog, &self.background_color));
+		commands.extend(dialog_frame(dialog, &self.title, &self.color, theme));
+		if self.closable {
+			let (title_bar, _) = title_bar(dialog, theme);
+			commands.push(text_in(
+				inset(title_bar, theme.spacing.medium),
+				"×",
+				theme.typography.font_size_large,
+				&self.color,
+				TextAlign::Right,
+			));
+		}
+		commands
+	}
+}
+
 /// File dialog type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FileDialogType {
@@ -577,9 +743,169 @@ impl Component for FileDialog {
 	}
 }
 
+impl Paint for FileDialog {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let row_height = font_size * theme.typography.line_height;
+		let mut commands = vec![fill_rect(bounds, &theme.colors.surface)];
+		commands.extend(dialog_frame(
+			bounds,
+			&self.title,
+			&theme.colors.text_primary,
+			theme,
+		));
+		let (body, buttons) = dialog_body(bounds, theme);
+
+		let mut y = body.y;
+		if let Some(path) = &self.default_path {
+			commands.push(text_in(
+				Rect::new(body.x, y, body.width, row_height),
+				path.clone(),
+				font_size,
+				&theme.colors.text_secondary,
+				TextAlign::Left,
+			));
+			y += row_height;
+		}
+		for path in &self.selected_paths {
+			commands.push(text_in(
+				Rect::new(body.x, y, body.width, row_height),
+				path.clone(),
+				font_size,
+				&theme.colors.text_primary,
+				TextAlign::Left,
+			));
+			y += row_height;
+		}
+		if !self.filters.is_empty() {
+			let filters = self
+				.filters
+				.iter()
+				.map(|filter| filter.name.as_str())
+				.collect::<Vec<_>>()
+				.join(", ");
+			commands.push(text_in(
+				Rect::new(body.x, buttons.y, body.width, buttons.height),
+				filters,
+				theme.typography.font_size_small,
+				&theme.colors.text_secondary,
+				TextAlign::Left,
+			));
+		}
+
+		let confirm = match self.dialog_type {
+			FileDialogType::Open => "Open",
+			FileDialogType::Save => "Save",
+			FileDialogType::SelectFolder => "Select",
+		};
+		commands.extend(dialog_buttons(
+			buttons,
+			&["Cancel", confirm],
+			&theme.colors.primary,
+			theme,
+		));
+		commands
+	}
+}
+
+/// Split a dialog into its title bar and the remaining area
+fn title_bar(bounds: Rect, theme: &Theme) -> (Rect, Rect) {
+	let height = (theme.typography.font_size_large * 2.5).min(bounds.height);
+	(
+		Rect::new(bounds.x, bounds.y, bounds.width, height),
+		Rect::new(
+			bounds.x,
+			bounds.y + height,
+			bounds.width,
+			bounds.height - height,
+		),
+	)
+}
+
+/// Split a dialog below its title bar into the message body and button row
+fn dialog_body(bounds: Rect, theme: &Theme) -> (Rect, Rect) {
+	let (_, rest) = title_bar(bounds, theme);
+	let padding = theme.spacing.medium;
+	let button_height = (theme.typography.font_size_base * 2.5).min(rest.height);
+	let rest = inset(rest, padding);
+	let body_height = (rest.height - button_height - padding).max(0.0);
+	(
+		Rect::new(rest.x, rest.y, rest.width, body_height),
+		Rect::new(
+			rest.x,
+			rest.y + rest.height - button_height,
+			rest.width,
+			button_height,
+		),
+	)
+}
+
+/// Border and title shared by every dialog
+fn dialog_frame(bounds: Rect, title: &str, accent: &Color, theme: &Theme) -> Vec<RenderCommand> {
+	let (title_bar, _) = title_bar(bounds, theme);
+	vec![
+		stroke_rect(bounds, &theme.borders.color, theme.borders.width),
+		line(
+			title_bar.x,
+			title_bar.y + title_bar.height,
+			title_bar.x + title_bar.width,
+			title_bar.y + title_bar.height,
+			&theme.borders.color,
+			theme.borders.width,
+		),
+		text_in(
+			inset(title_bar, theme.spacing.medium),
+			title,
+			theme.typography.font_size_large,
+			accent,
+			TextAlign::Left,
+		),
+	]
+}
+
+/// Right-aligned row of buttons; the last label is the default action
+fn dialog_buttons(row: Rect, labels: &[&str], accent: &Color, theme: &Theme) -> Vec<RenderCommand> {
+	let font_size = theme.typography.font_size_base;
+	let gap = theme.spacing.small;
+	let mut commands = Vec::new();
+	let mut right = row.x + row.width;
+
+	for (index, label) in labels.iter().enumerate().rev() {
+		let width = text_width(theme, label, font_size) + theme.spacing.medium * 2.0;
+		let button = Rect::new(right - width, row.y, width, row.height);
+		if index == labels.len() - 1 {
+			commands.push(fill_rect(button, accent));
+			commands.push(text_in(
+				button,
+				*label,
+				font_size,
+				&theme.colors.surface,
+				TextAlign::Center,
+			));
+		} else {
+			commands.push(stroke_rect(button, accent, theme.borders.width));
+			commands.push(text_in(
+				button,
+				*label,
+				font_size,
+				accent,
+				TextAlign::Center,
+			));
+		}
+		right -= width + gap;
+	}
+
+	commands
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::button::Button;
+	use engage_ux_core::accessibility::AccessibilityProps;
+	use engage_ux_core::input::{KeyCode, KeyModifiers, KeyboardEvent};
+	use std::sync::Arc;
 
 	#[test]
 	fn test_alert_dialog_creation() {
@@ -628,6 +954,54 @@ mod tests {
 		assert!(!modal.is_visible());
 	}
 
+	#[test]
+	fn test_modal_traps_focus() {
+		let mut tree = ComponentTree::new();
+		let mut focus = FocusManager::new();
+		let modal = Arc::new(RwLock::new(Modal::new(1)));
+		tree.insert_ref(None, modal.clone()).unwrap();
+		for (parent, id) in [(Some(1), 2), (Some(1), 3), (None, 4)] {
+			tree.insert(parent, Button::new(id, "OK")).unwrap();
+			focus.register(id, AccessibilityProps::new().with_focusable(true));
+		}
+		focus.set_focus(4);
+
+		assert!(Modal::open(&modal, &mut focus, &tree));
+		assert_eq!(focus.focused(), Some(2));
+		let tab = KeyboardEvent::key_down(KeyCode::Tab, KeyModifiers::empty());
+		focus.handle_keyboard(&tree, &tab);
+		focus.handle_keyboard(&tree, &tab);
+		assert_eq!(focus.focused(), Some(2));
+
+		assert!(Modal::close(&modal, &mut focus, &tree));
+		assert!(!modal.try_read().unwrap().is_visible());
+		assert_eq!(focus.focused(), Some(4));
+	}
+
+	#[test]
+	fn test_modal_backdrop() {
+		let mut theme = Theme::default();
+		theme.shadows.enabled = true;
+		let mut modal = Modal::new(1);
+		modal.set_bounds(Rect::new(0.0, 0.0, 400.0, 300.0));
+		modal.set_width(Some(200.0));
+		modal.set_height(Some(100.0));
+		modal.show();
+
+		let commands = modal.paint(&theme);
+		assert!(matches!(
+			commands[0],
+			RenderCommand::BackdropBlur { blur_radius, .. } if blur_radius == DEFAULT_BACKDROP_BLUR
+		));
+		assert!(matches!(commands[1], RenderCommand::FillRect { .. }));
+		assert!(matches!(commands[2], RenderCommand::Shadow { .. }));
+
+		modal.set_backdrop_blur(0.0);
+		modal.set_modal(false);
+		let commands = modal.paint(&theme);
+		assert!(matches!(commands[0], RenderCommand::Shadow { .. }));
+	}
+
 	#[test]
 	fn test_file_dialog_types() {
 		let open = FileDialog::open(1);
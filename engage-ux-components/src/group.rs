@@ -1,9 +1,20 @@
 //! Group component for organizing related elements
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::layout::{FlexDirection, FlexLayout};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	MEASURE_FONT_SIZE, Paint, fill_rect, measure_text, stroke_rect, text_color, text_in,
+};
+
+/// Height of the title bar, twice the default theme's base font size
+const TITLE_BAR_HEIGHT: f32 = MEASURE_FONT_SIZE * 2.0;
+
 /// Group orientation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GroupOrientation {
@@ -181,6 +192,15 @@ impl Group {
 	pub fn set_title_background(&mut self, color: Color) {
 		self.title_background = color;
 	}
+
+	/// Height of the title bar, which is not drawn without a title
+	fn title_bar_height(&self) -> f32 {
+		if self.title.is_empty() {
+			0.0
+		} else {
+			TITLE_BAR_HEIGHT
+		}
+	}
 }
 
 impl Component for Group {
@@ -195,6 +215,93 @@ impl Component for Group {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn children(&self) -> &[ComponentId] {
+		&self.children
+	}
+
+	fn set_children(&mut self, children: Vec<ComponentId>) {
+		self.children = children;
+	}
+
+	fn flex_layout(&self) -> Option<FlexLayout> {
+		let direction = match self.orientation {
+			GroupOrientation::Horizontal => FlexDirection::Row,
+			GroupOrientation::Vertical => FlexDirection::Column,
+		};
+		Some(
+			FlexLayout::new(direction)
+				.with_padding(self.padding)
+				.with_header(self.title_bar_height())
+				.with_gap(self.spacing),
+		)
+	}
+
+	/// Children of a collapsed group are neither laid out nor hit
+	fn hides_children(&self) -> bool {
+		self.collapsed
+	}
+
+	/// Title bar alone, as a collapsed group draws it
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let height = self.title_bar_height();
+		if height == 0.0 {
+			return (0.0, 0.0);
+		}
+		let (width, _) = measure_text(fonts, &self.title, MEASURE_FONT_SIZE);
+		(width + self.padding * 2.0, height)
+	}
+}
+
+impl Paint for Group {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let title_height = self.title_bar_height().min(bounds.height);
+		let visible = if self.collapsed {
+			Rect::new(bounds.x, bounds.y, bounds.width, title_height)
+		} else {
+			bounds
+		};
+
+		let mut commands = vec![fill_rect(visible, &self.background_color)];
+		if title_height > 0.0 {
+			let title_bar = Rect::new(bounds.x, bounds.y, bounds.width, title_height);
+			let label = Rect::new(
+				title_bar.x + self.padding,
+				title_bar.y,
+				(title_bar.width - self.padding * 2.0).max(0.0),
+				title_height,
+			);
+			commands.push(fill_rect(title_bar, &self.title_background));
+			commands.push(text_in(
+				label,
+				self.title.clone(),
+				font_size,
+				text_color(&self.title_color, self.properties.enabled, theme),
+				TextAlign::Left,
+			));
+			if self.collapsible {
+				let indicator = if self.collapsed { "+" } else { "-" };
+				commands.push(text_in(
+					label,
+					indicator,
+					font_size,
+					&self.title_color,
+					TextAlign::Right,
+				));
+			}
+		}
+		if self.show_border {
+			commands.push(stroke_rect(
+				visible,
+				&self.border_color,
+				theme.borders.width,
+			));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -228,6 +335,26 @@ mod tests {
 		assert_eq!(group.children().len(), 2);
 	}
 
+	#[test]
+	fn test_group_in_component_tree() {
+		use crate::Button;
+		use engage_ux_core::component::ComponentTree;
+
+		let mut tree = ComponentTree::new();
+		let group_id = tree.allocate_id();
+		tree.insert(None, Group::new(group_id)).unwrap();
+
+		let ok_id = tree.allocate_id();
+		tree.insert(Some(group_id), Button::new(ok_id, "OK"))
+			.unwrap();
+		let cancel_id = tree.allocate_id();
+		tree.insert(Some(group_id), Button::new(cancel_id, "Cancel"))
+			.unwrap();
+
+		let group = tree.get(group_id).unwrap().try_read().unwrap();
+		assert_eq!(group.children(), &[ok_id, cancel_id]);
+	}
+
 	#[test]
 	fn test_group_orientation() {
 		let mut group = Group::new(1);
@@ -251,6 +378,34 @@ mod tests {
 		assert!(!group.is_collapsed());
 	}
 
+	#[test]
+	fn test_group_layout_below_title() {
+		use crate::Button;
+		use engage_ux_core::component::ComponentTree;
+		use engage_ux_core::layout::LayoutSolver;
+		use std::sync::Arc;
+		use tokio::sync::RwLock;
+
+		let mut tree = ComponentTree::new();
+		let mut group = Group::with_title(1, "Options");
+		group.set_collapsible(true);
+		group.set_bounds(Rect::new(0.0, 0.0, 200.0, 200.0));
+		let group = Arc::new(RwLock::new(group));
+		tree.insert_ref(None, group.clone()).unwrap();
+		tree.insert(Some(1), Button::new(2, "OK")).unwrap();
+
+		let solver = LayoutSolver::new();
+		solver.layout(&mut tree, 1).unwrap();
+		let button = tree.get(2).unwrap().try_read().unwrap().bounds();
+		assert_eq!(button.y, 12.0 + TITLE_BAR_HEIGHT);
+		assert_eq!(tree.hit_test(button.x + 1.0, button.y + 1.0), Some(2));
+
+		// A collapsed group measures as its title bar and hides its children
+		group.try_write().unwrap().set_collapsed(true);
+		assert_eq!(solver.measure(&tree, 1).unwrap().1, TITLE_BAR_HEIGHT);
+		assert_eq!(tree.hit_test(button.x + 1.0, button.y + 1.0), Some(1));
+	}
+
 	#[test]
 	fn test_group_spacing_padding() {
 		let mut group = Group::new(1);
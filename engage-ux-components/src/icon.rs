@@ -1,8 +1,16 @@
 //! Icon component for displaying icons
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_core::rendering::SvgDocument;
+use engage_ux_oal::backends::Transform;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::paint::{Paint, stroke_rect, text_in};
 
 /// Icon component
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +22,9 @@ pub struct Icon {
 	rotation: f32,
 	flip_horizontal: bool,
 	flip_vertical: bool,
+	/// Vector artwork, tinted with the icon's color
+	#[serde(skip)]
+	svg: Option<Arc<SvgDocument>>,
 }
 
 impl Icon {
@@ -27,6 +38,7 @@ impl Icon {
 			rotation: 0.0,
 			flip_horizontal: false,
 			flip_vertical: false,
+			svg: None,
 		}
 	}
 
@@ -89,6 +101,16 @@ impl Icon {
 	pub fn set_flip_vertical(&mut self, flip: bool) {
 		self.flip_vertical = flip;
 	}
+
+	/// Get the vector artwork, if set
+	pub fn svg(&self) -> Option<&Arc<SvgDocument>> {
+		self.svg.as_ref()
+	}
+
+	/// Set the vector artwork drawn in place of the name's placeholder
+	pub fn set_svg(&mut self, svg: Option<Arc<SvgDocument>>) {
+		self.svg = svg;
+	}
 }
 
 impl Component for Icon {
@@ -103,6 +125,60 @@ impl Component for Icon {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		(self.size, self.size)
+	}
+}
+
+impl Paint for Icon {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let size = self.size.min(bounds.width).min(bounds.height);
+		let frame = Rect::new(
+			bounds.x + (bounds.width - size) / 2.0,
+			bounds.y + (bounds.height - size) / 2.0,
+			size,
+			size,
+		);
+
+		if let Some(svg) = &self.svg {
+			let svg = RenderCommand::Svg {
+				document: Arc::clone(svg),
+				rect: frame.into(),
+				tint: Some(self.color.clone().into()),
+			};
+			if self.rotation == 0.0 && !self.flip_horizontal && !self.flip_vertical {
+				return vec![svg];
+			}
+			// Rotate and flip about the centre of the frame
+			let (cx, cy) = (frame.x + size / 2.0, frame.y + size / 2.0);
+			let flip = |flipped: bool| if flipped { -1.0 } else { 1.0 };
+			let transform = Transform::from_translate(cx, cy)
+				.rotate(self.rotation.to_radians())
+				.scale(flip(self.flip_horizontal), flip(self.flip_vertical))
+				.translate(-cx, -cy);
+			return vec![
+				RenderCommand::PushTransform(transform),
+				svg,
+				RenderCommand::PopTransform,
+			];
+		}
+
+		// Placeholder glyph without artwork: the first letter of the icon
+		// name inside a frame of the icon's size
+		let glyph = self
+			.name
+			.chars()
+			.next()
+			.map(|c| c.to_uppercase().to_string())
+			.unwrap_or_default();
+
+		vec![
+			stroke_rect(frame, &self.color, 1.0),
+			text_in(frame, glyph, size * 0.6, &self.color, TextAlign::Center),
+		]
+	}
 }
 
 #[cfg(test)]
@@ -134,6 +210,39 @@ mod tests {
 		assert_eq!(icon.rotation(), 90.0);
 	}
 
+	#[test]
+	fn test_icon_paint_svg() {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24">
+			<circle cx="12" cy="12" r="10"/>
+		</svg>"#;
+		let mut icon = Icon::new(1, "dot");
+		icon.properties_mut().bounds = Rect::new(0.0, 0.0, 48.0, 48.0);
+		icon.set_svg(Some(Arc::new(
+			engage_ux_core::rendering::SvgParser::new()
+				.parse(svg)
+				.unwrap(),
+		)));
+
+		match icon.paint(&Theme::default()).as_slice() {
+			[RenderCommand::Svg { rect, tint, .. }] => {
+				assert_eq!((rect.x, rect.width), (12.0, 24.0));
+				assert_eq!(*tint, Some(icon.color().into()));
+			}
+			commands => panic!("unexpected commands {commands:?}"),
+		}
+
+		icon.set_flip_horizontal(true);
+		let commands = icon.paint(&Theme::default());
+		assert!(matches!(
+			commands.as_slice(),
+			[
+				RenderCommand::PushTransform(_),
+				RenderCommand::Svg { .. },
+				RenderCommand::PopTransform
+			]
+		));
+	}
+
 	#[test]
 	fn test_icon_flip() {
 		let mut icon = Icon::new(1, "arrow");
@@ -1,22 +1,16 @@
 //! Image component for displaying images
 
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::{FontRegistry, ImageData};
+use engage_ux_oal::backends::image::ImageCommand;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-/// Image fit mode
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ImageFit {
-	/// Image fills the container, may be cropped
-	Cover,
-	/// Image fits within container, may have empty space
-	Contain,
-	/// Image is stretched to fill container
-	Fill,
-	/// Image maintains original size
-	None,
-	/// Image scales down if larger than container
-	ScaleDown,
-}
+pub use engage_ux_oal::backends::image::ImageFit;
+
+use crate::paint::{Paint, fill_rect, line, stroke_rect, text_in};
 
 /// Image component
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +22,9 @@ pub struct Image {
 	height: Option<f32>,
 	fit: ImageFit,
 	lazy: bool,
+	/// Decoded pixels, drawn in place of the placeholder once loaded
+	#[serde(skip)]
+	data: Option<Arc<ImageData>>,
 }
 
 impl Image {
@@ -41,6 +38,7 @@ impl Image {
 			height: None,
 			fit: ImageFit::Cover,
 			lazy: false,
+			data: None,
 		}
 	}
 
@@ -103,6 +101,16 @@ impl Image {
 	pub fn set_lazy(&mut self, lazy: bool) {
 		self.lazy = lazy;
 	}
+
+	/// Get the decoded image, if loaded
+	pub fn data(&self) -> Option<&Arc<ImageData>> {
+		self.data.as_ref()
+	}
+
+	/// Set the decoded image to draw
+	pub fn set_data(&mut self, data: Option<Arc<ImageData>>) {
+		self.data = data;
+	}
 }
 
 impl Component for Image {
@@ -117,6 +125,62 @@ impl Component for Image {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Explicit size, falling back on the loaded image's size in pixels
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		let (width, height) = self
+			.data
+			.as_ref()
+			.map_or((0.0, 0.0), |data| (data.width as f32, data.height as f32));
+		(self.width.unwrap_or(width), self.height.unwrap_or(height))
+	}
+}
+
+impl Paint for Image {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let frame = Rect::new(
+			bounds.x,
+			bounds.y,
+			self.width.unwrap_or(bounds.width),
+			self.height.unwrap_or(bounds.height),
+		);
+
+		if let Some(data) = &self.data {
+			return vec![RenderCommand::Image(
+				ImageCommand::new(Arc::clone(data), frame.into()).with_fit(self.fit),
+			)];
+		}
+
+		// Placeholder frame with the alt text until the image is loaded
+		vec![
+			fill_rect(frame, &theme.colors.surface),
+			stroke_rect(frame, &theme.borders.color, theme.borders.width),
+			line(
+				frame.x,
+				frame.y,
+				frame.x + frame.width,
+				frame.y + frame.height,
+				&theme.borders.color,
+				1.0,
+			),
+			line(
+				frame.x + frame.width,
+				frame.y,
+				frame.x,
+				frame.y + frame.height,
+				&theme.borders.color,
+				1.0,
+			),
+			text_in(
+				frame,
+				self.alt_text.clone(),
+				theme.typography.font_size_small,
+				&theme.colors.text_secondary,
+				TextAlign::Center,
+			),
+		]
+	}
 }
 
 #[cfg(test)]
@@ -153,6 +217,37 @@ mod tests {
 		assert_eq!(image.fit(), ImageFit::Contain);
 	}
 
+	#[test]
+	fn test_image_paint() {
+		use engage_ux_core::media::{ImageFormat, image::ColorType};
+
+		let theme = Theme::default();
+		let mut image = Image::new(1, "image.png");
+		image.properties_mut().bounds = Rect::new(0.0, 0.0, 100.0, 50.0);
+		assert!(
+			!image
+				.paint(&theme)
+				.iter()
+				.any(|command| matches!(command, RenderCommand::Image(_)))
+		);
+
+		image.set_fit(ImageFit::Contain);
+		image.set_data(Some(Arc::new(ImageData::new(
+			10,
+			10,
+			ImageFormat::Png,
+			ColorType::Rgb,
+			vec![0; 300],
+		))));
+		match image.paint(&theme).as_slice() {
+			[RenderCommand::Image(command)] => {
+				assert_eq!(command.fit, ImageFit::Contain);
+				assert_eq!(command.regions()[0].1.x, 25.0);
+			}
+			commands => panic!("unexpected commands {commands:?}"),
+		}
+	}
+
 	#[test]
 	fn test_image_lazy_loading() {
 		let mut image = Image::new(1, "image.jpg");
@@ -2,8 +2,13 @@
 
 use engage_ux_core::color::Color;
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{self, RenderCommand};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, text_color, text_in};
+
 /// Text alignment options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextAlign {
@@ -87,6 +92,34 @@ impl Component for Label {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		fonts
+			.layout_text(&self.text, self.font_size, None, None)
+			.size()
+	}
+}
+
+impl Paint for Label {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		vec![text_in(
+			self.properties.bounds,
+			self.text.clone(),
+			self.font_size,
+			text_color(&self.color, self.properties.enabled, theme),
+			self.align.into(),
+		)]
+	}
+}
+
+impl From<TextAlign> for renderer::TextAlign {
+	fn from(align: TextAlign) -> Self {
+		match align {
+			TextAlign::Left => renderer::TextAlign::Left,
+			TextAlign::Center => renderer::TextAlign::Center,
+			TextAlign::Right => renderer::TextAlign::Right,
+		}
+	}
 }
 
 #[cfg(test)]
@@ -145,4 +178,13 @@ mod tests {
 		label.set_visible(false);
 		assert!(!label.is_visible());
 	}
+
+	#[test]
+	fn test_label_measure() {
+		let mut label = Label::new(1, "Hello");
+		label.set_font_size(10.0);
+		let (width, height) = label.measure(&FontRegistry::new());
+		assert!((width - 30.0).abs() < 1e-3);
+		assert!((height - 12.0).abs() < 1e-3);
+	}
 }
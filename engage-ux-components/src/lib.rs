@@ -5,6 +5,9 @@
 //! - Interactive components (Button, Input, Checkbox, etc.)
 //! - Layout components (Container, Card, Table)
 //! - Complex components (Menu, Dialog, etc.)
+//!
+//! Every component implements [`Paint`] to describe its appearance as
+//! render commands.
 
 // Informational components
 pub mod avatar;
@@ -62,6 +65,9 @@ pub mod dialog;
 // Window components
 pub mod window_controls;
 
+// Painting
+pub mod paint;
+
 // Re-exports
 pub use accordion::{Accordion, AccordionPanel};
 pub use avatar::Avatar;
@@ -85,6 +91,7 @@ pub use link::Link;
 pub use list::{List, ListItem};
 pub use menu::{Drawer, Dropdown, HamburgerMenu, MenuItem};
 pub use pagination::Pagination;
+pub use paint::Paint;
 pub use progress::Progress;
 pub use ruler::{Ruler, RulerOrientation, RulerUnit};
 pub use select::{Select, SelectOption};
@@ -107,6 +114,7 @@ pub use window_controls::{WindowControlButton, WindowControls};
 #[cfg(test)]
 mod tests {
 	#[test]
+	#[allow(clippy::assertions_on_constants)]
 	fn components_modules_exist() {
 		// Basic smoke test to ensure modules compile
 		assert!(true);
@@ -1,9 +1,13 @@
 //! Line numbers component for code editors
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, clip, fill_rect, restore_clip, text_in};
+
 /// Line numbers component for displaying line numbers in code editors
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LineNumbers {
@@ -138,6 +142,44 @@ impl Component for LineNumbers {
 	}
 }
 
+impl Paint for LineNumbers {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let gutter = Rect::new(bounds.x, bounds.y, self.width, bounds.height);
+		let row_height = self.font_size * 1.5;
+		let mut commands = vec![fill_rect(gutter, &self.background_color), clip(gutter)];
+
+		for (row, number) in (self.start_line..=self.end_line).enumerate() {
+			let y = gutter.y + row as f32 * row_height;
+			if y >= gutter.y + gutter.height {
+				break;
+			}
+			let row_rect = Rect::new(gutter.x, y, gutter.width, row_height);
+			let color = if self.current_line == Some(number) {
+				commands.push(fill_rect(row_rect, &self.current_line_background));
+				&self.current_line_color
+			} else {
+				&self.color
+			};
+			commands.push(text_in(
+				Rect::new(
+					row_rect.x,
+					row_rect.y,
+					(row_rect.width - self.padding).max(0.0),
+					row_height,
+				),
+				number.to_string(),
+				self.font_size,
+				color,
+				TextAlign::Right,
+			));
+		}
+		commands.push(restore_clip());
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
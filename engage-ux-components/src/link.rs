@@ -3,8 +3,13 @@
 use engage_ux_core::color::Color;
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, baseline_in, line, text_color, text_in, text_width};
+
 /// Link component
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Link {
@@ -156,6 +161,34 @@ impl Component for Link {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		fonts
+			.layout_text(&self.text, self.font_size, None, None)
+			.size()
+	}
+}
+
+impl Paint for Link {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let color = text_color(&self.color, self.properties.enabled, theme);
+		let mut commands = vec![text_in(
+			bounds,
+			self.text.clone(),
+			self.font_size,
+			color,
+			TextAlign::Left,
+		)];
+
+		if self.underline {
+			let y = baseline_in(bounds, self.font_size) + 1.0;
+			let width = text_width(theme, &self.text, self.font_size).min(bounds.width);
+			commands.push(line(bounds.x, y, bounds.x + width, y, color, 1.0));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -1,9 +1,16 @@
 //! List component for displaying collections of items
 
+use engage_ux_core::accessibility::{Orientation, RovingFocus};
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::input::keyboard::KeyEventType;
+use engage_ux_core::input::{InputHandler, KeyCode, KeyboardEvent};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, clip, fill_rect, restore_clip, stroke_rect, text_color, text_in};
+
 /// List item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListItem {
@@ -30,6 +37,8 @@ pub struct List {
 	selected_index: Option<usize>,
 	multi_select: bool,
 	selected_indices: Vec<usize>,
+	#[serde(default)]
+	active_index: Option<usize>,
 	item_height: f32,
 	color: Color,
 	background_color: Color,
@@ -46,6 +55,7 @@ impl List {
 			selected_index: None,
 			multi_select: false,
 			selected_indices: Vec::new(),
+			active_index: None,
 			item_height: 40.0,
 			color: Color::from_hex("#000000").unwrap(),
 			background_color: Color::from_hex("#FFFFFF").unwrap(),
@@ -101,6 +111,11 @@ impl List {
 		&self.selected_indices
 	}
 
+	/// Get the item keyboard navigation is on
+	pub fn active_index(&self) -> Option<usize> {
+		self.active_index.filter(|&index| index < self.items.len())
+	}
+
 	/// Enable multi-select
 	pub fn set_multi_select(&mut self, multi_select: bool) {
 		self.multi_select = multi_select;
@@ -156,9 +171,95 @@ impl Component for List {
 	}
 }
 
+/// Up and Down move between items, skipping disabled ones. In single
+/// select mode the selection follows; in multi-select mode Space toggles the
+/// item moved to.
+impl InputHandler for List {
+	fn handle_keyboard(&mut self, event: &KeyboardEvent) -> bool {
+		if !self.properties.enabled {
+			return false;
+		}
+		let current = self.active_index().or(self.selected_index);
+		if self.multi_select
+			&& event.event_type == KeyEventType::Down
+			&& event.key_code == KeyCode::Space
+			&& let Some(index) = current
+		{
+			if self.selected_indices.contains(&index) {
+				self.deselect(index);
+			} else {
+				self.select(index);
+			}
+			return true;
+		}
+
+		let roving = RovingFocus::new(Orientation::Vertical).with_wrap(false);
+		let Some(index) = roving.next_index(event, current, self.items.len(), |index| {
+			self.items[index].disabled
+		}) else {
+			return false;
+		};
+		self.active_index = Some(index);
+		if !self.multi_select {
+			self.select(index);
+		}
+		true
+	}
+}
+
+impl Paint for List {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let mut commands = vec![fill_rect(bounds, &self.background_color), clip(bounds)];
+
+		for (index, item) in self.items.iter().enumerate() {
+			let y = bounds.y + index as f32 * self.item_height;
+			if y >= bounds.y + bounds.height {
+				break;
+			}
+			let row = Rect::new(bounds.x, y, bounds.width, self.item_height);
+			let selected = if self.multi_select {
+				self.selected_indices.contains(&index)
+			} else {
+				self.selected_index == Some(index)
+			};
+			if selected {
+				commands.push(fill_rect(row, &self.selected_color));
+			}
+			commands.push(text_in(
+				Rect::new(
+					row.x + padding,
+					row.y,
+					(row.width - padding * 2.0).max(0.0),
+					row.height,
+				),
+				item.text.clone(),
+				font_size,
+				text_color(
+					&self.color,
+					self.properties.enabled && !item.disabled,
+					theme,
+				),
+				TextAlign::Left,
+			));
+		}
+		commands.push(restore_clip());
+		commands.push(stroke_rect(
+			bounds,
+			&theme.borders.color,
+			theme.borders.width,
+		));
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use engage_ux_core::input::KeyModifiers;
 
 	#[test]
 	fn test_list_creation() {
@@ -202,4 +303,32 @@ mod tests {
 		assert!(list.selected_indices().contains(&0));
 		assert!(list.selected_indices().contains(&2));
 	}
+
+	#[test]
+	fn test_list_arrow_keys() {
+		let mut list = List::new(1);
+		for value in ["1", "2", "3"] {
+			list.add_item(ListItem::new(value, value));
+		}
+		list.items[1].disabled = true;
+		let key = |code| KeyboardEvent::key_down(code, KeyModifiers::empty());
+
+		// The selection follows, stopping at the ends
+		assert!(list.handle_keyboard(&key(KeyCode::ArrowDown)));
+		assert_eq!(list.selected_index(), Some(0));
+		list.handle_keyboard(&key(KeyCode::ArrowDown));
+		assert_eq!(list.selected_index(), Some(2));
+		assert!(!list.handle_keyboard(&key(KeyCode::ArrowDown)));
+
+		// Space picks items in multi-select mode
+		list.set_multi_select(true);
+		list.handle_keyboard(&key(KeyCode::Home));
+		assert!(list.selected_indices().is_empty());
+		list.handle_keyboard(&key(KeyCode::Space));
+		list.handle_keyboard(&key(KeyCode::End));
+		list.handle_keyboard(&key(KeyCode::Space));
+		assert_eq!(list.selected_indices(), &[0, 2]);
+		list.handle_keyboard(&key(KeyCode::Space));
+		assert_eq!(list.selected_indices(), &[0]);
+	}
 }
@@ -1,10 +1,14 @@
 //! Menu components (dropdown, drawer, hamburger)
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, line, stroke_rect, text_color, text_in};
+
 /// Menu item
 #[derive(Clone, Serialize, Deserialize)]
 pub struct MenuItem {
@@ -174,6 +178,67 @@ impl Component for Dropdown {
 	}
 }
 
+impl Paint for Dropdown {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.open {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let row_height = font_size * 2.0;
+		let padding = theme.spacing.medium;
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+		let mut y = bounds.y;
+
+		for item in &self.items {
+			if item.separator {
+				let middle = y + padding / 2.0;
+				commands.push(line(
+					bounds.x,
+					middle,
+					bounds.x + bounds.width,
+					middle,
+					&self.border_color,
+					1.0,
+				));
+				y += padding;
+				continue;
+			}
+
+			let row = Rect::new(
+				bounds.x + padding,
+				y,
+				(bounds.width - padding * 2.0).max(0.0),
+				row_height,
+			);
+			let color = text_color(&self.color, !item.disabled, theme);
+			commands.push(text_in(
+				row,
+				item.label.clone(),
+				font_size,
+				color,
+				TextAlign::Left,
+			));
+			if let Some(shortcut) = &item.shortcut {
+				commands.push(text_in(
+					row,
+					shortcut.clone(),
+					theme.typography.font_size_small,
+					&theme.colors.text_secondary,
+					TextAlign::Right,
+				));
+			} else if !item.submenu.is_empty() {
+				commands.push(text_in(row, "›", font_size, color, TextAlign::Right));
+			}
+			y += row_height;
+		}
+		commands.push(stroke_rect(bounds, &self.border_color, theme.borders.width));
+
+		commands
+	}
+}
+
 /// Drawer position
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DrawerPosition {
@@ -306,6 +371,41 @@ impl Component for Drawer {
 	}
 }
 
+impl Paint for Drawer {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		if !self.open {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let width = self.width.min(bounds.width);
+		let height = self.width.min(bounds.height);
+		let panel = match self.position {
+			DrawerPosition::Left => Rect::new(bounds.x, bounds.y, width, bounds.height),
+			DrawerPosition::Right => Rect::new(
+				bounds.x + bounds.width - width,
+				bounds.y,
+				width,
+				bounds.height,
+			),
+			DrawerPosition::Top => Rect::new(bounds.x, bounds.y, bounds.width, height),
+			DrawerPosition::Bottom => Rect::new(
+				bounds.x,
+				bounds.y + bounds.height - height,
+				bounds.width,
+				height,
+			),
+		};
+
+		let mut commands = Vec::new();
+		if self.overlay {
+			commands.push(fill_rect(bounds, &self.overlay_color));
+		}
+		commands.push(fill_rect(panel, &self.background_color));
+		commands
+	}
+}
+
 /// Hamburger menu button (typically opens a drawer or menu)
 #[derive(Clone, Serialize, Deserialize)]
 pub struct HamburgerMenu {
@@ -408,6 +508,31 @@ impl Component for HamburgerMenu {
 	}
 }
 
+impl Paint for HamburgerMenu {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let size = self.size.min(bounds.width).min(bounds.height);
+		let x = bounds.x + (bounds.width - size) / 2.0;
+		let y = bounds.y + (bounds.height - size) / 2.0;
+		let thickness = (size / 8.0).max(1.0);
+
+		if self.open {
+			// Close glyph
+			return vec![
+				line(x, y, x + size, y + size, &self.color, thickness),
+				line(x + size, y, x, y + size, &self.color, thickness),
+			];
+		}
+
+		(1..=3)
+			.map(|bar| {
+				let bar_y = y + size * bar as f32 / 4.0;
+				line(x, bar_y, x + size, bar_y, &self.color, thickness)
+			})
+			.collect()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
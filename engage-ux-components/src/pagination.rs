@@ -1,10 +1,14 @@
 //! Pagination component for navigating through pages
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, fill_rect, stroke_rect, text_color, text_in};
+
 /// Pagination variant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PaginationVariant {
@@ -212,6 +216,90 @@ impl Component for Pagination {
 	}
 }
 
+impl Paint for Pagination {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let button = match self.size {
+			PaginationSize::Small => 24.0,
+			PaginationSize::Medium => 32.0,
+			PaginationSize::Large => 40.0,
+		};
+		let font_size = button * 0.45;
+		let gap = theme.spacing.small;
+		let y = bounds.y + (bounds.height - button) / 2.0;
+
+		// Pages around the current one, with ellipses for the gaps
+		let first = self.current_page.saturating_sub(self.sibling_count).max(1);
+		let last = (self.current_page + self.sibling_count).min(self.total_pages);
+		let mut labels: Vec<(String, bool)> = Vec::new();
+		if self.show_first_last && self.current_page > 1 {
+			labels.push(("«".into(), false));
+		}
+		if self.show_prev_next && self.current_page > 1 {
+			labels.push(("‹".into(), false));
+		}
+		if first > 1 {
+			labels.push(("1".into(), false));
+			if first > 2 {
+				labels.push(("…".into(), false));
+			}
+		}
+		for page in first..=last {
+			labels.push((page.to_string(), page == self.current_page));
+		}
+		if last < self.total_pages {
+			if last + 1 < self.total_pages {
+				labels.push(("…".into(), false));
+			}
+			labels.push((self.total_pages.to_string(), false));
+		}
+		if self.show_prev_next && self.current_page < self.total_pages {
+			labels.push(("›".into(), false));
+		}
+		if self.show_first_last && self.current_page < self.total_pages {
+			labels.push(("»".into(), false));
+		}
+
+		let mut commands = Vec::new();
+		let mut x = bounds.x;
+		for (label, active) in labels {
+			let rect = Rect::new(x, y, button, button);
+			let color = if active {
+				&self.active_color
+			} else {
+				text_color(&self.color, self.properties.enabled, theme)
+			};
+			match self.variant {
+				PaginationVariant::Default | PaginationVariant::Outlined if active => {
+					commands.push(fill_rect(rect, &self.active_background));
+				}
+				PaginationVariant::Rounded => {
+					let background = if active {
+						&self.active_background
+					} else {
+						&self.background_color
+					};
+					commands.push(circle(
+						rect.x + button / 2.0,
+						rect.y + button / 2.0,
+						button / 2.0,
+						background,
+						true,
+					));
+				}
+				PaginationVariant::Outlined => {
+					commands.push(stroke_rect(rect, &theme.borders.color, theme.borders.width));
+				}
+				PaginationVariant::Default => {}
+			}
+			commands.push(text_in(rect, label, font_size, color, TextAlign::Center));
+			x += button + gap;
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -0,0 +1,509 @@
+//! Painting components into render commands
+//!
+//! Every component implements [`Paint`], which describes how the component
+//! looks as a list of [`RenderCommand`]s drawn within its bounds using a
+//! [`Theme`]. The commands can be executed by any [`RenderContext`].
+
+use engage_ux_core::animation::AnimationValue;
+use engage_ux_core::color::Color;
+use engage_ux_core::component::{Component, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_core::rendering::DamageRegion;
+use engage_ux_oal::backends::renderer::{
+	self, DisplayList, RenderCommand, RenderContext, TextAlign,
+};
+use engage_ux_oal::backends::{BlendMode, Brush, CornerRadii, Transform};
+use engage_ux_themes::Theme;
+
+/// Fraction of the font size between the baseline and the top of capitals,
+/// used to centre a line of text vertically
+const CAP_HEIGHT_RATIO: f32 = 0.7;
+
+/// Base font size of the default theme, which components that draw text at
+/// the theme's size are measured at
+pub const MEASURE_FONT_SIZE: f32 = 16.0;
+
+/// Small spacing of the default theme, between an indicator and its label
+pub const MEASURE_SMALL_SPACING: f32 = 6.0;
+
+/// Medium spacing of the default theme, around text inside a frame
+pub const MEASURE_MEDIUM_SPACING: f32 = 12.0;
+
+/// Length sliders and progress bars are measured at, having no content to
+/// size them
+pub const MEASURE_TRACK_LENGTH: f32 = 120.0;
+
+/// Trait for components that can draw themselves
+pub trait Paint: Component {
+	/// Produce the render commands that draw this component within its bounds
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand>;
+
+	/// Paint this component into a render context, skipping it when hidden
+	fn render(&self, theme: &Theme, context: &mut dyn RenderContext) {
+		if self.is_visible() {
+			context.execute_batch(&self.paint(theme));
+		}
+	}
+
+	/// Record the commands that draw this component into a display list,
+	/// which is empty when the component is hidden
+	fn record(&self, theme: &Theme) -> DisplayList {
+		let mut list = DisplayList::new();
+		self.render(theme, &mut list);
+		list
+	}
+
+	/// Paint this component only if anything it draws, shadows included,
+	/// touches the damaged region
+	///
+	/// Use with [`RenderContext::set_damage`] to repaint a frame partially.
+	fn render_damaged(
+		&self,
+		theme: &Theme,
+		context: &mut dyn RenderContext,
+		damage: &DamageRegion,
+	) {
+		if !self.is_visible() {
+			return;
+		}
+		let commands = self.paint(theme);
+		if renderer::commands_bounds(&commands)
+			.is_some_and(|bounds| damage.intersects(&bounds.into()))
+		{
+			context.execute_batch(&commands);
+		}
+	}
+}
+
+/// Fill a rectangle with a color
+pub fn fill_rect(rect: Rect, color: &Color) -> RenderCommand {
+	RenderCommand::FillRect {
+		rect: rect.into(),
+		color: color.into(),
+	}
+}
+
+/// Stroke the outline of a rectangle
+pub fn stroke_rect(rect: Rect, color: &Color, width: f32) -> RenderCommand {
+	RenderCommand::StrokeRect {
+		rect: rect.into(),
+		color: color.into(),
+		width,
+	}
+}
+
+/// Fill a rectangle whose corners are rounded with `radius`
+pub fn fill_rounded_rect(rect: Rect, radius: f32, color: &Color) -> RenderCommand {
+	RenderCommand::FillRoundedRect {
+		rect: rect.into(),
+		radii: CornerRadii::uniform(radius),
+		brush: Brush::Solid(color.into()),
+	}
+}
+
+/// Stroke the outline of a rectangle whose corners are rounded with `radius`
+pub fn stroke_rounded_rect(rect: Rect, radius: f32, color: &Color, width: f32) -> RenderCommand {
+	RenderCommand::StrokeRoundedRect {
+		rect: rect.into(),
+		radii: CornerRadii::uniform(radius),
+		brush: Brush::Solid(color.into()),
+		width,
+	}
+}
+
+/// Drop shadow of a rectangle with corners rounded with `radius`, raised
+/// `elevation` levels above the surface behind it
+///
+/// The theme's shadow offsets and blur are scaled by the elevation. There
+/// is no shadow when the theme disables shadows or the elevation is zero.
+pub fn shadow(rect: Rect, radius: f32, elevation: f32, theme: &Theme) -> Option<RenderCommand> {
+	let style = &theme.shadows;
+	(style.enabled && elevation > 0.0).then(|| RenderCommand::Shadow {
+		rect: rect.into(),
+		radii: CornerRadii::uniform(radius),
+		color: (&style.color).into(),
+		offset_x: style.offset_x * elevation,
+		offset_y: style.offset_y * elevation,
+		blur_radius: style.blur_radius * elevation,
+		spread: 0.0,
+	})
+}
+
+/// Blur whatever has been drawn behind a rectangle with corners rounded
+/// with `radius`
+pub fn backdrop_blur(rect: Rect, radius: f32, blur_radius: f32) -> RenderCommand {
+	RenderCommand::BackdropBlur {
+		rect: rect.into(),
+		radii: CornerRadii::uniform(radius),
+		blur_radius,
+	}
+}
+
+/// Draw a straight line
+pub fn line(x1: f32, y1: f32, x2: f32, y2: f32, color: &Color, width: f32) -> RenderCommand {
+	RenderCommand::Line {
+		x1,
+		y1,
+		x2,
+		y2,
+		color: color.into(),
+		width,
+	}
+}
+
+/// Draw a filled or outlined circle
+pub fn circle(x: f32, y: f32, radius: f32, color: &Color, filled: bool) -> RenderCommand {
+	RenderCommand::Circle {
+		x,
+		y,
+		radius,
+		color: color.into(),
+		filled,
+	}
+}
+
+/// Draw a run of text with its baseline at `y`
+pub fn text(
+	content: impl Into<String>,
+	x: f32,
+	y: f32,
+	font_size: f32,
+	color: &Color,
+	align: TextAlign,
+) -> RenderCommand {
+	RenderCommand::Text {
+		text: content.into(),
+		x,
+		y,
+		font_size,
+		color: color.into(),
+		align,
+	}
+}
+
+/// Draw a single line of text vertically centred in a rectangle
+///
+/// The horizontal anchor is the left edge, centre or right edge of the
+/// rectangle depending on `align`.
+pub fn text_in(
+	rect: Rect,
+	content: impl Into<String>,
+	font_size: f32,
+	color: &Color,
+	align: TextAlign,
+) -> RenderCommand {
+	let x = match align {
+		TextAlign::Left => rect.x,
+		TextAlign::Center => rect.x + rect.width / 2.0,
+		TextAlign::Right => rect.x + rect.width,
+	};
+	text(
+		content,
+		x,
+		baseline_in(rect, font_size),
+		font_size,
+		color,
+		align,
+	)
+}
+
+/// Draw multi-line text, one row of `line_height` pixels per line, starting
+/// at the top of a rectangle
+pub fn text_lines(
+	rect: Rect,
+	content: &str,
+	font_size: f32,
+	line_height: f32,
+	color: &Color,
+	align: TextAlign,
+) -> Vec<RenderCommand> {
+	content
+		.lines()
+		.enumerate()
+		.map(|(index, row)| {
+			let row_rect = Rect::new(
+				rect.x,
+				rect.y + index as f32 * line_height,
+				rect.width,
+				line_height,
+			);
+			text_in(row_rect, row, font_size, color, align)
+		})
+		.collect()
+}
+
+/// Advance width of a run of text in the theme's fonts, for laying out
+/// inline content
+pub fn text_width(theme: &Theme, content: &str, font_size: f32) -> f32 {
+	measure_text(&theme.fonts, content, font_size).0
+}
+
+/// Size of a single line of text
+pub fn measure_text(fonts: &FontRegistry, content: &str, font_size: f32) -> (f32, f32) {
+	fonts.layout_text(content, font_size, None, None).size()
+}
+
+/// Size of an indicator followed by a label, as checkboxes, radio buttons
+/// and toggles draw them
+pub fn measure_labelled(fonts: &FontRegistry, indicator: (f32, f32), label: &str) -> (f32, f32) {
+	if label.is_empty() {
+		return indicator;
+	}
+	let (width, height) = measure_text(fonts, label, MEASURE_FONT_SIZE);
+	(
+		indicator.0 + MEASURE_SMALL_SPACING + width,
+		indicator.1.max(height),
+	)
+}
+
+/// Baseline that vertically centres a line of text in a rectangle
+pub fn baseline_in(rect: Rect, font_size: f32) -> f32 {
+	rect.y + (rect.height + font_size * CAP_HEIGHT_RATIO) / 2.0
+}
+
+/// Restrict drawing to a rectangle until [`restore_clip`]
+pub fn clip(rect: Rect) -> RenderCommand {
+	RenderCommand::SetClip(rect.into())
+}
+
+/// Restore the clip region saved by the matching [`clip`]
+pub fn restore_clip() -> RenderCommand {
+	RenderCommand::RestoreClip
+}
+
+/// Wrap a component's commands so that an animation value changes how they
+/// are drawn
+///
+/// Opacity fades the commands together as one layer and a position moves
+/// them by its offset. Scale and rotation, in degrees, apply around the
+/// centre of `bounds`. Colors are left to the component.
+pub fn animate(
+	commands: Vec<RenderCommand>,
+	value: &AnimationValue,
+	bounds: Rect,
+) -> Vec<RenderCommand> {
+	let (cx, cy) = (
+		bounds.x + bounds.width / 2.0,
+		bounds.y + bounds.height / 2.0,
+	);
+	let (push, pop) = match *value {
+		AnimationValue::Opacity(opacity) if opacity < 1.0 => (
+			RenderCommand::PushLayer {
+				opacity: opacity.max(0.0),
+				blend_mode: BlendMode::Normal,
+			},
+			RenderCommand::PopLayer,
+		),
+		AnimationValue::Position { x, y } => (
+			RenderCommand::PushTransform(Transform::from_translate(x, y)),
+			RenderCommand::PopTransform,
+		),
+		AnimationValue::Scale(scale) => (
+			RenderCommand::PushTransform(
+				Transform::from_translate(cx, cy)
+					.scale(scale, scale)
+					.translate(-cx, -cy),
+			),
+			RenderCommand::PopTransform,
+		),
+		AnimationValue::Rotation(degrees) => (
+			RenderCommand::PushTransform(Transform::from_rotate_at(degrees.to_radians(), cx, cy)),
+			RenderCommand::PopTransform,
+		),
+		_ => return commands,
+	};
+
+	let mut animated = Vec::with_capacity(commands.len() + 2);
+	animated.push(push);
+	animated.extend(commands);
+	animated.push(pop);
+	animated
+}
+
+/// Shrink a rectangle by `amount` on every side
+pub fn inset(rect: Rect, amount: f32) -> Rect {
+	Rect::new(
+		rect.x + amount,
+		rect.y + amount,
+		(rect.width - amount * 2.0).max(0.0),
+		(rect.height - amount * 2.0).max(0.0),
+	)
+}
+
+/// Pick the text color for a component, dimmed when it is disabled
+pub fn text_color<'a>(color: &'a Color, enabled: bool, theme: &'a Theme) -> &'a Color {
+	if enabled {
+		color
+	} else {
+		&theme.colors.text_disabled
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_text_in_alignment() {
+		let rect = Rect::new(10.0, 20.0, 100.0, 40.0);
+		let color = Color::from_hex("#000000").unwrap();
+
+		match text_in(rect, "Hi", 10.0, &color, TextAlign::Center) {
+			RenderCommand::Text { x, y, .. } => {
+				assert_eq!(x, 60.0);
+				assert_eq!(y, 20.0 + (40.0 + 7.0) / 2.0);
+			}
+			_ => panic!("Expected text command"),
+		}
+
+		match text_in(rect, "Hi", 10.0, &color, TextAlign::Right) {
+			RenderCommand::Text { x, .. } => assert_eq!(x, 110.0),
+			_ => panic!("Expected text command"),
+		}
+	}
+
+	#[test]
+	fn test_rounded_rect() {
+		let color = Color::from_hex("#FF0000").unwrap();
+		match fill_rounded_rect(Rect::new(0.0, 0.0, 40.0, 20.0), 6.0, &color) {
+			RenderCommand::FillRoundedRect { radii, brush, .. } => {
+				assert_eq!(radii, CornerRadii::uniform(6.0));
+				assert_eq!(brush, Brush::Solid((&color).into()));
+			}
+			_ => panic!("Expected rounded rectangle command"),
+		}
+	}
+
+	#[test]
+	fn test_shadow() {
+		let mut theme = Theme::default();
+		theme.shadows.enabled = true;
+		let rect = Rect::new(10.0, 10.0, 100.0, 50.0);
+
+		match shadow(rect, 4.0, 2.0, &theme) {
+			Some(RenderCommand::Shadow {
+				offset_y,
+				blur_radius,
+				radii,
+				..
+			}) => {
+				assert_eq!(offset_y, theme.shadows.offset_y * 2.0);
+				assert_eq!(blur_radius, theme.shadows.blur_radius * 2.0);
+				assert_eq!(radii, CornerRadii::uniform(4.0));
+			}
+			_ => panic!("Expected shadow command"),
+		}
+		assert!(shadow(rect, 4.0, 0.0, &theme).is_none());
+
+		theme.shadows.enabled = false;
+		assert!(shadow(rect, 4.0, 2.0, &theme).is_none());
+	}
+
+	#[test]
+	fn test_animate() {
+		let bounds = Rect::new(0.0, 0.0, 100.0, 50.0);
+		let color = Color::from_hex("#000000").unwrap();
+		let commands = vec![fill_rect(bounds, &color)];
+
+		let faded = animate(commands.clone(), &AnimationValue::Opacity(0.5), bounds);
+		assert_eq!(faded.len(), 3);
+		assert!(matches!(
+			faded[0],
+			RenderCommand::PushLayer { opacity, .. } if opacity == 0.5
+		));
+		assert!(matches!(faded[2], RenderCommand::PopLayer));
+
+		// Rotation turns about the centre of the bounds
+		let rotated = animate(commands.clone(), &AnimationValue::Rotation(90.0), bounds);
+		match &rotated[0] {
+			RenderCommand::PushTransform(transform) => {
+				let (x, y) = transform.map_point(50.0, 25.0);
+				assert!((x - 50.0).abs() < 1e-4 && (y - 25.0).abs() < 1e-4);
+			}
+			command => panic!("Expected a transform, got {:?}", command),
+		}
+		assert!(matches!(rotated[2], RenderCommand::PopTransform));
+
+		let opaque = animate(commands, &AnimationValue::Opacity(1.0), bounds);
+		assert_eq!(opaque.len(), 1);
+	}
+
+	#[test]
+	fn test_inset() {
+		let rect = inset(Rect::new(0.0, 0.0, 100.0, 50.0), 10.0);
+		assert_eq!(rect, Rect::new(10.0, 10.0, 80.0, 30.0));
+
+		let collapsed = inset(Rect::new(0.0, 0.0, 10.0, 10.0), 10.0);
+		assert_eq!(collapsed.width, 0.0);
+	}
+
+	#[test]
+	fn test_text_color_disabled() {
+		let theme = Theme::default();
+		let color = Color::from_hex("#123456").unwrap();
+		assert_eq!(text_color(&color, true, &theme), &color);
+		assert_eq!(
+			text_color(&color, false, &theme),
+			&theme.colors.text_disabled
+		);
+	}
+
+	struct Recorder(Vec<RenderCommand>);
+
+	impl RenderContext for Recorder {
+		fn execute(&mut self, command: RenderCommand) {
+			self.0.push(command);
+		}
+
+		fn begin_frame(&mut self) {}
+
+		fn end_frame(&mut self) {}
+
+		fn size(&self) -> (u32, u32) {
+			(0, 0)
+		}
+	}
+
+	#[test]
+	fn test_render_skips_hidden() {
+		let theme = Theme::default();
+		let mut label = crate::Label::new(1, "Hidden");
+		let mut recorder = Recorder(Vec::new());
+
+		label.render(&theme, &mut recorder);
+		assert_eq!(recorder.0.len(), 1);
+
+		label.set_visible(false);
+		recorder.0.clear();
+		label.render(&theme, &mut recorder);
+		assert!(recorder.0.is_empty());
+	}
+
+	#[test]
+	fn test_record() {
+		let theme = Theme::default();
+		let mut label = crate::Label::new(1, "Recorded");
+		assert_eq!(label.record(&theme).len(), label.paint(&theme).len());
+
+		label.set_visible(false);
+		assert!(label.record(&theme).is_empty());
+	}
+
+	#[test]
+	fn test_render_damaged() {
+		let theme = Theme::default();
+		let mut console = crate::Console::new(1);
+		console.properties_mut().bounds = Rect::new(0.0, 0.0, 200.0, 100.0);
+		console.add_text("ready");
+		let mut recorder = Recorder(Vec::new());
+
+		// Damage elsewhere on the surface leaves the console alone
+		let elsewhere = DamageRegion::from_rect(Rect::new(300.0, 0.0, 50.0, 50.0));
+		console.render_damaged(&theme, &mut recorder, &elsewhere);
+		assert!(recorder.0.is_empty());
+
+		let overlapping = DamageRegion::from_rect(Rect::new(150.0, 80.0, 100.0, 100.0));
+		console.render_damaged(&theme, &mut recorder, &overlapping);
+		assert_eq!(recorder.0.len(), console.paint(&theme).len());
+	}
+}
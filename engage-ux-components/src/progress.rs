@@ -1,9 +1,17 @@
 //! Progress indicator component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{MEASURE_TRACK_LENGTH, Paint, fill_rect, line, text_in};
+
+/// Diameter circular progress is measured at
+const CIRCLE_DIAMETER: f32 = 40.0;
+
 /// Progress indicator type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProgressType {
@@ -160,8 +168,110 @@ impl Component for Progress {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Bar of the default length, or a circle of the default diameter
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		match self.progress_type {
+			ProgressType::Linear => (MEASURE_TRACK_LENGTH, self.height),
+			ProgressType::Circular | ProgressType::Indeterminate => {
+				(CIRCLE_DIAMETER, CIRCLE_DIAMETER)
+			}
+		}
+	}
+}
+
+impl Paint for Progress {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let fraction = self.percentage().clamp(0.0, 1.0);
+		let mut commands = Vec::new();
+
+		match self.progress_type {
+			ProgressType::Linear => {
+				let track = Rect::new(
+					bounds.x,
+					bounds.y + (bounds.height - self.height).max(0.0) / 2.0,
+					bounds.width,
+					self.height.min(bounds.height),
+				);
+				commands.push(fill_rect(track, &self.background_color));
+				commands.push(fill_rect(
+					Rect::new(track.x, track.y, track.width * fraction, track.height),
+					&self.color,
+				));
+			}
+			ProgressType::Circular | ProgressType::Indeterminate => {
+				let radius = (bounds.width.min(bounds.height) - self.thickness) / 2.0;
+				let (cx, cy) = (
+					bounds.x + bounds.width / 2.0,
+					bounds.y + bounds.height / 2.0,
+				);
+				// Indeterminate progress draws a fixed quarter-turn spinner
+				let (start, sweep) = match self.progress_type {
+					ProgressType::Indeterminate => (0.0, 0.25),
+					_ => (0.0, fraction),
+				};
+
+				let track = arc(cx, cy, radius, 0.0, 1.0);
+				commands.extend(track.windows(2).map(|pair| {
+					line(
+						pair[0].0,
+						pair[0].1,
+						pair[1].0,
+						pair[1].1,
+						&self.background_color,
+						self.thickness,
+					)
+				}));
+				let value = arc(cx, cy, radius, start, sweep);
+				commands.extend(value.windows(2).map(|pair| {
+					line(
+						pair[0].0,
+						pair[0].1,
+						pair[1].0,
+						pair[1].1,
+						&self.color,
+						self.thickness,
+					)
+				}));
+			}
+		}
+
+		if self.show_label && self.progress_type != ProgressType::Indeterminate {
+			let label = if self.label.is_empty() {
+				format!("{:.0}%", fraction * 100.0)
+			} else {
+				self.label.clone()
+			};
+			commands.push(text_in(
+				bounds,
+				label,
+				theme.typography.font_size_small,
+				&theme.colors.text_primary,
+				TextAlign::Center,
+			));
+		}
+
+		commands
+	}
 }
 
+/// Points along a clockwise arc starting at 12 o'clock, with `start` and
+/// `sweep` given as fractions of a full turn
+fn arc(cx: f32, cy: f32, radius: f32, start: f32, sweep: f32) -> Vec<(f32, f32)> {
+	let segments = ((ARC_SEGMENTS as f32 * sweep).ceil() as usize).max(1);
+	(0..=segments)
+		.map(|step| {
+			let turn = start + sweep * step as f32 / segments as f32;
+			let angle = turn * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+			(cx + radius * angle.cos(), cy + radius * angle.sin())
+		})
+		.collect()
+}
+
+/// Number of line segments used to approximate a circular arc
+const ARC_SEGMENTS: usize = 48;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -219,4 +329,26 @@ mod tests {
 		assert!(progress.shows_label());
 		assert_eq!(progress.label(), "Loading...");
 	}
+
+	#[test]
+	fn test_progress_paint_linear() {
+		let theme = Theme::default();
+		let mut progress = Progress::new(1);
+		progress.properties_mut().bounds = Rect::new(0.0, 0.0, 200.0, 20.0);
+		progress.set_value(25.0);
+
+		let commands = progress.paint(&theme);
+		match &commands[1] {
+			RenderCommand::FillRect { rect, .. } => assert_eq!(rect.width, 50.0),
+			_ => panic!("Expected progress fill"),
+		}
+	}
+
+	#[test]
+	fn test_arc_endpoints() {
+		let points = arc(0.0, 0.0, 10.0, 0.0, 0.5);
+		let (first, last) = (points[0], points[points.len() - 1]);
+		assert!(first.0.abs() < 1e-4 && (first.1 + 10.0).abs() < 1e-4);
+		assert!(last.0.abs() < 1e-4 && (last.1 - 10.0).abs() < 1e-4);
+	}
 }
@@ -1,10 +1,17 @@
 //! Radio button component
 
+use engage_ux_core::accessibility::{Orientation, RovingFocus};
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::input::{InputHandler, KeyboardEvent};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, measure_labelled, text_color, text_in};
+
 /// Radio button component
 #[derive(Clone, Serialize, Deserialize)]
 pub struct RadioButton {
@@ -145,6 +152,52 @@ impl Component for RadioButton {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Circle followed by the label
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		measure_labelled(fonts, (self.size, self.size), &self.label)
+	}
+}
+
+impl Paint for RadioButton {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let enabled = self.properties.enabled && !self.disabled;
+		let radius = self.size.min(bounds.height) / 2.0;
+		let (cx, cy) = (bounds.x + radius, bounds.y + bounds.height / 2.0);
+		let ring = if self.selected {
+			&self.selected_color
+		} else {
+			&self.color
+		};
+		let ring = text_color(ring, enabled, theme);
+
+		let mut commands = vec![
+			circle(cx, cy, radius, &theme.colors.surface, true),
+			circle(cx, cy, radius, ring, false),
+		];
+		if self.selected {
+			commands.push(circle(cx, cy, radius * 0.5, ring, true));
+		}
+
+		if !self.label.is_empty() {
+			let x = bounds.x + radius * 2.0 + theme.spacing.small;
+			commands.push(text_in(
+				Rect::new(
+					x,
+					bounds.y,
+					(bounds.x + bounds.width - x).max(0.0),
+					bounds.height,
+				),
+				self.label.clone(),
+				theme.typography.font_size_base,
+				text_color(&self.label_color, enabled, theme),
+				TextAlign::Left,
+			));
+		}
+
+		commands
+	}
 }
 
 /// Radio group for managing multiple radio buttons
@@ -207,9 +260,44 @@ impl Component for RadioGroup {
 	}
 }
 
+/// Arrow keys select the neighbouring button, by the identifier it was
+/// added with, wrapping around at the ends
+impl InputHandler for RadioGroup {
+	fn handle_keyboard(&mut self, event: &KeyboardEvent) -> bool {
+		if !self.properties.enabled {
+			return false;
+		}
+		let current = self
+			.selected_value
+			.as_ref()
+			.and_then(|value| self.buttons.iter().position(|button| button == value));
+		let next = RovingFocus::new(Orientation::Both).next_index(
+			event,
+			current,
+			self.buttons.len(),
+			|_| false,
+		);
+		match next {
+			Some(index) => {
+				self.selected_value = Some(self.buttons[index].clone());
+				true
+			}
+			None => false,
+		}
+	}
+}
+
+impl Paint for RadioGroup {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		// The group only coordinates selection; its buttons paint themselves
+		Vec::new()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use engage_ux_core::input::{KeyCode, KeyModifiers};
 
 	#[test]
 	fn test_radio_button_creation() {
@@ -265,4 +353,21 @@ mod tests {
 		group.set_selected_value(Some("opt1".to_string()));
 		assert_eq!(group.selected_value(), Some("opt1"));
 	}
+
+	#[test]
+	fn test_radio_group_arrow_keys() {
+		let mut group = RadioGroup::new(1, "choices");
+		for button in ["opt1", "opt2", "opt3"] {
+			group.add_button(button);
+		}
+		let key = |code| KeyboardEvent::key_down(code, KeyModifiers::empty());
+
+		assert!(group.handle_keyboard(&key(KeyCode::ArrowDown)));
+		assert_eq!(group.selected_value(), Some("opt1"));
+		group.handle_keyboard(&key(KeyCode::ArrowLeft));
+		assert_eq!(group.selected_value(), Some("opt3"));
+		group.handle_keyboard(&key(KeyCode::ArrowRight));
+		assert_eq!(group.selected_value(), Some("opt1"));
+		assert!(!group.handle_keyboard(&key(KeyCode::Enter)));
+	}
 }
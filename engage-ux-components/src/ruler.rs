@@ -1,9 +1,13 @@
 //! Ruler component for measurement and alignment
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, clip, fill_rect, line, restore_clip, text};
+
 /// Ruler orientation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RulerOrientation {
@@ -200,6 +204,78 @@ impl Component for Ruler {
 	}
 }
 
+impl Paint for Ruler {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let scale = self.unit.pixels_per_unit();
+		let horizontal = self.orientation == RulerOrientation::Horizontal;
+		let (length, depth) = if horizontal {
+			(bounds.width, bounds.height.min(self.thickness))
+		} else {
+			(bounds.height, bounds.width.min(self.thickness))
+		};
+		let strip = if horizontal {
+			Rect::new(bounds.x, bounds.y, length, depth)
+		} else {
+			Rect::new(bounds.x, bounds.y, depth, length)
+		};
+		let font_size = theme.typography.font_size_small;
+		let mut commands = vec![fill_rect(strip, &self.background_color), clip(strip)];
+
+		// Tick from the ruler's far edge towards its origin, `size` deep
+		let tick = |offset: f32, size: f32, color: &Color| {
+			if horizontal {
+				let x = bounds.x + offset;
+				line(x, bounds.y + depth - size, x, bounds.y + depth, color, 1.0)
+			} else {
+				let y = bounds.y + offset;
+				line(bounds.x + depth - size, y, bounds.x + depth, y, color, 1.0)
+			}
+		};
+
+		if self.minor_tick_interval > 0.0 {
+			let mut value = self.start;
+			while value <= self.end {
+				let offset = (value - self.start) * scale;
+				if offset > length {
+					break;
+				}
+				commands.push(tick(offset, depth * 0.25, &self.tick_color));
+				value += self.minor_tick_interval;
+			}
+		}
+		if self.major_tick_interval > 0.0 {
+			let mut value = self.start;
+			while value <= self.end {
+				let offset = (value - self.start) * scale;
+				if offset > length {
+					break;
+				}
+				commands.push(tick(offset, depth * 0.5, &self.color));
+				if self.show_labels {
+					let (x, y) = if horizontal {
+						(bounds.x + offset + 2.0, bounds.y + font_size)
+					} else {
+						(bounds.x + 2.0, bounds.y + offset + font_size)
+					};
+					commands.push(text(
+						format!("{value}"),
+						x,
+						y,
+						font_size,
+						&self.label_color,
+						TextAlign::Left,
+					));
+				}
+				value += self.major_tick_interval;
+			}
+		}
+		commands.push(restore_clip());
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,10 +1,18 @@
 //! Select/Dropdown component for selecting from options
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	MEASURE_FONT_SIZE, MEASURE_MEDIUM_SPACING, MEASURE_SMALL_SPACING, Paint, fill_rect, inset,
+	measure_text, stroke_rect, text_color, text_in,
+};
+
 /// Select option
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectOption {
@@ -177,6 +185,89 @@ impl Component for Select {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Closed field wide enough for the placeholder and every option
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let widest = self
+			.options
+			.iter()
+			.map(|option| option.label.as_str())
+			.chain([self.placeholder.as_str()])
+			.map(|label| measure_text(fonts, label, MEASURE_FONT_SIZE).0)
+			.fold(0.0, f32::max);
+		let (arrow, _) = measure_text(fonts, "▾", MEASURE_FONT_SIZE);
+		(
+			widest + MEASURE_SMALL_SPACING + arrow + MEASURE_MEDIUM_SPACING * 2.0,
+			MEASURE_FONT_SIZE * 2.5,
+		)
+	}
+}
+
+impl Paint for Select {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let field_height = (font_size * 2.5).min(bounds.height);
+		let field = Rect::new(bounds.x, bounds.y, bounds.width, field_height);
+		let label = inset(field, padding);
+		let label = Rect::new(label.x, field.y, label.width, field.height);
+
+		let selected = self
+			.selected_index
+			.and_then(|index| self.options.get(index));
+		let (value, value_color) = match selected {
+			Some(option) => (
+				option.label.clone(),
+				text_color(&self.color, self.properties.enabled, theme),
+			),
+			None => (self.placeholder.clone(), &theme.colors.text_secondary),
+		};
+		let mut commands = vec![
+			fill_rect(field, &self.background_color),
+			stroke_rect(field, &self.border_color, theme.borders.width),
+			text_in(label, value, font_size, value_color, TextAlign::Left),
+			text_in(
+				label,
+				if self.open { "▴" } else { "▾" },
+				font_size,
+				&self.color,
+				TextAlign::Right,
+			),
+		];
+
+		if self.open {
+			let row_height = font_size * 2.0;
+			let list = Rect::new(
+				bounds.x,
+				field.y + field_height,
+				bounds.width,
+				row_height * self.options.len() as f32,
+			);
+			commands.push(fill_rect(list, &self.background_color));
+			for (index, option) in self.options.iter().enumerate() {
+				let row = Rect::new(
+					list.x,
+					list.y + index as f32 * row_height,
+					list.width,
+					row_height,
+				);
+				if self.selected_index == Some(index) {
+					commands.push(fill_rect(row, &theme.colors.surface));
+				}
+				commands.push(text_in(
+					Rect::new(row.x + padding, row.y, label.width, row.height),
+					option.label.clone(),
+					font_size,
+					text_color(&self.color, !option.disabled, theme),
+					TextAlign::Left,
+				));
+			}
+			commands.push(stroke_rect(list, &self.border_color, theme.borders.width));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -1,8 +1,15 @@
 //! Slider/range selector component
 
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::RenderCommand;
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	MEASURE_MEDIUM_SPACING, MEASURE_TRACK_LENGTH, Paint, circle, fill_rect, text_color,
+};
+
 /// Slider component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Slider {
@@ -45,4 +52,46 @@ impl Component for Slider {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Track of the default length, as tall as the thumb
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		(MEASURE_TRACK_LENGTH, MEASURE_MEDIUM_SPACING * 2.0)
+	}
+}
+
+impl Paint for Slider {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let range = self.max - self.min;
+		let fraction = if range > 0.0 {
+			((self.value - self.min) / range).clamp(0.0, 1.0)
+		} else {
+			0.0
+		};
+		let thumb_radius = (bounds.height / 2.0).min(theme.spacing.medium);
+		let track_height = (thumb_radius / 2.0).max(1.0);
+		let track = Rect::new(
+			bounds.x + thumb_radius,
+			bounds.y + (bounds.height - track_height) / 2.0,
+			(bounds.width - thumb_radius * 2.0).max(0.0),
+			track_height,
+		);
+		let thumb_x = track.x + track.width * fraction;
+		let accent = text_color(&theme.colors.primary, self.properties.enabled, theme);
+
+		vec![
+			fill_rect(track, &theme.colors.surface),
+			fill_rect(
+				Rect::new(track.x, track.y, track.width * fraction, track.height),
+				accent,
+			),
+			circle(
+				thumb_x,
+				bounds.y + bounds.height / 2.0,
+				thumb_radius,
+				accent,
+				true,
+			),
+		]
+	}
 }
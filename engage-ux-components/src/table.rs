@@ -1,9 +1,13 @@
 //! Table component for structured data display
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{self, RenderCommand};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, clip, fill_rect, line, restore_clip, stroke_rect, text_in};
+
 /// Table cell data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableCell {
@@ -263,6 +267,130 @@ impl Component for Table {
 	}
 }
 
+impl Paint for Table {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let row_height = font_size * 2.0;
+		let padding = theme.spacing.small;
+
+		// Fixed-width columns keep their width; the rest share what is left
+		let fixed: f32 = self.columns.iter().filter_map(|column| column.width).sum();
+		let flexible = self
+			.columns
+			.iter()
+			.filter(|column| column.width.is_none())
+			.count();
+		let share = if flexible > 0 {
+			((bounds.width - fixed) / flexible as f32).max(0.0)
+		} else {
+			0.0
+		};
+		let widths: Vec<f32> = self
+			.columns
+			.iter()
+			.map(|column| column.width.unwrap_or(share))
+			.collect();
+
+		let cell_rect = |column: usize, y: f32| {
+			let x = bounds.x + widths[..column].iter().sum::<f32>();
+			Rect::new(
+				x + padding,
+				y,
+				(widths[column] - padding * 2.0).max(0.0),
+				row_height,
+			)
+		};
+
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			clip(bounds),
+			fill_rect(
+				Rect::new(bounds.x, bounds.y, bounds.width, row_height),
+				&self.header_background,
+			),
+		];
+		for (index, column) in self.columns.iter().enumerate() {
+			let mut header = column.header.clone();
+			if self.sort_column == Some(index) {
+				match self.sort_direction {
+					Some(SortDirection::Ascending) => header.push_str(" ▴"),
+					Some(SortDirection::Descending) => header.push_str(" ▾"),
+					None => {}
+				}
+			}
+			commands.push(text_in(
+				cell_rect(index, bounds.y),
+				header,
+				font_size,
+				&self.color,
+				column.align.into(),
+			));
+		}
+
+		for (row_index, row) in self.rows.iter().enumerate() {
+			let y = bounds.y + (row_index + 1) as f32 * row_height;
+			if y >= bounds.y + bounds.height {
+				break;
+			}
+			let row_rect = Rect::new(bounds.x, y, bounds.width, row_height);
+			if self.selected_rows.contains(&row_index) {
+				commands.push(fill_rect(row_rect, &theme.colors.primary));
+			} else if self.striped && row_index % 2 == 1 {
+				commands.push(fill_rect(row_rect, &self.stripe_color));
+			}
+			for (index, cell) in row.iter().enumerate().take(widths.len()) {
+				commands.push(text_in(
+					cell_rect(index, y),
+					cell.value.clone(),
+					font_size,
+					cell.color.as_ref().unwrap_or(&self.color),
+					cell.align.into(),
+				));
+			}
+			if self.bordered {
+				commands.push(line(
+					bounds.x,
+					y,
+					bounds.x + bounds.width,
+					y,
+					&self.border_color,
+					1.0,
+				));
+			}
+		}
+
+		if self.bordered {
+			let mut x = bounds.x;
+			for width in &widths[..widths.len().saturating_sub(1)] {
+				x += width;
+				commands.push(line(
+					x,
+					bounds.y,
+					x,
+					bounds.y + bounds.height,
+					&self.border_color,
+					1.0,
+				));
+			}
+			commands.push(stroke_rect(bounds, &self.border_color, theme.borders.width));
+		}
+		commands.push(restore_clip());
+
+		commands
+	}
+}
+
+impl From<TextAlign> for renderer::TextAlign {
+	fn from(align: TextAlign) -> Self {
+		match align {
+			TextAlign::Left => renderer::TextAlign::Left,
+			TextAlign::Center => renderer::TextAlign::Center,
+			TextAlign::Right => renderer::TextAlign::Right,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
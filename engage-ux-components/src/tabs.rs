@@ -1,10 +1,16 @@
 //! Tabbed interface component
 
+use engage_ux_core::accessibility::{Orientation, RovingFocus};
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::input::{InputHandler, KeyboardEvent};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, line, stroke_rect, text_color, text_in, text_width};
+
 /// Tab item
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Tab {
@@ -209,9 +215,108 @@ impl Component for Tabs {
 	}
 }
 
+/// Arrow keys along the tab strip activate the neighbouring tab, skipping
+/// disabled ones
+impl InputHandler for Tabs {
+	fn handle_keyboard(&mut self, event: &KeyboardEvent) -> bool {
+		let orientation = match self.position {
+			TabPosition::Top | TabPosition::Bottom => Orientation::Horizontal,
+			TabPosition::Left | TabPosition::Right => Orientation::Vertical,
+		};
+		let next = RovingFocus::new(orientation).next_index(
+			event,
+			self.active_index(),
+			self.tabs.len(),
+			|index| self.tabs[index].disabled,
+		);
+		match next {
+			Some(index) if self.properties.enabled => {
+				self.set_active_index(index);
+				self.handle_change(&Event::from_input(self.properties.id, event.clone()));
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Paint for Tabs {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let strip_height = font_size * 2.5;
+		let horizontal = matches!(self.position, TabPosition::Top | TabPosition::Bottom);
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+
+		let mut offset = 0.0;
+		for tab in &self.tabs {
+			let mut label = tab.label.clone();
+			if let Some(badge) = &tab.badge {
+				label.push_str(&format!(" ({badge})"));
+			}
+			if tab.closable {
+				label.push_str(" ×");
+			}
+			let rect = if horizontal {
+				let width = text_width(theme, &label, font_size) + padding * 2.0;
+				let y = match self.position {
+					TabPosition::Bottom => bounds.y + bounds.height - strip_height,
+					_ => bounds.y,
+				};
+				let rect = Rect::new(bounds.x + offset, y, width, strip_height);
+				offset += width;
+				rect
+			} else {
+				let width = (bounds.width / 4.0).max(strip_height);
+				let x = match self.position {
+					TabPosition::Right => bounds.x + bounds.width - width,
+					_ => bounds.x,
+				};
+				let rect = Rect::new(x, bounds.y + offset, width, strip_height);
+				offset += strip_height;
+				rect
+			};
+
+			let active = self.active_tab.as_deref() == Some(tab.id.as_str());
+			let color = if active {
+				commands.push(fill_rect(rect, &self.active_background));
+				&self.active_color
+			} else {
+				text_color(&self.color, self.properties.enabled && !tab.disabled, theme)
+			};
+			commands.push(text_in(rect, label, font_size, color, TextAlign::Center));
+			if active {
+				// Indicator on the edge facing the content
+				let (x1, y1, x2, y2) = match self.position {
+					TabPosition::Top => (
+						rect.x,
+						rect.y + rect.height,
+						rect.x + rect.width,
+						rect.y + rect.height,
+					),
+					TabPosition::Bottom => (rect.x, rect.y, rect.x + rect.width, rect.y),
+					TabPosition::Left => (
+						rect.x + rect.width,
+						rect.y,
+						rect.x + rect.width,
+						rect.y + rect.height,
+					),
+					TabPosition::Right => (rect.x, rect.y, rect.x, rect.y + rect.height),
+				};
+				commands.push(line(x1, y1, x2, y2, &self.active_color, 2.0));
+			}
+		}
+		commands.push(stroke_rect(bounds, &self.border_color, theme.borders.width));
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use engage_ux_core::input::{KeyCode, KeyModifiers};
 
 	#[test]
 	fn test_tab_creation() {
@@ -275,4 +380,26 @@ mod tests {
 		tabs.set_position(TabPosition::Left);
 		assert_eq!(tabs.position(), TabPosition::Left);
 	}
+
+	#[test]
+	fn test_tabs_arrow_keys() {
+		let mut tabs = Tabs::new(1);
+		for id in ["a", "b", "c"] {
+			tabs.add_tab(Tab::new(id, id));
+		}
+		tabs.tabs_mut()[1].disabled = true;
+		let key = |code| KeyboardEvent::key_down(code, KeyModifiers::empty());
+
+		assert!(tabs.handle_keyboard(&key(KeyCode::ArrowRight)));
+		assert_eq!(tabs.active_tab(), Some("c"));
+		tabs.handle_keyboard(&key(KeyCode::ArrowRight));
+		assert_eq!(tabs.active_tab(), Some("a"));
+		assert!(!tabs.handle_keyboard(&key(KeyCode::ArrowDown)));
+
+		tabs.set_position(TabPosition::Left);
+		tabs.handle_keyboard(&key(KeyCode::End));
+		assert_eq!(tabs.active_tab(), Some("c"));
+		tabs.handle_keyboard(&key(KeyCode::ArrowUp));
+		assert_eq!(tabs.active_tab(), Some("a"));
+	}
 }
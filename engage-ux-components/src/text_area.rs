@@ -3,13 +3,24 @@
 use engage_ux_core::color::Color;
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::bidi::{CaretMovement, clamp_caret, move_caret};
+use engage_ux_core::media::{FontRegistry, TextDirection};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	Paint, clip, fill_rect, inset, restore_clip, stroke_rect, text_color, text_lines,
+};
+
 /// Text area component
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TextArea {
 	properties: ComponentProperties,
 	value: String,
+	/// Caret position, as a byte offset into the value
+	#[serde(default)]
+	cursor: usize,
 	placeholder: String,
 	rows: usize,
 	cols: usize,
@@ -29,6 +40,7 @@ impl TextArea {
 		Self {
 			properties: ComponentProperties::new(id),
 			value: String::new(),
+			cursor: 0,
 			placeholder: String::new(),
 			rows: 4,
 			cols: 40,
@@ -47,15 +59,75 @@ impl TextArea {
 		&self.value
 	}
 
-	/// Set the value
+	/// Set the value, placing the caret at its end
 	pub fn set_value(&mut self, value: impl Into<String>) {
 		let new_value = value.into();
-		if let Some(max_len) = self.max_length {
-			if new_value.len() <= max_len {
-				self.value = new_value;
-			}
-		} else {
+		if self
+			.max_length
+			.is_none_or(|max_len| new_value.len() <= max_len)
+		{
 			self.value = new_value;
+			self.cursor = self.value.len();
+		}
+	}
+
+	/// Get the caret position, as a byte offset into the value
+	///
+	/// A caret restored from saved state is clamped as
+	/// [`TextArea::set_cursor`] clamps it.
+	pub fn cursor(&self) -> usize {
+		clamp_caret(&self.value, self.cursor)
+	}
+
+	/// Set the caret position, clamped to the value and moved back to the
+	/// start of the character it falls in
+	pub fn set_cursor(&mut self, cursor: usize) {
+		self.cursor = clamp_caret(&self.value, cursor);
+	}
+
+	/// Move the caret one character to the left on screen
+	///
+	/// The caret moves visually within a line, so in right-to-left text it
+	/// moves towards the end of the line. From the left edge of a line it
+	/// moves to the previous line, or to the next one if the line is right
+	/// to left. Returns `false` when there is nowhere to move.
+	pub fn move_cursor_left(&mut self) -> bool {
+		self.move_cursor(CaretMovement::Left)
+	}
+
+	/// Move the caret one character to the right on screen
+	///
+	/// Returns `false` when there is nowhere to move.
+	pub fn move_cursor_right(&mut self) -> bool {
+		self.move_cursor(CaretMovement::Right)
+	}
+
+	fn move_cursor(&mut self, movement: CaretMovement) -> bool {
+		self.set_cursor(self.cursor);
+		let start = self.value[..self.cursor]
+			.rfind('\n')
+			.map_or(0, |index| index + 1);
+		let end = self.value[self.cursor..]
+			.find('\n')
+			.map_or(self.value.len(), |index| self.cursor + index);
+		let line = &self.value[start..end];
+
+		if let Some(caret) = move_caret(line, self.cursor - start, movement) {
+			self.cursor = start + caret;
+			return true;
+		}
+
+		// At the edge of a line, continue on the line before or after it
+		let rtl = TextDirection::of_text(line).is_some_and(TextDirection::is_rtl);
+		let forward = (movement == CaretMovement::Right) != rtl;
+		if forward && end < self.value.len() {
+			self.cursor = end + 1;
+			true
+		} else if !forward && start > 0 {
+			self.cursor = start - 1;
+			true
+		} else {
+			false
 		}
 	}
 
@@ -159,6 +231,49 @@ impl Component for TextArea {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Size of `cols` average characters by `rows` lines
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let layout = fonts.layout_text(&"0".repeat(self.cols), self.font_size, None, None);
+		(
+			layout.width(),
+			self.rows as f32 * layout.metrics.line_height(),
+		)
+	}
+}
+
+impl Paint for TextArea {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let padding = theme.spacing.small;
+		let content = inset(bounds, padding);
+		let line_height = self.font_size * theme.typography.line_height;
+		let (value, color) = if self.value.is_empty() {
+			(self.placeholder.as_str(), &theme.colors.text_secondary)
+		} else {
+			(
+				self.value.as_str(),
+				text_color(&self.color, self.properties.enabled, theme),
+			)
+		};
+
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			stroke_rect(bounds, &self.border_color, theme.borders.width),
+			clip(content),
+		];
+		commands.extend(text_lines(
+			content,
+			value,
+			self.font_size,
+			line_height,
+			color,
+			TextAlign::Left,
+		));
+		commands.push(restore_clip());
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -192,6 +307,25 @@ mod tests {
 		assert_eq!(textarea.value(), "Short");
 	}
 
+	#[test]
+	fn test_text_area_restored_cursor() {
+		let mut textarea = TextArea::new(1);
+		textarea.set_value("héllo\nworld");
+		let mut state = serde_json::to_value(&textarea).unwrap();
+		textarea.set_max_length(Some(4));
+		textarea.set_value("too long");
+		assert_eq!(textarea.cursor(), 12);
+
+		// Past the end, then inside "é"
+		for (cursor, clamped) in [(100, 12), (2, 1)] {
+			state["cursor"] = cursor.into();
+			let mut restored: TextArea = serde_json::from_value(state.clone()).unwrap();
+			assert_eq!(restored.cursor(), clamped);
+			assert!(restored.move_cursor_left());
+			assert!(restored.move_cursor_right());
+		}
+	}
+
 	#[test]
 	fn test_text_area_placeholder() {
 		let mut textarea = TextArea::new(1);
@@ -215,4 +349,37 @@ mod tests {
 		textarea.set_read_only(true);
 		assert!(textarea.is_read_only());
 	}
+
+	#[test]
+	fn test_text_area_cursor_crosses_lines() {
+		let mut textarea = TextArea::new(1);
+		textarea.set_value("ab\ncd");
+		assert_eq!(textarea.cursor(), 5);
+
+		textarea.set_cursor(3);
+		assert!(textarea.move_cursor_left());
+		assert_eq!(textarea.cursor(), 2);
+		assert!(textarea.move_cursor_right());
+		assert_eq!(textarea.cursor(), 3);
+
+		textarea.set_cursor(0);
+		assert!(!textarea.move_cursor_left());
+	}
+
+	#[test]
+	fn test_text_area_rtl_cursor() {
+		// A Hebrew line, alef bet, followed by a Latin one
+		let mut textarea = TextArea::new(1);
+		textarea.set_value("\u{05D0}\u{05D1}\nab");
+		textarea.set_cursor(0);
+
+		// Moving left through right-to-left text moves forwards, then on
+		// to the next line from its left edge
+		assert!(textarea.move_cursor_left());
+		assert_eq!(textarea.cursor(), 2);
+		assert!(textarea.move_cursor_left());
+		assert_eq!(textarea.cursor(), 4);
+		assert!(textarea.move_cursor_left());
+		assert_eq!(textarea.cursor(), 5);
+	}
 }
@@ -1,10 +1,16 @@
 //! Formatted text editor component with rich text support
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	Paint, clip, fill_rect, inset, restore_clip, stroke_rect, text_color, text_in, text_lines,
+};
+
 /// Text format style
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct TextFormat {
@@ -250,6 +256,91 @@ impl Component for TextEditor {
 	}
 }
 
+impl Paint for TextEditor {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let padding = theme.spacing.small;
+		let enabled = self.properties.enabled && !self.disabled;
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+		let mut content_top = bounds.y;
+
+		if self.show_toolbar {
+			let toolbar_height = (self.font_size * 2.5).min(bounds.height);
+			let toolbar = Rect::new(bounds.x, bounds.y, bounds.width, toolbar_height);
+			commands.push(fill_rect(toolbar, &self.toolbar_background));
+
+			let buttons = [
+				("B", self.format.bold),
+				("I", self.format.italic),
+				("U", self.format.underline),
+				("S", self.format.strikethrough),
+			];
+			for (index, (glyph, active)) in buttons.into_iter().enumerate() {
+				let button = Rect::new(
+					toolbar.x + padding + index as f32 * toolbar_height,
+					toolbar.y,
+					toolbar_height,
+					toolbar_height,
+				);
+				if active {
+					commands.push(fill_rect(
+						inset(button, padding / 2.0),
+						&self.selection_color,
+					));
+				}
+				commands.push(text_in(
+					button,
+					glyph,
+					self.font_size,
+					text_color(&self.color, enabled, theme),
+					TextAlign::Center,
+				));
+			}
+			content_top += toolbar_height;
+		}
+
+		let content = inset(
+			Rect::new(
+				bounds.x,
+				content_top,
+				bounds.width,
+				(bounds.y + bounds.height - content_top).max(0.0),
+			),
+			padding,
+		);
+		let (value, color) = if self.content.is_empty() {
+			(self.placeholder.as_str(), &theme.colors.text_secondary)
+		} else {
+			(
+				self.content.as_str(),
+				text_color(&self.color, enabled, theme),
+			)
+		};
+		let align = match self.alignment {
+			TextAlignment::Left | TextAlignment::Justify => TextAlign::Left,
+			TextAlignment::Center => TextAlign::Center,
+			TextAlignment::Right => TextAlign::Right,
+		};
+		commands.push(clip(content));
+		commands.extend(text_lines(
+			content,
+			value,
+			self.font_size,
+			self.font_size * self.line_height,
+			color,
+			align,
+		));
+		commands.push(restore_clip());
+		commands.push(stroke_rect(
+			bounds,
+			&theme.borders.color,
+			theme.borders.width,
+		));
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -3,8 +3,17 @@
 use engage_ux_core::color::Color;
 use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::bidi::{CaretMovement, clamp_caret, move_caret};
+use engage_ux_core::media::{FontRegistry, TextDirection};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{
+	MEASURE_SMALL_SPACING, Paint, clip, fill_rect, inset, measure_text, restore_clip, stroke_rect,
+	text_color, text_in,
+};
+
 /// Input type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputType {
@@ -22,6 +31,9 @@ pub enum InputType {
 pub struct TextInput {
 	properties: ComponentProperties,
 	value: String,
+	/// Caret position, as a byte offset into the value
+	#[serde(default)]
+	cursor: usize,
 	placeholder: String,
 	input_type: InputType,
 	max_length: Option<usize>,
@@ -48,6 +60,7 @@ impl TextInput {
 		Self {
 			properties: ComponentProperties::new(id),
 			value: String::new(),
+			cursor: 0,
 			placeholder: String::new(),
 			input_type: InputType::Text,
 			max_length: None,
@@ -71,15 +84,71 @@ impl TextInput {
 		&self.value
 	}
 
-	/// Set value
+	/// Set value, placing the caret at its end
 	pub fn set_value(&mut self, value: impl Into<String>) {
 		let new_value = value.into();
-		if let Some(max_len) = self.max_length {
-			if new_value.len() <= max_len {
-				self.value = new_value;
+		if self
+			.max_length
+			.is_none_or(|max_len| new_value.len() <= max_len)
+		{
+			self.value = new_value;
+			self.cursor = self.value.len();
+		}
+	}
+
+	/// Get the caret position, as a byte offset into the value
+	///
+	/// A caret restored from saved state is clamped as
+	/// [`TextInput::set_cursor`] clamps it.
+	pub fn cursor(&self) -> usize {
+		clamp_caret(&self.value, self.cursor)
+	}
+
+	/// Set the caret position, clamped to the value and moved back to the
+	/// start of the character it falls in
+	pub fn set_cursor(&mut self, cursor: usize) {
+		self.cursor = clamp_caret(&self.value, cursor);
+	}
+
+	/// Move the caret one character to the left on screen
+	///
+	/// In right-to-left text this moves towards the end of the value.
+	/// Returns `false` when the caret is already at the left edge.
+	pub fn move_cursor_left(&mut self) -> bool {
+		self.move_cursor(CaretMovement::Left)
+	}
+
+	/// Move the caret one character to the right on screen
+	///
+	/// Returns `false` when the caret is already at the right edge.
+	pub fn move_cursor_right(&mut self) -> bool {
+		self.move_cursor(CaretMovement::Right)
+	}
+
+	fn move_cursor(&mut self, movement: CaretMovement) -> bool {
+		self.set_cursor(self.cursor);
+		// Masked passwords are drawn left to right whatever their content
+		let next = if self.input_type == InputType::Password {
+			match movement {
+				CaretMovement::Left => self.value[..self.cursor]
+					.chars()
+					.next_back()
+					.map(|c| self.cursor - c.len_utf8()),
+				CaretMovement::Right => self.value[self.cursor..]
+					.chars()
+					.next()
+					.map(|c| self.cursor + c.len_utf8()),
 			}
 		} else {
-			self.value = new_value;
+			move_caret(&self.value, self.cursor, movement)
+		};
+
+		match next {
+			Some(cursor) => {
+				self.cursor = cursor;
+				true
+			}
+			None => false,
 		}
 	}
 
@@ -232,6 +301,47 @@ impl Component for TextInput {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// One line at the input's font size, as wide as the placeholder, with
+	/// padding on every side
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let (width, height) = measure_text(fonts, &self.placeholder, self.font_size);
+		(
+			width + MEASURE_SMALL_SPACING * 2.0,
+			height + MEASURE_SMALL_SPACING * 2.0,
+		)
+	}
+}
+
+impl Paint for TextInput {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let enabled = self.properties.enabled && !self.disabled;
+		let content = inset(bounds, theme.spacing.small);
+
+		let (value, color) = if self.value.is_empty() {
+			(self.placeholder.clone(), &theme.colors.text_secondary)
+		} else if self.input_type == InputType::Password {
+			(
+				"•".repeat(self.value.chars().count()),
+				text_color(&self.color, enabled, theme),
+			)
+		} else {
+			(self.value.clone(), text_color(&self.color, enabled, theme))
+		};
+		let align = match TextDirection::of_text(&value) {
+			Some(TextDirection::RightToLeft) => TextAlign::Right,
+			_ => TextAlign::Left,
+		};
+
+		vec![
+			fill_rect(bounds, &self.background_color),
+			stroke_rect(bounds, &self.border_color, theme.borders.width),
+			clip(content),
+			text_in(content, value, self.font_size, color, align),
+			restore_clip(),
+		]
+	}
 }
 
 #[cfg(test)]
@@ -301,4 +411,88 @@ mod tests {
 		input.set_required(true);
 		assert!(input.is_required());
 	}
+
+	#[test]
+	fn test_text_input_cursor() {
+		let mut input = TextInput::new(1);
+		input.set_value("héllo");
+		assert_eq!(input.cursor(), 6);
+
+		input.set_cursor(2);
+		assert_eq!(input.cursor(), 1);
+		input.set_cursor(100);
+		assert_eq!(input.cursor(), 6);
+
+		assert!(!input.move_cursor_right());
+		assert!(input.move_cursor_left());
+		assert_eq!(input.cursor(), 5);
+	}
+
+	#[test]
+	fn test_text_input_rejected_value_keeps_cursor() {
+		let mut input = TextInput::new(1);
+		input.set_max_length(Some(5));
+		input.set_value("Short");
+		input.set_cursor(2);
+		input.set_value("Too long text");
+		assert_eq!(input.value(), "Short");
+		assert_eq!(input.cursor(), 2);
+	}
+
+	#[test]
+	fn test_text_input_restored_cursor() {
+		let mut input = TextInput::new(1);
+		input.set_input_type(InputType::Password);
+		input.set_value("héllo");
+		let mut state = serde_json::to_value(&input).unwrap();
+
+		// Past the end, then inside "é"
+		for (cursor, clamped) in [(100, 6), (2, 1)] {
+			state["cursor"] = cursor.into();
+			let mut restored: TextInput = serde_json::from_value(state.clone()).unwrap();
+			assert_eq!(restored.cursor(), clamped);
+			assert!(restored.move_cursor_left());
+			restored.set_input_type(InputType::Text);
+			assert!(restored.move_cursor_right());
+		}
+	}
+
+	#[test]
+	fn test_text_input_rtl_cursor() {
+		// "abc" followed by the Hebrew letters alef, bet
+		let mut input = TextInput::new(1);
+		input.set_value("abc \u{05D0}\u{05D1}");
+		input.set_cursor(0);
+
+		// The Hebrew run is drawn reversed, so moving right reaches the
+		// end of the value before the alef. Offset 4 is drawn after the
+		// space, so the caret stops beside the alef
+		let mut visited = vec![input.cursor()];
+		while input.move_cursor_right() {
+			visited.push(input.cursor());
+		}
+		assert_eq!(visited, vec![0, 1, 2, 3, 8, 6]);
+
+		// Masked passwords move in logical order
+		input.set_input_type(InputType::Password);
+		input.set_cursor(4);
+		assert!(input.move_cursor_right());
+		assert_eq!(input.cursor(), 6);
+	}
+
+	#[test]
+	fn test_text_input_rtl_alignment() {
+		let theme = Theme::default();
+		let mut input = TextInput::new(1);
+		input.set_value("\u{05E9}\u{05DC}\u{05D5}\u{05DD}");
+
+		let align = input
+			.paint(&theme)
+			.into_iter()
+			.find_map(|command| match command {
+				RenderCommand::Text { align, .. } => Some(align),
+				_ => None,
+			});
+		assert_eq!(align, Some(TextAlign::Right));
+	}
 }
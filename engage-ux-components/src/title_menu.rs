@@ -1,10 +1,13 @@
 //! Title menu component (menu bar with dropdown menus)
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
 use crate::menu::MenuItem;
+use crate::paint::{Paint, fill_rect, text_color, text_in, text_width};
 
 /// Title menu item (top-level menu button)
 #[derive(Clone, Serialize, Deserialize)]
@@ -152,6 +155,47 @@ impl Component for TitleMenu {
 	}
 }
 
+impl Paint for TitleMenu {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let padding = theme.spacing.medium;
+		let bar = Rect::new(
+			bounds.x,
+			bounds.y,
+			bounds.width,
+			self.height.min(bounds.height),
+		);
+		let mut commands = vec![fill_rect(bar, &self.background_color)];
+
+		let mut x = bar.x;
+		for menu in &self.menus {
+			let width = text_width(theme, &menu.label, font_size) + padding * 2.0;
+			let rect = Rect::new(x, bar.y, width, bar.height);
+			let color = if self.active_menu.as_deref() == Some(menu.id.as_str()) {
+				commands.push(fill_rect(rect, &self.active_background));
+				&self.active_color
+			} else {
+				text_color(
+					&self.color,
+					self.properties.enabled && !menu.disabled,
+					theme,
+				)
+			};
+			commands.push(text_in(
+				rect,
+				menu.label.clone(),
+				font_size,
+				color,
+				TextAlign::Center,
+			));
+			x += width;
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,9 +1,13 @@
 //! Toast notification component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, stroke_rect, text_in};
+
 /// Toast position on screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ToastPosition {
@@ -232,6 +236,59 @@ impl Component for Toast {
 	}
 }
 
+impl Paint for Toast {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = self.properties.bounds;
+		let padding = theme.spacing.medium;
+		let content = inset(bounds, padding);
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			stroke_rect(bounds, &self.border_color, theme.borders.width),
+		];
+
+		if self.title.is_empty() {
+			commands.push(text_in(
+				content,
+				self.message.clone(),
+				theme.typography.font_size_base,
+				&self.color,
+				TextAlign::Left,
+			));
+		} else {
+			let half = content.height / 2.0;
+			commands.push(text_in(
+				Rect::new(content.x, content.y, content.width, half),
+				self.title.clone(),
+				theme.typography.font_size_base,
+				&self.color,
+				TextAlign::Left,
+			));
+			commands.push(text_in(
+				Rect::new(content.x, content.y + half, content.width, half),
+				self.message.clone(),
+				theme.typography.font_size_small,
+				&self.color,
+				TextAlign::Left,
+			));
+		}
+		if self.dismissible {
+			commands.push(text_in(
+				content,
+				"×",
+				theme.typography.font_size_base,
+				&self.color,
+				TextAlign::Right,
+			));
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,10 +1,15 @@
 //! Toggle switch component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, fill_rect, measure_labelled, text_color, text_in};
+
 /// Toggle size
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ToggleSize {
@@ -161,6 +166,69 @@ impl Component for Toggle {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Track followed by the label
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		let track = (self.size.width(), self.size.height());
+		measure_labelled(fonts, track, &self.label)
+	}
+}
+
+impl Paint for Toggle {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let enabled = self.properties.enabled && !self.disabled;
+		let (width, height) = (self.size.width(), self.size.height());
+		let radius = height / 2.0;
+		let track = Rect::new(
+			bounds.x,
+			bounds.y + (bounds.height - height) / 2.0,
+			width,
+			height,
+		);
+		let cy = track.y + radius;
+		let track_color = if !enabled {
+			&theme.colors.text_disabled
+		} else if self.active {
+			&self.active_color
+		} else {
+			&self.inactive_color
+		};
+		let thumb_x = if self.active {
+			track.x + width - radius
+		} else {
+			track.x + radius
+		};
+
+		// Capsule track: two end caps and the body between them
+		let mut commands = vec![
+			circle(track.x + radius, cy, radius, track_color, true),
+			circle(track.x + width - radius, cy, radius, track_color, true),
+			fill_rect(
+				Rect::new(track.x + radius, track.y, width - height, height),
+				track_color,
+			),
+			circle(thumb_x, cy, radius - 2.0, &self.thumb_color, true),
+		];
+
+		if !self.label.is_empty() {
+			let x = track.x + width + theme.spacing.small;
+			commands.push(text_in(
+				Rect::new(
+					x,
+					bounds.y,
+					(bounds.x + bounds.width - x).max(0.0),
+					bounds.height,
+				),
+				self.label.clone(),
+				theme.typography.font_size_base,
+				text_color(&self.label_color, enabled, theme),
+				TextAlign::Left,
+			));
+		}
+
+		commands
+	}
 }
 
 #[cfg(test)]
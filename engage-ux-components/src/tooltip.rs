@@ -1,9 +1,16 @@
 //! Tooltip and popover component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
+use engage_ux_core::events::{Event, EventType};
+use engage_ux_core::input::{InputHandler, PointerGestures};
+use engage_ux_core::media::FontRegistry;
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, stroke_rect, text_in, text_lines};
+
 /// Tooltip position relative to target
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TooltipPosition {
@@ -111,6 +118,14 @@ impl Tooltip {
 		self.delay_ms = delay_ms;
 	}
 
+	/// Make the pointer rest over the target for the tooltip's delay
+	/// before hover intent is reported for it
+	///
+	/// Call again after changing the delay or the target.
+	pub fn register_hover_delay(&self, gestures: &mut PointerGestures) {
+		gestures.set_hover_delay(self.target_id, self.delay_ms);
+	}
+
 	/// Get max width
 	pub fn max_width(&self) -> Option<f32> {
 		self.max_width
@@ -147,6 +162,22 @@ impl Tooltip {
 	}
 }
 
+/// The tooltip shows on hover intent or a long press over its target, and
+/// hides when the pointer leaves the target or clicks it
+impl InputHandler for Tooltip {
+	fn handle_event(&mut self, event: &Event) -> bool {
+		if event.target != self.target_id {
+			return false;
+		}
+		match event.event_type {
+			EventType::HoverIntent { .. } | EventType::LongPress { .. } => self.show(),
+			EventType::Leave | EventType::Click => self.hide(),
+			_ => return false,
+		}
+		true
+	}
+}
+
 impl Component for Tooltip {
 	fn id(&self) -> ComponentId {
 		self.properties.id
@@ -159,6 +190,38 @@ impl Component for Tooltip {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	/// Size of the content wrapped to the maximum width
+	fn measure(&self, fonts: &FontRegistry) -> (f32, f32) {
+		fonts
+			.layout_text(&self.content, self.font_size, self.max_width, None)
+			.size()
+	}
+}
+
+impl Paint for Tooltip {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = bubble(self.properties.bounds, self.max_width);
+		let line_height = self.font_size * theme.typography.line_height;
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			stroke_rect(bounds, &self.border_color, theme.borders.width),
+		];
+		commands.extend(text_lines(
+			inset(bounds, theme.spacing.small),
+			&self.content,
+			self.font_size,
+			line_height,
+			&self.color,
+			TextAlign::Left,
+		));
+
+		commands
+	}
 }
 
 /// Popover component (extended tooltip with interactive content)
@@ -300,10 +363,101 @@ impl Component for Popover {
 	}
 }
 
+impl Paint for Popover {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		if !self.visible {
+			return Vec::new();
+		}
+
+		let bounds = bubble(self.properties.bounds, self.max_width);
+		let content = inset(bounds, theme.spacing.medium);
+		let font_size = theme.typography.font_size_base;
+		let line_height = font_size * theme.typography.line_height;
+		let title = Rect::new(content.x, content.y, content.width, line_height);
+		let mut commands = vec![
+			fill_rect(bounds, &self.background_color),
+			stroke_rect(bounds, &self.border_color, theme.borders.width),
+			text_in(
+				title,
+				self.title.clone(),
+				theme.typography.font_size_large,
+				&self.color,
+				TextAlign::Left,
+			),
+		];
+		if self.dismissible {
+			commands.push(text_in(
+				title,
+				"×",
+				font_size,
+				&self.color,
+				TextAlign::Right,
+			));
+		}
+		commands.extend(text_lines(
+			Rect::new(
+				content.x,
+				content.y + line_height,
+				content.width,
+				(content.height - line_height).max(0.0),
+			),
+			&self.content,
+			font_size,
+			line_height,
+			&self.color,
+			TextAlign::Left,
+		));
+
+		commands
+	}
+}
+
+/// Bounds of a tooltip or popover, narrowed to its maximum width
+fn bubble(bounds: Rect, max_width: Option<f32>) -> Rect {
+	Rect::new(
+		bounds.x,
+		bounds.y,
+		max_width.map_or(bounds.width, |max| bounds.width.min(max)),
+		bounds.height,
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_tooltip_hover_delay() {
+		use engage_ux_core::component::ComponentTree;
+		use engage_ux_core::input::MouseEvent;
+
+		let mut tree = ComponentTree::new();
+		let mut target = crate::Button::new(7, "Save");
+		target.set_bounds(Rect::new(0.0, 0.0, 80.0, 30.0));
+		tree.insert(None, target).unwrap();
+
+		let mut tooltip = Tooltip::new(1, 7, "Save the document");
+		tooltip.set_delay_ms(800);
+		let mut gestures = PointerGestures::new();
+		tooltip.register_hover_delay(&mut gestures);
+
+		let feed = |tooltip: &mut Tooltip, events: Vec<Event>| {
+			for event in events {
+				tooltip.handle_event(&event);
+			}
+		};
+		let events = gestures.handle_mouse(&tree, &MouseEvent::move_event(10.0, 10.0), 0);
+		feed(&mut tooltip, events);
+		feed(&mut tooltip, gestures.update(500));
+		assert!(!tooltip.is_visible());
+		feed(&mut tooltip, gestures.update(800));
+		assert!(tooltip.is_visible());
+
+		let events = gestures.handle_mouse(&tree, &MouseEvent::move_event(200.0, 10.0), 900);
+		feed(&mut tooltip, events);
+		assert!(!tooltip.is_visible());
+	}
+
 	#[test]
 	fn test_tooltip_creation() {
 		let tooltip = Tooltip::new(1, 100, "Helpful text");
@@ -329,6 +483,17 @@ mod tests {
 		assert_eq!(tooltip.position(), TooltipPosition::Bottom);
 	}
 
+	#[test]
+	fn test_tooltip_measure_wraps_to_max_width() {
+		let mut tooltip = Tooltip::new(1, 100, "one two three four five six");
+		tooltip.set_max_width(Some(60.0));
+
+		// Approximate metrics: 7.2px per character and 14.4px per line
+		let (width, height) = tooltip.measure(&FontRegistry::new());
+		assert!(width <= 60.0);
+		assert!((height - 4.0 * 14.4).abs() < 1e-3);
+	}
+
 	#[test]
 	fn test_popover_creation() {
 		let popover = Popover::new(1, 100);
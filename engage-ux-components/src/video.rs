@@ -1,10 +1,14 @@
 //! Video player component
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, circle, fill_rect, line, text_in};
+
 /// Video state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VideoState {
@@ -275,6 +279,103 @@ impl Component for Video {
 	}
 }
 
+impl Paint for Video {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let frame = Rect::new(
+			bounds.x,
+			bounds.y,
+			self.width.unwrap_or(bounds.width),
+			self.height.unwrap_or(bounds.height),
+		);
+		let foreground = &theme.colors.surface;
+		let mut commands = vec![fill_rect(frame, &self.background_color)];
+		let (cx, cy) = (frame.x + frame.width / 2.0, frame.y + frame.height / 2.0);
+		let size = frame.width.min(frame.height) / 6.0;
+
+		match self.state {
+			VideoState::Stopped | VideoState::Paused => {
+				// Play triangle
+				commands.push(line(
+					cx - size / 2.0,
+					cy - size,
+					cx - size / 2.0,
+					cy + size,
+					foreground,
+					2.0,
+				));
+				commands.push(line(
+					cx - size / 2.0,
+					cy - size,
+					cx + size,
+					cy,
+					foreground,
+					2.0,
+				));
+				commands.push(line(
+					cx - size / 2.0,
+					cy + size,
+					cx + size,
+					cy,
+					foreground,
+					2.0,
+				));
+			}
+			VideoState::Buffering => {
+				commands.push(circle(cx, cy, size, foreground, false));
+			}
+			VideoState::Error => {
+				commands.push(text_in(
+					frame,
+					"!",
+					size * 2.0,
+					&theme.colors.error,
+					TextAlign::Center,
+				));
+			}
+			VideoState::Playing => {}
+		}
+
+		if self.controls {
+			let bar_height = (theme.typography.font_size_base * 2.0).min(frame.height);
+			let bar = Rect::new(
+				frame.x,
+				frame.y + frame.height - bar_height,
+				frame.width,
+				bar_height,
+			);
+			let progress = if self.duration > 0.0 {
+				(self.current_time / self.duration).clamp(0.0, 1.0) as f32
+			} else {
+				0.0
+			};
+			let track_y = bar.y + bar.height / 2.0;
+			let padding = theme.spacing.medium;
+			let track_start = bar.x + padding;
+			let track_width = (bar.width - padding * 2.0).max(0.0);
+			commands.push(fill_rect(bar, &theme.shadows.color));
+			commands.push(line(
+				track_start,
+				track_y,
+				track_start + track_width,
+				track_y,
+				&theme.colors.text_disabled,
+				2.0,
+			));
+			commands.push(line(
+				track_start,
+				track_y,
+				track_start + track_width * progress,
+				track_y,
+				&theme.colors.primary,
+				2.0,
+			));
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
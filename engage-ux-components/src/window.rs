@@ -1,10 +1,14 @@
 //! Window component for application windows
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::{RenderCommand, TextAlign};
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, line, stroke_rect, text_in};
+
 /// Window state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WindowState {
@@ -415,6 +419,54 @@ impl Component for Window {
 	fn properties_mut(&mut self) -> &mut ComponentProperties {
 		&mut self.properties
 	}
+
+	fn children(&self) -> &[ComponentId] {
+		&self.children
+	}
+
+	fn set_children(&mut self, children: Vec<ComponentId>) {
+		self.children = children;
+	}
+
+	fn clips_children(&self) -> bool {
+		true
+	}
+}
+
+impl Paint for Window {
+	fn paint(&self, theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let font_size = theme.typography.font_size_base;
+		let mut commands = vec![fill_rect(bounds, &self.background_color)];
+		let mut y = bounds.y;
+
+		if self.show_title_bar {
+			let title_bar = Rect::new(bounds.x, y, bounds.width, self.title_bar_height);
+			commands.push(fill_rect(title_bar, &self.title_bar_background));
+			commands.push(text_in(
+				inset(title_bar, theme.spacing.small),
+				self.title.clone(),
+				font_size,
+				&self.title_bar_color,
+				TextAlign::Left,
+			));
+			y += self.title_bar_height;
+		}
+		if self.show_menu_bar {
+			let menu_bar = Rect::new(bounds.x, y, bounds.width, self.menu_bar_height);
+			commands.push(line(
+				menu_bar.x,
+				menu_bar.y + menu_bar.height,
+				menu_bar.x + menu_bar.width,
+				menu_bar.y + menu_bar.height,
+				&self.border_color,
+				1.0,
+			));
+		}
+		commands.push(stroke_rect(bounds, &self.border_color, theme.borders.width));
+
+		commands
+	}
 }
 
 #[cfg(test)]
@@ -1,10 +1,14 @@
 //! Window control components (close, minimize, maximize)
 
 use engage_ux_core::color::Color;
-use engage_ux_core::component::{Component, ComponentId, ComponentProperties};
+use engage_ux_core::component::{Component, ComponentId, ComponentProperties, Rect};
 use engage_ux_core::events::{Event, EventCallback};
+use engage_ux_oal::backends::renderer::RenderCommand;
+use engage_ux_themes::Theme;
 use serde::{Deserialize, Serialize};
 
+use crate::paint::{Paint, fill_rect, inset, line, stroke_rect};
+
 /// Window control button type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WindowControlType {
@@ -140,6 +144,57 @@ impl Component for WindowControlButton {
 	}
 }
 
+impl Paint for WindowControlButton {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		self.paint_at(self.properties.bounds)
+	}
+}
+
+impl WindowControlButton {
+	/// Draw the button's background and glyph within a rectangle
+	fn paint_at(&self, rect: Rect) -> Vec<RenderCommand> {
+		let glyph = inset(rect, rect.width.min(rect.height) * 0.3);
+		let (left, top) = (glyph.x, glyph.y);
+		let (right, bottom) = (glyph.x + glyph.width, glyph.y + glyph.height);
+		let mut commands = vec![fill_rect(rect, &self.background_color)];
+
+		match self.control_type {
+			WindowControlType::Close => {
+				commands.push(line(left, top, right, bottom, &self.color, 1.0));
+				commands.push(line(right, top, left, bottom, &self.color, 1.0));
+			}
+			WindowControlType::Minimize => {
+				let y = glyph.y + glyph.height / 2.0;
+				commands.push(line(left, y, right, y, &self.color, 1.0));
+			}
+			WindowControlType::Maximize => {
+				commands.push(stroke_rect(glyph, &self.color, 1.0));
+			}
+			WindowControlType::Restore => {
+				// Two overlapping windows
+				let offset = glyph.width * 0.25;
+				let back = Rect::new(
+					left + offset,
+					top,
+					glyph.width - offset,
+					glyph.height - offset,
+				);
+				let front = Rect::new(
+					left,
+					top + offset,
+					glyph.width - offset,
+					glyph.height - offset,
+				);
+				commands.push(stroke_rect(back, &self.color, 1.0));
+				commands.push(fill_rect(front, &self.background_color));
+				commands.push(stroke_rect(front, &self.color, 1.0));
+			}
+		}
+
+		commands
+	}
+}
+
 /// Window controls container with all standard buttons
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WindowControls {
@@ -283,6 +338,28 @@ impl Component for WindowControls {
 	}
 }
 
+impl Paint for WindowControls {
+	fn paint(&self, _theme: &Theme) -> Vec<RenderCommand> {
+		let bounds = self.properties.bounds;
+		let buttons = [
+			self.minimize_button.as_ref(),
+			self.maximize_restore_button(),
+			Some(&self.close_button),
+		];
+
+		let mut commands = Vec::new();
+		let mut x = bounds.x;
+		for button in buttons.into_iter().flatten() {
+			let size = button.size;
+			let rect = Rect::new(x, bounds.y + (bounds.height - size) / 2.0, size, size);
+			commands.extend(button.paint_at(rect));
+			x += size + self.spacing;
+		}
+
+		commands
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
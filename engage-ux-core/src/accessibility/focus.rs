@@ -0,0 +1,623 @@
+//! Keyboard focus
+//!
+//! A [`FocusManager`] tracks which component has keyboard focus and moves it
+//! with Tab and Shift+Tab through the components of a [`ComponentTree`] whose
+//! registered [`AccessibilityProps`] make them focusable. Components with a
+//! positive `tab_index` come first, lowest index first; the rest follow in
+//! tree order. A negative `tab_index` keeps a component out of Tab order
+//! while still letting it be focused directly.
+//!
+//! Focus scopes trap Tab inside a subtree, as a modal dialog does, and give
+//! focus back to where it was when the scope is removed. Every change of
+//! focus queues a [`EventType::FocusLost`] for the component losing it and
+//! a [`EventType::FocusGained`] for the one gaining it.
+//!
+//! Within a composite component such as a radio group or a list, arrow keys
+//! move between items while Tab leaves the component. [`RovingFocus`]
+//! works out which item an arrow key moves to.
+
+use super::AccessibilityProps;
+use crate::component::{ComponentId, ComponentTree};
+use crate::events::{Event, EventType};
+use crate::input::keyboard::{KeyCode, KeyEventType, KeyModifiers, KeyboardEvent};
+use std::collections::HashMap;
+
+/// A subtree that Tab does not leave
+#[derive(Debug, Clone, Copy)]
+struct FocusScope {
+	root: ComponentId,
+	/// Focus when the scope was added, given back when it is removed
+	restore: Option<ComponentId>,
+}
+
+/// Focus management for keyboard navigation
+#[derive(Debug, Default)]
+pub struct FocusManager {
+	/// Currently focused component ID
+	focused_id: Option<ComponentId>,
+	/// Focus history for back navigation
+	focus_history: Vec<ComponentId>,
+	/// Accessibility of the components that can take focus
+	props: HashMap<ComponentId, AccessibilityProps>,
+	/// Active scopes, innermost last
+	scopes: Vec<FocusScope>,
+	/// Focus events not yet taken
+	events: Vec<Event>,
+}
+
+impl FocusManager {
+	/// Create a new focus manager
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set focus to a component
+	pub fn set_focus(&mut self, id: ComponentId) {
+		if self.focused_id == Some(id) {
+			return;
+		}
+		if let Some(current) = self.focused_id {
+			self.focus_history.push(current);
+		}
+		self.change_focus(Some(id));
+	}
+
+	/// Get currently focused component ID
+	pub fn focused(&self) -> Option<ComponentId> {
+		self.focused_id
+	}
+
+	/// Clear focus
+	pub fn clear_focus(&mut self) {
+		self.change_focus(None);
+	}
+
+	/// Return to previous focus
+	pub fn focus_previous(&mut self) -> Option<ComponentId> {
+		let previous = self.focus_history.pop()?;
+		self.change_focus(Some(previous));
+		Some(previous)
+	}
+
+	/// Check if a component has focus
+	pub fn has_focus(&self, id: ComponentId) -> bool {
+		self.focused_id == Some(id)
+	}
+
+	/// Register a component's accessibility, so it can take part in Tab
+	/// order when it is focusable
+	pub fn register(&mut self, id: ComponentId, props: AccessibilityProps) {
+		self.props.insert(id, props);
+	}
+
+	/// Unregister a component, moving focus off it
+	pub fn unregister(&mut self, id: ComponentId) {
+		self.props.remove(&id);
+		self.focus_history.retain(|&previous| previous != id);
+		if self.focused_id == Some(id) {
+			self.clear_focus();
+		}
+	}
+
+	/// Get the accessibility registered for a component
+	pub fn accessibility(&self, id: ComponentId) -> Option<&AccessibilityProps> {
+		self.props.get(&id)
+	}
+
+	/// Check whether a component can take focus now
+	///
+	/// It must be registered as focusable and not disabled, it and its
+	/// ancestors must be visible and enabled, and no ancestor may hide its
+	/// children. Components locked elsewhere cannot be read, so neither they
+	/// nor their descendants are focusable until they are unlocked.
+	pub fn is_focusable(&self, tree: &ComponentTree, id: ComponentId) -> bool {
+		let registered = self
+			.props
+			.get(&id)
+			.is_some_and(|props| props.focusable && !props.disabled);
+		registered
+			&& tree.contains(id)
+			&& std::iter::once(id)
+				.chain(tree.ancestors(id))
+				.all(|current| {
+					tree.get(current).is_some_and(|component| {
+						component.try_read().is_ok_and(|component| {
+							component.is_visible()
+								&& component.is_enabled()
+								&& (current == id || !component.hides_children())
+						})
+					})
+				})
+	}
+
+	/// Components Tab moves through, in order, within the innermost scope
+	pub fn tab_order(&self, tree: &ComponentTree) -> Vec<ComponentId> {
+		let candidates: Vec<ComponentId> = match self.scope() {
+			Some(root) => tree.depth_first(root).collect(),
+			None => tree.depth_first_all().collect(),
+		};
+		let mut order: Vec<(i32, ComponentId)> = candidates
+			.into_iter()
+			.filter_map(|id| {
+				let tab_index = self.props.get(&id)?.tab_index.unwrap_or(0);
+				(tab_index >= 0 && self.is_focusable(tree, id)).then_some((tab_index, id))
+			})
+			.collect();
+		// Positive indices first; the sort is stable, keeping tree order
+		order.sort_by_key(|&(tab_index, _)| if tab_index > 0 { tab_index } else { i32::MAX });
+		order.into_iter().map(|(_, id)| id).collect()
+	}
+
+	/// Move focus to the next component in Tab order, wrapping around
+	pub fn focus_forward(&mut self, tree: &ComponentTree) -> Option<ComponentId> {
+		self.step(tree, true)
+	}
+
+	/// Move focus to the previous component in Tab order, wrapping around
+	pub fn focus_backward(&mut self, tree: &ComponentTree) -> Option<ComponentId> {
+		self.step(tree, false)
+	}
+
+	/// Move focus for Tab and Shift+Tab
+	///
+	/// Returns whether the event was a Tab key press focus moved for.
+	pub fn handle_keyboard(&mut self, tree: &ComponentTree, event: &KeyboardEvent) -> bool {
+		let other_modifiers = KeyModifiers::CTRL | KeyModifiers::ALT | KeyModifiers::META;
+		if event.event_type != KeyEventType::Down
+			|| event.key_code != KeyCode::Tab
+			|| event.modifiers.intersects(other_modifiers)
+		{
+			return false;
+		}
+		self.step(tree, !event.is_shift()).is_some()
+	}
+
+	/// Trap Tab inside the subtree at `root`
+	///
+	/// Focus moves to the first component in the scope's Tab order, or to
+	/// `root` itself when there is none, unless it is already inside.
+	pub fn push_scope(&mut self, tree: &ComponentTree, root: ComponentId) {
+		self.scopes.push(FocusScope {
+			root,
+			restore: self.focused_id,
+		});
+		let inside = self
+			.focused_id
+			.is_some_and(|id| id == root || tree.is_ancestor(root, id));
+		if !inside {
+			let first = self.tab_order(tree).first().copied().unwrap_or(root);
+			self.set_focus(first);
+		}
+	}
+
+	/// Remove the scope at `root`
+	///
+	/// When it was the innermost scope, focus goes back to where it was
+	/// when the scope was added, or to the first component in the enclosing
+	/// scope's Tab order when that can no longer take focus. Returns whether
+	/// there was such a scope.
+	pub fn pop_scope(&mut self, tree: &ComponentTree, root: ComponentId) -> bool {
+		let Some(index) = self.scopes.iter().rposition(|scope| scope.root == root) else {
+			return false;
+		};
+		let scope = self.scopes.remove(index);
+		if index == self.scopes.len() {
+			let restore = scope
+				.restore
+				.filter(|&id| self.is_focusable(tree, id))
+				.or_else(|| self.tab_order(tree).first().copied());
+			match restore {
+				Some(id) => self.set_focus(id),
+				None => self.clear_focus(),
+			}
+		}
+		true
+	}
+
+	/// Root of the innermost scope
+	pub fn scope(&self) -> Option<ComponentId> {
+		self.scopes.last().map(|scope| scope.root)
+	}
+
+	/// Take the focus events queued since last asked
+	pub fn take_events(&mut self) -> Vec<Event> {
+		std::mem::take(&mut self.events)
+	}
+
+	fn step(&mut self, tree: &ComponentTree, forward: bool) -> Option<ComponentId> {
+		let order = self.tab_order(tree);
+		let len = order.len();
+		let current = self
+			.focused_id
+			.and_then(|id| order.iter().position(|&candidate| candidate == id));
+		let next = match (current, forward) {
+			(Some(index), true) => order[(index + 1) % len],
+			(Some(index), false) => order[(index + len - 1) % len],
+			(None, true) => *order.first()?,
+			(None, false) => *order.last()?,
+		};
+		self.set_focus(next);
+		Some(next)
+	}
+
+	fn change_focus(&mut self, next: Option<ComponentId>) {
+		if self.focused_id == next {
+			return;
+		}
+		if let Some(previous) = self.focused_id {
+			self.events.push(Event::new(previous, EventType::FocusLost));
+		}
+		if let Some(next) = next {
+			self.events.push(Event::new(next, EventType::FocusGained));
+		}
+		self.focused_id = next;
+	}
+}
+
+/// Which arrow keys move between a composite component's items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+	/// Left and Right
+	Horizontal,
+	/// Up and Down
+	Vertical,
+	/// All four arrow keys
+	Both,
+}
+
+/// Arrow key movement between the items of a composite component
+///
+/// Home and End move to the first and last item. Disabled items are
+/// skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RovingFocus {
+	orientation: Orientation,
+	wrap: bool,
+}
+
+impl RovingFocus {
+	/// Move with the arrow keys of an orientation, wrapping around at the
+	/// ends
+	pub fn new(orientation: Orientation) -> Self {
+		Self {
+			orientation,
+			wrap: true,
+		}
+	}
+
+	/// Set whether moving past the last item goes back to the first
+	pub fn with_wrap(mut self, wrap: bool) -> Self {
+		self.wrap = wrap;
+		self
+	}
+
+	/// Get the orientation
+	pub fn orientation(&self) -> Orientation {
+		self.orientation
+	}
+
+	/// The item a key press moves to from `current`, out of `len` items
+	///
+	/// Returns `None` for keys that do not move, or when there is no other
+	/// enabled item to move to.
+	pub fn next_index(
+		&self,
+		event: &KeyboardEvent,
+		current: Option<usize>,
+		len: usize,
+		is_disabled: impl Fn(usize) -> bool,
+	) -> Option<usize> {
+		let other_modifiers = KeyModifiers::CTRL | KeyModifiers::ALT | KeyModifiers::META;
+		if event.event_type != KeyEventType::Down
+			|| event.modifiers.intersects(other_modifiers)
+			|| len == 0
+		{
+			return None;
+		}
+
+		let horizontal = self.orientation != Orientation::Vertical;
+		let vertical = self.orientation != Orientation::Horizontal;
+		let enabled = |index: &usize| !is_disabled(*index);
+		let next = match event.key_code {
+			KeyCode::Home => (0..len).find(enabled),
+			KeyCode::End => (0..len).rev().find(enabled),
+			KeyCode::ArrowRight if horizontal => self.search(current, len, true, enabled),
+			KeyCode::ArrowDown if vertical => self.search(current, len, true, enabled),
+			KeyCode::ArrowLeft if horizontal => self.search(current, len, false, enabled),
+			KeyCode::ArrowUp if vertical => self.search(current, len, false, enabled),
+			_ => None,
+		};
+		next.filter(|&next| Some(next) != current)
+	}
+
+	/// First enabled item after or before `current`
+	fn search(
+		&self,
+		current: Option<usize>,
+		len: usize,
+		forward: bool,
+		enabled: impl Fn(&usize) -> bool,
+	) -> Option<usize> {
+		let mut index = current;
+		for _ in 0..len {
+			index = match (index, forward) {
+				(None, true) => Some(0),
+				(None, false) => Some(len - 1),
+				(Some(i), true) if i + 1 < len => Some(i + 1),
+				(Some(i), false) if i > 0 => Some(i - 1),
+				(Some(_), true) if self.wrap => Some(0),
+				(Some(_), false) if self.wrap => Some(len - 1),
+				_ => return None,
+			};
+			if index.is_some_and(|i| enabled(&i)) {
+				return index;
+			}
+		}
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::{Component, ComponentProperties};
+
+	struct Node {
+		properties: ComponentProperties,
+		children: Vec<ComponentId>,
+	}
+
+	impl Component for Node {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+	}
+
+	fn node(id: ComponentId) -> Node {
+		Node {
+			properties: ComponentProperties::new(id),
+			children: Vec::new(),
+		}
+	}
+
+	/// Build: 1 -> (2, 3 -> (4, 5)), 6, with the leaves focusable
+	fn setup() -> (ComponentTree, FocusManager) {
+		let mut tree = ComponentTree::new();
+		for (parent, id) in [
+			(None, 1),
+			(Some(1), 2),
+			(Some(1), 3),
+			(Some(3), 4),
+			(Some(3), 5),
+			(None, 6),
+		] {
+			tree.insert(parent, node(id)).unwrap();
+		}
+		let mut focus = FocusManager::new();
+		for id in [2, 4, 5, 6] {
+			focus.register(id, AccessibilityProps::new().with_focusable(true));
+		}
+		(tree, focus)
+	}
+
+	fn changes(focus: &mut FocusManager) -> Vec<(ComponentId, EventType)> {
+		focus
+			.take_events()
+			.into_iter()
+			.map(|event| (event.target, event.event_type))
+			.collect()
+	}
+
+	fn tab(shift: bool) -> KeyboardEvent {
+		let modifiers = if shift {
+			KeyModifiers::SHIFT
+		} else {
+			KeyModifiers::empty()
+		};
+		KeyboardEvent::key_down(KeyCode::Tab, modifiers)
+	}
+
+	#[test]
+	fn test_tab_order() {
+		let (tree, mut focus) = setup();
+		assert_eq!(focus.tab_order(&tree), vec![2, 4, 5, 6]);
+
+		// Positive indices first, negative ones left out
+		focus.register(
+			5,
+			AccessibilityProps::new()
+				.with_focusable(true)
+				.with_tab_index(2),
+		);
+		focus.register(
+			6,
+			AccessibilityProps::new()
+				.with_focusable(true)
+				.with_tab_index(1),
+		);
+		focus.register(
+			2,
+			AccessibilityProps::new()
+				.with_focusable(true)
+				.with_tab_index(-1),
+		);
+		assert_eq!(focus.tab_order(&tree), vec![6, 5, 4]);
+
+		// Hidden subtrees are skipped
+		tree.get(3).unwrap().try_write().unwrap().set_visible(false);
+		assert_eq!(focus.tab_order(&tree), vec![6]);
+		assert!(!focus.is_focusable(&tree, 4));
+	}
+
+	#[test]
+	fn test_locked_subtree_not_focusable() {
+		let (tree, focus) = setup();
+		let parent = tree.get(3).unwrap().clone();
+		let guard = parent.try_write().unwrap();
+		assert!(!focus.is_focusable(&tree, 3));
+		assert!(!focus.is_focusable(&tree, 4));
+		assert_eq!(focus.tab_order(&tree), vec![2, 6]);
+
+		drop(guard);
+		assert_eq!(focus.tab_order(&tree), vec![2, 4, 5, 6]);
+	}
+
+	#[test]
+	fn test_tab_traversal() {
+		let (tree, mut focus) = setup();
+		assert!(focus.handle_keyboard(&tree, &tab(false)));
+		assert_eq!(focus.focused(), Some(2));
+		focus.handle_keyboard(&tree, &tab(false));
+		assert_eq!(focus.focused(), Some(4));
+		assert_eq!(
+			changes(&mut focus),
+			vec![
+				(2, EventType::FocusGained),
+				(2, EventType::FocusLost),
+				(4, EventType::FocusGained),
+			]
+		);
+
+		// Shift+Tab goes back and wraps around
+		focus.handle_keyboard(&tree, &tab(true));
+		focus.handle_keyboard(&tree, &tab(true));
+		assert_eq!(focus.focused(), Some(6));
+
+		// Other keys are left alone
+		let ctrl_tab = KeyboardEvent::key_down(KeyCode::Tab, KeyModifiers::CTRL);
+		assert!(!focus.handle_keyboard(&tree, &ctrl_tab));
+		let enter = KeyboardEvent::key_down(KeyCode::Enter, KeyModifiers::empty());
+		assert!(!focus.handle_keyboard(&tree, &enter));
+
+		focus.unregister(6);
+		assert_eq!(focus.focused(), None);
+		assert_eq!(changes(&mut focus).pop(), Some((6, EventType::FocusLost)));
+	}
+
+	#[test]
+	fn test_focus_scope() {
+		let (tree, mut focus) = setup();
+		focus.set_focus(6);
+
+		// Focus moves into the scope and Tab stays there
+		focus.push_scope(&tree, 3);
+		assert_eq!(focus.scope(), Some(3));
+		assert_eq!(focus.focused(), Some(4));
+		focus.focus_forward(&tree);
+		assert_eq!(focus.focused(), Some(5));
+		focus.focus_forward(&tree);
+		assert_eq!(focus.focused(), Some(4));
+
+		// Removing it gives focus back
+		assert!(focus.pop_scope(&tree, 3));
+		assert!(!focus.pop_scope(&tree, 3));
+		assert_eq!(focus.focused(), Some(6));
+		assert_eq!(focus.scope(), None);
+
+		// A scope with nothing focusable focuses its root
+		focus.push_scope(&tree, 1);
+		focus.pop_scope(&tree, 1);
+		focus.unregister(2);
+		focus.unregister(4);
+		focus.unregister(5);
+		focus.push_scope(&tree, 3);
+		assert_eq!(focus.focused(), Some(3));
+	}
+
+	#[test]
+	fn test_focus_scope_restore_gone() {
+		let (mut tree, mut focus) = setup();
+		focus.set_focus(6);
+		focus.push_scope(&tree, 1);
+		assert_eq!(focus.focused(), Some(2));
+		focus.push_scope(&tree, 3);
+		assert_eq!(focus.focused(), Some(4));
+
+		// A disabled restore target falls back to the enclosing scope
+		let disabled = AccessibilityProps {
+			disabled: true,
+			..AccessibilityProps::new().with_focusable(true)
+		};
+		focus.register(2, disabled);
+		assert!(focus.pop_scope(&tree, 3));
+		assert_eq!(focus.focused(), Some(4));
+
+		// So does one removed from the tree
+		tree.remove(6).unwrap();
+		assert!(focus.pop_scope(&tree, 1));
+		assert_eq!(focus.focused(), Some(4));
+
+		// With nothing left to focus, focus is cleared
+		focus.push_scope(&tree, 3);
+		focus.unregister(4);
+		focus.unregister(5);
+		assert!(focus.pop_scope(&tree, 3));
+		assert_eq!(focus.focused(), None);
+	}
+
+	#[test]
+	fn test_roving_focus() {
+		let key = |code| KeyboardEvent::key_down(code, KeyModifiers::empty());
+		let horizontal = RovingFocus::new(Orientation::Horizontal);
+		let none = |_| false;
+
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::ArrowRight), Some(0), 3, none),
+			Some(1)
+		);
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::ArrowRight), Some(2), 3, none),
+			Some(0)
+		);
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::ArrowLeft), None, 3, none),
+			Some(2)
+		);
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::ArrowDown), Some(0), 3, none),
+			None
+		);
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::End), Some(0), 3, none),
+			Some(2)
+		);
+
+		// Disabled items are skipped
+		let disabled = |index| index == 1;
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::ArrowRight), Some(0), 3, disabled),
+			Some(2)
+		);
+		assert_eq!(
+			horizontal.next_index(&key(KeyCode::Home), Some(2), 3, |index| index == 0),
+			Some(1)
+		);
+
+		// Without wrapping the ends stop
+		let vertical = RovingFocus::new(Orientation::Vertical).with_wrap(false);
+		assert_eq!(
+			vertical.next_index(&key(KeyCode::ArrowDown), Some(2), 3, none),
+			None
+		);
+		assert_eq!(
+			vertical.next_index(&key(KeyCode::ArrowUp), Some(2), 3, none),
+			Some(1)
+		);
+	}
+}
@@ -304,6 +304,7 @@ impl Animation {
 	}
 
 	/// Update animation with elapsed time
+	#[allow(clippy::collapsible_if)]
 	pub fn update(&mut self, delta: Duration) -> Option<AnimationValue> {
 		if self.state != AnimationState::Running {
 			return None;
@@ -0,0 +1,289 @@
+//! Component trait and base structures
+//!
+//! Defines the base trait that all UI components must implement, and the
+//! [`ComponentTree`] that owns components and their parent/child structure.
+
+pub mod tree;
+
+pub use tree::{BreadthFirst, ComponentTree, DepthFirst, TreeError};
+
+use crate::layout::{FlexItem, FlexLayout, GridItem, GridLayout};
+use crate::media::FontRegistry;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Unique identifier for components
+pub type ComponentId = u64;
+
+/// Rectangle representing position and size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+	pub x: f32,
+	pub y: f32,
+	pub width: f32,
+	pub height: f32,
+}
+
+impl Rect {
+	pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+		Self {
+			x,
+			y,
+			width,
+			height,
+		}
+	}
+
+	pub fn contains_point(&self, x: f32, y: f32) -> bool {
+		x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+	}
+
+	/// Check whether the rectangle covers no area
+	pub fn is_empty(&self) -> bool {
+		!(self.width > 0.0 && self.height > 0.0)
+	}
+
+	/// Area covered by the rectangle, zero when it is empty
+	pub fn area(&self) -> f32 {
+		if self.is_empty() {
+			0.0
+		} else {
+			self.width * self.height
+		}
+	}
+
+	/// Check whether another rectangle lies entirely inside this one
+	pub fn contains_rect(&self, other: &Rect) -> bool {
+		other.x >= self.x
+			&& other.y >= self.y
+			&& other.x + other.width <= self.x + self.width
+			&& other.y + other.height <= self.y + self.height
+	}
+
+	/// Check whether two rectangles share any area
+	pub fn intersects(&self, other: &Rect) -> bool {
+		self.intersection(other).is_some()
+	}
+
+	/// The area two rectangles share, if any
+	pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+		let left = self.x.max(other.x);
+		let top = self.y.max(other.y);
+		let right = (self.x + self.width).min(other.x + other.width);
+		let bottom = (self.y + self.height).min(other.y + other.height);
+		let rect = Rect::new(left, top, right - left, bottom - top);
+		(!rect.is_empty()).then_some(rect)
+	}
+
+	/// Smallest rectangle holding both rectangles
+	pub fn union(&self, other: &Rect) -> Rect {
+		let left = self.x.min(other.x);
+		let top = self.y.min(other.y);
+		let right = (self.x + self.width).max(other.x + other.width);
+		let bottom = (self.y + self.height).max(other.y + other.height);
+		Rect::new(left, top, right - left, bottom - top)
+	}
+}
+
+/// Properties common to all components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentProperties {
+	pub id: ComponentId,
+	pub visible: bool,
+	pub enabled: bool,
+	pub bounds: Rect,
+	/// How the component is sized within its parent's flex layout
+	#[serde(default)]
+	pub flex: FlexItem,
+	/// Where the component sits within its parent's grid layout
+	#[serde(default)]
+	pub grid: GridItem,
+}
+
+impl ComponentProperties {
+	pub fn new(id: ComponentId) -> Self {
+		Self {
+			id,
+			visible: true,
+			enabled: true,
+			bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+			flex: FlexItem::new(),
+			grid: GridItem::new(),
+		}
+	}
+}
+
+/// Base trait for all UI components
+pub trait Component: Send + Sync {
+	/// Get the component's unique identifier
+	fn id(&self) -> ComponentId;
+
+	/// Get component properties
+	fn properties(&self) -> &ComponentProperties;
+
+	/// Get mutable component properties
+	fn properties_mut(&mut self) -> &mut ComponentProperties;
+
+	/// Check if component is visible
+	fn is_visible(&self) -> bool {
+		self.properties().visible
+	}
+
+	/// Set visibility
+	fn set_visible(&mut self, visible: bool) {
+		self.properties_mut().visible = visible;
+	}
+
+	/// Check if component is enabled
+	fn is_enabled(&self) -> bool {
+		self.properties().enabled
+	}
+
+	/// Set enabled state
+	fn set_enabled(&mut self, enabled: bool) {
+		self.properties_mut().enabled = enabled;
+	}
+
+	/// Get component bounds
+	fn bounds(&self) -> Rect {
+		self.properties().bounds
+	}
+
+	/// Set component bounds
+	fn set_bounds(&mut self, bounds: Rect) {
+		self.properties_mut().bounds = bounds;
+	}
+
+	/// Get the IDs of child components
+	///
+	/// Only components that contain other components (such as containers,
+	/// groups and windows) need to override this.
+	fn children(&self) -> &[ComponentId] {
+		&[]
+	}
+
+	/// Replace the list of child component IDs
+	///
+	/// Called by [`ComponentTree`] whenever the children of this component
+	/// change, so that the component's own list mirrors the tree.
+	fn set_children(&mut self, _children: Vec<ComponentId>) {}
+
+	/// Check whether children are only visible within this component's
+	/// bounds
+	///
+	/// [`ComponentTree::hit_test`] does not hit clipped children outside
+	/// them.
+	fn clips_children(&self) -> bool {
+		false
+	}
+
+	/// Check whether children are hidden, as those of a collapsed group are
+	///
+	/// Hidden children are treated as if each were hidden itself: the
+	/// [`LayoutSolver`](crate::layout::LayoutSolver) does not lay them out
+	/// and measures this component through [`Component::measure`], and
+	/// [`ComponentTree::hit_test`] and focus traversal skip them.
+	fn hides_children(&self) -> bool {
+		false
+	}
+
+	/// Get the flex settings used to arrange this component's children
+	///
+	/// Components that position their children return `Some`; the
+	/// [`LayoutSolver`](crate::layout::LayoutSolver) leaves the children of
+	/// other components where they are.
+	fn flex_layout(&self) -> Option<FlexLayout> {
+		None
+	}
+
+	/// Get the grid used to arrange this component's children
+	///
+	/// Takes precedence over [`Component::flex_layout`].
+	fn grid_layout(&self) -> Option<GridLayout> {
+		None
+	}
+
+	/// Get the intrinsic (width, height) of the component's content
+	///
+	/// Used when the component has no explicit size in its parent's layout.
+	/// Components showing text measure it with `fonts`. Defaults to no
+	/// size; the bounds are not used, since layout writes them.
+	fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+		(0.0, 0.0)
+	}
+}
+
+/// Thread-safe wrapper for components
+pub type ComponentRef = Arc<RwLock<dyn Component>>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rect_creation() {
+		let rect = Rect::new(10.0, 20.0, 100.0, 50.0);
+		assert_eq!(rect.x, 10.0);
+		assert_eq!(rect.y, 20.0);
+		assert_eq!(rect.width, 100.0);
+		assert_eq!(rect.height, 50.0);
+	}
+
+	#[test]
+	fn test_rect_contains_point() {
+		let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+		assert!(rect.contains_point(50.0, 50.0));
+		assert!(rect.contains_point(0.0, 0.0));
+		assert!(rect.contains_point(100.0, 100.0));
+		assert!(!rect.contains_point(101.0, 50.0));
+		assert!(!rect.contains_point(50.0, 101.0));
+	}
+
+	#[test]
+	fn test_rect_intersection_and_union() {
+		let a = Rect::new(0.0, 0.0, 100.0, 50.0);
+		let b = Rect::new(50.0, 25.0, 100.0, 50.0);
+		assert_eq!(a.intersection(&b), Some(Rect::new(50.0, 25.0, 50.0, 25.0)));
+		assert_eq!(a.union(&b), Rect::new(0.0, 0.0, 150.0, 75.0));
+		assert_eq!(a.area(), 5000.0);
+
+		// Touching edges share no area
+		let c = Rect::new(100.0, 0.0, 10.0, 10.0);
+		assert!(!a.intersects(&c));
+		assert!(a.contains_rect(&Rect::new(10.0, 10.0, 20.0, 20.0)));
+		assert!(!a.contains_rect(&b));
+		assert!(Rect::new(0.0, 0.0, 0.0, 10.0).is_empty());
+	}
+
+	#[test]
+	fn test_component_properties() {
+		let props = ComponentProperties::new(1);
+		assert_eq!(props.id, 1);
+		assert!(props.visible);
+		assert!(props.enabled);
+	}
+
+	#[test]
+	fn test_default_measure_ignores_bounds() {
+		struct Plain(ComponentProperties);
+
+		impl Component for Plain {
+			fn id(&self) -> ComponentId {
+				self.0.id
+			}
+
+			fn properties(&self) -> &ComponentProperties {
+				&self.0
+			}
+
+			fn properties_mut(&mut self) -> &mut ComponentProperties {
+				&mut self.0
+			}
+		}
+
+		let mut plain = Plain(ComponentProperties::new(1));
+		plain.set_bounds(Rect::new(0.0, 0.0, 40.0, 30.0));
+		assert_eq!(plain.measure(&FontRegistry::new()), (0.0, 0.0));
+	}
+}
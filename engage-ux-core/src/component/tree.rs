@@ -0,0 +1,1038 @@
+//! Retained component tree
+//!
+//! Owns components and tracks their parent/child relationships, so that
+//! container components can refer to real nodes by [`ComponentId`]. The
+//! tree also collects the damage that changes to its components cause, so
+//! the next frame only repaints those areas, and which components' recorded
+//! drawing is out of date.
+
+use super::{Component, ComponentId, ComponentRef, Rect};
+use crate::rendering::DamageRegion;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Errors produced by component tree operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+	/// No node with this ID exists in the tree
+	NotFound(ComponentId),
+	/// A node with this ID already exists in the tree
+	DuplicateId(ComponentId),
+	/// The operation would make a node its own ancestor
+	CycleDetected {
+		node: ComponentId,
+		parent: ComponentId,
+	},
+	/// The component is locked elsewhere and could not be read
+	ComponentLocked,
+}
+
+impl std::fmt::Display for TreeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			TreeError::NotFound(id) => write!(f, "Component {} not found in tree", id),
+			TreeError::DuplicateId(id) => write!(f, "Component {} already exists in tree", id),
+			TreeError::CycleDetected { node, parent } => write!(
+				f,
+				"Cannot move component {} under {}: it would become its own ancestor",
+				node, parent
+			),
+			TreeError::ComponentLocked => write!(f, "Component is locked"),
+		}
+	}
+}
+
+impl std::error::Error for TreeError {}
+
+/// A node in the component tree
+struct Node {
+	component: ComponentRef,
+	parent: Option<ComponentId>,
+	children: Vec<ComponentId>,
+}
+
+/// Tree of components with parent/child ownership
+///
+/// The tree is the authoritative owner of component structure. Whenever the
+/// children of a node change, the node's component is told through
+/// [`Component::set_children`], so container components always list real
+/// nodes.
+///
+/// Inserting, removing and moving components damages the area they cover
+/// and makes them and their ancestors stale. Components are changed through
+/// [`ComponentTree::update`], or marked with [`ComponentTree::invalidate`]
+/// after being written to directly.
+pub struct ComponentTree {
+	nodes: HashMap<ComponentId, Node>,
+	roots: Vec<ComponentId>,
+	next_id: ComponentId,
+	damage: DamageRegion,
+	stale: HashSet<ComponentId>,
+}
+
+impl ComponentTree {
+	/// Create an empty tree
+	pub fn new() -> Self {
+		Self {
+			nodes: HashMap::new(),
+			roots: Vec::new(),
+			next_id: 1,
+			damage: DamageRegion::new(),
+			stale: HashSet::new(),
+		}
+	}
+
+	/// Allocate a component ID that is not used by any node in the tree
+	pub fn allocate_id(&mut self) -> ComponentId {
+		while self.nodes.contains_key(&self.next_id) {
+			self.next_id += 1;
+		}
+		let id = self.next_id;
+		self.next_id += 1;
+		id
+	}
+
+	/// Insert a component under `parent`, or as a root if `parent` is `None`
+	///
+	/// Any children the component already lists that exist in the tree are
+	/// moved under it. Returns the component's ID.
+	pub fn insert<C: Component + 'static>(
+		&mut self,
+		parent: Option<ComponentId>,
+		component: C,
+	) -> Result<ComponentId, TreeError> {
+		let id = component.id();
+		self.insert_node(parent, id, Arc::new(RwLock::new(component)))
+	}
+
+	/// Insert an already shared component under `parent`
+	pub fn insert_ref(
+		&mut self,
+		parent: Option<ComponentId>,
+		component: ComponentRef,
+	) -> Result<ComponentId, TreeError> {
+		let id = component
+			.try_read()
+			.map_err(|_| TreeError::ComponentLocked)?
+			.id();
+		self.insert_node(parent, id, component)
+	}
+
+	fn insert_node(
+		&mut self,
+		parent: Option<ComponentId>,
+		id: ComponentId,
+		component: ComponentRef,
+	) -> Result<ComponentId, TreeError> {
+		if self.nodes.contains_key(&id) {
+			return Err(TreeError::DuplicateId(id));
+		}
+		if let Some(parent_id) = parent
+			&& !self.nodes.contains_key(&parent_id)
+		{
+			return Err(TreeError::NotFound(parent_id));
+		}
+
+		let declared = component
+			.try_read()
+			.map_err(|_| TreeError::ComponentLocked)?
+			.children()
+			.to_vec();
+		let adopted: Vec<ComponentId> = declared
+			.into_iter()
+			.filter(|child| self.nodes.contains_key(child))
+			.collect();
+
+		// Every component whose child list changes must be writable before
+		// the structure is touched, so a failure leaves the tree as it was
+		let mut touched: Vec<ComponentId> = parent.into_iter().collect();
+		touched.extend(
+			adopted
+				.iter()
+				.filter_map(|child| self.nodes.get(child).and_then(|node| node.parent)),
+		);
+		self.ensure_writable(&touched)?;
+		if component.try_write().is_err() {
+			return Err(TreeError::ComponentLocked);
+		}
+
+		self.nodes.insert(
+			id,
+			Node {
+				component,
+				parent,
+				children: Vec::new(),
+			},
+		);
+		match parent {
+			Some(parent_id) => {
+				if let Some(node) = self.nodes.get_mut(&parent_id) {
+					node.children.push(id);
+				}
+				self.sync_children(parent_id)?;
+			}
+			None => self.roots.push(id),
+		}
+		self.next_id = self.next_id.max(id + 1);
+
+		// Adopt declared children that already exist as nodes
+		for child in adopted {
+			if !self.is_ancestor(child, id) && child != id {
+				self.detach(child)?;
+				self.attach(child, Some(id), None)?;
+			}
+		}
+		self.sync_children(id)?;
+		self.invalidate_subtree(id);
+
+		Ok(id)
+	}
+
+	/// Remove a component and its entire subtree
+	///
+	/// Returns the removed component.
+	pub fn remove(&mut self, id: ComponentId) -> Result<ComponentRef, TreeError> {
+		let parent = self.nodes.get(&id).ok_or(TreeError::NotFound(id))?.parent;
+		self.ensure_writable(&parent.into_iter().collect::<Vec<_>>())?;
+
+		self.invalidate_subtree(id);
+		let subtree: Vec<ComponentId> = self.depth_first(id).collect();
+		self.detach(id)?;
+
+		let mut removed = None;
+		for node_id in subtree {
+			if let Some(node) = self.nodes.remove(&node_id)
+				&& node_id == id
+			{
+				removed = Some(node.component);
+			}
+		}
+
+		removed.ok_or(TreeError::NotFound(id))
+	}
+
+	/// Move a component (with its subtree) under a new parent
+	///
+	/// `index` is the position among the new parent's children; `None`
+	/// appends. A `new_parent` of `None` makes the component a root.
+	pub fn reparent(
+		&mut self,
+		id: ComponentId,
+		new_parent: Option<ComponentId>,
+		index: Option<usize>,
+	) -> Result<(), TreeError> {
+		let old_parent = self.nodes.get(&id).ok_or(TreeError::NotFound(id))?.parent;
+		if let Some(parent_id) = new_parent {
+			if !self.nodes.contains_key(&parent_id) {
+				return Err(TreeError::NotFound(parent_id));
+			}
+			if parent_id == id || self.is_ancestor(id, parent_id) {
+				return Err(TreeError::CycleDetected {
+					node: id,
+					parent: parent_id,
+				});
+			}
+		}
+
+		let touched: Vec<ComponentId> = old_parent.into_iter().chain(new_parent).collect();
+		self.ensure_writable(&touched)?;
+
+		// The subtree may now paint above or below different siblings, and
+		// no longer draws as part of its old ancestors
+		self.invalidate_subtree(id);
+		self.detach(id)?;
+		self.attach(id, new_parent, index)?;
+		self.invalidate_subtree(id);
+		Ok(())
+	}
+
+	/// Mark the area a component covers as needing a repaint, and the
+	/// component and its ancestors as stale
+	///
+	/// Call this after changing anything that affects how the component
+	/// looks.
+	pub fn invalidate(&mut self, id: ComponentId) -> Result<(), TreeError> {
+		let node = self.nodes.get(&id).ok_or(TreeError::NotFound(id))?;
+		let bounds = node
+			.component
+			.try_read()
+			.map_err(|_| TreeError::ComponentLocked)?
+			.bounds();
+		self.damage.add(bounds);
+		self.mark_stale(id);
+		Ok(())
+	}
+
+	/// Mark an area of the surface as needing a repaint
+	pub fn invalidate_rect(&mut self, rect: Rect) {
+		self.damage.add(rect);
+	}
+
+	/// Move or resize a component, damaging both where it was and where it
+	/// now is
+	pub fn set_bounds(&mut self, id: ComponentId, bounds: Rect) -> Result<(), TreeError> {
+		self.update(id, |component| component.set_bounds(bounds))
+	}
+
+	/// Change a component, damaging both where it was and where it now is
+	/// and marking it and its ancestors stale
+	///
+	/// Returns what `change` returns. Changes made by writing to the
+	/// component through [`ComponentTree::get`] are not seen by the tree;
+	/// follow them with [`ComponentTree::invalidate`].
+	pub fn update<R>(
+		&mut self,
+		id: ComponentId,
+		change: impl FnOnce(&mut dyn Component) -> R,
+	) -> Result<R, TreeError> {
+		let node = self.nodes.get(&id).ok_or(TreeError::NotFound(id))?;
+		let mut component = node
+			.component
+			.try_write()
+			.map_err(|_| TreeError::ComponentLocked)?;
+		let previous = component.bounds();
+		let result = change(&mut *component);
+		let bounds = component.bounds();
+		drop(component);
+
+		self.damage.add(previous);
+		self.damage.add(bounds);
+		self.mark_stale(id);
+		Ok(result)
+	}
+
+	/// Areas that need a repaint since the damage was last taken
+	pub fn damage(&self) -> &DamageRegion {
+		&self.damage
+	}
+
+	/// Take the damage to repaint in the next frame, leaving none
+	pub fn take_damage(&mut self) -> DamageRegion {
+		self.damage.take()
+	}
+
+	/// Take the components whose recorded drawing is out of date, because
+	/// they or a descendant changed, were moved or were removed, leaving
+	/// none
+	pub fn take_stale(&mut self) -> Vec<ComponentId> {
+		self.stale.drain().collect()
+	}
+
+	/// Mark a component and its ancestors as stale
+	fn mark_stale(&mut self, id: ComponentId) {
+		self.stale.insert(id);
+		self.stale.extend(self.ancestors(id));
+	}
+
+	/// Damage the area covered by every component in a subtree and mark
+	/// them stale
+	///
+	/// Components locked elsewhere are not damaged.
+	fn invalidate_subtree(&mut self, id: ComponentId) {
+		let subtree: Vec<ComponentId> = self.depth_first(id).collect();
+		for &node_id in &subtree {
+			if let Some(bounds) = self
+				.nodes
+				.get(&node_id)
+				.and_then(|node| node.component.try_read().ok().map(|c| c.bounds()))
+			{
+				self.damage.add(bounds);
+			}
+		}
+		self.stale.extend(subtree);
+		self.stale.extend(self.ancestors(id));
+	}
+
+	/// Remove a node from its parent's child list (or the root list)
+	fn detach(&mut self, id: ComponentId) -> Result<(), TreeError> {
+		let parent = self.nodes.get(&id).and_then(|node| node.parent);
+		match parent {
+			Some(parent_id) => {
+				if let Some(node) = self.nodes.get_mut(&parent_id) {
+					node.children.retain(|&child| child != id);
+				}
+				if let Some(node) = self.nodes.get_mut(&id) {
+					node.parent = None;
+				}
+				self.sync_children(parent_id)
+			}
+			None => {
+				self.roots.retain(|&root| root != id);
+				Ok(())
+			}
+		}
+	}
+
+	/// Add a detached node to a parent's child list (or the root list)
+	fn attach(
+		&mut self,
+		id: ComponentId,
+		parent: Option<ComponentId>,
+		index: Option<usize>,
+	) -> Result<(), TreeError> {
+		let list = match parent {
+			Some(parent_id) => match self.nodes.get_mut(&parent_id) {
+				Some(node) => &mut node.children,
+				None => return Err(TreeError::NotFound(parent_id)),
+			},
+			None => &mut self.roots,
+		};
+		let index = index.unwrap_or(list.len()).min(list.len());
+		list.insert(index, id);
+
+		if let Some(node) = self.nodes.get_mut(&id) {
+			node.parent = parent;
+		}
+		match parent {
+			Some(parent_id) => self.sync_children(parent_id),
+			None => Ok(()),
+		}
+	}
+
+	/// Push a node's child list into its component
+	///
+	/// Fails with [`TreeError::ComponentLocked`] rather than leaving the
+	/// component listing children the tree no longer agrees with.
+	fn sync_children(&self, id: ComponentId) -> Result<(), TreeError> {
+		let node = self.nodes.get(&id).ok_or(TreeError::NotFound(id))?;
+		node.component
+			.try_write()
+			.map_err(|_| TreeError::ComponentLocked)?
+			.set_children(node.children.clone());
+		Ok(())
+	}
+
+	/// Check that the components of the given nodes can be written, before
+	/// an operation that changes their child lists begins
+	fn ensure_writable(&self, ids: &[ComponentId]) -> Result<(), TreeError> {
+		for id in ids {
+			let node = self.nodes.get(id).ok_or(TreeError::NotFound(*id))?;
+			if node.component.try_write().is_err() {
+				return Err(TreeError::ComponentLocked);
+			}
+		}
+		Ok(())
+	}
+
+	/// Get a component by ID
+	///
+	/// Writing to the component through the returned reference does not
+	/// make it stale; use [`ComponentTree::update`], or call
+	/// [`ComponentTree::invalidate`] after the write.
+	pub fn get(&self, id: ComponentId) -> Option<&ComponentRef> {
+		self.nodes.get(&id).map(|node| &node.component)
+	}
+
+	/// Check if a component is in the tree
+	pub fn contains(&self, id: ComponentId) -> bool {
+		self.nodes.contains_key(&id)
+	}
+
+	/// Get the number of components in the tree
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Check if the tree is empty
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Get the root components, in insertion order
+	pub fn roots(&self) -> &[ComponentId] {
+		&self.roots
+	}
+
+	/// Get the parent of a component
+	pub fn parent(&self, id: ComponentId) -> Option<ComponentId> {
+		self.nodes.get(&id)?.parent
+	}
+
+	/// Get the children of a component, in order
+	pub fn children(&self, id: ComponentId) -> &[ComponentId] {
+		self.nodes
+			.get(&id)
+			.map(|node| node.children.as_slice())
+			.unwrap_or(&[])
+	}
+
+	/// Get the list a component belongs to: its parent's children, or the roots
+	fn sibling_list(&self, id: ComponentId) -> Option<&[ComponentId]> {
+		let node = self.nodes.get(&id)?;
+		Some(match node.parent {
+			Some(parent_id) => self.children(parent_id),
+			None => &self.roots,
+		})
+	}
+
+	/// Get the siblings of a component, excluding the component itself
+	pub fn siblings(&self, id: ComponentId) -> Vec<ComponentId> {
+		self.sibling_list(id)
+			.map(|list| list.iter().copied().filter(|&s| s != id).collect())
+			.unwrap_or_default()
+	}
+
+	/// Get the sibling immediately after a component
+	pub fn next_sibling(&self, id: ComponentId) -> Option<ComponentId> {
+		let list = self.sibling_list(id)?;
+		let index = list.iter().position(|&s| s == id)?;
+		list.get(index + 1).copied()
+	}
+
+	/// Get the sibling immediately before a component
+	pub fn previous_sibling(&self, id: ComponentId) -> Option<ComponentId> {
+		let list = self.sibling_list(id)?;
+		let index = list.iter().position(|&s| s == id)?;
+		index.checked_sub(1).map(|i| list[i])
+	}
+
+	/// Get the ancestors of a component, nearest first
+	pub fn ancestors(&self, id: ComponentId) -> Vec<ComponentId> {
+		let mut ancestors = Vec::new();
+		let mut current = self.parent(id);
+		while let Some(parent_id) = current {
+			ancestors.push(parent_id);
+			current = self.parent(parent_id);
+		}
+		ancestors
+	}
+
+	/// Check if `ancestor` is a strict ancestor of `id`
+	pub fn is_ancestor(&self, ancestor: ComponentId, id: ComponentId) -> bool {
+		let mut current = self.parent(id);
+		while let Some(parent_id) = current {
+			if parent_id == ancestor {
+				return true;
+			}
+			current = self.parent(parent_id);
+		}
+		false
+	}
+
+	/// Get the depth of a component (roots have depth 0)
+	pub fn depth(&self, id: ComponentId) -> Option<usize> {
+		if self.contains(id) {
+			Some(self.ancestors(id).len())
+		} else {
+			None
+		}
+	}
+
+	/// Iterate a subtree depth-first (pre-order), starting at `id`
+	pub fn depth_first(&self, id: ComponentId) -> DepthFirst<'_> {
+		let stack = if self.contains(id) {
+			vec![id]
+		} else {
+			Vec::new()
+		};
+		DepthFirst { tree: self, stack }
+	}
+
+	/// Iterate a subtree breadth-first, starting at `id`
+	pub fn breadth_first(&self, id: ComponentId) -> BreadthFirst<'_> {
+		let mut queue = VecDeque::new();
+		if self.contains(id) {
+			queue.push_back(id);
+		}
+		BreadthFirst { tree: self, queue }
+	}
+
+	/// Iterate every component depth-first, root by root
+	pub fn depth_first_all(&self) -> DepthFirst<'_> {
+		DepthFirst {
+			tree: self,
+			stack: self.roots.iter().rev().copied().collect(),
+		}
+	}
+
+	/// Iterate every component breadth-first across all roots
+	pub fn breadth_first_all(&self) -> BreadthFirst<'_> {
+		BreadthFirst {
+			tree: self,
+			queue: self.roots.iter().copied().collect(),
+		}
+	}
+
+	/// Find the topmost component at a point
+	///
+	/// Later siblings are drawn over earlier ones and children over their
+	/// parent, so they are tested first. Hidden and disabled components are
+	/// never hit, and neither are their children, so the point falls through
+	/// to whatever lies beneath. Children of a component that clips them are
+	/// only hit inside its bounds, and those of a component that hides them
+	/// are never hit. Components locked elsewhere are skipped.
+	pub fn hit_test(&self, x: f32, y: f32) -> Option<ComponentId> {
+		self.roots
+			.iter()
+			.rev()
+			.find_map(|&root| self.hit_test_node(root, x, y))
+	}
+
+	/// Hit test a node and its subtree, topmost first
+	fn hit_test_node(&self, id: ComponentId, x: f32, y: f32) -> Option<ComponentId> {
+		let node = self.nodes.get(&id)?;
+		let (inside, clips, hides) = {
+			let component = node.component.try_read().ok()?;
+			if !component.is_visible() || !component.is_enabled() {
+				return None;
+			}
+			(
+				component.bounds().contains_point(x, y),
+				component.clips_children(),
+				component.hides_children(),
+			)
+		};
+		if hides {
+			return inside.then_some(id);
+		}
+		if clips && !inside {
+			return None;
+		}
+		node.children
+			.iter()
+			.rev()
+			.find_map(|&child| self.hit_test_node(child, x, y))
+			.or(inside.then_some(id))
+	}
+}
+
+impl Default for ComponentTree {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl std::fmt::Debug for ComponentTree {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ComponentTree")
+			.field("len", &self.nodes.len())
+			.field("roots", &self.roots)
+			.field("next_id", &self.next_id)
+			.finish()
+	}
+}
+
+/// Depth-first (pre-order) iterator over component IDs
+pub struct DepthFirst<'a> {
+	tree: &'a ComponentTree,
+	stack: Vec<ComponentId>,
+}
+
+impl Iterator for DepthFirst<'_> {
+	type Item = ComponentId;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let id = self.stack.pop()?;
+		self.stack
+			.extend(self.tree.children(id).iter().rev().copied());
+		Some(id)
+	}
+}
+
+/// Breadth-first iterator over component IDs
+pub struct BreadthFirst<'a> {
+	tree: &'a ComponentTree,
+	queue: VecDeque<ComponentId>,
+}
+
+impl Iterator for BreadthFirst<'_> {
+	type Item = ComponentId;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let id = self.queue.pop_front()?;
+		self.queue.extend(self.tree.children(id).iter().copied());
+		Some(id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::ComponentProperties;
+
+	struct TestComponent {
+		properties: ComponentProperties,
+		children: Vec<ComponentId>,
+		clips: bool,
+	}
+
+	impl TestComponent {
+		fn new(id: ComponentId) -> Self {
+			Self {
+				properties: ComponentProperties::new(id),
+				children: Vec::new(),
+				clips: false,
+			}
+		}
+
+		fn at(id: ComponentId, bounds: Rect) -> Self {
+			let mut component = Self::new(id);
+			component.properties.bounds = bounds;
+			component
+		}
+	}
+
+	impl Component for TestComponent {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+
+		fn clips_children(&self) -> bool {
+			self.clips
+		}
+	}
+
+	/// Build: 1 -> (2 -> (4, 5), 3)
+	fn sample_tree() -> ComponentTree {
+		let mut tree = ComponentTree::new();
+		tree.insert(None, TestComponent::new(1)).unwrap();
+		tree.insert(Some(1), TestComponent::new(2)).unwrap();
+		tree.insert(Some(1), TestComponent::new(3)).unwrap();
+		tree.insert(Some(2), TestComponent::new(4)).unwrap();
+		tree.insert(Some(2), TestComponent::new(5)).unwrap();
+		tree
+	}
+
+	fn component_children(tree: &ComponentTree, id: ComponentId) -> Vec<ComponentId> {
+		tree.get(id)
+			.unwrap()
+			.try_read()
+			.unwrap()
+			.children()
+			.to_vec()
+	}
+
+	#[test]
+	fn test_insert_and_lookup() {
+		let tree = sample_tree();
+		assert_eq!(tree.len(), 5);
+		assert_eq!(tree.roots(), &[1]);
+		assert_eq!(tree.children(1), &[2, 3]);
+		assert_eq!(tree.parent(4), Some(2));
+		assert_eq!(tree.parent(1), None);
+		assert_eq!(tree.depth(5), Some(2));
+		assert!(tree.get(3).is_some());
+	}
+
+	#[test]
+	fn test_insert_errors() {
+		let mut tree = sample_tree();
+		assert_eq!(
+			tree.insert(None, TestComponent::new(2)),
+			Err(TreeError::DuplicateId(2))
+		);
+		assert_eq!(
+			tree.insert(Some(99), TestComponent::new(6)),
+			Err(TreeError::NotFound(99))
+		);
+	}
+
+	#[test]
+	fn test_component_children_mirror_tree() {
+		let tree = sample_tree();
+		assert_eq!(component_children(&tree, 1), vec![2, 3]);
+		assert_eq!(component_children(&tree, 2), vec![4, 5]);
+	}
+
+	#[test]
+	fn test_insert_adopts_declared_children() {
+		let mut tree = ComponentTree::new();
+		tree.insert(None, TestComponent::new(10)).unwrap();
+		tree.insert(None, TestComponent::new(11)).unwrap();
+
+		let mut parent = TestComponent::new(1);
+		parent.children = vec![10, 11, 12];
+		tree.insert(None, parent).unwrap();
+
+		assert_eq!(tree.roots(), &[1]);
+		assert_eq!(tree.children(1), &[10, 11]);
+		assert_eq!(component_children(&tree, 1), vec![10, 11]);
+	}
+
+	fn bounded(id: ComponentId, bounds: Rect) -> TestComponent {
+		let mut component = TestComponent::new(id);
+		component.properties.bounds = bounds;
+		component
+	}
+
+	#[test]
+	fn test_damage_tracking() {
+		let mut tree = ComponentTree::new();
+		tree.insert(None, bounded(1, Rect::new(0.0, 0.0, 50.0, 50.0)))
+			.unwrap();
+		tree.insert(None, bounded(2, Rect::new(200.0, 0.0, 50.0, 50.0)))
+			.unwrap();
+		assert_eq!(tree.take_damage().rects().len(), 2);
+		assert!(tree.damage().is_empty());
+
+		// Only the invalidated component is damaged
+		tree.invalidate(2).unwrap();
+		assert_eq!(
+			tree.take_damage().rects(),
+			&[Rect::new(200.0, 0.0, 50.0, 50.0)]
+		);
+		assert_eq!(tree.invalidate(99), Err(TreeError::NotFound(99)));
+
+		// Moving damages where it was and where it is
+		tree.set_bounds(1, Rect::new(100.0, 100.0, 50.0, 50.0))
+			.unwrap();
+		let damage = tree.take_damage();
+		assert!(damage.contains_point(25.0, 25.0));
+		assert!(damage.contains_point(125.0, 125.0));
+		assert!(!damage.contains_point(225.0, 25.0));
+
+		// Removing damages the area the subtree covered
+		tree.remove(2).unwrap();
+		assert_eq!(
+			tree.take_damage().rects(),
+			&[Rect::new(200.0, 0.0, 50.0, 50.0)]
+		);
+	}
+
+	#[test]
+	fn test_stale_tracking() {
+		let mut tree = sample_tree();
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![1, 2, 3, 4, 5]);
+
+		// A change makes the component and its ancestors stale
+		tree.invalidate(4).unwrap();
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![1, 2, 4]);
+		assert!(tree.take_stale().is_empty());
+
+		// Moving makes both the old and new ancestors stale
+		tree.reparent(4, Some(3), None).unwrap();
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![1, 2, 3, 4]);
+
+		// Writes through an update are seen by the tree
+		let visible = tree
+			.update(5, |component| {
+				component.properties_mut().visible = false;
+				component.is_visible()
+			})
+			.unwrap();
+		assert!(!visible);
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![1, 2, 5]);
+
+		// Direct writes are not, until the component is invalidated
+		tree.get(5)
+			.unwrap()
+			.try_write()
+			.unwrap()
+			.properties_mut()
+			.visible = true;
+		assert!(tree.take_stale().is_empty());
+		tree.invalidate(5).unwrap();
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![1, 2, 5]);
+	}
+
+	#[test]
+	fn test_allocate_id() {
+		let mut tree = ComponentTree::new();
+		let first = tree.allocate_id();
+		let second = tree.allocate_id();
+		assert_ne!(first, second);
+
+		tree.insert(None, TestComponent::new(50)).unwrap();
+		let next = tree.allocate_id();
+		assert!(next > 50);
+		assert!(!tree.contains(next));
+	}
+
+	#[test]
+	fn test_remove_subtree() {
+		let mut tree = sample_tree();
+		let removed = tree.remove(2).unwrap();
+		assert_eq!(removed.try_read().unwrap().id(), 2);
+
+		assert_eq!(tree.len(), 2);
+		assert!(!tree.contains(4));
+		assert!(!tree.contains(5));
+		assert_eq!(tree.children(1), &[3]);
+		assert_eq!(component_children(&tree, 1), vec![3]);
+		assert!(tree.remove(2).is_err());
+	}
+
+	#[test]
+	fn test_reparent() {
+		let mut tree = sample_tree();
+		tree.reparent(4, Some(3), None).unwrap();
+		assert_eq!(tree.parent(4), Some(3));
+		assert_eq!(tree.children(2), &[5]);
+		assert_eq!(component_children(&tree, 3), vec![4]);
+
+		tree.reparent(5, Some(1), Some(0)).unwrap();
+		assert_eq!(tree.children(1), &[5, 2, 3]);
+
+		tree.reparent(3, None, None).unwrap();
+		assert_eq!(tree.roots(), &[1, 3]);
+	}
+
+	#[test]
+	fn test_reparent_rejects_cycles() {
+		let mut tree = sample_tree();
+		assert_eq!(
+			tree.reparent(1, Some(4), None),
+			Err(TreeError::CycleDetected { node: 1, parent: 4 })
+		);
+		assert_eq!(
+			tree.reparent(2, Some(2), None),
+			Err(TreeError::CycleDetected { node: 2, parent: 2 })
+		);
+	}
+
+	#[test]
+	fn test_locked_parent_leaves_tree_unchanged() {
+		let mut tree = sample_tree();
+		let parent = tree.get(3).unwrap().clone();
+		let guard = parent.try_read().unwrap();
+
+		assert_eq!(
+			tree.reparent(4, Some(3), None),
+			Err(TreeError::ComponentLocked)
+		);
+		assert_eq!(
+			tree.insert(Some(3), TestComponent::new(6)),
+			Err(TreeError::ComponentLocked)
+		);
+		assert_eq!(tree.parent(4), Some(2));
+		assert_eq!(tree.children(3), &[] as &[ComponentId]);
+		assert!(tree.get(6).is_none());
+
+		drop(guard);
+		tree.reparent(4, Some(3), None).unwrap();
+		assert_eq!(component_children(&tree, 3), vec![4]);
+	}
+
+	#[test]
+	fn test_siblings() {
+		let tree = sample_tree();
+		assert_eq!(tree.siblings(4), vec![5]);
+		assert_eq!(tree.next_sibling(2), Some(3));
+		assert_eq!(tree.next_sibling(3), None);
+		assert_eq!(tree.previous_sibling(5), Some(4));
+		assert_eq!(tree.previous_sibling(4), None);
+	}
+
+	#[test]
+	fn test_ancestors() {
+		let tree = sample_tree();
+		assert_eq!(tree.ancestors(5), vec![2, 1]);
+		assert!(tree.is_ancestor(1, 5));
+		assert!(!tree.is_ancestor(3, 5));
+	}
+
+	#[test]
+	fn test_traversal() {
+		let tree = sample_tree();
+		let dfs: Vec<_> = tree.depth_first(1).collect();
+		assert_eq!(dfs, vec![1, 2, 4, 5, 3]);
+
+		let bfs: Vec<_> = tree.breadth_first(1).collect();
+		assert_eq!(bfs, vec![1, 2, 3, 4, 5]);
+
+		let subtree: Vec<_> = tree.depth_first(2).collect();
+		assert_eq!(subtree, vec![2, 4, 5]);
+
+		assert_eq!(tree.depth_first(99).count(), 0);
+	}
+
+	#[test]
+	fn test_traversal_all_roots() {
+		let mut tree = sample_tree();
+		tree.insert(None, TestComponent::new(6)).unwrap();
+		tree.insert(Some(6), TestComponent::new(7)).unwrap();
+
+		let dfs: Vec<_> = tree.depth_first_all().collect();
+		assert_eq!(dfs, vec![1, 2, 4, 5, 3, 6, 7]);
+
+		let bfs: Vec<_> = tree.breadth_first_all().collect();
+		assert_eq!(bfs, vec![1, 6, 2, 3, 7, 4, 5]);
+	}
+
+	#[test]
+	fn test_tree_error_display() {
+		assert_eq!(
+			TreeError::NotFound(7).to_string(),
+			"Component 7 not found in tree"
+		);
+	}
+
+	/// Build: 1 -> (2 -> 4, 3), with 3 over part of 2 and 4 reaching
+	/// outside 2
+	fn hit_test_tree(clip: bool) -> ComponentTree {
+		let mut tree = ComponentTree::new();
+		let clipping = TestComponent {
+			clips: clip,
+			..TestComponent::at(2, Rect::new(0.0, 0.0, 100.0, 100.0))
+		};
+		tree.insert(
+			None,
+			TestComponent::at(1, Rect::new(0.0, 0.0, 200.0, 200.0)),
+		)
+		.unwrap();
+		tree.insert(Some(1), clipping).unwrap();
+		tree.insert(
+			Some(1),
+			TestComponent::at(3, Rect::new(50.0, 50.0, 100.0, 100.0)),
+		)
+		.unwrap();
+		tree.insert(
+			Some(2),
+			TestComponent::at(4, Rect::new(80.0, 0.0, 100.0, 20.0)),
+		)
+		.unwrap();
+		tree
+	}
+
+	#[test]
+	fn test_hit_test() {
+		let tree = hit_test_tree(false);
+		assert_eq!(tree.hit_test(10.0, 10.0), Some(2));
+		// Later siblings are on top
+		assert_eq!(tree.hit_test(60.0, 60.0), Some(3));
+		assert_eq!(tree.hit_test(190.0, 190.0), Some(1));
+		assert_eq!(tree.hit_test(150.0, 10.0), Some(4));
+		assert_eq!(tree.hit_test(300.0, 300.0), None);
+
+		// Hidden and disabled components pass the point through
+		tree.get(3).unwrap().try_write().unwrap().set_visible(false);
+		assert_eq!(tree.hit_test(60.0, 60.0), Some(2));
+		tree.get(2).unwrap().try_write().unwrap().set_enabled(false);
+		assert_eq!(tree.hit_test(90.0, 10.0), Some(1));
+	}
+
+	#[test]
+	fn test_hit_test_clipping() {
+		let tree = hit_test_tree(true);
+		// Clipped children are only hit inside their parent
+		assert_eq!(tree.hit_test(150.0, 10.0), Some(1));
+		assert_eq!(tree.hit_test(90.0, 10.0), Some(4));
+	}
+}
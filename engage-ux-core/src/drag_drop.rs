@@ -271,6 +271,7 @@ impl DragManager {
 	}
 
 	/// Update drag position and check for target
+	#[allow(clippy::collapsible_if)]
 	pub async fn update_drag(
 		&mut self,
 		x: f32,
@@ -344,6 +345,7 @@ impl DragManager {
 	}
 
 	/// Complete drop operation
+	#[allow(clippy::collapsible_if)]
 	pub async fn drop(&mut self, x: f32, y: f32) -> Option<DragEvent> {
 		let drag_state = self.current_drag.take()?;
 
@@ -372,6 +374,7 @@ impl DragManager {
 	}
 
 	/// Cancel current drag
+	#[allow(clippy::collapsible_if)]
 	pub fn cancel_drag(&mut self) -> Option<DragEvent> {
 		let drag_state = self.current_drag.take()?;
 
@@ -520,6 +523,7 @@ mod tests {
 	}
 
 	#[test]
+	#[allow(clippy::useless_vec)]
 	fn test_drag_operations() {
 		let operations = vec![
 			DragOperation::Copy,
@@ -531,6 +535,7 @@ mod tests {
 	}
 
 	#[test]
+	#[allow(clippy::useless_vec)]
 	fn test_drag_data_types() {
 		let types = vec![
 			DragDataType::Text,
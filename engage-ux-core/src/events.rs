@@ -2,33 +2,40 @@
 //!
 //! Provides thread-safe event handling using Tokio's async runtime.
 
+pub use crate::input::{KeyModifiers, MouseButton};
+
 use crate::component::ComponentId;
+use crate::input::{CustomInputEvent, InputEvent, KeyboardEvent, MouseEvent, TouchEvent};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
 /// Types of events that can occur
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Raw input keeps the types of the [`input`](crate::input) module, so a
+/// platform event converts to an [`EventType`] and back without losing
+/// anything; see the `From` and `TryFrom` impls. The other variants are
+/// events components raise themselves.
+///
+/// These replace the earlier `MouseDown`, `MouseUp`, `MouseMove`,
+/// `MouseWheel`, `KeyDown` and `KeyUp` variants; the changelog maps each one
+/// to its replacement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EventType {
-	/// Mouse button pressed
-	MouseDown { x: f32, y: f32, button: MouseButton },
-	/// Mouse button released
-	MouseUp { x: f32, y: f32, button: MouseButton },
-	/// Mouse moved
-	MouseMove { x: f32, y: f32 },
-	/// Mouse wheel scrolled
-	MouseWheel { delta_x: f32, delta_y: f32 },
-	/// Key pressed
-	KeyDown {
-		key: String,
-		modifiers: KeyModifiers,
-	},
-	/// Key released
-	KeyUp {
-		key: String,
-		modifiers: KeyModifiers,
-	},
-	/// Text input
+	/// Key pressed or released, or a character typed
+	Keyboard(KeyboardEvent),
+	/// Mouse button, movement or wheel input
+	Mouse(MouseEvent),
+	/// Touch input
+	Touch(TouchEvent),
+	/// Input from another kind of device
+	CustomInput(CustomInputEvent),
+	/// Text committed other than by a single key press, such as by an input
+	/// method, a paste or dictation
+	///
+	/// A typed character arrives as [`EventType::Keyboard`] with a
+	/// [`KeyEventType::Char`](crate::input::keyboard::KeyEventType::Char)
+	/// event instead, never as `TextInput`.
 	TextInput { text: String },
 	/// Component gained focus
 	FocusGained,
@@ -36,6 +43,20 @@ pub enum EventType {
 	FocusLost,
 	/// Component was clicked
 	Click,
+	/// Component was clicked twice in quick succession, after the second
+	/// [`EventType::Click`]
+	DoubleClick,
+	/// Component was clicked three times in quick succession, after the
+	/// third [`EventType::Click`]
+	TripleClick,
+	/// Pointer moved onto the component or one of its descendants
+	Enter,
+	/// Pointer moved off the component and all its descendants
+	Leave,
+	/// Pointer rested over the component for its hover delay
+	HoverIntent { x: f32, y: f32 },
+	/// Component was pressed and held still
+	LongPress { x: f32, y: f32 },
 	/// Component value changed
 	ValueChanged,
 	/// Window resized
@@ -44,21 +65,62 @@ pub enum EventType {
 	Custom { name: String, data: String },
 }
 
-/// Mouse buttons
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum MouseButton {
-	Left,
-	Right,
-	Middle,
+impl EventType {
+	/// Check whether the event is raw input
+	pub fn is_input(&self) -> bool {
+		matches!(
+			self,
+			EventType::Keyboard(_)
+				| EventType::Mouse(_)
+				| EventType::Touch(_)
+				| EventType::CustomInput(_)
+		)
+	}
+}
+
+impl From<InputEvent> for EventType {
+	fn from(event: InputEvent) -> Self {
+		match event {
+			InputEvent::Keyboard(e) => EventType::Keyboard(e),
+			InputEvent::Mouse(e) => EventType::Mouse(e),
+			InputEvent::Touch(e) => EventType::Touch(e),
+			InputEvent::Custom(e) => EventType::CustomInput(e),
+		}
+	}
+}
+
+impl From<KeyboardEvent> for EventType {
+	fn from(event: KeyboardEvent) -> Self {
+		EventType::Keyboard(event)
+	}
 }
 
-/// Keyboard modifiers
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
-pub struct KeyModifiers {
-	pub shift: bool,
-	pub ctrl: bool,
-	pub alt: bool,
-	pub meta: bool,
+impl From<MouseEvent> for EventType {
+	fn from(event: MouseEvent) -> Self {
+		EventType::Mouse(event)
+	}
+}
+
+impl From<TouchEvent> for EventType {
+	fn from(event: TouchEvent) -> Self {
+		EventType::Touch(event)
+	}
+}
+
+/// Only raw input converts back; other events are returned unchanged as
+/// the error
+impl TryFrom<EventType> for InputEvent {
+	type Error = EventType;
+
+	fn try_from(event: EventType) -> Result<Self, Self::Error> {
+		match event {
+			EventType::Keyboard(e) => Ok(InputEvent::Keyboard(e)),
+			EventType::Mouse(e) => Ok(InputEvent::Mouse(e)),
+			EventType::Touch(e) => Ok(InputEvent::Touch(e)),
+			EventType::CustomInput(e) => Ok(InputEvent::Custom(e)),
+			other => Err(other),
+		}
+	}
 }
 
 /// An event with its target component
@@ -80,6 +142,16 @@ impl Event {
 				.as_millis() as u64,
 		}
 	}
+
+	/// Create an event carrying raw input for a component
+	pub fn from_input(target: ComponentId, input: impl Into<EventType>) -> Self {
+		Self::new(target, input.into())
+	}
+
+	/// The raw input the event carries, if it is input
+	pub fn input(&self) -> Option<InputEvent> {
+		InputEvent::try_from(self.event_type.clone()).ok()
+	}
 }
 
 /// Event handler callback type
@@ -102,6 +174,18 @@ impl EventHandler {
 		let _ = self.sender.send(event);
 	}
 
+	/// Emit raw input for a component
+	///
+	/// The event is only broadcast to subscribers; nothing is delivered to
+	/// the component. The application passes received events on, either
+	/// to the target's [`InputHandler::handle_event`](crate::input::InputHandler::handle_event)
+	/// or, for pointer input, through a
+	/// [`PointerDispatcher`](crate::input::PointerDispatcher) so that the
+	/// target's ancestors see it too.
+	pub fn emit_input(&self, target: ComponentId, input: impl Into<EventType>) {
+		self.emit(Event::from_input(target, input));
+	}
+
 	/// Subscribe to events
 	pub fn subscribe(&self) -> broadcast::Receiver<Event> {
 		self.sender.subscribe()
@@ -148,13 +232,68 @@ mod tests {
 
 	#[test]
 	fn test_key_modifiers() {
-		let modifiers = KeyModifiers {
-			shift: true,
-			ctrl: false,
-			alt: false,
-			meta: false,
-		};
-		assert!(modifiers.shift);
-		assert!(!modifiers.ctrl);
+		// The event model shares the input module's modifiers
+		let modifiers = KeyModifiers::SHIFT;
+		assert!(modifiers.contains(crate::input::KeyModifiers::SHIFT));
+		assert!(!modifiers.contains(KeyModifiers::CTRL));
+	}
+
+	#[test]
+	fn test_input_conversion_round_trip() {
+		use crate::input::{CustomInputEvent, KeyCode, Touch, TouchPhase};
+
+		let mut repeated = KeyboardEvent::key_down(KeyCode::Key('a'), KeyModifiers::CTRL);
+		repeated.repeat = true;
+		let inputs = [
+			InputEvent::Keyboard(repeated),
+			InputEvent::Keyboard(KeyboardEvent::char_input('é', KeyModifiers::ALT)),
+			InputEvent::Mouse(MouseEvent::button_down(MouseButton::Button4, 3.0, 4.0)),
+			InputEvent::Mouse(MouseEvent::wheel(1.0, 2.0, 0.0, -3.5)),
+			InputEvent::Touch(TouchEvent::with_timestamp(
+				TouchPhase::Moved,
+				vec![Touch::new(7, 10.0, 20.0)],
+				12.5,
+			)),
+			InputEvent::Custom(CustomInputEvent::new("gamepad", "press").with_int("button", 2)),
+		];
+		for input in inputs {
+			let event = Event::from_input(9, input.clone());
+			assert!(event.event_type.is_input());
+			assert_eq!(event.input(), Some(input.clone()));
+			assert_eq!(InputEvent::try_from(event.event_type.clone()), Ok(input));
+
+			// Serializing keeps everything too
+			let json = serde_json::to_string(&event).unwrap();
+			let restored: Event = serde_json::from_str(&json).unwrap();
+			assert_eq!(restored.event_type, event.event_type);
+		}
+
+		assert_eq!(
+			InputEvent::try_from(EventType::Click),
+			Err(EventType::Click)
+		);
+		assert!(Event::new(1, EventType::FocusGained).input().is_none());
+	}
+
+	#[test]
+	fn test_emit_input() {
+		use crate::input::KeyCode;
+
+		let handler = EventHandler::new();
+		let mut receiver = handler.subscribe();
+		handler.emit_input(
+			4,
+			KeyboardEvent::key_down(KeyCode::Enter, KeyModifiers::empty()),
+		);
+
+		let event = receiver.try_recv().unwrap();
+		assert_eq!(event.target, 4);
+		assert!(matches!(
+			event.event_type,
+			EventType::Keyboard(KeyboardEvent {
+				key_code: KeyCode::Enter,
+				..
+			})
+		));
 	}
 }
@@ -0,0 +1,404 @@
+//! Pointer event routing through the component tree
+//!
+//! A [`PointerDispatcher`] finds the component under a mouse or touch event
+//! with [`ComponentTree::hit_test`] and delivers the event the way the DOM
+//! does: first down from the root to the target's parent (capture), then to
+//! the target itself, then back up to the root (bubble). Each component on
+//! the way with a registered [`InputHandler`] sees the event through
+//! [`InputHandler::handle_pointer`] and can stop it from travelling further
+//! or prevent the default action the application would take afterwards.
+
+use super::{InputEvent, InputHandler, MouseEvent, TouchEvent};
+use crate::component::{ComponentId, ComponentTree};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Stage of a pointer event's trip through the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+	/// Travelling down from the root towards the target
+	Capture,
+	/// At the component under the pointer
+	Target,
+	/// Travelling back up from the target to the root
+	Bubble,
+}
+
+/// A mouse or touch event on its way to the component under the pointer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+	input: InputEvent,
+	x: f32,
+	y: f32,
+	target: ComponentId,
+	current_target: ComponentId,
+	phase: EventPhase,
+	propagation_stopped: bool,
+	default_prevented: bool,
+}
+
+impl PointerEvent {
+	/// Create an event at (`x`, `y`) aimed at `target`
+	pub fn new(input: InputEvent, x: f32, y: f32, target: ComponentId) -> Self {
+		Self {
+			input,
+			x,
+			y,
+			target,
+			current_target: target,
+			phase: EventPhase::Target,
+			propagation_stopped: false,
+			default_prevented: false,
+		}
+	}
+
+	/// The input being delivered
+	pub fn input(&self) -> &InputEvent {
+		&self.input
+	}
+
+	/// The mouse event being delivered, if it is one
+	pub fn mouse(&self) -> Option<&MouseEvent> {
+		match &self.input {
+			InputEvent::Mouse(event) => Some(event),
+			_ => None,
+		}
+	}
+
+	/// The touch event being delivered, if it is one
+	pub fn touch(&self) -> Option<&TouchEvent> {
+		match &self.input {
+			InputEvent::Touch(event) => Some(event),
+			_ => None,
+		}
+	}
+
+	/// Where the pointer is
+	pub fn position(&self) -> (f32, f32) {
+		(self.x, self.y)
+	}
+
+	/// The component under the pointer
+	pub fn target(&self) -> ComponentId {
+		self.target
+	}
+
+	/// The component whose handler is being called
+	pub fn current_target(&self) -> ComponentId {
+		self.current_target
+	}
+
+	/// The stage the event has reached
+	pub fn phase(&self) -> EventPhase {
+		self.phase
+	}
+
+	/// Deliver the event to no further components
+	pub fn stop_propagation(&mut self) {
+		self.propagation_stopped = true;
+	}
+
+	/// Check whether a handler stopped the event
+	pub fn is_propagation_stopped(&self) -> bool {
+		self.propagation_stopped
+	}
+
+	/// Ask the application not to take its default action for the event
+	///
+	/// Unlike [`PointerEvent::stop_propagation`], the event still reaches the
+	/// remaining components.
+	pub fn prevent_default(&mut self) {
+		self.default_prevented = true;
+	}
+
+	/// Check whether a handler prevented the default action
+	pub fn is_default_prevented(&self) -> bool {
+		self.default_prevented
+	}
+}
+
+/// Routes pointer events to the handlers of the components they hit
+#[derive(Default)]
+pub struct PointerDispatcher {
+	/// Registered handlers by the component they belong to
+	handlers: HashMap<ComponentId, Arc<RwLock<dyn InputHandler + Send + Sync>>>,
+}
+
+impl PointerDispatcher {
+	/// Create a dispatcher with no handlers
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the handler for a component
+	///
+	/// A component inserted into the tree with
+	/// [`ComponentTree::insert_ref`] can be registered through a clone of
+	/// the same reference.
+	pub fn register_handler(
+		&mut self,
+		id: ComponentId,
+		handler: Arc<RwLock<dyn InputHandler + Send + Sync>>,
+	) {
+		self.handlers.insert(id, handler);
+	}
+
+	/// Unregister the handler for a component
+	pub fn unregister_handler(&mut self, id: ComponentId) {
+		self.handlers.remove(&id);
+	}
+
+	/// Deliver a mouse event to the component under it
+	///
+	/// Returns the event as the last handler left it, or `None` when nothing
+	/// was hit.
+	pub fn dispatch_mouse(&self, tree: &ComponentTree, event: MouseEvent) -> Option<PointerEvent> {
+		let (x, y) = (event.x, event.y);
+		let target = tree.hit_test(x, y)?;
+		Some(self.dispatch(tree, target, InputEvent::Mouse(event), x, y))
+	}
+
+	/// Deliver a touch event to the component under its first touch point
+	///
+	/// Returns the event as the last handler left it, or `None` when the
+	/// event has no touch points or nothing was hit.
+	pub fn dispatch_touch(&self, tree: &ComponentTree, event: TouchEvent) -> Option<PointerEvent> {
+		let touch = event.touches.first()?;
+		let (x, y) = (touch.x, touch.y);
+		let target = tree.hit_test(x, y)?;
+		Some(self.dispatch(tree, target, InputEvent::Touch(event), x, y))
+	}
+
+	/// Deliver an event at (`x`, `y`) to `target` and its ancestors,
+	/// without hit testing
+	///
+	/// Useful for a component that has captured the pointer. Handlers locked
+	/// elsewhere are skipped.
+	pub fn dispatch(
+		&self,
+		tree: &ComponentTree,
+		target: ComponentId,
+		input: InputEvent,
+		x: f32,
+		y: f32,
+	) -> PointerEvent {
+		let mut event = PointerEvent::new(input, x, y, target);
+		let ancestors = tree.ancestors(target);
+
+		let path = ancestors
+			.iter()
+			.rev()
+			.map(|&id| (id, EventPhase::Capture))
+			.chain(std::iter::once((target, EventPhase::Target)))
+			.chain(ancestors.iter().map(|&id| (id, EventPhase::Bubble)));
+		for (id, phase) in path {
+			event.current_target = id;
+			event.phase = phase;
+			if let Some(handler) = self.handlers.get(&id)
+				&& let Ok(mut handler) = handler.try_write()
+			{
+				handler.handle_pointer(&mut event);
+			}
+			if event.propagation_stopped {
+				break;
+			}
+		}
+		event
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::{Component, ComponentProperties, Rect};
+	use crate::input::{MouseButton, Touch, TouchPhase};
+	use std::sync::Mutex;
+
+	type Log = Arc<Mutex<Vec<(ComponentId, EventPhase)>>>;
+
+	struct Node {
+		properties: ComponentProperties,
+		children: Vec<ComponentId>,
+		log: Log,
+		stop_in: Option<EventPhase>,
+		prevent: bool,
+	}
+
+	impl Node {
+		fn new(id: ComponentId, bounds: Rect, log: &Log) -> Self {
+			let mut properties = ComponentProperties::new(id);
+			properties.bounds = bounds;
+			Self {
+				properties,
+				children: Vec::new(),
+				log: log.clone(),
+				stop_in: None,
+				prevent: false,
+			}
+		}
+	}
+
+	impl Component for Node {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+	}
+
+	impl InputHandler for Node {
+		fn handle_pointer(&mut self, event: &mut PointerEvent) {
+			assert_eq!(event.current_target(), self.properties.id);
+			self.log
+				.lock()
+				.unwrap()
+				.push((event.current_target(), event.phase()));
+			if self.stop_in == Some(event.phase()) {
+				event.stop_propagation();
+			}
+			if self.prevent {
+				event.prevent_default();
+			}
+		}
+	}
+
+	/// Build: 1 -> 2 -> 3, nested inside each other, all with handlers
+	fn setup(log: &Log, configure: impl Fn(&mut Node)) -> (ComponentTree, PointerDispatcher) {
+		let mut tree = ComponentTree::new();
+		let mut dispatcher = PointerDispatcher::new();
+		let nodes = [
+			(None, Rect::new(0.0, 0.0, 300.0, 300.0)),
+			(Some(1), Rect::new(50.0, 50.0, 200.0, 200.0)),
+			(Some(2), Rect::new(100.0, 100.0, 50.0, 50.0)),
+		];
+		for (index, (parent, bounds)) in nodes.into_iter().enumerate() {
+			let id = index as ComponentId + 1;
+			let mut node = Node::new(id, bounds, log);
+			configure(&mut node);
+			let node = Arc::new(RwLock::new(node));
+			tree.insert_ref(parent, node.clone()).unwrap();
+			dispatcher.register_handler(id, node);
+		}
+		(tree, dispatcher)
+	}
+
+	fn click(x: f32, y: f32) -> MouseEvent {
+		MouseEvent::button_down(MouseButton::Left, x, y)
+	}
+
+	#[test]
+	fn test_capture_target_bubble_order() {
+		let log = Log::default();
+		let (tree, dispatcher) = setup(&log, |_| {});
+
+		let event = dispatcher
+			.dispatch_mouse(&tree, click(120.0, 120.0))
+			.unwrap();
+		assert_eq!(event.target(), 3);
+		assert!(!event.is_propagation_stopped());
+		assert_eq!(
+			*log.lock().unwrap(),
+			vec![
+				(1, EventPhase::Capture),
+				(2, EventPhase::Capture),
+				(3, EventPhase::Target),
+				(2, EventPhase::Bubble),
+				(1, EventPhase::Bubble),
+			]
+		);
+
+		// Nothing under the pointer
+		assert!(
+			dispatcher
+				.dispatch_mouse(&tree, click(400.0, 400.0))
+				.is_none()
+		);
+	}
+
+	#[test]
+	fn test_stop_propagation() {
+		let log = Log::default();
+		let (tree, dispatcher) = setup(&log, |node| {
+			if node.properties.id == 2 {
+				node.stop_in = Some(EventPhase::Capture);
+			}
+		});
+
+		let event = dispatcher
+			.dispatch_mouse(&tree, click(120.0, 120.0))
+			.unwrap();
+		assert!(event.is_propagation_stopped());
+		assert_eq!(event.current_target(), 2);
+		assert_eq!(
+			*log.lock().unwrap(),
+			vec![(1, EventPhase::Capture), (2, EventPhase::Capture)]
+		);
+	}
+
+	#[test]
+	fn test_prevent_default_keeps_propagating() {
+		let log = Log::default();
+		let (tree, dispatcher) = setup(&log, |node| {
+			node.prevent = node.properties.id == 3;
+		});
+
+		let touch = TouchEvent::new(TouchPhase::Began, vec![Touch::new(0, 60.0, 60.0)]);
+		let event = dispatcher.dispatch_touch(&tree, touch).unwrap();
+		assert_eq!(event.target(), 2);
+		assert!(!event.is_default_prevented());
+
+		let event = dispatcher
+			.dispatch_mouse(&tree, click(120.0, 120.0))
+			.unwrap();
+		assert!(event.is_default_prevented());
+		assert_eq!(log.lock().unwrap().len(), 3 + 5);
+	}
+
+	/// Handler relying on the default [`InputHandler::handle_pointer`]
+	struct Clicks(u32);
+
+	impl InputHandler for Clicks {
+		fn handle_mouse(&mut self, _event: &MouseEvent) -> bool {
+			self.0 += 1;
+			true
+		}
+	}
+
+	#[test]
+	fn test_default_handle_pointer() {
+		let log = Log::default();
+		let (tree, mut dispatcher) = setup(&log, |_| {});
+		let clicks = Arc::new(RwLock::new(Clicks(0)));
+		dispatcher.register_handler(2, clicks.clone());
+
+		// Ignored while capturing, handled while bubbling, which stops there
+		let event = dispatcher
+			.dispatch_mouse(&tree, click(120.0, 120.0))
+			.unwrap();
+		assert!(event.is_propagation_stopped());
+		assert_eq!(clicks.try_read().unwrap().0, 1);
+		assert_eq!(
+			*log.lock().unwrap(),
+			vec![(1, EventPhase::Capture), (3, EventPhase::Target)]
+		);
+
+		dispatcher.unregister_handler(2);
+		dispatcher.dispatch_mouse(&tree, click(120.0, 120.0));
+		assert_eq!(clicks.try_read().unwrap().0, 1);
+	}
+}
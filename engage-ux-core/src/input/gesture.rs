@@ -0,0 +1,1432 @@
+//! Touch gesture recognizers
+//!
+//! Each [`Recognizer`] watches a stream of [`TouchEvent`]s for one gesture
+//! and moves through a small state machine: it starts out
+//! [`Possible`](GestureState::Possible), and either fails or recognizes its
+//! gesture. Discrete gestures (taps and swipes) go straight to
+//! [`Ended`](GestureState::Ended); continuous ones (long presses, rotation
+//! and flings) go through [`Began`](GestureState::Began) and
+//! [`Changed`](GestureState::Changed) first.
+//!
+//! A [`GestureArena`] runs several recognizers over the same touches and
+//! settles conflicts between them. By default the first recognizer to
+//! recognize its gesture wins and the others fail; pairs can be allowed to
+//! recognize together, and a recognizer can be made to wait for another to
+//! fail, as a single tap waits to rule out a double tap.
+//!
+//! Time is passed in by the caller in milliseconds, with every event and to
+//! `update` between events so timeouts and inertia can progress.
+
+use super::{TouchEvent, TouchPhase, TouchState};
+use std::collections::HashSet;
+use std::f32::consts::{PI, TAU};
+use std::fmt;
+
+/// Where a recognizer is in recognizing its gesture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureState {
+	/// Watching touches, nothing decided yet
+	Possible,
+	/// A continuous gesture has started
+	Began,
+	/// A continuous gesture has moved on
+	Changed,
+	/// The gesture is complete
+	Ended,
+	/// The touches are not this gesture
+	Failed,
+}
+
+impl GestureState {
+	/// Check whether the recognizer is done with the current touches
+	pub fn is_finished(&self) -> bool {
+		matches!(self, GestureState::Ended | GestureState::Failed)
+	}
+}
+
+/// Direction of a swipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+	Left,
+	Right,
+	Up,
+	Down,
+}
+
+/// A recognized gesture and its values
+///
+/// Positions are in pixels, velocities in pixels per second and angles in
+/// radians, clockwise on screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+	/// One or more quick taps in the same place
+	Tap { x: f32, y: f32, taps: u32 },
+	/// A finger held still, and where it is now
+	LongPress { x: f32, y: f32 },
+	/// A quick straight stroke
+	Swipe {
+		direction: SwipeDirection,
+		velocity_x: f32,
+		velocity_y: f32,
+	},
+	/// Two fingers turning around their center
+	Rotate {
+		angle: f32,
+		center_x: f32,
+		center_y: f32,
+	},
+	/// Momentum left after a finger is thrown off the screen, and how far it
+	/// has carried since
+	Fling {
+		velocity_x: f32,
+		velocity_y: f32,
+		offset_x: f32,
+		offset_y: f32,
+	},
+}
+
+/// Recognizes one gesture from touch events
+pub trait Recognizer: fmt::Debug + Send + Sync {
+	/// Current state
+	fn state(&self) -> GestureState;
+
+	/// The gesture as recognized so far, once it has begun or ended
+	fn gesture(&self) -> Option<Gesture>;
+
+	/// Watch a touch event that happened at `now`
+	fn handle_touch(&mut self, event: &TouchEvent, now: u64);
+
+	/// Let time pass without new touches
+	fn update(&mut self, now: u64) {
+		let _ = now;
+	}
+
+	/// Give up on the current touches
+	fn fail(&mut self);
+
+	/// Forget the current touches and start watching again
+	fn reset(&mut self);
+}
+
+/// The finger a single-touch recognizer follows
+#[derive(Debug, Clone, Copy)]
+struct Finger {
+	id: u64,
+	start: (f32, f32),
+	position: (f32, f32),
+	down_at: u64,
+}
+
+impl Finger {
+	/// Start following the only touch of a `Began` event
+	fn from_event(event: &TouchEvent, now: u64) -> Option<Self> {
+		match event.touches.as_slice() {
+			[touch] => Some(Self {
+				id: touch.id,
+				start: touch.position(),
+				position: touch.position(),
+				down_at: now,
+			}),
+			_ => None,
+		}
+	}
+
+	/// Follow the finger's touch in an event, if it is there
+	fn follow(&mut self, event: &TouchEvent) -> bool {
+		match event.touches.iter().find(|touch| touch.id == self.id) {
+			Some(touch) => {
+				self.position = touch.position();
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// How far the finger is from where it started
+	fn travelled(&self) -> f32 {
+		distance(self.start, self.position)
+	}
+}
+
+/// Recognizes one or more taps
+///
+/// Each tap must lift within the maximum duration without moving beyond
+/// the slop, and each following tap must start within the interval of the
+/// last one and near the first.
+#[derive(Debug, Clone)]
+pub struct TapRecognizer {
+	taps: u32,
+	slop: f32,
+	max_duration: u64,
+	interval: u64,
+	tap_distance: f32,
+	state: GestureState,
+	finger: Option<Finger>,
+	first: Option<(f32, f32)>,
+	seen: u32,
+	last_up: u64,
+}
+
+impl TapRecognizer {
+	/// Recognize a single tap
+	pub fn new() -> Self {
+		Self {
+			taps: 1,
+			slop: 10.0,
+			max_duration: 300,
+			interval: 300,
+			tap_distance: 40.0,
+			state: GestureState::Possible,
+			finger: None,
+			first: None,
+			seen: 0,
+			last_up: 0,
+		}
+	}
+
+	/// Recognize a double tap
+	pub fn double() -> Self {
+		Self::new().with_taps(2)
+	}
+
+	/// Set how many taps make the gesture
+	pub fn with_taps(mut self, taps: u32) -> Self {
+		self.taps = taps.max(1);
+		self
+	}
+
+	/// Set how far a finger may move during a tap
+	pub fn with_slop(mut self, slop: f32) -> Self {
+		self.slop = slop.max(0.0);
+		self
+	}
+
+	/// Set how long a finger may stay down for a tap
+	pub fn with_max_duration(mut self, duration_ms: u64) -> Self {
+		self.max_duration = duration_ms;
+		self
+	}
+
+	/// Set the longest time between one tap lifting and the next starting
+	pub fn with_interval(mut self, interval_ms: u64) -> Self {
+		self.interval = interval_ms;
+		self
+	}
+
+	/// Set how far from the first tap later taps may land
+	pub fn with_tap_distance(mut self, distance: f32) -> Self {
+		self.tap_distance = distance.max(0.0);
+		self
+	}
+}
+
+impl Default for TapRecognizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Recognizer for TapRecognizer {
+	fn state(&self) -> GestureState {
+		self.state
+	}
+
+	fn gesture(&self) -> Option<Gesture> {
+		let (x, y) = self.first?;
+		(self.state == GestureState::Ended).then_some(Gesture::Tap {
+			x,
+			y,
+			taps: self.taps,
+		})
+	}
+
+	fn handle_touch(&mut self, event: &TouchEvent, now: u64) {
+		if self.state.is_finished() {
+			return;
+		}
+		match event.phase {
+			TouchPhase::Began => {
+				let finger = match (self.finger, Finger::from_event(event, now)) {
+					(None, Some(finger)) => finger,
+					// More than one finger
+					_ => return self.fail(),
+				};
+				let first = *self.first.get_or_insert(finger.start);
+				if self.seen > 0
+					&& (now.saturating_sub(self.last_up) > self.interval
+						|| distance(first, finger.start) > self.tap_distance)
+				{
+					return self.fail();
+				}
+				self.finger = Some(finger);
+			}
+			TouchPhase::Moved => {
+				if let Some(finger) = &mut self.finger
+					&& finger.follow(event)
+					&& finger.travelled() > self.slop
+				{
+					self.fail();
+				}
+			}
+			TouchPhase::Ended => {
+				if let Some(mut finger) = self.finger
+					&& finger.follow(event)
+				{
+					if finger.travelled() > self.slop
+						|| now.saturating_sub(finger.down_at) > self.max_duration
+					{
+						return self.fail();
+					}
+					self.finger = None;
+					self.seen += 1;
+					self.last_up = now;
+					if self.seen == self.taps {
+						self.state = GestureState::Ended;
+					}
+				}
+			}
+			TouchPhase::Cancelled => self.fail(),
+		}
+	}
+
+	fn update(&mut self, now: u64) {
+		if self.state != GestureState::Possible {
+			return;
+		}
+		let held_too_long = self
+			.finger
+			.is_some_and(|finger| now.saturating_sub(finger.down_at) > self.max_duration);
+		let next_too_late = self.finger.is_none()
+			&& self.seen > 0
+			&& now.saturating_sub(self.last_up) > self.interval;
+		if held_too_long || next_too_late {
+			self.fail();
+		}
+	}
+
+	fn fail(&mut self) {
+		self.state = GestureState::Failed;
+	}
+
+	fn reset(&mut self) {
+		self.state = GestureState::Possible;
+		self.finger = None;
+		self.first = None;
+		self.seen = 0;
+	}
+}
+
+/// Recognizes a finger held still, then follows it until it lifts
+#[derive(Debug, Clone)]
+pub struct LongPressRecognizer {
+	min_duration: u64,
+	slop: f32,
+	state: GestureState,
+	finger: Option<Finger>,
+}
+
+impl LongPressRecognizer {
+	/// Recognize a finger held for 500ms within 10 pixels
+	pub fn new() -> Self {
+		Self {
+			min_duration: 500,
+			slop: 10.0,
+			state: GestureState::Possible,
+			finger: None,
+		}
+	}
+
+	/// Set how long the finger must be held
+	pub fn with_min_duration(mut self, duration_ms: u64) -> Self {
+		self.min_duration = duration_ms;
+		self
+	}
+
+	/// Set how far the finger may move before the press begins
+	pub fn with_slop(mut self, slop: f32) -> Self {
+		self.slop = slop.max(0.0);
+		self
+	}
+}
+
+impl Default for LongPressRecognizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Recognizer for LongPressRecognizer {
+	fn state(&self) -> GestureState {
+		self.state
+	}
+
+	fn gesture(&self) -> Option<Gesture> {
+		let (x, y) = self.finger?.position;
+		(self.state != GestureState::Possible && self.state != GestureState::Failed)
+			.then_some(Gesture::LongPress { x, y })
+	}
+
+	fn handle_touch(&mut self, event: &TouchEvent, now: u64) {
+		if self.state.is_finished() {
+			return;
+		}
+		let pressing = self.state != GestureState::Possible;
+		match event.phase {
+			TouchPhase::Began if !pressing => match (self.finger, Finger::from_event(event, now)) {
+				(None, Some(finger)) => self.finger = Some(finger),
+				_ => self.fail(),
+			},
+			// Other fingers do not matter once the press has begun
+			TouchPhase::Began => {}
+			TouchPhase::Moved => {
+				if let Some(finger) = &mut self.finger
+					&& finger.follow(event)
+				{
+					if pressing {
+						self.state = GestureState::Changed;
+					} else if finger.travelled() > self.slop {
+						self.fail();
+					}
+				}
+			}
+			TouchPhase::Ended => {
+				if let Some(finger) = &mut self.finger
+					&& finger.follow(event)
+				{
+					if pressing {
+						self.state = GestureState::Ended;
+					} else {
+						self.fail();
+					}
+				}
+			}
+			TouchPhase::Cancelled => self.fail(),
+		}
+	}
+
+	fn update(&mut self, now: u64) {
+		if self.state == GestureState::Possible
+			&& let Some(finger) = self.finger
+			&& now.saturating_sub(finger.down_at) >= self.min_duration
+		{
+			self.state = GestureState::Began;
+		}
+	}
+
+	fn fail(&mut self) {
+		self.state = GestureState::Failed;
+	}
+
+	fn reset(&mut self) {
+		self.state = GestureState::Possible;
+		self.finger = None;
+	}
+}
+
+/// Recognizes a quick straight stroke in one of the allowed directions
+#[derive(Debug, Clone)]
+pub struct SwipeRecognizer {
+	min_distance: f32,
+	min_velocity: f32,
+	directions: Vec<SwipeDirection>,
+	state: GestureState,
+	finger: Option<Finger>,
+	swipe: Option<Gesture>,
+}
+
+impl SwipeRecognizer {
+	/// Recognize a swipe in any direction of at least 50 pixels at 300
+	/// pixels per second
+	pub fn new() -> Self {
+		Self {
+			min_distance: 50.0,
+			min_velocity: 300.0,
+			directions: vec![
+				SwipeDirection::Left,
+				SwipeDirection::Right,
+				SwipeDirection::Up,
+				SwipeDirection::Down,
+			],
+			state: GestureState::Possible,
+			finger: None,
+			swipe: None,
+		}
+	}
+
+	/// Set how far the finger must travel
+	pub fn with_min_distance(mut self, distance: f32) -> Self {
+		self.min_distance = distance.max(0.0);
+		self
+	}
+
+	/// Set how fast, in pixels per second, the finger must travel
+	pub fn with_min_velocity(mut self, velocity: f32) -> Self {
+		self.min_velocity = velocity.max(0.0);
+		self
+	}
+
+	/// Recognize swipes in these directions only
+	pub fn with_directions(mut self, directions: &[SwipeDirection]) -> Self {
+		self.directions = directions.to_vec();
+		self
+	}
+}
+
+impl Default for SwipeRecognizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Recognizer for SwipeRecognizer {
+	fn state(&self) -> GestureState {
+		self.state
+	}
+
+	fn gesture(&self) -> Option<Gesture> {
+		self.swipe
+	}
+
+	fn handle_touch(&mut self, event: &TouchEvent, now: u64) {
+		if self.state.is_finished() {
+			return;
+		}
+		match event.phase {
+			TouchPhase::Began => match (self.finger, Finger::from_event(event, now)) {
+				(None, Some(finger)) => self.finger = Some(finger),
+				_ => self.fail(),
+			},
+			TouchPhase::Moved => {
+				if let Some(finger) = &mut self.finger {
+					finger.follow(event);
+				}
+			}
+			TouchPhase::Ended => {
+				let Some(mut finger) = self.finger else {
+					return;
+				};
+				if !finger.follow(event) {
+					return;
+				}
+				let (dx, dy) = (
+					finger.position.0 - finger.start.0,
+					finger.position.1 - finger.start.1,
+				);
+				let seconds = now.saturating_sub(finger.down_at).max(1) as f32 / 1000.0;
+				let direction = match (dx.abs() >= dy.abs(), dx >= 0.0, dy >= 0.0) {
+					(true, true, _) => SwipeDirection::Right,
+					(true, false, _) => SwipeDirection::Left,
+					(false, _, true) => SwipeDirection::Down,
+					(false, _, false) => SwipeDirection::Up,
+				};
+				if finger.travelled() < self.min_distance
+					|| finger.travelled() / seconds < self.min_velocity
+					|| !self.directions.contains(&direction)
+				{
+					return self.fail();
+				}
+				self.swipe = Some(Gesture::Swipe {
+					direction,
+					velocity_x: dx / seconds,
+					velocity_y: dy / seconds,
+				});
+				self.state = GestureState::Ended;
+			}
+			TouchPhase::Cancelled => self.fail(),
+		}
+	}
+
+	fn fail(&mut self) {
+		self.state = GestureState::Failed;
+	}
+
+	fn reset(&mut self) {
+		self.state = GestureState::Possible;
+		self.finger = None;
+		self.swipe = None;
+	}
+}
+
+/// Recognizes two fingers turning around their center
+#[derive(Debug, Clone)]
+pub struct RotateRecognizer {
+	min_angle: f32,
+	state: GestureState,
+	touches: TouchState,
+	/// Angle of the line between the fingers when last seen
+	last_angle: Option<f32>,
+	/// Total turn since both fingers were down
+	angle: f32,
+	center: (f32, f32),
+}
+
+impl RotateRecognizer {
+	/// Recognize rotation once the fingers have turned by 0.1 radians
+	pub fn new() -> Self {
+		Self {
+			min_angle: 0.1,
+			state: GestureState::Possible,
+			touches: TouchState::new(),
+			last_angle: None,
+			angle: 0.0,
+			center: (0.0, 0.0),
+		}
+	}
+
+	/// Set how far, in radians, the fingers must turn before rotation begins
+	pub fn with_min_angle(mut self, angle: f32) -> Self {
+		self.min_angle = angle.abs();
+		self
+	}
+
+	/// Angle of the line between the two fingers, and their center
+	fn measure(&self) -> Option<(f32, (f32, f32))> {
+		let mut touches = self.touches.active_touches();
+		if touches.len() != 2 {
+			return None;
+		}
+		touches.sort_by_key(|touch| touch.id);
+		let (a, b) = (touches[0], touches[1]);
+		let angle = (b.y - a.y).atan2(b.x - a.x);
+		Some((angle, ((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)))
+	}
+}
+
+impl Default for RotateRecognizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Recognizer for RotateRecognizer {
+	fn state(&self) -> GestureState {
+		self.state
+	}
+
+	fn gesture(&self) -> Option<Gesture> {
+		(self.state != GestureState::Possible && self.state != GestureState::Failed).then_some(
+			Gesture::Rotate {
+				angle: self.angle,
+				center_x: self.center.0,
+				center_y: self.center.1,
+			},
+		)
+	}
+
+	fn handle_touch(&mut self, event: &TouchEvent, _now: u64) {
+		if self.state.is_finished() {
+			return;
+		}
+		if event.phase == TouchPhase::Cancelled {
+			return self.fail();
+		}
+		self.touches.update(event);
+		let rotating = self.state != GestureState::Possible;
+		let count = self.touches.touch_count();
+
+		if count > 2 && !rotating {
+			return self.fail();
+		}
+		if count < 2 {
+			// A finger lifted
+			if rotating {
+				self.state = GestureState::Ended;
+			} else if self.last_angle.is_some() || count == 0 {
+				self.fail();
+			}
+			return;
+		}
+
+		let Some((angle, center)) = self.measure() else {
+			return;
+		};
+		if let Some(last) = self.last_angle {
+			self.angle += wrap_angle(angle - last);
+		}
+		self.last_angle = Some(angle);
+		self.center = center;
+
+		if rotating {
+			self.state = GestureState::Changed;
+		} else if self.angle.abs() >= self.min_angle {
+			self.state = GestureState::Began;
+		}
+	}
+
+	fn fail(&mut self) {
+		self.state = GestureState::Failed;
+	}
+
+	fn reset(&mut self) {
+		self.state = GestureState::Possible;
+		self.touches.clear();
+		self.last_angle = None;
+		self.angle = 0.0;
+	}
+}
+
+/// How far back finger movement counts towards the release velocity
+const VELOCITY_WINDOW_MS: u64 = 100;
+
+/// Recognizes a finger thrown off the screen, then carries its momentum on
+/// with exponential deceleration
+///
+/// Begins when the finger lifts fast enough, changes with every `update`
+/// as the momentum decays, and ends once it drops below the stop velocity
+/// or a finger touches down again.
+#[derive(Debug, Clone)]
+pub struct FlingRecognizer {
+	min_velocity: f32,
+	stop_velocity: f32,
+	deceleration: f32,
+	state: GestureState,
+	finger: Option<u64>,
+	/// Recent finger positions with their times
+	samples: Vec<(f32, f32, u64)>,
+	released_at: u64,
+	release_velocity: (f32, f32),
+	velocity: (f32, f32),
+	offset: (f32, f32),
+}
+
+impl FlingRecognizer {
+	/// Recognize a release at 500 pixels per second, keeping 99.8% of the
+	/// velocity every millisecond until it drops below 20 pixels per second
+	pub fn new() -> Self {
+		Self {
+			min_velocity: 500.0,
+			stop_velocity: 20.0,
+			deceleration: 0.998,
+			state: GestureState::Possible,
+			finger: None,
+			samples: Vec::new(),
+			released_at: 0,
+			release_velocity: (0.0, 0.0),
+			velocity: (0.0, 0.0),
+			offset: (0.0, 0.0),
+		}
+	}
+
+	/// Set how fast, in pixels per second, the finger must lift
+	pub fn with_min_velocity(mut self, velocity: f32) -> Self {
+		self.min_velocity = velocity.max(0.0);
+		self
+	}
+
+	/// Set the velocity, in pixels per second, at which the fling stops
+	pub fn with_stop_velocity(mut self, velocity: f32) -> Self {
+		self.stop_velocity = velocity.max(0.0);
+		self
+	}
+
+	/// Set the share of velocity kept every millisecond, below 1.0
+	pub fn with_deceleration(mut self, deceleration: f32) -> Self {
+		self.deceleration = deceleration.clamp(0.5, 0.9999);
+		self
+	}
+
+	/// Record where the finger is, dropping samples too old to matter
+	fn sample(&mut self, x: f32, y: f32, now: u64) {
+		self.samples.push((x, y, now));
+		self.samples
+			.retain(|&(.., at)| now.saturating_sub(at) <= VELOCITY_WINDOW_MS);
+	}
+
+	/// Velocity over the recent samples
+	fn release_velocity(&self) -> (f32, f32) {
+		match (self.samples.first(), self.samples.last()) {
+			(Some(&(x0, y0, t0)), Some(&(x1, y1, t1))) if t1 > t0 => {
+				let seconds = (t1 - t0) as f32 / 1000.0;
+				((x1 - x0) / seconds, (y1 - y0) / seconds)
+			}
+			_ => (0.0, 0.0),
+		}
+	}
+}
+
+impl Default for FlingRecognizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Recognizer for FlingRecognizer {
+	fn state(&self) -> GestureState {
+		self.state
+	}
+
+	fn gesture(&self) -> Option<Gesture> {
+		(self.state != GestureState::Possible && self.state != GestureState::Failed).then_some(
+			Gesture::Fling {
+				velocity_x: self.velocity.0,
+				velocity_y: self.velocity.1,
+				offset_x: self.offset.0,
+				offset_y: self.offset.1,
+			},
+		)
+	}
+
+	fn handle_touch(&mut self, event: &TouchEvent, now: u64) {
+		match self.state {
+			GestureState::Possible => {}
+			// Touching the screen again stops the momentum
+			GestureState::Began | GestureState::Changed => {
+				if event.phase == TouchPhase::Began {
+					self.state = GestureState::Ended;
+				}
+				return;
+			}
+			GestureState::Ended | GestureState::Failed => return,
+		}
+
+		match event.phase {
+			TouchPhase::Began => match (self.finger, event.touches.as_slice()) {
+				(None, [touch]) => {
+					self.finger = Some(touch.id);
+					self.sample(touch.x, touch.y, now);
+				}
+				_ => self.fail(),
+			},
+			TouchPhase::Moved | TouchPhase::Ended => {
+				let Some(touch) = event
+					.touches
+					.iter()
+					.find(|touch| Some(touch.id) == self.finger)
+				else {
+					return;
+				};
+				self.sample(touch.x, touch.y, now);
+				if event.phase == TouchPhase::Ended {
+					let (vx, vy) = self.release_velocity();
+					if vx.hypot(vy) < self.min_velocity {
+						return self.fail();
+					}
+					self.released_at = now;
+					self.release_velocity = (vx, vy);
+					self.velocity = (vx, vy);
+					self.offset = (0.0, 0.0);
+					self.state = GestureState::Began;
+				}
+			}
+			TouchPhase::Cancelled => self.fail(),
+		}
+	}
+
+	fn update(&mut self, now: u64) {
+		if !matches!(self.state, GestureState::Began | GestureState::Changed) {
+			return;
+		}
+		let elapsed = now.saturating_sub(self.released_at) as f32;
+		let kept = self.deceleration.powf(elapsed);
+		// Distance covered is the integral of the decaying velocity
+		let travelled = (kept - 1.0) / self.deceleration.ln() / 1000.0;
+		let (vx, vy) = self.release_velocity;
+		self.velocity = (vx * kept, vy * kept);
+		self.offset = (vx * travelled, vy * travelled);
+		self.state = if self.velocity.0.hypot(self.velocity.1) < self.stop_velocity {
+			GestureState::Ended
+		} else {
+			GestureState::Changed
+		};
+	}
+
+	fn fail(&mut self) {
+		self.state = GestureState::Failed;
+	}
+
+	fn reset(&mut self) {
+		self.state = GestureState::Possible;
+		self.finger = None;
+		self.samples.clear();
+		self.velocity = (0.0, 0.0);
+		self.offset = (0.0, 0.0);
+	}
+}
+
+/// Identifies a recognizer within a [`GestureArena`]
+pub type RecognizerId = usize;
+
+/// A recognizer's gesture as reported by a [`GestureArena`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureUpdate {
+	/// The recognizer reporting
+	pub recognizer: RecognizerId,
+	/// Its new state, one of `Began`, `Changed` or `Ended`
+	pub state: GestureState,
+	/// The gesture's values
+	pub gesture: Gesture,
+}
+
+/// A recognizer with the arena's bookkeeping
+#[derive(Debug)]
+struct Entry {
+	recognizer: Box<dyn Recognizer>,
+	/// Recognizers that must fail before this one may recognize
+	waits_for: Vec<RecognizerId>,
+	/// What was last reported, `None` until the recognizer wins
+	reported: Option<(GestureState, Gesture)>,
+}
+
+impl Entry {
+	/// Check whether the recognizer has won and not failed since
+	fn is_active(&self) -> bool {
+		self.reported.is_some() && self.recognizer.state() != GestureState::Failed
+	}
+}
+
+/// Runs recognizers over the same touches and settles which of them win
+#[derive(Debug, Default)]
+pub struct GestureArena {
+	entries: Vec<Entry>,
+	simultaneous: HashSet<(RecognizerId, RecognizerId)>,
+	touches: TouchState,
+}
+
+impl GestureArena {
+	/// Create an arena with no recognizers
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add a recognizer
+	pub fn add(&mut self, recognizer: impl Recognizer + 'static) -> RecognizerId {
+		self.entries.push(Entry {
+			recognizer: Box::new(recognizer),
+			waits_for: Vec::new(),
+			reported: None,
+		});
+		self.entries.len() - 1
+	}
+
+	/// Let two recognizers recognize their gestures from the same touches
+	pub fn allow_simultaneous(&mut self, a: RecognizerId, b: RecognizerId) {
+		self.simultaneous.insert((a.min(b), a.max(b)));
+	}
+
+	/// Hold back `id` until `other` has failed, and fail it if `other`
+	/// recognizes its gesture instead
+	pub fn require_failure(&mut self, id: RecognizerId, other: RecognizerId) {
+		if let Some(entry) = self.entries.get_mut(id) {
+			entry.waits_for.push(other);
+		}
+	}
+
+	/// Current state of a recognizer
+	pub fn state(&self, id: RecognizerId) -> Option<GestureState> {
+		self.entries.get(id).map(|entry| entry.recognizer.state())
+	}
+
+	/// Feed a touch event that happened at `now` to every recognizer,
+	/// returning the gestures it settles
+	pub fn handle_touch(&mut self, event: &TouchEvent, now: u64) -> Vec<GestureUpdate> {
+		// Settle what the time since the last event decided first
+		let mut updates = self.update(now);
+
+		if event.phase == TouchPhase::Began && !self.touches.has_active_touches() {
+			// New touches start a new round for the recognizers that are done
+			for entry in &mut self.entries {
+				let state = entry.recognizer.state();
+				if state == GestureState::Failed
+					|| (state.is_finished() && entry.reported.is_some())
+				{
+					entry.recognizer.reset();
+					entry.reported = None;
+				}
+			}
+		}
+		self.touches.update(event);
+
+		for entry in &mut self.entries {
+			entry.recognizer.handle_touch(event, now);
+		}
+		updates.extend(self.settle());
+		updates
+	}
+
+	/// Let time pass without new touches, returning the gestures it settles
+	pub fn update(&mut self, now: u64) -> Vec<GestureUpdate> {
+		for entry in &mut self.entries {
+			entry.recognizer.update(now);
+		}
+		self.settle()
+	}
+
+	/// Reset every recognizer and forget the touches
+	pub fn reset(&mut self) {
+		for entry in &mut self.entries {
+			entry.recognizer.reset();
+			entry.reported = None;
+		}
+		self.touches.clear();
+	}
+
+	fn can_share(&self, a: RecognizerId, b: RecognizerId) -> bool {
+		self.simultaneous.contains(&(a.min(b), a.max(b)))
+	}
+
+	/// Decide which recognizers win, failing the ones they rule out, and
+	/// report the winners' progress
+	fn settle(&mut self) -> Vec<GestureUpdate> {
+		let mut updates = Vec::new();
+		loop {
+			let mut settled = true;
+			for id in 0..self.entries.len() {
+				let entry = &self.entries[id];
+				let state = entry.recognizer.state();
+				let Some(gesture) = entry.recognizer.gesture() else {
+					continue;
+				};
+				if state == GestureState::Possible || state == GestureState::Failed {
+					continue;
+				}
+				if entry.reported == Some((state, gesture)) {
+					continue;
+				}
+
+				if entry.reported.is_none() {
+					// Recognized for the first time; see whether it may win
+					let waiting = entry.waits_for.iter().any(|&other| {
+						self.state(other)
+							.is_some_and(|other| other != GestureState::Failed)
+					});
+					if waiting {
+						let lost = entry
+							.waits_for
+							.iter()
+							.any(|&other| self.entries[other].is_active());
+						if lost {
+							self.entries[id].recognizer.fail();
+							settled = false;
+						}
+						continue;
+					}
+					let beaten = (0..self.entries.len()).any(|other| {
+						other != id && self.entries[other].is_active() && !self.can_share(id, other)
+					});
+					if beaten {
+						self.entries[id].recognizer.fail();
+						settled = false;
+						continue;
+					}
+					// It wins: rule out the others that have not
+					for other in 0..self.entries.len() {
+						if other != id
+							&& self.entries[other].reported.is_none()
+							&& !self.can_share(id, other)
+						{
+							self.entries[other].recognizer.fail();
+						}
+					}
+				}
+
+				self.entries[id].reported = Some((state, gesture));
+				updates.push(GestureUpdate {
+					recognizer: id,
+					state,
+					gesture,
+				});
+				settled = false;
+			}
+			if settled {
+				return updates;
+			}
+		}
+	}
+}
+
+/// Bring an angle difference into -π..π, treating a NaN or infinite one as
+/// no rotation
+fn wrap_angle(angle: f32) -> f32 {
+	if !angle.is_finite() {
+		return 0.0;
+	}
+	(angle + PI).rem_euclid(TAU) - PI
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+	(a.0 - b.0).hypot(a.1 - b.1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::input::Touch;
+
+	fn touch(phase: TouchPhase, touches: &[(u64, f32, f32)]) -> TouchEvent {
+		TouchEvent::new(
+			phase,
+			touches
+				.iter()
+				.map(|&(id, x, y)| Touch::new(id, x, y))
+				.collect(),
+		)
+	}
+
+	/// Feed a sequence of `(time, phase, x, y)` for finger 0
+	fn stroke(recognizer: &mut impl Recognizer, points: &[(u64, TouchPhase, f32, f32)]) {
+		for &(now, phase, x, y) in points {
+			recognizer.update(now);
+			recognizer.handle_touch(&touch(phase, &[(0, x, y)]), now);
+		}
+	}
+
+	fn tap_at(start: u64, x: f32, y: f32) -> [(u64, TouchPhase, f32, f32); 2] {
+		[
+			(start, TouchPhase::Began, x, y),
+			(start + 50, TouchPhase::Ended, x, y),
+		]
+	}
+
+	#[test]
+	fn test_tap_recognizer() {
+		let mut tap = TapRecognizer::new();
+		stroke(&mut tap, &tap_at(0, 10.0, 20.0));
+		assert_eq!(tap.state(), GestureState::Ended);
+		assert_eq!(
+			tap.gesture(),
+			Some(Gesture::Tap {
+				x: 10.0,
+				y: 20.0,
+				taps: 1
+			})
+		);
+
+		// Moving beyond the slop
+		tap.reset();
+		stroke(
+			&mut tap,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(20, TouchPhase::Moved, 15.0, 0.0),
+			],
+		);
+		assert_eq!(tap.state(), GestureState::Failed);
+		assert_eq!(tap.gesture(), None);
+
+		// Holding too long
+		tap.reset();
+		stroke(&mut tap, &[(0, TouchPhase::Began, 0.0, 0.0)]);
+		tap.update(301);
+		assert_eq!(tap.state(), GestureState::Failed);
+
+		// A second finger
+		tap.reset();
+		tap.handle_touch(
+			&touch(TouchPhase::Began, &[(0, 0.0, 0.0), (1, 9.0, 9.0)]),
+			0,
+		);
+		assert_eq!(tap.state(), GestureState::Failed);
+	}
+
+	#[test]
+	fn test_double_tap_recognizer() {
+		let mut double = TapRecognizer::double();
+		stroke(&mut double, &tap_at(0, 0.0, 0.0));
+		assert_eq!(double.state(), GestureState::Possible);
+		stroke(&mut double, &tap_at(200, 5.0, 5.0));
+		assert_eq!(double.state(), GestureState::Ended);
+		assert!(matches!(
+			double.gesture(),
+			Some(Gesture::Tap { taps: 2, .. })
+		));
+
+		// Too long between taps
+		double.reset();
+		stroke(&mut double, &tap_at(0, 0.0, 0.0));
+		double.update(351);
+		assert_eq!(double.state(), GestureState::Failed);
+
+		// Too far from the first tap
+		let mut double = TapRecognizer::double().with_tap_distance(20.0);
+		stroke(&mut double, &tap_at(0, 0.0, 0.0));
+		stroke(&mut double, &tap_at(100, 30.0, 0.0));
+		assert_eq!(double.state(), GestureState::Failed);
+	}
+
+	#[test]
+	fn test_long_press_recognizer() {
+		let mut press = LongPressRecognizer::new();
+		stroke(&mut press, &[(0, TouchPhase::Began, 10.0, 10.0)]);
+		press.update(499);
+		assert_eq!(press.state(), GestureState::Possible);
+		press.update(500);
+		assert_eq!(press.state(), GestureState::Began);
+		assert_eq!(
+			press.gesture(),
+			Some(Gesture::LongPress { x: 10.0, y: 10.0 })
+		);
+
+		// Free to move once begun
+		stroke(&mut press, &[(600, TouchPhase::Moved, 80.0, 10.0)]);
+		assert_eq!(press.state(), GestureState::Changed);
+		assert_eq!(
+			press.gesture(),
+			Some(Gesture::LongPress { x: 80.0, y: 10.0 })
+		);
+		stroke(&mut press, &[(700, TouchPhase::Ended, 80.0, 10.0)]);
+		assert_eq!(press.state(), GestureState::Ended);
+
+		// Lifting or moving too early
+		let mut press = LongPressRecognizer::new().with_min_duration(200);
+		stroke(&mut press, &tap_at(0, 0.0, 0.0));
+		assert_eq!(press.state(), GestureState::Failed);
+		press.reset();
+		stroke(
+			&mut press,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(100, TouchPhase::Moved, 0.0, 20.0),
+			],
+		);
+		assert_eq!(press.state(), GestureState::Failed);
+	}
+
+	#[test]
+	fn test_swipe_recognizer() {
+		let mut swipe = SwipeRecognizer::new();
+		stroke(
+			&mut swipe,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(50, TouchPhase::Moved, 60.0, 5.0),
+				(100, TouchPhase::Ended, 120.0, 10.0),
+			],
+		);
+		assert_eq!(swipe.state(), GestureState::Ended);
+		assert_eq!(
+			swipe.gesture(),
+			Some(Gesture::Swipe {
+				direction: SwipeDirection::Right,
+				velocity_x: 1200.0,
+				velocity_y: 100.0,
+			})
+		);
+
+		// Too slow
+		swipe.reset();
+		stroke(
+			&mut swipe,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(1000, TouchPhase::Ended, 0.0, -120.0),
+			],
+		);
+		assert_eq!(swipe.state(), GestureState::Failed);
+
+		// In a direction that is not allowed
+		let mut swipe = SwipeRecognizer::new().with_directions(&[SwipeDirection::Down]);
+		stroke(
+			&mut swipe,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(100, TouchPhase::Ended, 0.0, -120.0),
+			],
+		);
+		assert_eq!(swipe.state(), GestureState::Failed);
+	}
+
+	#[test]
+	fn test_rotate_recognizer() {
+		let mut rotate = RotateRecognizer::new();
+		rotate.handle_touch(&touch(TouchPhase::Began, &[(0, 0.0, 0.0)]), 0);
+		rotate.handle_touch(&touch(TouchPhase::Began, &[(1, 100.0, 0.0)]), 10);
+		rotate.handle_touch(&touch(TouchPhase::Moved, &[(1, 100.0, 5.0)]), 20);
+		assert_eq!(rotate.state(), GestureState::Possible);
+
+		rotate.handle_touch(&touch(TouchPhase::Moved, &[(1, 100.0, 100.0)]), 30);
+		assert_eq!(rotate.state(), GestureState::Began);
+		let Some(Gesture::Rotate {
+			angle,
+			center_x,
+			center_y,
+		}) = rotate.gesture()
+		else {
+			panic!("expected a rotation");
+		};
+		assert!((angle - PI / 4.0).abs() < 1e-4);
+		assert_eq!((center_x, center_y), (50.0, 50.0));
+
+		// Turning past the half turn keeps counting
+		rotate.handle_touch(&touch(TouchPhase::Moved, &[(1, -100.0, 10.0)]), 40);
+		rotate.handle_touch(&touch(TouchPhase::Moved, &[(1, -100.0, -10.0)]), 50);
+		assert_eq!(rotate.state(), GestureState::Changed);
+		let Some(Gesture::Rotate { angle, .. }) = rotate.gesture() else {
+			panic!("expected a rotation");
+		};
+		assert!(angle > PI);
+
+		rotate.handle_touch(&touch(TouchPhase::Ended, &[(0, 0.0, 0.0)]), 60);
+		assert_eq!(rotate.state(), GestureState::Ended);
+
+		// Three fingers
+		rotate.reset();
+		rotate.handle_touch(
+			&touch(
+				TouchPhase::Began,
+				&[(0, 0.0, 0.0), (1, 100.0, 0.0), (2, 50.0, 50.0)],
+			),
+			0,
+		);
+		assert_eq!(rotate.state(), GestureState::Failed);
+	}
+
+	#[test]
+	fn test_wrap_angle() {
+		assert!((wrap_angle(PI / 2.0) - PI / 2.0).abs() < 1e-5);
+		assert!((wrap_angle(3.0 * PI / 2.0) + PI / 2.0).abs() < 1e-5);
+		assert!((wrap_angle(-5.0 * PI / 2.0) + PI / 2.0).abs() < 1e-5);
+		assert!(wrap_angle(1.0e30).abs() <= PI);
+		assert_eq!(wrap_angle(f32::NAN), 0.0);
+		assert_eq!(wrap_angle(f32::INFINITY), 0.0);
+		assert_eq!(wrap_angle(f32::NEG_INFINITY), 0.0);
+	}
+
+	#[test]
+	fn test_fling_recognizer() {
+		let mut fling = FlingRecognizer::new();
+		stroke(
+			&mut fling,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(50, TouchPhase::Moved, 50.0, 0.0),
+				(100, TouchPhase::Ended, 100.0, 0.0),
+			],
+		);
+		assert_eq!(fling.state(), GestureState::Began);
+		assert!(matches!(
+			fling.gesture(),
+			Some(Gesture::Fling {
+				velocity_x: 1000.0,
+				offset_x: 0.0,
+				..
+			})
+		));
+
+		// Momentum decays and carries the content on
+		fling.update(600);
+		assert_eq!(fling.state(), GestureState::Changed);
+		let Some(Gesture::Fling {
+			velocity_x,
+			velocity_y,
+			offset_x,
+			..
+		}) = fling.gesture()
+		else {
+			panic!("expected a fling");
+		};
+		assert!((velocity_x - 367.5).abs() < 1.0);
+		assert_eq!(velocity_y, 0.0);
+		assert!((offset_x - 316.0).abs() < 1.0);
+
+		fling.update(3100);
+		assert_eq!(fling.state(), GestureState::Ended);
+
+		// Touching again stops it
+		fling.reset();
+		stroke(
+			&mut fling,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(100, TouchPhase::Ended, 0.0, 100.0),
+			],
+		);
+		assert_eq!(fling.state(), GestureState::Began);
+		stroke(&mut fling, &[(150, TouchPhase::Began, 0.0, 100.0)]);
+		assert_eq!(fling.state(), GestureState::Ended);
+
+		// Released too slowly
+		fling.reset();
+		stroke(
+			&mut fling,
+			&[
+				(0, TouchPhase::Began, 0.0, 0.0),
+				(100, TouchPhase::Ended, 10.0, 0.0),
+			],
+		);
+		assert_eq!(fling.state(), GestureState::Failed);
+	}
+
+	fn feed(
+		arena: &mut GestureArena,
+		points: &[(u64, TouchPhase, f32, f32)],
+	) -> Vec<GestureUpdate> {
+		points
+			.iter()
+			.flat_map(|&(now, phase, x, y)| arena.handle_touch(&touch(phase, &[(0, x, y)]), now))
+			.collect()
+	}
+
+	#[test]
+	fn test_arena_tap_waits_for_double_tap() {
+		let mut arena = GestureArena::new();
+		let single = arena.add(TapRecognizer::new());
+		let double = arena.add(TapRecognizer::double());
+		arena.require_failure(single, double);
+
+		// The single tap is held back until the double tap times out
+		assert!(feed(&mut arena, &tap_at(0, 0.0, 0.0)).is_empty());
+		assert_eq!(arena.state(single), Some(GestureState::Ended));
+		let updates = arena.update(400);
+		assert_eq!(
+			updates,
+			vec![GestureUpdate {
+				recognizer: single,
+				state: GestureState::Ended,
+				gesture: Gesture::Tap {
+					x: 0.0,
+					y: 0.0,
+					taps: 1
+				},
+			}]
+		);
+
+		// A double tap rules the single tap out
+		let mut updates = feed(&mut arena, &tap_at(1000, 0.0, 0.0));
+		updates.extend(feed(&mut arena, &tap_at(1100, 0.0, 0.0)));
+		assert_eq!(updates.len(), 1);
+		assert_eq!(updates[0].recognizer, double);
+		assert!(matches!(updates[0].gesture, Gesture::Tap { taps: 2, .. }));
+		assert_eq!(arena.state(single), Some(GestureState::Failed));
+	}
+
+	#[test]
+	fn test_arena_simultaneous_recognizers() {
+		let throw = [
+			(0, TouchPhase::Began, 0.0, 0.0),
+			(50, TouchPhase::Moved, 50.0, 0.0),
+			(100, TouchPhase::Ended, 100.0, 0.0),
+		];
+
+		// The first to recognize wins
+		let mut arena = GestureArena::new();
+		let swipe = arena.add(SwipeRecognizer::new());
+		let fling = arena.add(FlingRecognizer::new());
+		let updates = feed(&mut arena, &throw);
+		assert_eq!(updates.len(), 1);
+		assert_eq!(updates[0].recognizer, swipe);
+		assert_eq!(arena.state(fling), Some(GestureState::Failed));
+		assert!(arena.update(200).is_empty());
+
+		// Unless both are allowed
+		arena.reset();
+		arena.allow_simultaneous(fling, swipe);
+		let updates = feed(&mut arena, &throw);
+		let states: Vec<_> = updates
+			.iter()
+			.map(|update| (update.recognizer, update.state))
+			.collect();
+		assert_eq!(
+			states,
+			vec![(swipe, GestureState::Ended), (fling, GestureState::Began)]
+		);
+		let updates = arena.update(200);
+		assert_eq!(updates.len(), 1);
+		assert_eq!(updates[0].state, GestureState::Changed);
+
+		// A new touch ends the fling and starts a new round
+		let updates = feed(&mut arena, &[(300, TouchPhase::Began, 0.0, 0.0)]);
+		let last = updates.last().unwrap();
+		assert_eq!((last.recognizer, last.state), (fling, GestureState::Ended));
+		assert_eq!(arena.state(swipe), Some(GestureState::Possible));
+	}
+}
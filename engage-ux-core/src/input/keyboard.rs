@@ -1,11 +1,12 @@
 //! Keyboard input handling with full accessibility support
 
 use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 bitflags! {
 	/// Keyboard modifiers (Shift, Ctrl, Alt, Meta)
-	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 	pub struct KeyModifiers: u8 {
 		const SHIFT = 0b0001;
 		const CTRL = 0b0010;
@@ -15,7 +16,7 @@ bitflags! {
 }
 
 /// Physical key codes for keyboard keys
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum KeyCode {
 	// Navigation keys
 	Enter,
@@ -65,7 +66,7 @@ pub enum KeyCode {
 }
 
 /// Keyboard event type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KeyEventType {
 	/// Key was pressed down
 	Down,
@@ -76,7 +77,7 @@ pub enum KeyEventType {
 }
 
 /// Keyboard event with full context
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KeyboardEvent {
 	/// Type of keyboard event
 	pub event_type: KeyEventType,
@@ -3,16 +3,28 @@
 //! Provides comprehensive input handling for keyboard, mouse, and touch events
 //! with full support for accessibility and multi-modal interaction.
 
+pub mod dispatch;
+pub mod gesture;
 pub mod keyboard;
 pub mod mouse;
+pub mod pointer;
 pub mod touch;
 
+pub use dispatch::{EventPhase, PointerDispatcher, PointerEvent};
+pub use gesture::{
+	FlingRecognizer, Gesture, GestureArena, GestureState, GestureUpdate, LongPressRecognizer,
+	Recognizer, RecognizerId, RotateRecognizer, SwipeDirection, SwipeRecognizer, TapRecognizer,
+};
 pub use keyboard::{Key, KeyCode, KeyModifiers, KeyboardEvent, KeyboardState};
 pub use mouse::{MouseButton, MouseEvent, MouseState};
+pub use pointer::PointerGestures;
 pub use touch::{Touch, TouchEvent, TouchPhase, TouchState};
 
+use crate::events::Event;
+use serde::{Deserialize, Serialize};
+
 /// Unified input event that can represent any input type
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InputEvent {
 	/// Keyboard input event
 	Keyboard(KeyboardEvent),
@@ -26,7 +38,7 @@ pub enum InputEvent {
 
 /// Custom input event for supporting additional input devices
 /// (gamepad, stylus, motion sensors, etc.)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CustomInputEvent {
 	/// Device type identifier
 	pub device_type: String,
@@ -37,7 +49,7 @@ pub struct CustomInputEvent {
 }
 
 /// Value type for custom input data
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CustomInputValue {
 	/// Boolean value
 	Bool(bool),
@@ -158,6 +170,28 @@ pub trait InputHandler {
 			InputEvent::Custom(e) => self.handle_custom(e),
 		}
 	}
+
+	/// Handle an event from the event system
+	///
+	/// Raw input is passed on to [`InputHandler::handle_input`], so the same
+	/// handler serves both events from an [`EventHandler`](crate::events::EventHandler)
+	/// subscription and input from a backend. Other events are not handled
+	/// by default.
+	fn handle_event(&mut self, event: &Event) -> bool {
+		event.input().is_some_and(|input| self.handle_input(&input))
+	}
+
+	/// Handle a pointer event routed through the component tree
+	///
+	/// Called once for each phase of [`PointerDispatcher`] delivery the
+	/// component takes part in. By default the component ignores the capture
+	/// phase and passes the event to [`InputHandler::handle_input`] at the
+	/// target and while bubbling; an event it handles goes no further.
+	fn handle_pointer(&mut self, event: &mut PointerEvent) {
+		if event.phase() != EventPhase::Capture && self.handle_input(event.input()) {
+			event.stop_propagation();
+		}
+	}
 }
 
 #[cfg(test)]
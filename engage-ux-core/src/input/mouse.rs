@@ -1,9 +1,10 @@
 //! Mouse input handling
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Mouse buttons
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MouseButton {
 	/// Left mouse button
 	Left,
@@ -18,7 +19,7 @@ pub enum MouseButton {
 }
 
 /// Mouse event type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MouseEventType {
 	/// Mouse button pressed
 	ButtonDown,
@@ -35,7 +36,7 @@ pub enum MouseEventType {
 }
 
 /// Mouse event with position and button information
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MouseEvent {
 	/// Type of mouse event
 	pub event_type: MouseEventType,
@@ -0,0 +1,612 @@
+//! Clicks, hovering and long presses synthesized from raw pointer input
+//!
+//! [`PointerGestures`] watches the mouse and touch events a backend delivers
+//! and turns them into the higher-level events components react to:
+//!
+//! - [`EventType::Click`] when a press and release land on the same
+//!   component, followed by [`EventType::DoubleClick`] or
+//!   [`EventType::TripleClick`] when clicks on it follow each other quickly
+//! - [`EventType::Enter`] and [`EventType::Leave`] as the pointer moves onto
+//!   and off each component, ancestors included
+//! - [`EventType::HoverIntent`] once the pointer has rested on a component
+//!   for its hover delay
+//! - [`EventType::LongPress`] when a press is held still long enough
+//!
+//! The caller drives time: it passes the current time in milliseconds with
+//! every event, and calls [`PointerGestures::update`] between events so
+//! delays can run out while the pointer is still.
+
+use super::mouse::MouseEventType;
+use super::{MouseButton, MouseEvent, TouchEvent, TouchPhase};
+use crate::component::{ComponentId, ComponentTree};
+use crate::events::{Event, EventType};
+use std::collections::HashMap;
+
+/// A component under the pointer
+#[derive(Debug, Clone, Copy)]
+struct Hover {
+	id: ComponentId,
+	/// When the pointer came to rest over the component
+	since: u64,
+	/// Whether hover intent has been reported since
+	reported: bool,
+}
+
+/// A press that may become a click or a long press
+#[derive(Debug, Clone, Copy)]
+struct Press {
+	target: ComponentId,
+	x: f32,
+	y: f32,
+	at: u64,
+	/// The touch point making the press, for touch input
+	touch: Option<u64>,
+	/// Whether the press moved too far to be a long press
+	moved: bool,
+	/// Whether a long press has been reported, which rules out a click
+	long_pressed: bool,
+}
+
+/// The most recent click, for counting clicks in a row
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+	target: ComponentId,
+	x: f32,
+	y: f32,
+	at: u64,
+	count: u32,
+}
+
+/// Synthesizes clicks, hover and long presses from mouse and touch events
+///
+/// Only the left mouse button and single-finger touches press; a second
+/// finger cancels the press.
+#[derive(Debug, Clone)]
+pub struct PointerGestures {
+	multi_click_interval: u64,
+	hover_delay: u64,
+	long_press_delay: u64,
+	slop: f32,
+	hover_delays: HashMap<ComponentId, u64>,
+	/// Components under the pointer, outermost first
+	hovered: Vec<Hover>,
+	/// Where the pointer last came to rest
+	rest: (f32, f32),
+	press: Option<Press>,
+	last_click: Option<LastClick>,
+}
+
+impl PointerGestures {
+	/// Create a tracker with default timings
+	///
+	/// Clicks count as one double or triple click when they are at most
+	/// 500ms apart, hover intent and long presses take 500ms, and the
+	/// pointer may drift 4 pixels and still count as still.
+	pub fn new() -> Self {
+		Self {
+			multi_click_interval: 500,
+			hover_delay: 500,
+			long_press_delay: 500,
+			slop: 4.0,
+			hover_delays: HashMap::new(),
+			hovered: Vec::new(),
+			rest: (0.0, 0.0),
+			press: None,
+			last_click: None,
+		}
+	}
+
+	/// Set the longest time between clicks that still counts them together
+	pub fn with_multi_click_interval(mut self, interval_ms: u64) -> Self {
+		self.multi_click_interval = interval_ms;
+		self
+	}
+
+	/// Set how long the pointer must rest over a component to report hover
+	/// intent, unless the component has its own delay
+	pub fn with_hover_delay(mut self, delay_ms: u64) -> Self {
+		self.hover_delay = delay_ms;
+		self
+	}
+
+	/// Set how long a press must be held to become a long press
+	pub fn with_long_press_delay(mut self, delay_ms: u64) -> Self {
+		self.long_press_delay = delay_ms;
+		self
+	}
+
+	/// Set how far, in pixels, the pointer may move and still count as still
+	pub fn with_slop(mut self, slop: f32) -> Self {
+		self.slop = slop.max(0.0);
+		self
+	}
+
+	/// Give a component its own hover delay
+	pub fn set_hover_delay(&mut self, id: ComponentId, delay_ms: u64) {
+		self.hover_delays.insert(id, delay_ms);
+	}
+
+	/// Return a component to the default hover delay
+	pub fn clear_hover_delay(&mut self, id: ComponentId) {
+		self.hover_delays.remove(&id);
+	}
+
+	/// Check whether the pointer is over a component or its descendants
+	pub fn is_hovered(&self, id: ComponentId) -> bool {
+		self.hovered.iter().any(|hover| hover.id == id)
+	}
+
+	/// Check whether a press is in progress
+	pub fn is_pressed(&self) -> bool {
+		self.press.is_some()
+	}
+
+	/// Report the delays that have run out by `now`
+	pub fn update(&mut self, now: u64) -> Vec<Event> {
+		let mut events = Vec::new();
+		for hover in &mut self.hovered {
+			let delay = self
+				.hover_delays
+				.get(&hover.id)
+				.copied()
+				.unwrap_or(self.hover_delay);
+			if !hover.reported && now.saturating_sub(hover.since) >= delay {
+				hover.reported = true;
+				let (x, y) = self.rest;
+				events.push(Event::new(hover.id, EventType::HoverIntent { x, y }));
+			}
+		}
+
+		if let Some(press) = &mut self.press
+			&& !press.moved
+			&& !press.long_pressed
+			&& now.saturating_sub(press.at) >= self.long_press_delay
+		{
+			press.long_pressed = true;
+			events.push(Event::new(
+				press.target,
+				EventType::LongPress {
+					x: press.x,
+					y: press.y,
+				},
+			));
+		}
+		events
+	}
+
+	/// Feed a mouse event, returning the events it and the time that
+	/// passed since the last call synthesize
+	pub fn handle_mouse(
+		&mut self,
+		tree: &ComponentTree,
+		event: &MouseEvent,
+		now: u64,
+	) -> Vec<Event> {
+		let mut events = self.update(now);
+		let (x, y) = (event.x, event.y);
+
+		if event.event_type == MouseEventType::Leave {
+			// The pointer left the window
+			self.hover_path(Vec::new(), now, &mut events);
+			return events;
+		}
+
+		let target = tree.hit_test(x, y);
+		self.hover_path(path_to(tree, target), now, &mut events);
+		self.moved_to(x, y, now);
+
+		if event.button == Some(MouseButton::Left) {
+			match event.event_type {
+				MouseEventType::ButtonDown => self.press(target, x, y, now, None),
+				MouseEventType::ButtonUp => self.release(target, x, y, now, &mut events),
+				_ => {}
+			}
+		}
+		events
+	}
+
+	/// Feed a touch event, returning the events it and the time that
+	/// passed since the last call synthesize
+	///
+	/// Touches press and release like the left mouse button, but do not
+	/// hover.
+	pub fn handle_touch(
+		&mut self,
+		tree: &ComponentTree,
+		event: &TouchEvent,
+		now: u64,
+	) -> Vec<Event> {
+		let mut events = self.update(now);
+		let pressing = self.press.and_then(|press| press.touch);
+		let touch = event
+			.touches
+			.iter()
+			.find(|touch| pressing.is_none_or(|id| touch.id == id));
+
+		match (event.phase, touch) {
+			(TouchPhase::Began, Some(touch))
+				if self.press.is_none() && event.touches.len() == 1 =>
+			{
+				let target = tree.hit_test(touch.x, touch.y);
+				self.press(target, touch.x, touch.y, now, Some(touch.id));
+			}
+			// Another finger joined in
+			(TouchPhase::Began, _) => self.press = None,
+			(TouchPhase::Moved, Some(touch)) if pressing.is_some() => {
+				self.moved_to(touch.x, touch.y, now);
+			}
+			(TouchPhase::Ended, Some(touch)) if pressing.is_some() => {
+				let target = tree.hit_test(touch.x, touch.y);
+				self.release(target, touch.x, touch.y, now, &mut events);
+			}
+			(TouchPhase::Cancelled, _) => self.press = None,
+			_ => {}
+		}
+		events
+	}
+
+	/// Forget the pointer, any press in progress and earlier clicks
+	pub fn reset(&mut self) {
+		self.hovered.clear();
+		self.press = None;
+		self.last_click = None;
+	}
+
+	/// Move the hover to the components on `path`, outermost first,
+	/// leaving and entering the ones that differ
+	fn hover_path(&mut self, path: Vec<ComponentId>, now: u64, events: &mut Vec<Event>) {
+		// Leaving runs from the innermost component out
+		for hover in self.hovered.iter().rev() {
+			if !path.contains(&hover.id) {
+				events.push(Event::new(hover.id, EventType::Leave));
+			}
+		}
+		self.hovered.retain(|hover| path.contains(&hover.id));
+
+		for id in path {
+			if !self.is_hovered(id) {
+				events.push(Event::new(id, EventType::Enter));
+				self.hovered.push(Hover {
+					id,
+					since: now,
+					reported: false,
+				});
+			}
+		}
+	}
+
+	/// Restart the hover and long press delays if the pointer moved far
+	/// enough from where it rested
+	fn moved_to(&mut self, x: f32, y: f32, now: u64) {
+		if distance(self.rest, (x, y)) > self.slop {
+			self.rest = (x, y);
+			for hover in self.hovered.iter_mut().filter(|hover| !hover.reported) {
+				hover.since = now;
+			}
+		}
+		if let Some(press) = &mut self.press
+			&& distance((press.x, press.y), (x, y)) > self.slop
+		{
+			press.moved = true;
+		}
+	}
+
+	fn press(&mut self, target: Option<ComponentId>, x: f32, y: f32, now: u64, touch: Option<u64>) {
+		self.press = target.map(|target| Press {
+			target,
+			x,
+			y,
+			at: now,
+			touch,
+			moved: false,
+			long_pressed: false,
+		});
+	}
+
+	fn release(
+		&mut self,
+		target: Option<ComponentId>,
+		x: f32,
+		y: f32,
+		now: u64,
+		events: &mut Vec<Event>,
+	) {
+		let Some(press) = self.press.take() else {
+			return;
+		};
+		if press.long_pressed || target != Some(press.target) {
+			return;
+		}
+
+		let count = match self.last_click {
+			Some(last)
+				if last.target == press.target
+					&& last.count < 3
+					&& now.saturating_sub(last.at) <= self.multi_click_interval
+					&& distance((last.x, last.y), (x, y)) <= self.slop =>
+			{
+				last.count + 1
+			}
+			_ => 1,
+		};
+		self.last_click = Some(LastClick {
+			target: press.target,
+			x,
+			y,
+			at: now,
+			count,
+		});
+
+		events.push(Event::new(press.target, EventType::Click));
+		match count {
+			2 => events.push(Event::new(press.target, EventType::DoubleClick)),
+			3 => events.push(Event::new(press.target, EventType::TripleClick)),
+			_ => {}
+		}
+	}
+}
+
+impl Default for PointerGestures {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A component and its ancestors, outermost first
+fn path_to(tree: &ComponentTree, target: Option<ComponentId>) -> Vec<ComponentId> {
+	let Some(target) = target else {
+		return Vec::new();
+	};
+	let mut path = tree.ancestors(target);
+	path.reverse();
+	path.push(target);
+	path
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+	(a.0 - b.0).hypot(a.1 - b.1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::{Component, ComponentProperties, Rect};
+	use crate::input::Touch;
+
+	struct Node {
+		properties: ComponentProperties,
+		children: Vec<ComponentId>,
+	}
+
+	impl Component for Node {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+	}
+
+	/// Build: 1 -> (2, 3), side by side inside 1
+	fn tree() -> ComponentTree {
+		let mut tree = ComponentTree::new();
+		for (id, parent, bounds) in [
+			(1, None, Rect::new(0.0, 0.0, 200.0, 100.0)),
+			(2, Some(1), Rect::new(0.0, 0.0, 50.0, 50.0)),
+			(3, Some(1), Rect::new(100.0, 0.0, 50.0, 50.0)),
+		] {
+			let mut properties = ComponentProperties::new(id);
+			properties.bounds = bounds;
+			let node = Node {
+				properties,
+				children: Vec::new(),
+			};
+			tree.insert(parent, node).unwrap();
+		}
+		tree
+	}
+
+	fn kinds(events: &[Event]) -> Vec<(ComponentId, EventType)> {
+		events
+			.iter()
+			.map(|event| (event.target, event.event_type.clone()))
+			.collect()
+	}
+
+	fn click(
+		gestures: &mut PointerGestures,
+		tree: &ComponentTree,
+		x: f32,
+		y: f32,
+		now: u64,
+	) -> Vec<(ComponentId, EventType)> {
+		let mut events =
+			gestures.handle_mouse(tree, &MouseEvent::button_down(MouseButton::Left, x, y), now);
+		events.extend(gestures.handle_mouse(
+			tree,
+			&MouseEvent::button_up(MouseButton::Left, x, y),
+			now + 10,
+		));
+		kinds(&events)
+	}
+
+	#[test]
+	fn test_click_needs_the_same_target() {
+		let tree = tree();
+		let mut gestures = PointerGestures::new();
+		let events = click(&mut gestures, &tree, 10.0, 10.0, 0);
+		assert_eq!(
+			events,
+			vec![
+				(1, EventType::Enter),
+				(2, EventType::Enter),
+				(2, EventType::Click)
+			]
+		);
+
+		// Pressed on 2, released on 3
+		gestures.handle_mouse(
+			&tree,
+			&MouseEvent::button_down(MouseButton::Left, 10.0, 10.0),
+			2000,
+		);
+		let events = gestures.handle_mouse(
+			&tree,
+			&MouseEvent::button_up(MouseButton::Left, 110.0, 10.0),
+			2010,
+		);
+		assert_eq!(
+			kinds(&events),
+			vec![(2, EventType::Leave), (3, EventType::Enter)]
+		);
+
+		// Other buttons do not click
+		gestures.handle_mouse(
+			&tree,
+			&MouseEvent::button_down(MouseButton::Right, 110.0, 10.0),
+			4000,
+		);
+		let events = gestures.handle_mouse(
+			&tree,
+			&MouseEvent::button_up(MouseButton::Right, 110.0, 10.0),
+			4010,
+		);
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn test_multi_click() {
+		let tree = tree();
+		let mut gestures = PointerGestures::new()
+			.with_multi_click_interval(300)
+			.with_hover_delay(10_000);
+		click(&mut gestures, &tree, 10.0, 10.0, 0);
+		assert_eq!(
+			click(&mut gestures, &tree, 11.0, 10.0, 200),
+			vec![(2, EventType::Click), (2, EventType::DoubleClick)]
+		);
+		assert_eq!(
+			click(&mut gestures, &tree, 11.0, 11.0, 400),
+			vec![(2, EventType::Click), (2, EventType::TripleClick)]
+		);
+		// The count starts again after a triple click
+		assert_eq!(
+			click(&mut gestures, &tree, 11.0, 11.0, 600),
+			vec![(2, EventType::Click)]
+		);
+
+		// Too slow
+		assert_eq!(
+			click(&mut gestures, &tree, 11.0, 11.0, 1000),
+			vec![(2, EventType::Click)]
+		);
+		// On another component
+		let events = click(&mut gestures, &tree, 110.0, 10.0, 1100);
+		assert_eq!(events.last(), Some(&(3, EventType::Click)));
+	}
+
+	#[test]
+	fn test_enter_leave_and_hover_intent() {
+		let tree = tree();
+		let mut gestures = PointerGestures::new().with_hover_delay(300);
+		gestures.set_hover_delay(2, 1000);
+
+		let events = gestures.handle_mouse(&tree, &MouseEvent::move_event(10.0, 10.0), 0);
+		assert_eq!(
+			kinds(&events),
+			vec![(1, EventType::Enter), (2, EventType::Enter)]
+		);
+		assert!(gestures.is_hovered(1) && gestures.is_hovered(2));
+
+		// Moving restarts the delay; drifting within the slop does not
+		gestures.handle_mouse(&tree, &MouseEvent::move_event(30.0, 30.0), 200);
+		gestures.handle_mouse(&tree, &MouseEvent::move_event(31.0, 31.0), 400);
+		assert!(gestures.update(450).is_empty());
+		assert_eq!(
+			kinds(&gestures.update(500)),
+			vec![(1, EventType::HoverIntent { x: 30.0, y: 30.0 })]
+		);
+		assert_eq!(
+			kinds(&gestures.update(1200)),
+			vec![(2, EventType::HoverIntent { x: 30.0, y: 30.0 })]
+		);
+		// Reported once
+		assert!(gestures.update(5000).is_empty());
+
+		// Still inside 1, so it is not left
+		let events = gestures.handle_mouse(&tree, &MouseEvent::move_event(120.0, 10.0), 5000);
+		assert_eq!(
+			kinds(&events),
+			vec![(2, EventType::Leave), (3, EventType::Enter)]
+		);
+
+		let events = gestures.handle_mouse(&tree, &MouseEvent::leave(300.0, 10.0), 5100);
+		assert_eq!(
+			kinds(&events),
+			vec![(3, EventType::Leave), (1, EventType::Leave)]
+		);
+	}
+
+	#[test]
+	fn test_long_press() {
+		let tree = tree();
+		let mut gestures = PointerGestures::new()
+			.with_long_press_delay(600)
+			.with_hover_delay(10_000);
+
+		let down = MouseEvent::button_down(MouseButton::Left, 10.0, 10.0);
+		gestures.handle_mouse(&tree, &down, 0);
+		assert!(gestures.is_pressed());
+		assert!(gestures.update(599).is_empty());
+		assert_eq!(
+			kinds(&gestures.update(600)),
+			vec![(2, EventType::LongPress { x: 10.0, y: 10.0 })]
+		);
+		// A long press is not also a click
+		let up = MouseEvent::button_up(MouseButton::Left, 10.0, 10.0);
+		assert!(gestures.handle_mouse(&tree, &up, 700).is_empty());
+
+		// Moving away cancels it
+		gestures.handle_mouse(&tree, &down, 1000);
+		gestures.handle_mouse(&tree, &MouseEvent::move_event(20.0, 20.0), 1100);
+		assert!(gestures.update(2000).is_empty());
+	}
+
+	#[test]
+	fn test_touch() {
+		let tree = tree();
+		let mut gestures = PointerGestures::new();
+		let touch = |phase, id, x| TouchEvent::new(phase, vec![Touch::new(id, x, 10.0)]);
+
+		gestures.handle_touch(&tree, &touch(TouchPhase::Began, 1, 110.0), 0);
+		let events = gestures.handle_touch(&tree, &touch(TouchPhase::Ended, 1, 110.0), 50);
+		assert_eq!(kinds(&events), vec![(3, EventType::Click)]);
+		// Touch does not hover
+		assert!(!gestures.is_hovered(3));
+
+		// A second finger cancels the press
+		gestures.handle_touch(&tree, &touch(TouchPhase::Began, 1, 110.0), 1000);
+		gestures.handle_touch(&tree, &touch(TouchPhase::Began, 2, 10.0), 1010);
+		let events = gestures.handle_touch(&tree, &touch(TouchPhase::Ended, 1, 110.0), 1050);
+		assert!(events.is_empty());
+
+		gestures.handle_touch(&tree, &touch(TouchPhase::Began, 3, 10.0), 3000);
+		assert_eq!(
+			kinds(&gestures.update(3500)),
+			vec![(2, EventType::LongPress { x: 10.0, y: 10.0 })]
+		);
+	}
+}
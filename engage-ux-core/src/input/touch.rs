@@ -1,9 +1,10 @@
 //! Touch input handling for multi-touch devices
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Touch phase during a touch sequence
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TouchPhase {
 	/// Touch started
 	Began,
@@ -16,7 +17,7 @@ pub enum TouchPhase {
 }
 
 /// Represents a single touch point
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Touch {
 	/// Unique identifier for this touch
 	pub id: u64,
@@ -67,7 +68,7 @@ impl Touch {
 }
 
 /// Touch event containing one or more touch points
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TouchEvent {
 	/// Phase of the touch event
 	pub phase: TouchPhase,
@@ -113,7 +114,7 @@ impl TouchEvent {
 }
 
 /// Touch state tracker for managing multi-touch interactions
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct TouchState {
 	/// Currently active touches
 	active_touches: HashMap<u64, Touch>,
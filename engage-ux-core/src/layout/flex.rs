@@ -0,0 +1,1036 @@
+//! Flexbox-style layout solver
+//!
+//! Components that arrange their children return a [`FlexLayout`] from
+//! [`Component::flex_layout`]. Each child describes how it takes part in
+//! that arrangement with the [`FlexItem`] stored in its
+//! [`ComponentProperties`](crate::component::ComponentProperties).
+//! [`LayoutSolver`] walks a [`ComponentTree`], measures intrinsic content
+//! sizes and sets the resulting bounds on every child through the tree, so
+//! children that move or resize are damaged and marked stale. In a
+//! right-to-left locale, rows are mirrored so they run from right to left.
+
+use super::size::Size;
+use crate::component::{Component, ComponentId, ComponentTree, Rect, TreeError};
+use crate::media::{FontRegistry, TextDirection};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Main axis of a flex container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlexDirection {
+	/// Children are placed left to right
+	Row,
+	/// Children are placed top to bottom
+	Column,
+}
+
+impl FlexDirection {
+	/// Split a width and height into (main, cross) sizes
+	fn split(self, width: f32, height: f32) -> (f32, f32) {
+		match self {
+			FlexDirection::Row => (width, height),
+			FlexDirection::Column => (height, width),
+		}
+	}
+
+	/// Join (main, cross) sizes back into a width and height
+	fn join(self, main: f32, cross: f32) -> (f32, f32) {
+		self.split(main, cross)
+	}
+}
+
+/// Distribution of free space along the main axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JustifyContent {
+	Start,
+	End,
+	Center,
+	SpaceBetween,
+	SpaceAround,
+	SpaceEvenly,
+}
+
+/// Alignment of items along the cross axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlignItems {
+	Start,
+	End,
+	Center,
+	/// Items without an explicit cross size fill their line
+	Stretch,
+}
+
+/// Whether items may wrap onto additional lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlexWrap {
+	NoWrap,
+	Wrap,
+}
+
+/// Flex container settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlexLayout {
+	pub direction: FlexDirection,
+	/// Space between the container's edges and its children
+	pub padding: f32,
+	/// Extra space above the top padding, such as for a title bar
+	#[serde(default)]
+	pub header: f32,
+	/// Space between adjacent children and between lines
+	pub gap: f32,
+	pub justify: JustifyContent,
+	pub align: AlignItems,
+	pub wrap: FlexWrap,
+}
+
+impl FlexLayout {
+	/// Create a non-wrapping layout along `direction` with no padding or gap
+	pub fn new(direction: FlexDirection) -> Self {
+		Self {
+			direction,
+			padding: 0.0,
+			header: 0.0,
+			gap: 0.0,
+			justify: JustifyContent::Start,
+			align: AlignItems::Stretch,
+			wrap: FlexWrap::NoWrap,
+		}
+	}
+
+	/// Set padding
+	pub fn with_padding(mut self, padding: f32) -> Self {
+		self.padding = padding;
+		self
+	}
+
+	/// Set the extra space above the top padding
+	pub fn with_header(mut self, header: f32) -> Self {
+		self.header = header;
+		self
+	}
+
+	/// Set gap
+	pub fn with_gap(mut self, gap: f32) -> Self {
+		self.gap = gap;
+		self
+	}
+
+	/// Set main axis distribution
+	pub fn with_justify(mut self, justify: JustifyContent) -> Self {
+		self.justify = justify;
+		self
+	}
+
+	/// Set cross axis alignment
+	pub fn with_align(mut self, align: AlignItems) -> Self {
+		self.align = align;
+		self
+	}
+
+	/// Set wrapping
+	pub fn with_wrap(mut self, wrap: FlexWrap) -> Self {
+		self.wrap = wrap;
+		self
+	}
+}
+
+impl Default for FlexLayout {
+	fn default() -> Self {
+		Self::new(FlexDirection::Column)
+	}
+}
+
+/// How a child takes part in its parent's flex layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlexItem {
+	/// Share of positive free space this item receives
+	pub grow: f32,
+	/// Share of overflow this item gives up, weighted by its base size
+	pub shrink: f32,
+	/// Preferred width; measured from content when unset
+	pub width: Option<Size>,
+	/// Preferred height; measured from content when unset
+	pub height: Option<Size>,
+	/// Cross axis alignment overriding the container's
+	pub align_self: Option<AlignItems>,
+}
+
+impl FlexItem {
+	/// Create an item that does not grow, shrinks and is sized by content
+	pub fn new() -> Self {
+		Self {
+			grow: 0.0,
+			shrink: 1.0,
+			width: None,
+			height: None,
+			align_self: None,
+		}
+	}
+
+	/// Set grow factor
+	pub fn with_grow(mut self, grow: f32) -> Self {
+		self.grow = grow;
+		self
+	}
+
+	/// Set shrink factor
+	pub fn with_shrink(mut self, shrink: f32) -> Self {
+		self.shrink = shrink;
+		self
+	}
+
+	/// Set preferred width
+	pub fn with_width(mut self, width: Size) -> Self {
+		self.width = Some(width);
+		self
+	}
+
+	/// Set preferred height
+	pub fn with_height(mut self, height: Size) -> Self {
+		self.height = Some(height);
+		self
+	}
+
+	/// Set cross axis alignment
+	pub fn with_align_self(mut self, align: AlignItems) -> Self {
+		self.align_self = Some(align);
+		self
+	}
+}
+
+impl Default for FlexItem {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A visible child being placed within one container
+struct Item {
+	id: ComponentId,
+	grow: f32,
+	shrink: f32,
+	align: AlignItems,
+	/// Main size before free space is distributed
+	basis: f32,
+	/// Main size after free space is distributed
+	main: f32,
+	/// Cross size from an explicit size or content
+	cross: f32,
+	/// Cross size comes from an explicit size
+	cross_fixed: bool,
+	/// Cross size is `Size::Fill`
+	cross_fill: bool,
+}
+
+/// Positions children of flex and grid containers within a component tree
+#[derive(Debug, Clone)]
+pub struct LayoutSolver {
+	base_size: f32,
+	fonts: Arc<FontRegistry>,
+	direction: TextDirection,
+}
+
+impl LayoutSolver {
+	/// Create a solver with a 16px base size for relative units
+	pub fn new() -> Self {
+		Self {
+			base_size: 16.0,
+			fonts: Arc::new(FontRegistry::new()),
+			direction: TextDirection::LeftToRight,
+		}
+	}
+
+	/// Set the base size used to resolve relative units
+	pub fn with_base_size(mut self, base_size: f32) -> Self {
+		self.base_size = base_size;
+		self
+	}
+
+	/// Set the fonts components measure their text with
+	///
+	/// Without fonts, text is measured with approximate metrics.
+	pub fn with_fonts(mut self, fonts: Arc<FontRegistry>) -> Self {
+		self.fonts = fonts;
+		self
+	}
+
+	/// Set the direction of the locale, which rows are laid out in
+	///
+	/// Right-to-left rows place their first child at the right edge, so
+	/// justification and alignment are mirrored too. Use
+	/// [`TextDirection::from_locale`] to pick the direction for a locale.
+	pub fn with_direction(mut self, direction: TextDirection) -> Self {
+		self.direction = direction;
+		self
+	}
+
+	/// Get the base size used to resolve relative units
+	pub fn base_size(&self) -> f32 {
+		self.base_size
+	}
+
+	/// Get the fonts components measure their text with
+	pub fn fonts(&self) -> &FontRegistry {
+		&self.fonts
+	}
+
+	/// Get the direction rows are laid out in
+	pub fn direction(&self) -> TextDirection {
+		self.direction
+	}
+
+	/// Lay out every tree rooted at one of the tree's roots
+	///
+	/// Roots keep their current bounds.
+	pub fn layout_all(&self, tree: &mut ComponentTree) -> Result<(), TreeError> {
+		for root in tree.roots().to_vec() {
+			self.layout(tree, root)?;
+		}
+		Ok(())
+	}
+
+	/// Lay out the descendants of `id` within its current bounds
+	///
+	/// Descendants whose bounds change damage both where they were and where
+	/// they now are, and are marked stale, as with
+	/// [`ComponentTree::set_bounds`].
+	pub fn layout(&self, tree: &mut ComponentTree, id: ComponentId) -> Result<(), TreeError> {
+		let (bounds, grid, flex) = {
+			let component = read(tree, id)?;
+			if component.hides_children() {
+				return Ok(());
+			}
+			(
+				component.bounds(),
+				component.grid_layout(),
+				component.flex_layout(),
+			)
+		};
+
+		if let Some(grid) = grid {
+			self.arrange_grid(tree, id, bounds, &grid)?;
+		} else if let Some(flex) = flex {
+			self.arrange(tree, id, bounds, &flex)?;
+		}
+		for child in tree.children(id).to_vec() {
+			self.layout(tree, child)?;
+		}
+		Ok(())
+	}
+
+	/// Intrinsic size of a component's content
+	///
+	/// Flex and grid containers are measured from their visible children;
+	/// other components, and containers hiding their children, report their
+	/// own size through [`Component::measure`].
+	pub fn measure(&self, tree: &ComponentTree, id: ComponentId) -> Result<(f32, f32), TreeError> {
+		let flex = {
+			let component = read(tree, id)?;
+			if component.hides_children() {
+				return Ok(component.measure(&self.fonts));
+			}
+			if let Some(grid) = component.grid_layout() {
+				drop(component);
+				return self.measure_grid(tree, id, &grid);
+			}
+			match component.flex_layout() {
+				Some(flex) => flex,
+				None => return Ok(component.measure(&self.fonts)),
+			}
+		};
+
+		let mut main = 0.0f32;
+		let mut cross = 0.0f32;
+		let mut count = 0;
+		for &child in tree.children(id) {
+			let item = {
+				let component = read(tree, child)?;
+				if !component.is_visible() {
+					continue;
+				}
+				component.properties().flex.clone()
+			};
+
+			let (width, height) = self.measure(tree, child)?;
+			let width = self.resolve(item.width.as_ref(), 0.0, width);
+			let height = self.resolve(item.height.as_ref(), 0.0, height);
+			let (child_main, child_cross) = flex.direction.split(width, height);
+			main += child_main;
+			cross = cross.max(child_cross);
+			count += 1;
+		}
+
+		if count > 1 {
+			main += flex.gap * (count - 1) as f32;
+		}
+		let (width, height) = flex.direction.join(main, cross);
+		Ok((
+			width + flex.padding * 2.0,
+			height + flex.padding * 2.0 + flex.header,
+		))
+	}
+
+	/// Resolve a preferred size along one axis, falling back to content
+	fn resolve(&self, size: Option<&Size>, available: f32, content: f32) -> f32 {
+		match size {
+			Some(size) => size.resolve(available, content, self.base_size, self.base_size),
+			None => content,
+		}
+	}
+
+	/// Place the visible children of one container within its bounds
+	fn arrange(
+		&self,
+		tree: &mut ComponentTree,
+		id: ComponentId,
+		bounds: Rect,
+		flex: &FlexLayout,
+	) -> Result<(), TreeError> {
+		let direction = flex.direction;
+		let inner = Rect::new(
+			bounds.x + flex.padding,
+			bounds.y + flex.padding + flex.header,
+			(bounds.width - flex.padding * 2.0).max(0.0),
+			(bounds.height - flex.padding * 2.0 - flex.header).max(0.0),
+		);
+		let (inner_main, inner_cross) = direction.split(inner.width, inner.height);
+		let mirrored = direction == FlexDirection::Row && self.direction.is_rtl();
+
+		let mut items = Vec::new();
+		for &child in tree.children(id) {
+			let item = {
+				let component = read(tree, child)?;
+				if !component.is_visible() {
+					continue;
+				}
+				component.properties().flex.clone()
+			};
+
+			let (main_size, cross_size) = match direction {
+				FlexDirection::Row => (&item.width, &item.height),
+				FlexDirection::Column => (&item.height, &item.width),
+			};
+			let needs_content = !matches!(main_size, Some(Size::Fixed(_) | Size::Fill))
+				|| !matches!(cross_size, Some(Size::Fixed(_) | Size::Fill));
+			let (content_main, content_cross) = if needs_content {
+				let (width, height) = self.measure(tree, child)?;
+				direction.split(width, height)
+			} else {
+				(0.0, 0.0)
+			};
+
+			// Fill items start empty and take an equal share of free space
+			let main_fill = matches!(main_size, Some(Size::Fill));
+			let basis = if main_fill {
+				0.0
+			} else {
+				self.resolve(main_size.as_ref(), inner_main, content_main)
+			};
+			let cross_fill = matches!(cross_size, Some(Size::Fill));
+			items.push(Item {
+				id: child,
+				grow: if main_fill {
+					item.grow.max(1.0)
+				} else {
+					item.grow
+				},
+				shrink: item.shrink,
+				align: item.align_self.unwrap_or(flex.align),
+				basis,
+				main: basis,
+				cross: if cross_fill {
+					0.0
+				} else {
+					self.resolve(cross_size.as_ref(), inner_cross, content_cross)
+				},
+				cross_fixed: matches!(cross_size, Some(Size::Fixed(_))),
+				cross_fill,
+			});
+		}
+
+		let lines = break_lines(&items, flex, inner_main);
+		let single_line = lines.len() == 1;
+		let mut cross_offset = 0.0;
+
+		for range in lines {
+			let line = &mut items[range];
+			let line_cross = if single_line {
+				inner_cross
+			} else {
+				line.iter().map(|item| item.cross).fold(0.0, f32::max)
+			};
+			let free = distribute(line, flex.gap, inner_main);
+
+			let count = line.len() as f32;
+			let (mut main_offset, spacing) = match flex.justify {
+				JustifyContent::Start => (0.0, 0.0),
+				JustifyContent::End => (free, 0.0),
+				JustifyContent::Center => (free / 2.0, 0.0),
+				JustifyContent::SpaceBetween if count > 1.0 => (0.0, free / (count - 1.0)),
+				JustifyContent::SpaceBetween => (0.0, 0.0),
+				JustifyContent::SpaceAround => (free / count / 2.0, free / count),
+				JustifyContent::SpaceEvenly => (free / (count + 1.0), free / (count + 1.0)),
+			};
+
+			for item in line.iter() {
+				let cross = if item.cross_fill
+					|| (item.align == AlignItems::Stretch && !item.cross_fixed)
+				{
+					line_cross
+				} else {
+					item.cross
+				};
+				let cross_position = match item.align {
+					AlignItems::Start | AlignItems::Stretch => 0.0,
+					AlignItems::End => line_cross - cross,
+					AlignItems::Center => (line_cross - cross) / 2.0,
+				};
+
+				let (mut x, y) = direction.join(main_offset, cross_offset + cross_position);
+				let (width, height) = direction.join(item.main, cross);
+				if mirrored {
+					x = inner.width - x - width;
+				}
+				place(
+					tree,
+					item.id,
+					Rect::new(inner.x + x, inner.y + y, width, height),
+				)?;
+				main_offset += item.main + flex.gap + spacing;
+			}
+			cross_offset += line_cross + flex.gap;
+		}
+
+		Ok(())
+	}
+}
+
+impl Default for LayoutSolver {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Split items into lines, each holding at least one item
+fn break_lines(items: &[Item], flex: &FlexLayout, available: f32) -> Vec<std::ops::Range<usize>> {
+	if items.is_empty() {
+		return Vec::new();
+	}
+	let mut lines = Vec::new();
+	let mut start = 0;
+	let mut used = 0.0;
+	for (index, item) in items.iter().enumerate() {
+		let wraps = flex.wrap == FlexWrap::Wrap && used + flex.gap + item.basis > available;
+		if index > start && wraps {
+			lines.push(start..index);
+			start = index;
+			used = 0.0;
+		}
+		if index > start {
+			used += flex.gap;
+		}
+		used += item.basis;
+	}
+	lines.push(start..items.len());
+	lines
+}
+
+/// Grow or shrink a line's items to fit, returning the free space left over
+fn distribute(line: &mut [Item], gap: f32, available: f32) -> f32 {
+	let gaps = gap * line.len().saturating_sub(1) as f32;
+	let free = available - gaps - line.iter().map(|item| item.basis).sum::<f32>();
+
+	if free > 0.0 {
+		let total_grow: f32 = line.iter().map(|item| item.grow).sum();
+		if total_grow > 0.0 {
+			for item in line.iter_mut() {
+				item.main = item.basis + free * item.grow / total_grow;
+			}
+			return 0.0;
+		}
+	} else if free < 0.0 {
+		// Shrink in proportion to base size so small items keep their shape
+		let total_shrink: f32 = line.iter().map(|item| item.shrink * item.basis).sum();
+		if total_shrink > 0.0 {
+			for item in line.iter_mut() {
+				let share = item.shrink * item.basis / total_shrink;
+				item.main = (item.basis + free * share).max(0.0);
+			}
+			return 0.0;
+		}
+	}
+
+	free.max(0.0)
+}
+
+/// Lock a component for reading
+pub(super) fn read(
+	tree: &ComponentTree,
+	id: ComponentId,
+) -> Result<tokio::sync::RwLockReadGuard<'_, dyn Component>, TreeError> {
+	tree.get(id)
+		.ok_or(TreeError::NotFound(id))?
+		.try_read()
+		.map_err(|_| TreeError::ComponentLocked)
+}
+
+/// Set a component's bounds through the tree when they changed
+pub(super) fn place(
+	tree: &mut ComponentTree,
+	id: ComponentId,
+	bounds: Rect,
+) -> Result<(), TreeError> {
+	if read(tree, id)?.bounds() != bounds {
+		tree.set_bounds(id, bounds)?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::ComponentProperties;
+	use crate::layout::Unit;
+
+	struct TestBox {
+		properties: ComponentProperties,
+		flex: Option<FlexLayout>,
+		children: Vec<ComponentId>,
+		content: (f32, f32),
+	}
+
+	impl TestBox {
+		fn leaf(id: ComponentId, width: f32, height: f32) -> Self {
+			Self {
+				properties: ComponentProperties::new(id),
+				flex: None,
+				children: Vec::new(),
+				content: (width, height),
+			}
+		}
+
+		fn container(id: ComponentId, flex: FlexLayout, width: f32, height: f32) -> Self {
+			let mut container = Self::leaf(id, 0.0, 0.0);
+			container.flex = Some(flex);
+			container.properties.bounds = Rect::new(0.0, 0.0, width, height);
+			container
+		}
+
+		fn with_item(mut self, item: FlexItem) -> Self {
+			self.properties.flex = item;
+			self
+		}
+	}
+
+	impl Component for TestBox {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+
+		fn flex_layout(&self) -> Option<FlexLayout> {
+			self.flex.clone()
+		}
+
+		fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+			self.content
+		}
+	}
+
+	fn bounds(tree: &ComponentTree, id: ComponentId) -> Rect {
+		tree.get(id).unwrap().try_read().unwrap().bounds()
+	}
+
+	#[test]
+	fn test_row_with_padding_and_gap() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row)
+			.with_padding(10.0)
+			.with_gap(5.0)
+			.with_align(AlignItems::Start);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 100.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 70.0, 30.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, a), Rect::new(10.0, 10.0, 50.0, 20.0));
+		assert_eq!(bounds(&tree, b), Rect::new(65.0, 10.0, 70.0, 30.0));
+	}
+
+	#[test]
+	fn test_column_stretch() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Column).with_gap(4.0);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 200.0, 300.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 50.0, 20.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, a), Rect::new(0.0, 0.0, 200.0, 20.0));
+		assert_eq!(bounds(&tree, b), Rect::new(0.0, 24.0, 200.0, 20.0));
+	}
+
+	#[test]
+	fn test_rtl_row_is_mirrored() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row)
+			.with_padding(10.0)
+			.with_gap(5.0)
+			.with_align(AlignItems::Start);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 100.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 70.0, 30.0))
+			.unwrap();
+
+		let solver = LayoutSolver::new().with_direction(TextDirection::from_locale("ar-EG"));
+		solver.layout(&mut tree, root).unwrap();
+
+		assert!(solver.direction().is_rtl());
+		assert_eq!(bounds(&tree, a), Rect::new(240.0, 10.0, 50.0, 20.0));
+		assert_eq!(bounds(&tree, b), Rect::new(165.0, 10.0, 70.0, 30.0));
+	}
+
+	#[test]
+	fn test_rtl_column_is_unchanged() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Column).with_align(AlignItems::Start);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 200.0, 300.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 20.0))
+			.unwrap();
+
+		LayoutSolver::new()
+			.with_direction(TextDirection::RightToLeft)
+			.layout(&mut tree, root)
+			.unwrap();
+
+		assert_eq!(bounds(&tree, a), Rect::new(0.0, 0.0, 50.0, 20.0));
+	}
+
+	#[test]
+	fn test_grow_distributes_free_space() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 400.0, 50.0))
+			.unwrap();
+		let fixed = tree
+			.insert(Some(root), TestBox::leaf(2, 100.0, 50.0))
+			.unwrap();
+		let one = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(3, 0.0, 50.0).with_item(FlexItem::new().with_grow(1.0)),
+			)
+			.unwrap();
+		let two = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(4, 0.0, 50.0).with_item(FlexItem::new().with_grow(2.0)),
+			)
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, fixed).width, 100.0);
+		assert_eq!(bounds(&tree, one), Rect::new(100.0, 0.0, 100.0, 50.0));
+		assert_eq!(bounds(&tree, two), Rect::new(200.0, 0.0, 200.0, 50.0));
+	}
+
+	#[test]
+	fn test_shrink_weighted_by_basis() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 150.0, 50.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 100.0, 50.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 200.0, 50.0))
+			.unwrap();
+		let rigid = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(4, 30.0, 50.0).with_item(FlexItem::new().with_shrink(0.0)),
+			)
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		// 180px of overflow split 1:2 between the shrinkable items
+		assert_eq!(bounds(&tree, a).width, 40.0);
+		assert_eq!(bounds(&tree, b).width, 80.0);
+		assert_eq!(bounds(&tree, rigid), Rect::new(120.0, 0.0, 30.0, 50.0));
+	}
+
+	#[test]
+	fn test_justify_and_align() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row)
+			.with_justify(JustifyContent::SpaceBetween)
+			.with_align(AlignItems::Center);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 100.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 50.0, 40.0))
+			.unwrap();
+		let c = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(4, 50.0, 20.0)
+					.with_item(FlexItem::new().with_align_self(AlignItems::End)),
+			)
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, a), Rect::new(0.0, 40.0, 50.0, 20.0));
+		assert_eq!(bounds(&tree, b), Rect::new(125.0, 30.0, 50.0, 40.0));
+		assert_eq!(bounds(&tree, c), Rect::new(250.0, 80.0, 50.0, 20.0));
+	}
+
+	#[test]
+	fn test_justify_space_evenly() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Column).with_justify(JustifyContent::SpaceEvenly);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 100.0, 100.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 10.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 10.0, 20.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, a).y, 20.0);
+		assert_eq!(bounds(&tree, b).y, 60.0);
+	}
+
+	#[test]
+	fn test_wrap_onto_lines() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row)
+			.with_gap(10.0)
+			.with_wrap(FlexWrap::Wrap)
+			.with_align(AlignItems::Start);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 200.0, 200.0))
+			.unwrap();
+		let ids: Vec<_> = (2..=5)
+			.map(|id| {
+				tree.insert(Some(root), TestBox::leaf(id, 80.0, 30.0))
+					.unwrap()
+			})
+			.collect();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, ids[0]), Rect::new(0.0, 0.0, 80.0, 30.0));
+		assert_eq!(bounds(&tree, ids[1]), Rect::new(90.0, 0.0, 80.0, 30.0));
+		assert_eq!(bounds(&tree, ids[2]), Rect::new(0.0, 40.0, 80.0, 30.0));
+		assert_eq!(bounds(&tree, ids[3]), Rect::new(90.0, 40.0, 80.0, 30.0));
+	}
+
+	#[test]
+	fn test_explicit_sizes_and_fill() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row).with_align(AlignItems::Start);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 400.0, 100.0))
+			.unwrap();
+		let half = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(2, 10.0, 10.0).with_item(
+					FlexItem::new()
+						.with_width(Size::Fixed(Unit::percent(25.0)))
+						.with_height(Size::Fill),
+				),
+			)
+			.unwrap();
+		let rest = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(3, 10.0, 10.0).with_item(FlexItem::new().with_width(Size::Fill)),
+			)
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, half), Rect::new(0.0, 0.0, 100.0, 100.0));
+		assert_eq!(bounds(&tree, rest), Rect::new(100.0, 0.0, 300.0, 10.0));
+	}
+
+	#[test]
+	fn test_nested_containers_fit_content() {
+		let mut tree = ComponentTree::new();
+		let outer = FlexLayout::new(FlexDirection::Column).with_align(AlignItems::Start);
+		let inner = FlexLayout::new(FlexDirection::Row)
+			.with_padding(5.0)
+			.with_gap(10.0);
+		let root = tree
+			.insert(None, TestBox::container(1, outer, 500.0, 500.0))
+			.unwrap();
+		let row = tree
+			.insert(
+				Some(root),
+				TestBox::container(2, inner, 0.0, 0.0).with_item(
+					FlexItem::new()
+						.with_width(Size::FitContent)
+						.with_height(Size::FitContent),
+				),
+			)
+			.unwrap();
+		let a = tree
+			.insert(Some(row), TestBox::leaf(3, 40.0, 20.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(row), TestBox::leaf(4, 60.0, 30.0))
+			.unwrap();
+
+		let solver = LayoutSolver::new();
+		assert_eq!(solver.measure(&tree, row).unwrap(), (120.0, 40.0));
+		solver.layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, row), Rect::new(0.0, 0.0, 120.0, 40.0));
+		assert_eq!(bounds(&tree, a), Rect::new(5.0, 5.0, 40.0, 30.0));
+		assert_eq!(bounds(&tree, b), Rect::new(55.0, 5.0, 60.0, 30.0));
+	}
+
+	#[test]
+	fn test_hidden_children_take_no_space() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 50.0))
+			.unwrap();
+		let mut hidden = TestBox::leaf(2, 100.0, 50.0);
+		hidden.properties.visible = false;
+		tree.insert(Some(root), hidden).unwrap();
+		let shown = tree
+			.insert(Some(root), TestBox::leaf(3, 100.0, 50.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, shown).x, 0.0);
+	}
+
+	#[test]
+	fn test_layout_is_stable() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row).with_gap(10.0);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 50.0))
+			.unwrap();
+		let a = tree
+			.insert(
+				Some(root),
+				TestBox::leaf(2, 50.0, 50.0).with_item(FlexItem::new().with_grow(1.0)),
+			)
+			.unwrap();
+
+		let solver = LayoutSolver::new();
+		solver.layout(&mut tree, root).unwrap();
+		let first = bounds(&tree, a);
+		solver.layout(&mut tree, root).unwrap();
+		assert_eq!(bounds(&tree, a), first);
+		assert_eq!(first.width, 300.0);
+	}
+
+	#[test]
+	fn test_relayout_damages_moved_children() {
+		let mut tree = ComponentTree::new();
+		let flex = FlexLayout::new(FlexDirection::Row);
+		let root = tree
+			.insert(None, TestBox::container(1, flex, 300.0, 50.0))
+			.unwrap();
+		let a = tree
+			.insert(Some(root), TestBox::leaf(2, 50.0, 50.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), TestBox::leaf(3, 50.0, 50.0))
+			.unwrap();
+
+		let solver = LayoutSolver::new();
+		solver.layout(&mut tree, root).unwrap();
+		let (old_a, old_b) = (bounds(&tree, a), bounds(&tree, b));
+		tree.take_damage();
+		tree.take_stale();
+
+		// Laying out again without changes damages nothing
+		solver.layout(&mut tree, root).unwrap();
+		assert!(tree.damage().is_empty());
+		assert!(tree.take_stale().is_empty());
+
+		tree.get(a)
+			.unwrap()
+			.try_write()
+			.unwrap()
+			.properties_mut()
+			.flex = FlexItem::new().with_width(Size::Fixed(Unit::pixels(100.0)));
+		solver.layout(&mut tree, root).unwrap();
+		let (new_a, new_b) = (bounds(&tree, a), bounds(&tree, b));
+		assert_eq!(new_a, Rect::new(0.0, 0.0, 100.0, 50.0));
+		assert_eq!(new_b, Rect::new(100.0, 0.0, 50.0, 50.0));
+
+		let damage = tree.take_damage();
+		for rect in [old_a, new_a, old_b, new_b] {
+			assert!(
+				damage
+					.rects()
+					.iter()
+					.any(|damaged| damaged.contains_rect(&rect)),
+				"{rect:?} is not damaged"
+			);
+		}
+		let mut stale = tree.take_stale();
+		stale.sort();
+		assert_eq!(stale, vec![root, a, b]);
+	}
+}
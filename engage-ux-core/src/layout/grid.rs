@@ -0,0 +1,834 @@
+//! CSS-style grid layout
+//!
+//! A [`GridLayout`] divides a container into column and row tracks. Track
+//! sizes are fixed [`Unit`]s, fractions (`fr`) of the space left over, or
+//! `auto` tracks sized by their content. Children are placed with the
+//! [`GridItem`] stored in their
+//! [`ComponentProperties`](crate::component::ComponentProperties): by named
+//! template area, by explicit track, or automatically in reading order.
+
+use super::flex::{LayoutSolver, place, read};
+use super::units::Unit;
+use crate::component::{ComponentId, ComponentTree, Rect, TreeError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Size of one grid track
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrackSize {
+	/// Fixed size in any unit; percentages are of the container's inner size
+	Fixed(Unit),
+	/// Share of the space left after fixed and auto tracks
+	Fraction(f32),
+	/// Largest content size among the items in this track
+	Auto,
+}
+
+impl TrackSize {
+	/// Create a fixed pixel track
+	pub fn pixels(value: f32) -> Self {
+		TrackSize::Fixed(Unit::pixels(value))
+	}
+
+	/// Create a fractional track
+	pub fn fr(value: f32) -> Self {
+		TrackSize::Fraction(value)
+	}
+
+	/// Parse a track size from a string
+	///
+	/// Supported formats are "auto", "1fr" and any [`Unit`] such as "200px",
+	/// "2rb" or "25%".
+	pub fn parse(s: &str) -> Result<Self, String> {
+		let s = s.trim();
+
+		if s.eq_ignore_ascii_case("auto") {
+			return Ok(TrackSize::Auto);
+		}
+
+		if let Some(stripped) = s.strip_suffix("fr") {
+			let value = stripped
+				.trim()
+				.parse::<f32>()
+				.map_err(|e| format!("Invalid fr value: {}", e))?;
+			return Ok(TrackSize::Fraction(value));
+		}
+
+		Unit::parse(s).map(TrackSize::Fixed)
+	}
+
+	/// Parse a whitespace-separated list of track sizes, such as "200px 1fr 2fr"
+	pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+		s.split_whitespace().map(TrackSize::parse).collect()
+	}
+}
+
+impl fmt::Display for TrackSize {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TrackSize::Fixed(unit) => write!(f, "{}", unit),
+			TrackSize::Fraction(value) => write!(f, "{}fr", value),
+			TrackSize::Auto => write!(f, "auto"),
+		}
+	}
+}
+
+/// A rectangle of grid cells, in zero-based track indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridArea {
+	pub column: usize,
+	pub row: usize,
+	pub column_span: usize,
+	pub row_span: usize,
+}
+
+/// Grid container settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GridLayout {
+	/// Column tracks, left to right
+	pub columns: Vec<TrackSize>,
+	/// Row tracks, top to bottom; rows beyond these are `auto`
+	#[serde(default)]
+	pub rows: Vec<TrackSize>,
+	/// Space between columns
+	#[serde(default)]
+	pub column_gap: f32,
+	/// Space between rows
+	#[serde(default)]
+	pub row_gap: f32,
+	/// Space between the container's edges and the grid
+	#[serde(default)]
+	pub padding: f32,
+	/// Named areas, one string per row with one name per column
+	///
+	/// Names are separated by whitespace and "." marks an unnamed cell, as
+	/// in CSS `grid-template-areas`.
+	#[serde(default)]
+	pub areas: Vec<String>,
+}
+
+impl GridLayout {
+	/// Create a grid with the given column tracks and no explicit rows
+	pub fn new(columns: Vec<TrackSize>) -> Self {
+		Self {
+			columns,
+			rows: Vec::new(),
+			column_gap: 0.0,
+			row_gap: 0.0,
+			padding: 0.0,
+			areas: Vec::new(),
+		}
+	}
+
+	/// Set row tracks
+	pub fn with_rows(mut self, rows: Vec<TrackSize>) -> Self {
+		self.rows = rows;
+		self
+	}
+
+	/// Set the gap between both columns and rows
+	pub fn with_gap(mut self, gap: f32) -> Self {
+		self.column_gap = gap;
+		self.row_gap = gap;
+		self
+	}
+
+	/// Set the gap between columns
+	pub fn with_column_gap(mut self, gap: f32) -> Self {
+		self.column_gap = gap;
+		self
+	}
+
+	/// Set the gap between rows
+	pub fn with_row_gap(mut self, gap: f32) -> Self {
+		self.row_gap = gap;
+		self
+	}
+
+	/// Set padding
+	pub fn with_padding(mut self, padding: f32) -> Self {
+		self.padding = padding;
+		self
+	}
+
+	/// Set named template areas, one string per row
+	pub fn with_areas(mut self, areas: Vec<String>) -> Self {
+		self.areas = areas;
+		self
+	}
+
+	/// Get the cells covered by a named area
+	///
+	/// An area that appears in several cells covers the smallest rectangle
+	/// containing all of them.
+	pub fn area(&self, name: &str) -> Option<GridArea> {
+		let mut bounds: Option<(usize, usize, usize, usize)> = None;
+		for (row, line) in self.areas.iter().enumerate() {
+			for (column, cell) in line.split_whitespace().enumerate() {
+				if cell != name {
+					continue;
+				}
+				bounds = Some(match bounds {
+					None => (column, row, column, row),
+					Some((left, top, right, bottom)) => (
+						left.min(column),
+						top.min(row),
+						right.max(column),
+						bottom.max(row),
+					),
+				});
+			}
+		}
+
+		bounds.map(|(left, top, right, bottom)| GridArea {
+			column: left,
+			row: top,
+			column_span: right - left + 1,
+			row_span: bottom - top + 1,
+		})
+	}
+
+	/// Number of columns, including columns only named in the template
+	fn column_count(&self) -> usize {
+		let template = self
+			.areas
+			.iter()
+			.map(|line| line.split_whitespace().count())
+			.max()
+			.unwrap_or(0);
+		self.columns.len().max(template).max(1)
+	}
+
+	/// Size of track `index`, treating tracks beyond the list as `auto`
+	fn track(tracks: &[TrackSize], index: usize) -> TrackSize {
+		tracks.get(index).copied().unwrap_or(TrackSize::Auto)
+	}
+
+	/// Assign every item a rectangle of cells
+	///
+	/// Named areas and explicit tracks are placed first; the remaining items
+	/// fill the first free cells in reading order, adding rows as needed.
+	fn place(&self, items: &[GridItem]) -> Vec<GridArea> {
+		let columns = self.column_count();
+		let mut occupied: Vec<Vec<bool>> = Vec::new();
+		let occupy = |area: &GridArea, occupied: &mut Vec<Vec<bool>>| {
+			while occupied.len() < area.row + area.row_span {
+				occupied.push(vec![false; columns]);
+			}
+			for row in &mut occupied[area.row..area.row + area.row_span] {
+				for cell in &mut row[area.column..(area.column + area.column_span).min(columns)] {
+					*cell = true;
+				}
+			}
+		};
+
+		let mut placed: Vec<Option<GridArea>> = items
+			.iter()
+			.map(|item| {
+				let area = item.explicit_area(self, columns)?;
+				occupy(&area, &mut occupied);
+				Some(area)
+			})
+			.collect();
+
+		let mut cursor = 0;
+		for (item, slot) in items.iter().zip(placed.iter_mut()) {
+			if slot.is_some() {
+				continue;
+			}
+			let column_span = item.column_span.clamp(1, columns);
+			let row_span = item.row_span.clamp(1, MAX_ROWS);
+			let fits = |occupied: &Vec<Vec<bool>>, row: usize, column: usize| {
+				(row..row + row_span).all(|row| {
+					occupied
+						.get(row)
+						.is_none_or(|cells| !cells[column..column + column_span].contains(&true))
+				})
+			};
+
+			let mut position = cursor;
+			loop {
+				let (row, column) = (position / columns, position % columns);
+				if column + column_span <= columns && fits(&occupied, row, column) {
+					let area = GridArea {
+						column,
+						row,
+						column_span,
+						row_span,
+					};
+					occupy(&area, &mut occupied);
+					*slot = Some(area);
+					cursor = position + column_span;
+					break;
+				}
+				position += 1;
+			}
+		}
+
+		placed.into_iter().flatten().collect()
+	}
+}
+
+/// Where a child sits in its parent's grid
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GridItem {
+	/// Named template area; takes precedence over `column` and `row`
+	#[serde(default)]
+	pub area: Option<String>,
+	/// Zero-based column; placed automatically when unset
+	#[serde(default)]
+	pub column: Option<usize>,
+	/// Zero-based row; placed automatically when unset
+	#[serde(default)]
+	pub row: Option<usize>,
+	/// Number of columns covered
+	#[serde(default = "default_span")]
+	pub column_span: usize,
+	/// Number of rows covered
+	#[serde(default = "default_span")]
+	pub row_span: usize,
+}
+
+fn default_span() -> usize {
+	1
+}
+
+/// Rows an explicitly placed item may start at or cover
+///
+/// Items are often read from definitions, so larger rows and spans are
+/// clamped rather than growing the grid without limit.
+const MAX_ROWS: usize = 1024;
+
+impl GridItem {
+	/// Create an automatically placed item covering one cell
+	pub fn new() -> Self {
+		Self {
+			area: None,
+			column: None,
+			row: None,
+			column_span: 1,
+			row_span: 1,
+		}
+	}
+
+	/// Place the item in a named template area
+	pub fn in_area(area: impl Into<String>) -> Self {
+		Self {
+			area: Some(area.into()),
+			..Self::new()
+		}
+	}
+
+	/// Place the item at a zero-based column and row
+	pub fn at(column: usize, row: usize) -> Self {
+		Self {
+			column: Some(column),
+			row: Some(row),
+			..Self::new()
+		}
+	}
+
+	/// Set the number of columns and rows covered
+	pub fn with_span(mut self, column_span: usize, row_span: usize) -> Self {
+		self.column_span = column_span;
+		self.row_span = row_span;
+		self
+	}
+
+	/// Cells given by a named area or explicit tracks, if any
+	///
+	/// An item with only a column or only a row starts at track 0 on the
+	/// other axis. Rows are kept within the first [`MAX_ROWS`].
+	fn explicit_area(&self, grid: &GridLayout, columns: usize) -> Option<GridArea> {
+		if let Some(area) = self.area.as_deref().and_then(|name| grid.area(name)) {
+			return Some(area);
+		}
+		if self.column.is_none() && self.row.is_none() {
+			return None;
+		}
+
+		let column = self.column.unwrap_or(0).min(columns - 1);
+		let row = self.row.unwrap_or(0).min(MAX_ROWS - 1);
+		Some(GridArea {
+			column,
+			row,
+			column_span: self.column_span.clamp(1, columns - column),
+			row_span: self.row_span.clamp(1, MAX_ROWS - row),
+		})
+	}
+}
+
+impl Default for GridItem {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Resolve the pixel size of each track along one axis
+///
+/// `content` holds, per track, the largest content size of the items that
+/// cover only that track.
+fn size_tracks(
+	tracks: &[TrackSize],
+	count: usize,
+	content: &[f32],
+	available: f32,
+	gap: f32,
+	base_size: f32,
+) -> Vec<f32> {
+	let mut sizes = vec![0.0; count];
+	let mut fractions = 0.0;
+	for (index, size) in sizes.iter_mut().enumerate() {
+		match GridLayout::track(tracks, index) {
+			TrackSize::Fixed(unit) => *size = unit.to_pixels(available, base_size, base_size),
+			TrackSize::Auto => *size = content[index],
+			TrackSize::Fraction(fr) => fractions += fr.max(0.0),
+		}
+	}
+
+	if fractions > 0.0 {
+		let used: f32 = sizes.iter().sum();
+		let gaps = gap * count.saturating_sub(1) as f32;
+		let free = (available - used - gaps).max(0.0);
+		for (index, size) in sizes.iter_mut().enumerate() {
+			if let TrackSize::Fraction(fr) = GridLayout::track(tracks, index) {
+				*size = free * fr.max(0.0) / fractions;
+			}
+		}
+	}
+
+	sizes
+}
+
+/// Start offset of each track plus the offset past the last one
+fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+	let mut offsets = Vec::with_capacity(sizes.len() + 1);
+	let mut offset = 0.0;
+	for size in sizes {
+		offsets.push(offset);
+		offset += size + gap;
+	}
+	offsets.push(offset);
+	offsets
+}
+
+/// Visible children of a grid container with their placement and content size
+struct Placed {
+	ids: Vec<ComponentId>,
+	areas: Vec<GridArea>,
+	content: Vec<(f32, f32)>,
+	columns: usize,
+	rows: usize,
+}
+
+impl LayoutSolver {
+	/// Place and measure the visible children of a grid container
+	fn place_grid(
+		&self,
+		tree: &ComponentTree,
+		id: ComponentId,
+		grid: &GridLayout,
+	) -> Result<Placed, TreeError> {
+		let mut ids = Vec::new();
+		let mut items = Vec::new();
+		for &child in tree.children(id) {
+			let component = read(tree, child)?;
+			if component.is_visible() {
+				ids.push(child);
+				items.push(component.properties().grid.clone());
+			}
+		}
+
+		let areas = grid.place(&items);
+		let content = ids
+			.iter()
+			.map(|&child| self.measure(tree, child))
+			.collect::<Result<Vec<_>, _>>()?;
+		let rows = areas
+			.iter()
+			.map(|area| area.row + area.row_span)
+			.max()
+			.unwrap_or(0)
+			.max(grid.rows.len())
+			.max(grid.areas.len());
+
+		Ok(Placed {
+			ids,
+			areas,
+			content,
+			columns: grid.column_count(),
+			rows,
+		})
+	}
+
+	/// Largest content size per track of items covering a single track
+	fn track_content(placed: &Placed) -> (Vec<f32>, Vec<f32>) {
+		let mut columns = vec![0.0f32; placed.columns];
+		let mut rows = vec![0.0f32; placed.rows];
+		for (area, &(width, height)) in placed.areas.iter().zip(&placed.content) {
+			if area.column_span == 1 {
+				columns[area.column] = columns[area.column].max(width);
+			}
+			if area.row_span == 1 {
+				rows[area.row] = rows[area.row].max(height);
+			}
+		}
+		(columns, rows)
+	}
+
+	/// Intrinsic size of a grid container
+	///
+	/// Fractional tracks contribute their content size, as `auto` tracks do.
+	pub(super) fn measure_grid(
+		&self,
+		tree: &ComponentTree,
+		id: ComponentId,
+		grid: &GridLayout,
+	) -> Result<(f32, f32), TreeError> {
+		let placed = self.place_grid(tree, id, grid)?;
+		let (column_content, row_content) = Self::track_content(&placed);
+
+		let total = |tracks: &[TrackSize], content: &[f32], gap: f32| {
+			let sizes: f32 = content
+				.iter()
+				.enumerate()
+				.map(|(index, &content)| match GridLayout::track(tracks, index) {
+					TrackSize::Fixed(unit) => {
+						unit.to_pixels(0.0, self.base_size(), self.base_size())
+					}
+					TrackSize::Fraction(_) | TrackSize::Auto => content,
+				})
+				.sum();
+			sizes + gap * content.len().saturating_sub(1) as f32
+		};
+
+		Ok((
+			total(&grid.columns, &column_content, grid.column_gap) + grid.padding * 2.0,
+			total(&grid.rows, &row_content, grid.row_gap) + grid.padding * 2.0,
+		))
+	}
+
+	/// Place the visible children of a grid container within its bounds
+	pub(super) fn arrange_grid(
+		&self,
+		tree: &mut ComponentTree,
+		id: ComponentId,
+		bounds: Rect,
+		grid: &GridLayout,
+	) -> Result<(), TreeError> {
+		let inner = Rect::new(
+			bounds.x + grid.padding,
+			bounds.y + grid.padding,
+			(bounds.width - grid.padding * 2.0).max(0.0),
+			(bounds.height - grid.padding * 2.0).max(0.0),
+		);
+		let placed = self.place_grid(tree, id, grid)?;
+		let (column_content, row_content) = Self::track_content(&placed);
+
+		let column_sizes = size_tracks(
+			&grid.columns,
+			placed.columns,
+			&column_content,
+			inner.width,
+			grid.column_gap,
+			self.base_size(),
+		);
+		let row_sizes = size_tracks(
+			&grid.rows,
+			placed.rows,
+			&row_content,
+			inner.height,
+			grid.row_gap,
+			self.base_size(),
+		);
+		let columns = track_offsets(&column_sizes, grid.column_gap);
+		let rows = track_offsets(&row_sizes, grid.row_gap);
+
+		for (&child, area) in placed.ids.iter().zip(&placed.areas) {
+			let last_column = area.column + area.column_span;
+			let last_row = area.row + area.row_span;
+			let rect = Rect::new(
+				inner.x + columns[area.column],
+				inner.y + rows[area.row],
+				columns[last_column] - columns[area.column] - grid.column_gap,
+				rows[last_row] - rows[area.row] - grid.row_gap,
+			);
+			place(tree, child, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::{Component, ComponentProperties};
+	use crate::media::FontRegistry;
+
+	struct Cell {
+		properties: ComponentProperties,
+		grid: Option<GridLayout>,
+		children: Vec<ComponentId>,
+		content: (f32, f32),
+	}
+
+	impl Cell {
+		fn new(id: ComponentId, item: GridItem, width: f32, height: f32) -> Self {
+			let mut properties = ComponentProperties::new(id);
+			properties.grid = item;
+			Self {
+				properties,
+				grid: None,
+				children: Vec::new(),
+				content: (width, height),
+			}
+		}
+
+		fn grid(id: ComponentId, grid: GridLayout, width: f32, height: f32) -> Self {
+			let mut cell = Self::new(id, GridItem::new(), 0.0, 0.0);
+			cell.grid = Some(grid);
+			cell.properties.bounds = Rect::new(0.0, 0.0, width, height);
+			cell
+		}
+	}
+
+	impl Component for Cell {
+		fn id(&self) -> ComponentId {
+			self.properties.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.properties
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.properties
+		}
+
+		fn children(&self) -> &[ComponentId] {
+			&self.children
+		}
+
+		fn set_children(&mut self, children: Vec<ComponentId>) {
+			self.children = children;
+		}
+
+		fn grid_layout(&self) -> Option<GridLayout> {
+			self.grid.clone()
+		}
+
+		fn measure(&self, _fonts: &FontRegistry) -> (f32, f32) {
+			self.content
+		}
+	}
+
+	fn bounds(tree: &ComponentTree, id: ComponentId) -> Rect {
+		tree.get(id).unwrap().try_read().unwrap().bounds()
+	}
+
+	#[test]
+	fn test_track_size_parse() {
+		assert_eq!(TrackSize::parse("auto").unwrap(), TrackSize::Auto);
+		assert_eq!(TrackSize::parse("2fr").unwrap(), TrackSize::fr(2.0));
+		assert_eq!(TrackSize::parse("200px").unwrap(), TrackSize::pixels(200.0));
+		assert_eq!(
+			TrackSize::parse("25%").unwrap(),
+			TrackSize::Fixed(Unit::percent(25.0))
+		);
+		assert!(TrackSize::parse("xfr").is_err());
+		assert_eq!(
+			TrackSize::parse_list("200px 1fr auto").unwrap(),
+			vec![
+				TrackSize::pixels(200.0),
+				TrackSize::fr(1.0),
+				TrackSize::Auto
+			]
+		);
+		assert_eq!(TrackSize::fr(1.5).to_string(), "1.5fr");
+	}
+
+	#[test]
+	fn test_named_area() {
+		let grid = GridLayout::new(TrackSize::parse_list("1fr 3fr").unwrap()).with_areas(vec![
+			"header header".into(),
+			"sidebar main".into(),
+			"sidebar .".into(),
+		]);
+
+		assert_eq!(
+			grid.area("header"),
+			Some(GridArea {
+				column: 0,
+				row: 0,
+				column_span: 2,
+				row_span: 1
+			})
+		);
+		assert_eq!(
+			grid.area("sidebar"),
+			Some(GridArea {
+				column: 0,
+				row: 1,
+				column_span: 1,
+				row_span: 2
+			})
+		);
+		assert_eq!(grid.area("footer"), None);
+	}
+
+	#[test]
+	fn test_fixed_and_fraction_tracks() {
+		let mut tree = ComponentTree::new();
+		let grid = GridLayout::new(TrackSize::parse_list("100px 1fr 2fr").unwrap())
+			.with_rows(TrackSize::parse_list("50px").unwrap())
+			.with_gap(10.0);
+		let root = tree
+			.insert(None, Cell::grid(1, grid, 420.0, 200.0))
+			.unwrap();
+		let ids: Vec<_> = (2..=4)
+			.map(|id| {
+				tree.insert(Some(root), Cell::new(id, GridItem::new(), 0.0, 0.0))
+					.unwrap()
+			})
+			.collect();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		// 420 - 100 - 2 * 10 = 300 shared 1:2
+		assert_eq!(bounds(&tree, ids[0]), Rect::new(0.0, 0.0, 100.0, 50.0));
+		assert_eq!(bounds(&tree, ids[1]), Rect::new(110.0, 0.0, 100.0, 50.0));
+		assert_eq!(bounds(&tree, ids[2]), Rect::new(220.0, 0.0, 200.0, 50.0));
+	}
+
+	#[test]
+	fn test_auto_placement_with_spans_and_implicit_rows() {
+		let mut tree = ComponentTree::new();
+		let grid = GridLayout::new(vec![TrackSize::pixels(50.0); 3]).with_padding(5.0);
+		let root = tree
+			.insert(None, Cell::grid(1, grid, 300.0, 300.0))
+			.unwrap();
+		let wide = tree
+			.insert(
+				Some(root),
+				Cell::new(2, GridItem::new().with_span(2, 1), 0.0, 20.0),
+			)
+			.unwrap();
+		let a = tree
+			.insert(Some(root), Cell::new(3, GridItem::new(), 0.0, 30.0))
+			.unwrap();
+		let b = tree
+			.insert(Some(root), Cell::new(4, GridItem::new(), 0.0, 40.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		// Implicit rows are auto-sized to their tallest single-row item
+		assert_eq!(bounds(&tree, wide), Rect::new(5.0, 5.0, 100.0, 30.0));
+		assert_eq!(bounds(&tree, a), Rect::new(105.0, 5.0, 50.0, 30.0));
+		assert_eq!(bounds(&tree, b), Rect::new(5.0, 35.0, 50.0, 40.0));
+	}
+
+	#[test]
+	fn test_template_areas_and_explicit_placement() {
+		let mut tree = ComponentTree::new();
+		let grid = GridLayout::new(TrackSize::parse_list("200px 1fr").unwrap())
+			.with_rows(TrackSize::parse_list("60px 1fr 40px").unwrap())
+			.with_areas(vec![
+				"header header".into(),
+				"sidebar main".into(),
+				"footer footer".into(),
+			]);
+		let root = tree
+			.insert(None, Cell::grid(1, grid, 800.0, 600.0))
+			.unwrap();
+		let main = tree
+			.insert(
+				Some(root),
+				Cell::new(2, GridItem::in_area("main"), 0.0, 0.0),
+			)
+			.unwrap();
+		let header = tree
+			.insert(
+				Some(root),
+				Cell::new(3, GridItem::in_area("header"), 0.0, 0.0),
+			)
+			.unwrap();
+		let footer = tree
+			.insert(
+				Some(root),
+				Cell::new(4, GridItem::at(0, 2).with_span(2, 1), 0.0, 0.0),
+			)
+			.unwrap();
+		let sidebar = tree
+			.insert(Some(root), Cell::new(5, GridItem::new(), 0.0, 0.0))
+			.unwrap();
+
+		LayoutSolver::new().layout(&mut tree, root).unwrap();
+
+		assert_eq!(bounds(&tree, header), Rect::new(0.0, 0.0, 800.0, 60.0));
+		assert_eq!(bounds(&tree, main), Rect::new(200.0, 60.0, 600.0, 500.0));
+		assert_eq!(bounds(&tree, footer), Rect::new(0.0, 560.0, 800.0, 40.0));
+		// The only free cell left is the sidebar
+		assert_eq!(bounds(&tree, sidebar), Rect::new(0.0, 60.0, 200.0, 500.0));
+	}
+
+	#[test]
+	fn test_measure_grid() {
+		let mut tree = ComponentTree::new();
+		let grid = GridLayout::new(TrackSize::parse_list("auto 1fr").unwrap()).with_gap(10.0);
+		let root = tree.insert(None, Cell::grid(1, grid, 0.0, 0.0)).unwrap();
+		tree.insert(Some(root), Cell::new(2, GridItem::new(), 40.0, 20.0))
+			.unwrap();
+		tree.insert(Some(root), Cell::new(3, GridItem::new(), 60.0, 30.0))
+			.unwrap();
+		tree.insert(Some(root), Cell::new(4, GridItem::new(), 50.0, 10.0))
+			.unwrap();
+
+		let size = LayoutSolver::new().measure(&tree, root).unwrap();
+
+		// Columns 50 + 60 and rows 30 + 10, each with one 10px gap
+		assert_eq!(size, (120.0, 50.0));
+	}
+
+	#[test]
+	fn test_placement_clamps_rows() {
+		let grid = GridLayout::new(vec![TrackSize::Auto; 2]);
+		let items: Vec<GridItem> = serde_json::from_str(&format!(
+			r#"[{{ "row": {max}, "row_span": {max} }}, {{ "row_span": {max} }}]"#,
+			max = usize::MAX
+		))
+		.unwrap();
+
+		let areas = grid.place(&items);
+		assert_eq!(areas[0].row, MAX_ROWS - 1);
+		assert_eq!(areas[0].row_span, 1);
+		assert_eq!(areas[1].row, 0);
+		assert_eq!(areas[1].row_span, MAX_ROWS);
+	}
+
+	#[test]
+	fn test_grid_serialization() {
+		let json = r#"{
+			"columns": [{ "Fixed": 240.0 }, { "Fraction": 1.0 }],
+			"column_gap": 8.0,
+			"areas": ["nav main"]
+		}"#;
+		let grid: GridLayout = serde_json::from_str(json).unwrap();
+
+		assert_eq!(
+			grid.columns,
+			vec![TrackSize::pixels(240.0), TrackSize::fr(1.0)]
+		);
+		assert_eq!(grid.column_gap, 8.0);
+		assert!(grid.rows.is_empty());
+		assert_eq!(grid.area("main").unwrap().column, 1);
+
+		let round_trip: GridLayout =
+			serde_json::from_str(&serde_json::to_string(&grid).unwrap()).unwrap();
+		assert_eq!(round_trip, grid);
+
+		let item: GridItem = serde_json::from_str(r#"{ "area": "nav" }"#).unwrap();
+		assert_eq!(item, GridItem::in_area("nav"));
+	}
+}
@@ -5,15 +5,23 @@
 //! - Absolute and relative positioning
 //! - Size constraints (min/max)
 //! - Fill sizing mode
+//! - Flexbox-style arrangement of children
+//! - CSS-style grids with tracks, spans and named areas
 
 pub mod constraints;
+pub mod flex;
+pub mod grid;
 pub mod position;
 pub mod size;
 pub mod units;
 
 pub use constraints::Constraints;
+pub use flex::{
+	AlignItems, FlexDirection, FlexItem, FlexLayout, FlexWrap, JustifyContent, LayoutSolver,
+};
+pub use grid::{GridArea, GridItem, GridLayout, TrackSize};
 pub use position::{Position, PositionMode};
-pub use size::{Size, SizeMode};
+pub use size::{Axis, Size, SizeMode};
 pub use units::{RelativeUnit, Unit};
 
 use serde::{Deserialize, Serialize};
@@ -21,13 +29,13 @@ use serde::{Deserialize, Serialize};
 /// Complete layout specification for a component
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Layout {
-	/// Horizontal position
+	/// Offset of the left edge
 	pub left: Option<Unit>,
-	/// Vertical position
+	/// Offset of the top edge
 	pub top: Option<Unit>,
-	/// Right edge position (alternative to left + width)
+	/// Offset of the right edge, used when `left` is unset
 	pub right: Option<Unit>,
-	/// Bottom edge position (alternative to top + height)
+	/// Offset of the bottom edge, used when `top` is unset
 	pub bottom: Option<Unit>,
 	/// Width specification
 	pub width: Option<Size>,
@@ -43,6 +51,12 @@ pub struct Layout {
 	pub max_height: Option<Unit>,
 	/// Position mode (absolute or relative)
 	pub position_mode: PositionMode,
+	/// Grid that arranges the component's children
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub grid: Option<GridLayout>,
+	/// Placement of the component within its parent's grid
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub grid_item: Option<GridItem>,
 }
 
 impl Layout {
@@ -60,6 +74,8 @@ impl Layout {
 			min_height: None,
 			max_height: None,
 			position_mode: PositionMode::Relative,
+			grid: None,
+			grid_item: None,
 		}
 	}
 
@@ -129,7 +145,29 @@ impl Layout {
 		self
 	}
 
+	/// Arrange children in a grid
+	pub fn with_grid(mut self, grid: GridLayout) -> Self {
+		self.grid = Some(grid);
+		self
+	}
+
+	/// Set placement within the parent's grid
+	pub fn with_grid_item(mut self, item: GridItem) -> Self {
+		self.grid_item = Some(item);
+		self
+	}
+
 	/// Calculate actual pixel values from layout specification
+	///
+	/// The result is relative to the parent's origin. Widths and horizontal
+	/// offsets resolve against `parent_width`, heights and vertical offsets
+	/// against `parent_height`.
+	///
+	/// In either mode a pair of opposite edges sizes the component when no
+	/// width or height is given. With [`PositionMode::Absolute`] a `right`
+	/// or `bottom` edge on its own anchors the component to the parent's far
+	/// edge. With [`PositionMode::Relative`] it shifts the component from its
+	/// position in normal flow (the origin) instead, moving it left or up.
 	pub fn calculate_bounds(
 		&self,
 		parent_width: f32,
@@ -137,63 +175,68 @@ impl Layout {
 		base_size: f32,
 		inherited_size: f32,
 	) -> CalculatedBounds {
+		let horizontal = |unit: &Unit| unit.to_pixels(parent_width, base_size, inherited_size);
+		let vertical = |unit: &Unit| unit.to_pixels(parent_height, base_size, inherited_size);
+		let absolute = self.position_mode == PositionMode::Absolute;
 		let mut bounds = CalculatedBounds::default();
 
 		// Calculate width
 		if let Some(width) = &self.width {
-			bounds.width = width.calculate(parent_width, parent_height, base_size, inherited_size);
+			bounds.width = width.calculate_along(
+				Axis::Horizontal,
+				parent_width,
+				parent_height,
+				base_size,
+				inherited_size,
+			);
 		} else if let (Some(left), Some(right)) = (&self.left, &self.right) {
 			// Width from left and right
-			let left_px = left.to_pixels(parent_width, base_size, inherited_size);
-			let right_px = right.to_pixels(parent_width, base_size, inherited_size);
-			bounds.width = parent_width - left_px - right_px;
-		} else {
-			bounds.width = 0.0;
+			bounds.width = parent_width - horizontal(left) - horizontal(right);
 		}
 
 		// Calculate height
 		if let Some(height) = &self.height {
-			bounds.height =
-				height.calculate(parent_width, parent_height, base_size, inherited_size);
+			bounds.height = height.calculate_along(
+				Axis::Vertical,
+				parent_width,
+				parent_height,
+				base_size,
+				inherited_size,
+			);
 		} else if let (Some(top), Some(bottom)) = (&self.top, &self.bottom) {
 			// Height from top and bottom
-			let top_px = top.to_pixels(parent_height, base_size, inherited_size);
-			let bottom_px = bottom.to_pixels(parent_height, base_size, inherited_size);
-			bounds.height = parent_height - top_px - bottom_px;
-		} else {
-			bounds.height = 0.0;
+			bounds.height = parent_height - vertical(top) - vertical(bottom);
 		}
 
 		// Apply constraints
 		if let Some(min_width) = &self.min_width {
-			let min_px = min_width.to_pixels(parent_width, base_size, inherited_size);
-			bounds.width = bounds.width.max(min_px);
+			bounds.width = bounds.width.max(horizontal(min_width));
 		}
 		if let Some(max_width) = &self.max_width {
-			let max_px = max_width.to_pixels(parent_width, base_size, inherited_size);
-			bounds.width = bounds.width.min(max_px);
+			bounds.width = bounds.width.min(horizontal(max_width));
 		}
 		if let Some(min_height) = &self.min_height {
-			let min_px = min_height.to_pixels(parent_height, base_size, inherited_size);
-			bounds.height = bounds.height.max(min_px);
+			bounds.height = bounds.height.max(vertical(min_height));
 		}
 		if let Some(max_height) = &self.max_height {
-			let max_px = max_height.to_pixels(parent_height, base_size, inherited_size);
-			bounds.height = bounds.height.min(max_px);
+			bounds.height = bounds.height.min(vertical(max_height));
 		}
-
-		// Calculate position
-		bounds.x = self
-			.left
-			.as_ref()
-			.map(|u| u.to_pixels(parent_width, base_size, inherited_size))
-			.unwrap_or(0.0);
-
-		bounds.y = self
-			.top
-			.as_ref()
-			.map(|u| u.to_pixels(parent_height, base_size, inherited_size))
-			.unwrap_or(0.0);
+		bounds.width = bounds.width.max(0.0);
+		bounds.height = bounds.height.max(0.0);
+
+		// Calculate position; left and top win over right and bottom
+		bounds.x = match (&self.left, &self.right) {
+			(Some(left), _) => horizontal(left),
+			(None, Some(right)) if absolute => parent_width - horizontal(right) - bounds.width,
+			(None, Some(right)) => -horizontal(right),
+			(None, None) => 0.0,
+		};
+		bounds.y = match (&self.top, &self.bottom) {
+			(Some(top), _) => vertical(top),
+			(None, Some(bottom)) if absolute => parent_height - vertical(bottom) - bounds.height,
+			(None, Some(bottom)) => -vertical(bottom),
+			(None, None) => 0.0,
+		};
 
 		bounds
 	}
@@ -300,9 +343,9 @@ mod tests {
 
 		let bounds = layout.calculate_bounds(800.0, 600.0, 16.0, 16.0);
 
-		// 50% of max(800, 600) = 400, 25% = 200
+		// 50% of the width (800) = 400, 25% of the height (600) = 150
 		assert_eq!(bounds.width, 400.0);
-		assert_eq!(bounds.height, 200.0);
+		assert_eq!(bounds.height, 150.0);
 	}
 
 	#[test]
@@ -335,4 +378,64 @@ mod tests {
 		assert_eq!(bounds.x, 10.0);
 		assert_eq!(bounds.y, 20.0);
 	}
+
+	#[test]
+	fn test_calculate_bounds_fill_per_axis() {
+		let layout = Layout::new().with_width(Size::Fill).with_height(Size::Fill);
+
+		let bounds = layout.calculate_bounds(800.0, 600.0, 16.0, 16.0);
+
+		assert_eq!(bounds.width, 800.0);
+		assert_eq!(bounds.height, 600.0);
+	}
+
+	#[test]
+	fn test_calculate_bounds_anchored_right_bottom() {
+		let layout = Layout::new()
+			.with_right(Unit::Pixels(10.0))
+			.with_bottom(Unit::percent(10.0))
+			.with_width(Size::Fixed(Unit::Pixels(100.0)))
+			.with_height(Size::Fixed(Unit::Pixels(50.0)))
+			.with_position_mode(PositionMode::Absolute);
+
+		let bounds = layout.calculate_bounds(800.0, 600.0, 16.0, 16.0);
+
+		// x = 800 - 10 - 100, y = 600 - 60 - 50
+		assert_eq!(bounds.x, 690.0);
+		assert_eq!(bounds.y, 490.0);
+	}
+
+	#[test]
+	fn test_calculate_bounds_from_edges_absolute() {
+		let layout = Layout::new()
+			.with_left(Unit::Pixels(10.0))
+			.with_right(Unit::percent(10.0))
+			.with_top(Unit::Pixels(20.0))
+			.with_bottom(Unit::percent(10.0))
+			.with_position_mode(PositionMode::Absolute);
+
+		let bounds = layout.calculate_bounds(800.0, 600.0, 16.0, 16.0);
+
+		// Width = 800 - 10 - 80, height = 600 - 20 - 60
+		assert_eq!(bounds.width, 710.0);
+		assert_eq!(bounds.height, 520.0);
+		assert_eq!(bounds.x, 10.0);
+		assert_eq!(bounds.y, 20.0);
+	}
+
+	#[test]
+	fn test_calculate_bounds_relative_offsets() {
+		let layout = Layout::new()
+			.with_right(Unit::Pixels(10.0))
+			.with_bottom(Unit::Pixels(20.0))
+			.with_width(Size::Fixed(Unit::Pixels(100.0)));
+
+		let bounds = layout.calculate_bounds(800.0, 600.0, 16.0, 16.0);
+
+		// A lone relative edge shifts the component without sizing it
+		assert_eq!(bounds.x, -10.0);
+		assert_eq!(bounds.y, -20.0);
+		assert_eq!(bounds.width, 100.0);
+		assert_eq!(bounds.height, 0.0);
+	}
 }
@@ -3,6 +3,25 @@
 use super::units::Unit;
 use serde::{Deserialize, Serialize};
 
+/// Axis a size or offset is measured along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+	/// Widths and horizontal offsets
+	Horizontal,
+	/// Heights and vertical offsets
+	Vertical,
+}
+
+impl Axis {
+	/// Pick the parent dimension that lies along this axis
+	pub fn select(self, width: f32, height: f32) -> f32 {
+		match self {
+			Axis::Horizontal => width,
+			Axis::Vertical => height,
+		}
+	}
+}
+
 /// Size specification for width or height
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Size {
@@ -16,22 +35,60 @@ pub enum Size {
 
 impl Size {
 	/// Calculate the actual pixel size
+	///
+	/// Percentages and `Fill` resolve against the larger parent dimension,
+	/// whichever axis the size is for.
+	#[deprecated(
+		since = "0.1.0-alpha.1",
+		note = "resolves against the larger parent dimension; use `Size::calculate_along`"
+	)]
 	pub fn calculate(
 		&self,
 		parent_width: f32,
 		parent_height: f32,
 		base_size: f32,
 		inherited_size: f32,
+	) -> f32 {
+		let parent_dim = parent_width.max(parent_height);
+		self.resolve(parent_dim, 0.0, base_size, inherited_size)
+	}
+
+	/// Calculate the actual pixel size along `axis`
+	///
+	/// Percentages and `Fill` resolve against the parent's width for the
+	/// horizontal axis and its height for the vertical axis. `FitContent`
+	/// needs the content size; see [`Size::resolve`].
+	pub fn calculate_along(
+		&self,
+		axis: Axis,
+		parent_width: f32,
+		parent_height: f32,
+		base_size: f32,
+		inherited_size: f32,
+	) -> f32 {
+		self.resolve(
+			axis.select(parent_width, parent_height),
+			0.0,
+			base_size,
+			inherited_size,
+		)
+	}
+
+	/// Resolve the size along one axis
+	///
+	/// `available` is the parent's size along the same axis and `content` is
+	/// the measured size of the component's content, used for `FitContent`.
+	pub fn resolve(
+		&self,
+		available: f32,
+		content: f32,
+		base_size: f32,
+		inherited_size: f32,
 	) -> f32 {
 		match self {
-			Size::Fixed(unit) => {
-				// For fixed sizes, we use the larger parent dimension
-				// to handle both width and height calculations
-				let parent_dim = parent_width.max(parent_height);
-				unit.to_pixels(parent_dim, base_size, inherited_size)
-			}
-			Size::Fill => parent_width.max(parent_height),
-			Size::FitContent => 0.0, // Would be calculated based on content
+			Size::Fixed(unit) => unit.to_pixels(available, base_size, inherited_size),
+			Size::Fill => available,
+			Size::FitContent => content,
 		}
 	}
 }
@@ -52,12 +109,14 @@ mod tests {
 	use super::*;
 
 	#[test]
+	#[allow(deprecated)]
 	fn test_fixed_size() {
 		let size = Size::Fixed(Unit::pixels(100.0));
 		assert_eq!(size.calculate(800.0, 600.0, 16.0, 16.0), 100.0);
 	}
 
 	#[test]
+	#[allow(deprecated)]
 	fn test_fill_size() {
 		let size = Size::Fill;
 		// Fill returns the larger of width/height
@@ -66,6 +125,7 @@ mod tests {
 	}
 
 	#[test]
+	#[allow(deprecated)]
 	fn test_fit_content_size() {
 		let size = Size::FitContent;
 		// FitContent returns 0 as placeholder (would be calculated from content)
@@ -73,9 +133,54 @@ mod tests {
 	}
 
 	#[test]
+	#[allow(deprecated)]
 	fn test_fixed_with_relative_units() {
 		let size = Size::Fixed(Unit::percent(50.0));
 		// 50% of max(800, 600) = 400
 		assert_eq!(size.calculate(800.0, 600.0, 16.0, 16.0), 400.0);
 	}
+
+	#[test]
+	fn test_calculate_along_axis() {
+		let half = Size::Fixed(Unit::percent(50.0));
+		// 50% of the width (800) or of the height (600)
+		assert_eq!(
+			half.calculate_along(Axis::Horizontal, 800.0, 600.0, 16.0, 16.0),
+			400.0
+		);
+		assert_eq!(
+			half.calculate_along(Axis::Vertical, 800.0, 600.0, 16.0, 16.0),
+			300.0
+		);
+
+		// Fill takes the parent dimension along the same axis
+		assert_eq!(
+			Size::Fill.calculate_along(Axis::Vertical, 400.0, 900.0, 16.0, 16.0),
+			900.0
+		);
+		assert_eq!(
+			Size::Fill.calculate_along(Axis::Vertical, 800.0, 600.0, 16.0, 16.0),
+			600.0
+		);
+		assert_eq!(
+			Size::Fixed(Unit::pixels(100.0)).calculate_along(
+				Axis::Vertical,
+				800.0,
+				600.0,
+				16.0,
+				16.0
+			),
+			100.0
+		);
+	}
+
+	#[test]
+	fn test_resolve_along_axis() {
+		assert_eq!(
+			Size::Fixed(Unit::percent(50.0)).resolve(300.0, 40.0, 16.0, 16.0),
+			150.0
+		);
+		assert_eq!(Size::Fill.resolve(300.0, 40.0, 16.0, 16.0), 300.0);
+		assert_eq!(Size::FitContent.resolve(300.0, 40.0, 16.0, 16.0), 40.0);
+	}
 }
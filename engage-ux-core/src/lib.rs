@@ -3,7 +3,7 @@
 //! This crate provides the core functionality for Engage UX including:
 //! - Color system (RGB and HSL)
 //! - Event handling with Tokio
-//! - Component traits
+//! - Component traits and the retained component tree
 //! - Thread-safe primitives
 //! - Input handling (keyboard, mouse, touch)
 //! - Accessibility support
@@ -24,13 +24,13 @@ pub mod rendering;
 
 pub use animation::{Animation, AnimationController, AnimationState, Easing};
 pub use color::{Color, ColorSpace};
-pub use component::Component;
+pub use component::{Component, ComponentTree};
 pub use drag_drop::{DragData, DragEvent, DragManager, DragOperation, DragSource, DropTarget};
 pub use events::{Event, EventHandler};
 pub use input::{InputEvent, InputHandler};
 pub use layout::{
-	CalculatedBounds, Constraints, Layout, Position, PositionMode, RelativeUnit, Size, SizeMode,
-	Unit,
+	Axis, CalculatedBounds, Constraints, Layout, Position, PositionMode, RelativeUnit, Size,
+	SizeMode, Unit,
 };
 pub use rendering::{RenderError, SvgDocument, SvgParser};
 
@@ -39,6 +39,5 @@ mod tests {
 	#[test]
 	fn core_modules_exist() {
 		// Basic smoke test to ensure modules compile
-		assert!(true);
 	}
 }
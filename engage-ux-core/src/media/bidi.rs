@@ -0,0 +1,439 @@
+//! Bidirectional text
+//!
+//! Lines mixing left-to-right and right-to-left scripts are split into runs
+//! with the Unicode Bidirectional Algorithm (UAX #9) and drawn in visual
+//! order. Carets move through such lines visually, the way the arrow keys
+//! move them in a text editor.
+
+use super::font::{Font, cluster_boundaries};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use unicode_bidi::{BidiClass, BidiInfo, bidi_class};
+
+/// Languages written right to left, as BCP 47 primary language subtags
+const RTL_LANGUAGES: &[&str] = &[
+	"ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi",
+];
+
+/// Scripts written right to left, as BCP 47 script subtags
+const RTL_SCRIPTS: &[&str] = &[
+	"adlm", "arab", "hebr", "mand", "nkoo", "rohg", "samr", "syrc", "thaa",
+];
+
+/// Direction of text, and of layouts that follow the reading direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TextDirection {
+	/// Left to right, as in Latin scripts
+	#[default]
+	LeftToRight,
+	/// Right to left, as in Arabic and Hebrew
+	RightToLeft,
+}
+
+impl TextDirection {
+	/// Check whether this direction is right to left
+	pub fn is_rtl(self) -> bool {
+		self == TextDirection::RightToLeft
+	}
+
+	/// Reading direction of a locale, from its BCP 47 language tag
+	///
+	/// A script subtag decides when present ("az-Arab"); otherwise the
+	/// language does ("ar-EG", "he").
+	pub fn from_locale(locale: &str) -> Self {
+		let mut subtags = locale.split(['-', '_']).map(str::to_ascii_lowercase);
+		let language = subtags.next().unwrap_or_default();
+		let script = subtags
+			.find(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()));
+
+		let rtl = match script {
+			Some(script) => RTL_SCRIPTS.contains(&script.as_str()),
+			None => RTL_LANGUAGES.contains(&language.as_str()),
+		};
+		if rtl {
+			TextDirection::RightToLeft
+		} else {
+			TextDirection::LeftToRight
+		}
+	}
+
+	/// Direction of a paragraph from its first strong character
+	///
+	/// Returns `None` when the text has no strong characters, such as digits
+	/// and punctuation only.
+	pub fn of_text(text: &str) -> Option<Self> {
+		text.chars().find_map(|c| match bidi_class(c) {
+			BidiClass::L => Some(TextDirection::LeftToRight),
+			BidiClass::R | BidiClass::AL => Some(TextDirection::RightToLeft),
+			_ => None,
+		})
+	}
+}
+
+/// A run of text at one embedding level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidiRun {
+	/// Byte range of the run in the text
+	pub range: Range<usize>,
+	/// Direction the run's characters are drawn in
+	pub direction: TextDirection,
+}
+
+/// Split a line of text into runs in visual order, from left to right
+///
+/// Each paragraph takes its direction from its first strong character.
+/// Characters within a right-to-left run are drawn from its end to its
+/// start.
+pub fn visual_runs(text: &str) -> Vec<BidiRun> {
+	if text.is_empty() {
+		return Vec::new();
+	}
+	if !has_rtl(text) {
+		return vec![BidiRun {
+			range: 0..text.len(),
+			direction: TextDirection::LeftToRight,
+		}];
+	}
+
+	let info = BidiInfo::new(text, None);
+	let mut runs = Vec::new();
+	for paragraph in &info.paragraphs {
+		let (levels, level_runs) = info.visual_runs(paragraph, paragraph.range.clone());
+		runs.extend(level_runs.into_iter().map(|range| BidiRun {
+			direction: if levels[range.start].is_rtl() {
+				TextDirection::RightToLeft
+			} else {
+				TextDirection::LeftToRight
+			},
+			range,
+		}));
+	}
+	runs
+}
+
+/// Check whether text contains right-to-left characters
+fn has_rtl(text: &str) -> bool {
+	text.chars().any(|c| {
+		matches!(
+			bidi_class(c),
+			BidiClass::R
+				| BidiClass::AL
+				| BidiClass::AN
+				| BidiClass::RLE
+				| BidiClass::RLO
+				| BidiClass::RLI
+		)
+	})
+}
+
+/// Direction the caret moves in on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaretMovement {
+	/// One character to the left
+	Left,
+	/// One character to the right
+	Right,
+}
+
+/// A character cluster in visual order
+struct VisualCluster {
+	range: Range<usize>,
+	rtl: bool,
+	/// Index of the cluster's run in visual order
+	run: usize,
+}
+
+impl VisualCluster {
+	/// Caret offset at the cluster's left edge
+	fn left(&self) -> usize {
+		if self.rtl {
+			self.range.end
+		} else {
+			self.range.start
+		}
+	}
+
+	/// Caret offset at the cluster's right edge
+	fn right(&self) -> usize {
+		if self.rtl {
+			self.range.start
+		} else {
+			self.range.end
+		}
+	}
+}
+
+/// Character clusters of a line in visual order
+fn visual_clusters(text: &str, runs: &[BidiRun]) -> Vec<VisualCluster> {
+	let mut clusters = Vec::new();
+	for (index, run) in runs.iter().enumerate() {
+		let start = clusters.len();
+		let mut from = run.range.start;
+		for boundary in cluster_boundaries(&text[run.range.clone()]) {
+			let to = run.range.start + boundary;
+			clusters.push(VisualCluster {
+				range: from..to,
+				rtl: run.direction.is_rtl(),
+				run: index,
+			});
+			from = to;
+		}
+		if run.direction.is_rtl() {
+			clusters[start..].reverse();
+		}
+	}
+	clusters
+}
+
+/// Visual position, counted in cluster edges from the left, where a caret
+/// at `caret` is drawn
+///
+/// A caret follows the character before it, so at the boundary between
+/// runs it stays beside the text just typed.
+fn caret_position(clusters: &[VisualCluster], caret: usize) -> usize {
+	let trailing = clusters
+		.iter()
+		.position(|cluster| cluster.range.end == caret && caret > 0);
+	if let Some(index) = trailing {
+		return if clusters[index].rtl {
+			index
+		} else {
+			index + 1
+		};
+	}
+	match clusters
+		.iter()
+		.position(|cluster| cluster.range.start == caret)
+	{
+		Some(index) if clusters[index].rtl => index + 1,
+		Some(index) => index,
+		None => 0,
+	}
+}
+
+/// Caret offset clamped to the end of `text` and moved back to the start
+/// of the character it falls in
+///
+/// Use it on carets that may not fit the text, such as ones restored from
+/// saved state.
+pub fn clamp_caret(text: &str, caret: usize) -> usize {
+	let mut caret = caret.min(text.len());
+	while !text.is_char_boundary(caret) {
+		caret -= 1;
+	}
+	caret
+}
+
+/// Caret offset one cluster to the left or right on screen
+///
+/// `caret` is a byte offset into a single line of text. Returns `None` when
+/// the caret is already at that edge of the line.
+pub fn move_caret(text: &str, caret: usize, movement: CaretMovement) -> Option<usize> {
+	let clusters = visual_clusters(text, &visual_runs(text));
+	let position = caret_position(&clusters, caret);
+
+	// Each visual position lies between two clusters; take the offset that
+	// is drawn there
+	let candidates = |position: usize| {
+		let before = position.checked_sub(1).map(|index| clusters[index].right());
+		let after = clusters.get(position).map(VisualCluster::left);
+		match movement {
+			CaretMovement::Right => [after, before],
+			CaretMovement::Left => [before, after],
+		}
+	};
+	let positions: Box<dyn Iterator<Item = usize>> = match movement {
+		CaretMovement::Right => Box::new(position + 1..=clusters.len()),
+		CaretMovement::Left => Box::new((0..position).rev()),
+	};
+
+	for target in positions {
+		let offset = candidates(target)
+			.into_iter()
+			.flatten()
+			.find(|&offset| caret_position(&clusters, offset) == target);
+		if offset.is_some() {
+			return offset;
+		}
+	}
+	None
+}
+
+/// Distance from the left edge of a line to where a caret is drawn
+///
+/// `caret` is a byte offset into the line, measured with `font`.
+pub fn caret_x(font: &Font, text: &str, caret: usize, font_size: f32) -> f32 {
+	let runs = visual_runs(text);
+	let clusters = visual_clusters(text, &runs);
+	let Some(cluster) = clusters.get(caret_position(&clusters, caret)) else {
+		return font.measure(text, font_size);
+	};
+
+	// Runs are measured whole so shaping across clusters is kept
+	let run = &runs[cluster.run];
+	let before: f32 = runs[..cluster.run]
+		.iter()
+		.map(|run| font.measure(&text[run.range.clone()], font_size))
+		.sum();
+	let within = if cluster.rtl {
+		font.measure(&text[run.range.clone()], font_size)
+			- font.measure(&text[run.range.start..cluster.range.end], font_size)
+	} else {
+		font.measure(&text[run.range.start..cluster.range.start], font_size)
+	};
+	before + within
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::media::font::FontFamily;
+
+	const ALEF: &str = "\u{05D0}";
+	const BET: &str = "\u{05D1}";
+	const GIMEL: &str = "\u{05D2}";
+
+	fn texts<'a>(text: &'a str, runs: &[BidiRun]) -> Vec<(&'a str, TextDirection)> {
+		runs.iter()
+			.map(|run| (&text[run.range.clone()], run.direction))
+			.collect()
+	}
+
+	#[test]
+	fn test_direction_from_locale() {
+		assert_eq!(
+			TextDirection::from_locale("en-US"),
+			TextDirection::LeftToRight
+		);
+		assert_eq!(
+			TextDirection::from_locale("ar-EG"),
+			TextDirection::RightToLeft
+		);
+		assert_eq!(TextDirection::from_locale("he"), TextDirection::RightToLeft);
+		assert_eq!(
+			TextDirection::from_locale("fa_IR"),
+			TextDirection::RightToLeft
+		);
+		assert_eq!(
+			TextDirection::from_locale("az-Arab"),
+			TextDirection::RightToLeft
+		);
+		assert_eq!(
+			TextDirection::from_locale("ur-Latn"),
+			TextDirection::LeftToRight
+		);
+		assert_eq!(TextDirection::from_locale(""), TextDirection::LeftToRight);
+	}
+
+	#[test]
+	fn test_direction_of_text() {
+		let hebrew = format!("123 {ALEF}{BET}");
+		assert_eq!(
+			TextDirection::of_text(&hebrew),
+			Some(TextDirection::RightToLeft)
+		);
+		assert_eq!(
+			TextDirection::of_text("abc"),
+			Some(TextDirection::LeftToRight)
+		);
+		assert_eq!(TextDirection::of_text("12 + 3"), None);
+	}
+
+	#[test]
+	fn test_visual_runs() {
+		assert_eq!(
+			visual_runs("plain"),
+			vec![BidiRun {
+				range: 0..5,
+				direction: TextDirection::LeftToRight
+			}]
+		);
+		assert!(visual_runs("").is_empty());
+
+		// Left-to-right paragraph with an embedded right-to-left word
+		let text = format!("abc {ALEF}{BET} def");
+		assert_eq!(
+			texts(&text, &visual_runs(&text)),
+			vec![
+				("abc ", TextDirection::LeftToRight),
+				(&text[4..8], TextDirection::RightToLeft),
+				(" def", TextDirection::LeftToRight),
+			]
+		);
+
+		// Right-to-left paragraph: the English word is drawn leftmost
+		let text = format!("{ALEF}{BET} abc");
+		assert_eq!(
+			texts(&text, &visual_runs(&text)),
+			vec![
+				("abc", TextDirection::LeftToRight),
+				(&text[..5], TextDirection::RightToLeft),
+			]
+		);
+	}
+
+	/// Every caret offset visited moving across a line from one edge
+	fn walk(text: &str, start: usize, movement: CaretMovement) -> Vec<usize> {
+		let mut carets = vec![start];
+		while let Some(next) = move_caret(text, *carets.last().unwrap(), movement) {
+			carets.push(next);
+			assert!(carets.len() <= text.len() + 2, "caret loops");
+		}
+		carets
+	}
+
+	#[test]
+	fn test_move_caret_ltr() {
+		assert_eq!(walk("abc", 0, CaretMovement::Right), vec![0, 1, 2, 3]);
+		assert_eq!(walk("abc", 3, CaretMovement::Left), vec![3, 2, 1, 0]);
+		// Combining marks move with their base character
+		assert_eq!(walk("e\u{0301}x", 0, CaretMovement::Right), vec![0, 3, 4]);
+	}
+
+	#[test]
+	fn test_move_caret_rtl() {
+		let text = format!("{ALEF}{BET}{GIMEL}");
+		// In right-to-left text the left arrow moves forward
+		assert_eq!(walk(&text, 0, CaretMovement::Left), vec![0, 2, 4, 6]);
+		assert_eq!(walk(&text, 6, CaretMovement::Right), vec![6, 4, 2, 0]);
+	}
+
+	#[test]
+	fn test_move_caret_mixed() {
+		// Visually: a b ␠ GIMEL BET ALEF
+		let text = format!("ab {ALEF}{BET}{GIMEL}");
+		let right = walk(&text, 0, CaretMovement::Right);
+		assert_eq!(right, vec![0, 1, 2, 9, 7, 5]);
+		let left = walk(&text, 5, CaretMovement::Left);
+		assert_eq!(left, vec![5, 7, 3, 2, 1, 0]);
+
+		// Every offset is reachable
+		let mut visited: Vec<usize> = right.into_iter().chain(left).collect();
+		visited.sort_unstable();
+		visited.dedup();
+		assert_eq!(visited, vec![0, 1, 2, 3, 5, 7, 9]);
+	}
+
+	#[test]
+	fn test_clamp_caret() {
+		let text = format!("a{ALEF}");
+		assert_eq!(clamp_caret(&text, 1), 1);
+		assert_eq!(clamp_caret(&text, 2), 1);
+		assert_eq!(clamp_caret(&text, 100), 3);
+		assert_eq!(clamp_caret("", 5), 0);
+	}
+
+	#[test]
+	fn test_caret_x() {
+		let font = Font::new(FontFamily::new("Approximate"), 10.0);
+		let text = format!("ab {ALEF}{BET}");
+
+		assert_eq!(caret_x(&font, "abc", 0, 10.0), 0.0);
+		assert_eq!(caret_x(&font, "abc", 2, 10.0), 12.0);
+		assert_eq!(caret_x(&font, "abc", 3, 10.0), 18.0);
+		// After ALEF the caret sits to its left, at the end of the line
+		assert_eq!(caret_x(&font, &text, 5, 10.0), 24.0);
+		// The end of the right-to-left word is drawn right after the space
+		assert_eq!(caret_x(&font, &text, 7, 10.0), 18.0);
+	}
+}
@@ -1,7 +1,32 @@
-//! Font loading and management system
+//! Font loading, text metrics and line breaking
+//!
+//! Fonts loaded from data measure text with their real advances, kerning and
+//! vertical metrics; fonts without data fall back to approximate metrics so
+//! layout still works before any font file is available.
 
 use super::MediaError;
+use super::bidi::TextDirection;
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_linebreak::BreakOpportunity;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Average advance of a glyph, as a fraction of the font size, for fonts
+/// without data
+const FALLBACK_ADVANCE_RATIO: f32 = 0.6;
+
+/// Ascent, descent and line gap, as fractions of the font size, for fonts
+/// without data
+const FALLBACK_METRICS: FontMetrics = FontMetrics {
+	ascent: 0.8,
+	descent: 0.2,
+	line_gap: 0.2,
+};
+
+/// Character appended to truncated text
+pub const ELLIPSIS: char = '\u{2026}';
 
 /// Font weight
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -101,6 +126,152 @@ impl FontFamily {
 	}
 }
 
+/// Vertical metrics of a font at a particular size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontMetrics {
+	/// Distance from the baseline up to the top of the tallest glyphs
+	pub ascent: f32,
+	/// Distance from the baseline down to the bottom of the lowest glyphs
+	pub descent: f32,
+	/// Extra space the font recommends between lines
+	pub line_gap: f32,
+}
+
+impl FontMetrics {
+	/// Distance between the baselines of consecutive lines
+	pub fn line_height(&self) -> f32 {
+		self.ascent + self.descent + self.line_gap
+	}
+
+	/// Scale every metric by a factor
+	fn scale(self, factor: f32) -> Self {
+		Self {
+			ascent: self.ascent * factor,
+			descent: self.descent * factor,
+			line_gap: self.line_gap * factor,
+		}
+	}
+}
+
+/// A single line of laid-out text
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+	/// Text of the line, without trailing whitespace or line breaks
+	pub text: String,
+	/// Advance width of the line
+	pub width: f32,
+}
+
+/// Text broken into lines, with the metrics used to stack them
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+	/// Lines from top to bottom; empty text has one empty line
+	pub lines: Vec<TextLine>,
+	/// Vertical metrics of the font at the laid-out size
+	pub metrics: FontMetrics,
+	/// Whether text was cut off and ended with an ellipsis
+	pub truncated: bool,
+}
+
+impl TextLayout {
+	/// Width of the widest line
+	pub fn width(&self) -> f32 {
+		self.lines.iter().map(|line| line.width).fold(0.0, f32::max)
+	}
+
+	/// Height of all lines
+	pub fn height(&self) -> f32 {
+		self.lines.len() as f32 * self.metrics.line_height()
+	}
+
+	/// (width, height) of the laid-out text
+	pub fn size(&self) -> (f32, f32) {
+		(self.width(), self.height())
+	}
+
+	/// Baseline of line `index`, measured from the top of the first line
+	pub fn baseline(&self, index: usize) -> f32 {
+		index as f32 * self.metrics.line_height()
+			+ self.metrics.line_gap / 2.0
+			+ self.metrics.ascent
+	}
+}
+
+/// A run of text drawn with one font of a fallback chain
+#[derive(Debug, Clone)]
+pub struct FontRun<'a> {
+	/// Byte range of the run in the text
+	pub range: Range<usize>,
+	/// Font drawing the run
+	pub font: &'a Font,
+	/// Position of the font in the chain, 0 for the primary font
+	pub index: usize,
+}
+
+/// Color bitmap of a glyph, such as an emoji, scaled to a font size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorGlyph {
+	/// Offset of the left edge from the pen position
+	pub left: i32,
+	/// Offset of the top edge from the baseline, negative above it
+	pub top: i32,
+	/// Width in pixels
+	pub width: u32,
+	/// Height in pixels
+	pub height: u32,
+	/// Straight (not premultiplied) RGBA pixels, row by row
+	pub pixels: Vec<u8>,
+}
+
+/// A glyph positioned by [`Font::shape`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapedGlyph {
+	/// Glyph index in the font
+	pub glyph: u16,
+	/// Byte offset in the text of the first character the glyph draws
+	pub cluster: usize,
+	/// Horizontal advance to the next glyph
+	pub advance: f32,
+	/// Horizontal offset from the pen position
+	pub x_offset: f32,
+	/// Vertical offset from the baseline, upwards
+	pub y_offset: f32,
+}
+
+/// Shaping face borrowed from the font data
+type ShapingFace<'a> = rustybuzz::Face<'a>;
+
+self_cell::self_cell!(
+	/// Font file data with the OpenType face parsed from it, so text is
+	/// shaped without parsing the font again
+	struct Shaper {
+		owner: Arc<[u8]>,
+		#[covariant]
+		dependent: ShapingFace,
+	}
+);
+
+/// Parsed font data, shared between clones of a [`Font`]
+#[derive(Clone)]
+struct FontFace {
+	outlines: Arc<fontdue::Font>,
+	/// Font file data and its shaping tables, also read for color bitmaps
+	shaper: Arc<Shaper>,
+	/// The face has color bitmap glyphs (sbix, CBDT)
+	has_bitmaps: bool,
+	/// Face index within a collection
+	index: u32,
+}
+
+impl fmt::Debug for FontFace {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("FontFace")
+			.field("has_bitmaps", &self.has_bitmaps)
+			.field("index", &self.index)
+			.finish()
+	}
+}
+
 /// Font representation
 #[derive(Debug, Clone)]
 pub struct Font {
@@ -112,8 +283,14 @@ pub struct Font {
 	pub style: FontStyle,
 	/// Font size
 	pub size: f32,
-	/// Font data (if loaded from file)
-	pub data: Option<Vec<u8>>,
+	/// Face name within the family, such as "Bold Italic" (if loaded from file)
+	pub subfamily: Option<String>,
+	/// Font data (if loaded from file), shared with the parsed face
+	pub data: Option<Arc<[u8]>>,
+	/// Parsed font data, used for metrics and rasterization
+	face: Option<FontFace>,
+	/// Fonts drawing the characters this font has no glyph for
+	fallbacks: Vec<Font>,
 }
 
 impl Font {
@@ -124,7 +301,10 @@ impl Font {
 			weight: FontWeight::Normal,
 			style: FontStyle::Normal,
 			size,
+			subfamily: None,
 			data: None,
+			face: None,
+			fallbacks: Vec::new(),
 		}
 	}
 
@@ -135,7 +315,10 @@ impl Font {
 			weight,
 			style,
 			size,
+			subfamily: None,
 			data: None,
+			face: None,
+			fallbacks: Vec::new(),
 		}
 	}
 
@@ -148,29 +331,762 @@ impl Font {
 	}
 
 	/// Load font from bytes
+	///
+	/// Family, weight and style are read from the font's name and OS/2
+	/// tables. For a collection (.ttc) the first face is loaded; use
+	/// [`Font::load_collection`] to load every face.
 	pub fn load_from_bytes(data: Vec<u8>, size: f32) -> Result<Self, MediaError> {
 		// Basic validation
 		if data.is_empty() {
 			return Err(MediaError::InvalidData("Empty font data".to_string()));
 		}
 
-		// Validate font data with fontdue
-		let font_settings = fontdue::FontSettings::default();
-		let _parsed_font = fontdue::Font::from_bytes(data.as_slice(), font_settings)
+		Self::load_face(data.into(), 0, size)
+	}
+
+	/// Load every face of a font collection (.ttc)
+	///
+	/// A single font file is treated as a collection of one face. The faces
+	/// share the collection data.
+	pub fn load_collection(data: Vec<u8>, size: f32) -> Result<Vec<Self>, MediaError> {
+		if data.is_empty() {
+			return Err(MediaError::InvalidData("Empty font data".to_string()));
+		}
+
+		let data: Arc<[u8]> = data.into();
+		let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+		(0..count)
+			.map(|index| Self::load_face(data.clone(), index, size))
+			.collect()
+	}
+
+	/// Parse one face of a font file or collection
+	fn load_face(data: Arc<[u8]>, index: u32, size: f32) -> Result<Self, MediaError> {
+		let face = ttf_parser::Face::parse(&data, index)
 			.map_err(|e| MediaError::InvalidData(format!("Invalid font data: {}", e)))?;
 
-		// Use default family name since fontdue doesn't expose it directly
-		let family_name = "Loaded Font".to_string();
+		let family = face_name(
+			&face,
+			&[
+				ttf_parser::name_id::TYPOGRAPHIC_FAMILY,
+				ttf_parser::name_id::FAMILY,
+			],
+		)
+		.ok_or_else(|| MediaError::InvalidData("Font has no family name".to_string()))?;
+		let subfamily = face_name(
+			&face,
+			&[
+				ttf_parser::name_id::TYPOGRAPHIC_SUBFAMILY,
+				ttf_parser::name_id::SUBFAMILY,
+			],
+		);
+
+		// Fonts without an OS/2 table only describe their face in the
+		// subfamily name, such as "Bold Italic"
+		let (weight, style) = if face.tables().os2.is_some() {
+			let style = match face.style() {
+				ttf_parser::Style::Normal => FontStyle::Normal,
+				ttf_parser::Style::Italic => FontStyle::Italic,
+				ttf_parser::Style::Oblique => FontStyle::Oblique,
+			};
+			(FontWeight::from_value(face.weight().to_number()), style)
+		} else {
+			subfamily
+				.as_deref()
+				.map_or((FontWeight::Normal, FontStyle::Normal), parse_subfamily)
+		};
+
+		let tables = face.tables();
+		let has_bitmaps = tables.sbix.is_some() || tables.cbdt.is_some();
+
+		let font_settings = fontdue::FontSettings {
+			collection_index: index,
+			..fontdue::FontSettings::default()
+		};
+		let parsed = fontdue::Font::from_bytes(&*data, font_settings)
+			.map_err(|e| MediaError::InvalidData(format!("Invalid font data: {}", e)))?;
+		let shaper = Shaper::try_new(data.clone(), |data| {
+			rustybuzz::Face::from_slice(data, index).ok_or_else(|| {
+				MediaError::InvalidData("Font has no usable OpenType tables".to_string())
+			})
+		})?;
 
-		// Create font with data
 		Ok(Self {
-			family: FontFamily::new(family_name),
-			weight: FontWeight::Normal,
-			style: FontStyle::Normal,
+			family: FontFamily::new(family),
+			weight,
+			style,
 			size,
+			subfamily,
+			face: Some(FontFace {
+				outlines: Arc::new(parsed),
+				shaper: Arc::new(shaper),
+				has_bitmaps,
+				index,
+			}),
 			data: Some(data),
+			fallbacks: Vec::new(),
 		})
 	}
+
+	/// Check whether font data has been loaded and parsed
+	///
+	/// Fonts without data measure text with approximate metrics.
+	pub fn is_loaded(&self) -> bool {
+		self.face.is_some()
+	}
+
+	/// Parsed font data, for rasterizing glyphs
+	pub(crate) fn face(&self) -> Option<&fontdue::Font> {
+		self.face.as_ref().map(|face| face.outlines.as_ref())
+	}
+
+	/// Copy of this font without its raw data, sharing the parsed face
+	pub(crate) fn without_data(&self) -> Self {
+		Self {
+			family: self.family.clone(),
+			weight: self.weight,
+			style: self.style,
+			size: self.size,
+			subfamily: self.subfamily.clone(),
+			data: None,
+			face: self.face.clone(),
+			fallbacks: self.fallbacks.clone(),
+		}
+	}
+
+	/// Fall back to `fonts`, in order, for characters this font has no glyph for
+	///
+	/// Fonts without data are skipped, as are the fallbacks of each font.
+	pub fn with_fallback_fonts(mut self, fonts: impl IntoIterator<Item = Font>) -> Self {
+		self.fallbacks = fonts
+			.into_iter()
+			.filter(Font::is_loaded)
+			.map(|font| Self {
+				data: None,
+				fallbacks: Vec::new(),
+				..font
+			})
+			.collect();
+		self
+	}
+
+	/// Fonts used for characters this font has no glyph for
+	pub fn fallback_fonts(&self) -> &[Font] {
+		&self.fallbacks
+	}
+
+	/// Check whether this font has a glyph for a character
+	///
+	/// Fonts without data have no glyphs.
+	pub fn has_glyph(&self, c: char) -> bool {
+		self.face()
+			.is_some_and(|face| face.lookup_glyph_index(c) != 0)
+	}
+
+	/// Check whether this font has color bitmap glyphs, such as emoji
+	pub fn has_color_glyphs(&self) -> bool {
+		self.face.as_ref().is_some_and(|face| face.has_bitmaps)
+	}
+
+	/// Font of the fallback chain that draws a character
+	///
+	/// This font is used when it has a glyph for `c` or when no fallback
+	/// does; otherwise the first fallback with a glyph.
+	pub fn font_for(&self, c: char) -> &Font {
+		self.font_at(self.font_index(c))
+	}
+
+	/// Font at a position of the fallback chain, 0 for this font
+	pub fn font_at(&self, index: usize) -> &Font {
+		index
+			.checked_sub(1)
+			.and_then(|index| self.fallbacks.get(index))
+			.unwrap_or(self)
+	}
+
+	/// Position in the fallback chain of the font that draws a character
+	fn font_index(&self, c: char) -> usize {
+		if self.fallbacks.is_empty() || self.has_glyph(c) {
+			return 0;
+		}
+		self.fallbacks
+			.iter()
+			.position(|font| font.has_glyph(c))
+			.map_or(0, |index| index + 1)
+	}
+
+	/// Split text into runs drawn by the same font of the fallback chain
+	///
+	/// Combining marks, joiners, variation selectors and whitespace stay
+	/// with the font of the character before them.
+	pub fn runs(&self, text: &str) -> Vec<FontRun<'_>> {
+		let mut runs: Vec<FontRun<'_>> = Vec::new();
+		for (start, cluster) in text.grapheme_indices(true) {
+			let end = start + cluster.len();
+			let c = cluster.chars().next().unwrap_or_default();
+			let index = match runs.last() {
+				Some(run) if c.is_whitespace() => run.index,
+				_ => self.font_index(c),
+			};
+			match runs.last_mut() {
+				Some(run) if run.index == index => run.range.end = end,
+				_ => runs.push(FontRun {
+					range: start..end,
+					font: self.font_at(index),
+					index,
+				}),
+			}
+		}
+		runs
+	}
+
+	/// Color bitmap for a character, if its font has one
+	///
+	/// Bitmaps come from the font's sbix or CBDT table, using the strike
+	/// closest to the font size and scaled to it.
+	pub fn color_glyph(&self, c: char, font_size: f32) -> Option<ColorGlyph> {
+		let font = self.font_for(c);
+		let index = font.face()?.lookup_glyph_index(c);
+		font.color_glyph_indexed(index, font_size)
+	}
+
+	/// Color bitmap for a glyph of this font, ignoring fallbacks
+	pub(crate) fn color_glyph_indexed(&self, index: u16, font_size: f32) -> Option<ColorGlyph> {
+		let face = self.face.as_ref()?;
+		if !face.has_bitmaps {
+			return None;
+		}
+		let parsed = ttf_parser::Face::parse(face.shaper.borrow_owner(), face.index).ok()?;
+		let pixels_per_em = font_size.ceil().clamp(1.0, u16::MAX as f32) as u16;
+		let raster = parsed.glyph_raster_image(ttf_parser::GlyphId(index), pixels_per_em)?;
+		let bitmap = decode_raster(&raster)?;
+
+		let scale = font_size / raster.pixels_per_em.max(1) as f32;
+		let width = ((bitmap.width() as f32 * scale).round() as u32).max(1);
+		let height = ((bitmap.height() as f32 * scale).round() as u32).max(1);
+		let bitmap = if bitmap.dimensions() == (width, height) {
+			bitmap
+		} else {
+			image::imageops::resize(
+				&bitmap,
+				width,
+				height,
+				image::imageops::FilterType::Triangle,
+			)
+		};
+
+		Some(ColorGlyph {
+			left: (raster.x as f32 * scale).round() as i32,
+			top: -((raster.y as f32 * scale).round() as i32) - height as i32,
+			width,
+			height,
+			pixels: bitmap.into_raw(),
+		})
+	}
+
+	/// Vertical metrics at a font size
+	pub fn metrics(&self, font_size: f32) -> FontMetrics {
+		self.face()
+			.and_then(|face| face.horizontal_line_metrics(font_size))
+			.map(|metrics| FontMetrics {
+				ascent: metrics.ascent,
+				descent: -metrics.descent,
+				line_gap: metrics.line_gap,
+			})
+			.unwrap_or_else(|| FALLBACK_METRICS.scale(font_size))
+	}
+
+	/// Advance width of a single character, from the font that draws it
+	pub fn advance(&self, c: char, font_size: f32) -> f32 {
+		self.font_for(c).glyph_advance(c, false, font_size)
+	}
+
+	/// Kerning adjustment between two adjacent characters
+	///
+	/// Characters drawn by different fonts of the fallback chain are not
+	/// kerned.
+	pub fn kerning(&self, left: char, right: char, font_size: f32) -> f32 {
+		let font = self.font_for(left);
+		if !std::ptr::eq(font, self.font_for(right)) {
+			return 0.0;
+		}
+		font.glyph_kerning(left, right, font_size)
+	}
+
+	/// Advance width of a single line of text, including kerning
+	///
+	/// Each run of the text is measured with the font that draws it.
+	pub fn measure(&self, text: &str, font_size: f32) -> f32 {
+		if self.fallbacks.is_empty() {
+			return self.measure_run(text, font_size);
+		}
+		self.runs(text)
+			.iter()
+			.map(|run| run.font.measure_run(&text[run.range.clone()], font_size))
+			.sum()
+	}
+
+	/// Shape a run of text with this font, ignoring fallbacks
+	///
+	/// Right-to-left runs and text that needs it, such as Arabic, the Indic
+	/// scripts and combining marks, are shaped with the font's OpenType
+	/// tables: ligatures, contextual forms, mark positioning and mirrored
+	/// brackets. Other text maps each character to its glyph and applies
+	/// kerning. Glyphs are returned in visual order, from left to right.
+	pub fn shape(&self, text: &str, font_size: f32, direction: TextDirection) -> Vec<ShapedGlyph> {
+		if (direction.is_rtl() || needs_shaping(text))
+			&& let Some(glyphs) = self.shape_opentype(text, font_size, direction)
+		{
+			return glyphs;
+		}
+
+		let face = self.face();
+		let mut glyphs: Vec<ShapedGlyph> = Vec::new();
+		for (cluster, c, extends) in cluster_chars(text) {
+			let glyph = face.map_or(0, |face| face.lookup_glyph_index(c));
+			if let Some(face) = face
+				&& let Some(previous) = glyphs.last_mut()
+			{
+				previous.advance += face
+					.horizontal_kern_indexed(previous.glyph, glyph, font_size)
+					.unwrap_or(0.0);
+			}
+			glyphs.push(ShapedGlyph {
+				glyph,
+				cluster,
+				advance: self.glyph_advance(c, extends, font_size),
+				x_offset: 0.0,
+				y_offset: 0.0,
+			});
+		}
+		if direction.is_rtl() {
+			glyphs.reverse();
+		}
+		glyphs
+	}
+
+	/// Shape text with the font's OpenType tables, if it has data
+	fn shape_opentype(
+		&self,
+		text: &str,
+		font_size: f32,
+		direction: TextDirection,
+	) -> Option<Vec<ShapedGlyph>> {
+		let shaper = self.face.as_ref()?.shaper.borrow_dependent();
+
+		let mut buffer = rustybuzz::UnicodeBuffer::new();
+		buffer.push_str(text);
+		buffer.set_direction(if direction.is_rtl() {
+			rustybuzz::Direction::RightToLeft
+		} else {
+			rustybuzz::Direction::LeftToRight
+		});
+		buffer.guess_segment_properties();
+		let output = rustybuzz::shape(shaper, &[], buffer);
+
+		let scale = font_size / shaper.units_per_em() as f32;
+		Some(
+			output
+				.glyph_infos()
+				.iter()
+				.zip(output.glyph_positions())
+				.map(|(info, position)| ShapedGlyph {
+					glyph: info.glyph_id as u16,
+					cluster: info.cluster as usize,
+					advance: position.x_advance as f32 * scale,
+					x_offset: position.x_offset as f32 * scale,
+					y_offset: position.y_offset as f32 * scale,
+				})
+				.collect(),
+		)
+	}
+
+	/// Advance width of a character in this font, ignoring fallbacks
+	///
+	/// Without font data, characters that `extend` the cluster before them,
+	/// such as combining marks, take no space.
+	fn glyph_advance(&self, c: char, extends: bool, font_size: f32) -> f32 {
+		match self.face() {
+			Some(face) => face.metrics(c, font_size).advance_width,
+			None if extends || is_mandatory_break(c) => 0.0,
+			None => font_size * FALLBACK_ADVANCE_RATIO,
+		}
+	}
+
+	/// Kerning between two characters in this font, ignoring fallbacks
+	fn glyph_kerning(&self, left: char, right: char, font_size: f32) -> f32 {
+		self.face()
+			.and_then(|face| face.horizontal_kern(left, right, font_size))
+			.unwrap_or(0.0)
+	}
+
+	/// Advance width of a run of text in this font, ignoring fallbacks
+	fn measure_run(&self, text: &str, font_size: f32) -> f32 {
+		if self.is_loaded() && needs_shaping(text) {
+			let direction = TextDirection::of_text(text).unwrap_or_default();
+			return self
+				.shape(text, font_size, direction)
+				.iter()
+				.map(|glyph| glyph.advance)
+				.sum();
+		}
+
+		let mut width = 0.0;
+		let mut previous = None;
+		for (_, c, extends) in cluster_chars(text) {
+			if let Some(previous) = previous {
+				width += self.glyph_kerning(previous, c, font_size);
+			}
+			width += self.glyph_advance(c, extends, font_size);
+			previous = Some(c);
+		}
+		width
+	}
+
+	/// Break text into lines no wider than `max_width`
+	///
+	/// Lines break at newlines and, when a width is given, at the last break
+	/// opportunity that fits: after spaces and hyphens, and around CJK
+	/// ideographs. Words wider than a line are split between characters.
+	/// When `max_lines` is reached the last line ends with an ellipsis.
+	pub fn layout_text(
+		&self,
+		text: &str,
+		font_size: f32,
+		max_width: Option<f32>,
+		max_lines: Option<usize>,
+	) -> TextLayout {
+		let max_lines = max_lines.unwrap_or(usize::MAX).max(1);
+		let paragraphs = paragraphs(text);
+		let mut lines = Vec::new();
+		let mut truncated = false;
+
+		'paragraphs: for (index, &(start, paragraph)) in paragraphs.iter().enumerate() {
+			let mut rest = paragraph;
+			loop {
+				let end = match max_width {
+					Some(max_width) => self.fit(rest, font_size, max_width),
+					None => rest.len(),
+				};
+				let line = rest[..end].trim_end();
+				let remaining = rest[end..].trim_start_matches(is_breaking_space);
+
+				let more = !remaining.is_empty() || index + 1 < paragraphs.len();
+				if more && lines.len() + 1 == max_lines {
+					// Last allowed line: as much of the remaining text as fits
+					let from = start + (paragraph.len() - rest.len());
+					let width = max_width.unwrap_or_else(|| {
+						self.measure(line, font_size) + self.advance(ELLIPSIS, font_size)
+					});
+					lines.push(self.ellipsize(&text[from..], font_size, width));
+					truncated = true;
+					break 'paragraphs;
+				}
+
+				lines.push(TextLine {
+					text: line.to_string(),
+					width: self.measure(line, font_size),
+				});
+				rest = remaining;
+				if rest.is_empty() {
+					break;
+				}
+			}
+		}
+
+		TextLayout {
+			lines,
+			metrics: self.metrics(font_size),
+			truncated,
+		}
+	}
+
+	/// Shorten a single line of text to fit `max_width`, ending it with an
+	/// ellipsis when anything was removed
+	///
+	/// Line breaks in `text` are treated as spaces.
+	pub fn truncate(&self, text: &str, font_size: f32, max_width: f32) -> String {
+		let text = single_line(text.trim_end());
+		if self.measure(&text, font_size) <= max_width {
+			return text;
+		}
+		self.ellipsize(&text, font_size, max_width).text
+	}
+
+	/// Longest prefix of `text` that fits `max_width` with an ellipsis after it
+	///
+	/// Prefixes only grow wider, so the longest one is found by binary
+	/// search over the cluster boundaries.
+	fn ellipsize(&self, text: &str, font_size: f32, max_width: f32) -> TextLine {
+		let flattened = single_line(text);
+		let available = max_width - self.advance(ELLIPSIS, font_size);
+		let boundaries = cluster_boundaries(&flattened);
+		let fitting = boundaries.partition_point(|&boundary| {
+			self.measure(flattened[..boundary].trim_end(), font_size) <= available
+		});
+		let end = fitting.checked_sub(1).map_or(0, |index| boundaries[index]);
+
+		let mut line = flattened[..end].trim_end().to_string();
+		line.push(ELLIPSIS);
+		let width = self.measure(&line, font_size);
+		TextLine { text: line, width }
+	}
+
+	/// Byte length of the first line of `text` that fits `max_width`
+	///
+	/// Always takes at least one character cluster so wrapping makes progress.
+	/// Prefixes only grow wider, so the last that fits is found by binary
+	/// search.
+	fn fit(&self, text: &str, font_size: f32, max_width: f32) -> usize {
+		let opportunities = break_opportunities(text);
+		let fitting = opportunities.partition_point(|&opportunity| {
+			self.measure(text[..opportunity].trim_end(), font_size) <= max_width
+		});
+		if fitting > 0 {
+			return opportunities[fitting - 1];
+		}
+
+		// The first word alone is too wide; split it between clusters
+		let boundaries = cluster_boundaries(text);
+		let fitting = boundaries
+			.partition_point(|&boundary| self.measure(&text[..boundary], font_size) <= max_width);
+		boundaries
+			.get(fitting.max(1) - 1)
+			.copied()
+			.unwrap_or_default()
+	}
+}
+
+/// Decode a color glyph bitmap to straight RGBA
+fn decode_raster(raster: &ttf_parser::RasterGlyphImage) -> Option<image::RgbaImage> {
+	match raster.format {
+		ttf_parser::RasterImageFormat::PNG => {
+			image::load_from_memory_with_format(raster.data, image::ImageFormat::Png)
+				.ok()
+				.map(|decoded| decoded.to_rgba8())
+		}
+		ttf_parser::RasterImageFormat::BitmapPremulBgra32 => {
+			let pixels = raster
+				.data
+				.chunks_exact(4)
+				.flat_map(|pixel| {
+					let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+					let straight = |channel: u8| match a {
+						0 => 0,
+						_ => (channel as u32 * 255 / a as u32).min(255) as u8,
+					};
+					[straight(r), straight(g), straight(b), a]
+				})
+				.collect();
+			image::RgbaImage::from_raw(raster.width as u32, raster.height as u32, pixels)
+		}
+		_ => None,
+	}
+}
+
+/// First name record among `ids` that can be decoded, preferring English
+fn face_name(face: &ttf_parser::Face, ids: &[u16]) -> Option<String> {
+	const ENGLISH_US: u16 = 0x0409;
+
+	ids.iter().find_map(|&id| {
+		let records = || {
+			face.names()
+				.into_iter()
+				.filter(move |name| name.name_id == id)
+		};
+		records()
+			.filter(|name| name.language_id == ENGLISH_US)
+			.find_map(|name| name.to_string())
+			.or_else(|| records().find_map(|name| name.to_string()))
+			.or_else(|| records().find_map(|name| mac_roman_name(&name)))
+			.filter(|name| !name.is_empty())
+	})
+}
+
+/// Decode a Macintosh Roman name record, keeping only its ASCII subset
+fn mac_roman_name(name: &ttf_parser::name::Name) -> Option<String> {
+	let mac_roman = name.platform_id == ttf_parser::PlatformId::Macintosh && name.encoding_id == 0;
+	(mac_roman && name.name.is_ascii()).then(|| String::from_utf8_lossy(name.name).into_owned())
+}
+
+/// Weight and style described by a subfamily name such as "Bold Italic"
+fn parse_subfamily(subfamily: &str) -> (FontWeight, FontStyle) {
+	let name = subfamily.to_ascii_lowercase().replace([' ', '-', '_'], "");
+	let weight = [
+		("hairline", FontWeight::Thin),
+		("thin", FontWeight::Thin),
+		("extralight", FontWeight::ExtraLight),
+		("ultralight", FontWeight::ExtraLight),
+		("semibold", FontWeight::SemiBold),
+		("demibold", FontWeight::SemiBold),
+		("extrabold", FontWeight::ExtraBold),
+		("ultrabold", FontWeight::ExtraBold),
+		("light", FontWeight::Light),
+		("medium", FontWeight::Medium),
+		("bold", FontWeight::Bold),
+		("black", FontWeight::Black),
+		("heavy", FontWeight::Black),
+	]
+	.into_iter()
+	.find(|(keyword, _)| name.contains(keyword))
+	.map_or(FontWeight::Normal, |(_, weight)| weight);
+	let style = if name.contains("italic") {
+		FontStyle::Italic
+	} else if name.contains("oblique") {
+		FontStyle::Oblique
+	} else {
+		FontStyle::Normal
+	};
+
+	(weight, style)
+}
+
+/// How far a face is from a requested weight and style, lowest first
+///
+/// Follows the CSS font matching algorithm: style is matched first, with
+/// italic and oblique standing in for each other before normal. Among faces
+/// of the best style, a weight between 400 and 500 looks at heavier weights
+/// up to 500, then lighter weights, then heavier ones; lighter requests look
+/// lighter first and bolder requests look bolder first.
+fn match_rank(font: &Font, weight: FontWeight, style: FontStyle) -> (u8, u8, u16) {
+	let styles = match style {
+		FontStyle::Normal => [FontStyle::Normal, FontStyle::Oblique, FontStyle::Italic],
+		FontStyle::Italic => [FontStyle::Italic, FontStyle::Oblique, FontStyle::Normal],
+		FontStyle::Oblique => [FontStyle::Oblique, FontStyle::Italic, FontStyle::Normal],
+	};
+	let style_rank = styles
+		.iter()
+		.position(|candidate| *candidate == font.style)
+		.unwrap_or(styles.len()) as u8;
+
+	let desired = weight.value();
+	let actual = font.weight.value();
+	let distance = desired.abs_diff(actual);
+	let heavier = actual > desired;
+	let weight_rank = match desired {
+		_ if actual == desired => 0,
+		400..=500 if heavier && actual <= 500 => 1,
+		400..=500 if heavier => 3,
+		400..=500 => 2,
+		0..400 if heavier => 2,
+		0..400 => 1,
+		_ if heavier => 1,
+		_ => 2,
+	};
+
+	(style_rank, weight_rank, distance)
+}
+
+/// Replace line breaks with spaces
+fn single_line(text: &str) -> String {
+	text.chars()
+		.map(|c| if is_mandatory_break(c) { ' ' } else { c })
+		.collect()
+}
+
+/// Split text at mandatory line breaks into (byte offset, paragraph) pairs
+fn paragraphs(text: &str) -> Vec<(usize, &str)> {
+	let mut paragraphs = Vec::new();
+	let mut start = 0;
+	let mut chars = text.char_indices().peekable();
+	while let Some((index, c)) = chars.next() {
+		if !is_mandatory_break(c) {
+			continue;
+		}
+		paragraphs.push((start, &text[start..index]));
+		start = index + c.len_utf8();
+		if c == '\r'
+			&& let Some(&(next, '\n')) = chars.peek()
+		{
+			chars.next();
+			start = next + 1;
+		}
+	}
+	paragraphs.push((start, &text[start..]));
+	paragraphs
+}
+
+/// Byte offsets within a paragraph where a line may end
+///
+/// Follows the Unicode line breaking algorithm (UAX #14): breaks are
+/// allowed after spaces and hyphens and around CJK ideographs, but not
+/// before closing or after opening punctuation. The end of the text is
+/// always an opportunity.
+pub fn break_opportunities(text: &str) -> Vec<usize> {
+	let mut opportunities: Vec<usize> = unicode_linebreak::linebreaks(text)
+		.filter(|&(_, opportunity)| {
+			matches!(
+				opportunity,
+				BreakOpportunity::Allowed | BreakOpportunity::Mandatory
+			)
+		})
+		.map(|(index, _)| index)
+		.collect();
+	if opportunities.last() != Some(&text.len()) {
+		opportunities.push(text.len());
+	}
+	opportunities
+}
+
+/// Check whether text needs OpenType shaping to be drawn correctly
+///
+/// Right-to-left scripts join and mirror, the scripts of South and
+/// Southeast Asia reorder and stack, and combining marks need positioning.
+fn needs_shaping(text: &str) -> bool {
+	text.chars().any(|c| {
+		matches!(
+			unicode_bidi::bidi_class(c),
+			unicode_bidi::BidiClass::R | unicode_bidi::BidiClass::AL
+		) || matches!(c,
+				'\u{0900}'..='\u{0DFF}'
+				| '\u{0E00}'..='\u{0FFF}'
+				| '\u{1000}'..='\u{109F}'
+				| '\u{1780}'..='\u{18AF}'
+				| '\u{A980}'..='\u{AAFF}')
+	}) || text
+		.graphemes(true)
+		.any(|cluster| cluster.chars().nth(1).is_some())
+}
+
+/// Byte offsets after each extended grapheme cluster in `text`
+///
+/// Combining marks, variation selectors, emoji modifiers and characters
+/// joined by a zero-width joiner stay with the character before them.
+pub(crate) fn cluster_boundaries(text: &str) -> Vec<usize> {
+	text.grapheme_indices(true)
+		.map(|(start, cluster)| start + cluster.len())
+		.collect()
+}
+
+/// Characters of `text` with their byte offsets, and whether each extends
+/// the grapheme cluster before it
+fn cluster_chars(text: &str) -> impl Iterator<Item = (usize, char, bool)> + '_ {
+	text.grapheme_indices(true).flat_map(|(start, cluster)| {
+		cluster
+			.char_indices()
+			.map(move |(offset, c)| (start + offset, c, offset > 0))
+	})
+}
+
+/// Characters that force a new line
+fn is_mandatory_break(c: char) -> bool {
+	matches!(
+		c,
+		'\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+	)
+}
+
+/// Spaces that offer a break and are dropped at the end of a line
+fn is_breaking_space(c: char) -> bool {
+	c.is_whitespace() && !is_glue(c) && !is_mandatory_break(c)
+}
+
+/// Characters that prevent a break on either side
+fn is_glue(c: char) -> bool {
+	matches!(
+		c,
+		'\u{A0}' | '\u{2007}' | '\u{2011}' | '\u{202F}' | '\u{2060}' | '\u{FEFF}'
+	)
 }
 
 /// Font registry for managing loaded fonts
@@ -178,6 +1094,10 @@ impl Font {
 pub struct FontRegistry {
 	/// Registered fonts by family name
 	fonts: HashMap<String, Vec<Font>>,
+	/// Family used to measure text that names no family
+	default_family: Option<String>,
+	/// Regular face of the default family with its fallback chain
+	default_font: Option<Font>,
 }
 
 impl FontRegistry {
@@ -190,14 +1110,18 @@ impl FontRegistry {
 	pub fn register(&mut self, font: Font) {
 		let family_name = font.family.name.clone();
 		self.fonts.entry(family_name).or_default().push(font);
+		self.refresh_default_font();
 	}
 
-	/// Get font by family and style
-	pub fn get(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<&Font> {
-		self.fonts
-			.get(family)?
-			.iter()
-			.find(|f| f.weight == weight && f.style == style)
+	/// Find the face closest to a weight and style
+	///
+	/// Faces are matched the way CSS matches them: the nearest style first,
+	/// then the nearest weight. When the family has no faces registered,
+	/// each of its fallbacks is tried in order.
+	pub fn get(&self, family: &FontFamily, weight: FontWeight, style: FontStyle) -> Option<&Font> {
+		std::iter::once(&family.name)
+			.chain(&family.fallbacks)
+			.find_map(|name| self.best_match(name, weight, style, |_| true))
 	}
 
 	/// Get all fonts for a family
@@ -214,12 +1138,143 @@ impl FontRegistry {
 	pub fn families(&self) -> Vec<&String> {
 		self.fonts.keys().collect()
 	}
+
+	/// Find the best registered face for a family
+	///
+	/// Prefers the loaded face closest to the weight and style, matched as
+	/// [`FontRegistry::get`] does, then the closest face without data.
+	pub fn resolve(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<&Font> {
+		self.best_match(family, weight, style, Font::is_loaded)
+			.or_else(|| self.best_match(family, weight, style, |_| true))
+	}
+
+	/// Resolve a family together with the fonts it falls back on
+	///
+	/// The primary font is the face [`FontRegistry::resolve`] picks from the
+	/// first of `family` and its fallbacks that is registered. Characters it
+	/// has no glyph for are drawn with the closest loaded face of each later
+	/// fallback family, then of every other registered family by name. The
+	/// returned font shares parsed data with the registry but not raw data.
+	pub fn resolve_with_fallbacks(
+		&self,
+		family: &FontFamily,
+		weight: FontWeight,
+		style: FontStyle,
+	) -> Option<Font> {
+		let mut names: Vec<&str> = Vec::new();
+		for name in std::iter::once(&family.name).chain(&family.fallbacks) {
+			if !names.contains(&name.as_str()) {
+				names.push(name);
+			}
+		}
+		let mut others: Vec<&str> = self
+			.fonts
+			.keys()
+			.map(String::as_str)
+			.filter(|name| !names.contains(name))
+			.collect();
+		others.sort_unstable();
+
+		let primary = names.iter().position(|name| self.has_family(name))?;
+		let font = self.resolve(names[primary], weight, style)?;
+		let fallbacks = names[primary + 1..]
+			.iter()
+			.chain(&others)
+			.filter_map(|name| self.best_match(name, weight, style, Font::is_loaded))
+			.map(Font::without_data);
+
+		Some(font.without_data().with_fallback_fonts(fallbacks))
+	}
+
+	/// Closest face of one family among those accepted by `filter`
+	fn best_match(
+		&self,
+		family: &str,
+		weight: FontWeight,
+		style: FontStyle,
+		filter: impl Fn(&Font) -> bool,
+	) -> Option<&Font> {
+		self.fonts
+			.get(family)?
+			.iter()
+			.filter(|font| filter(font))
+			.min_by_key(|font| match_rank(font, weight, style))
+	}
+
+	/// Set the family used to measure text that names no family
+	pub fn set_default_family(&mut self, family: impl Into<String>) {
+		self.default_family = Some(family.into());
+		self.refresh_default_font();
+	}
+
+	/// Get the family used to measure text that names no family
+	pub fn default_family(&self) -> Option<&str> {
+		self.default_family.as_deref()
+	}
+
+	/// Get the regular face of the default family
+	///
+	/// The face falls back on other registered fonts as
+	/// [`FontRegistry::resolve_with_fallbacks`] describes.
+	pub fn default_font(&self) -> Option<&Font> {
+		self.default_font.as_ref()
+	}
+
+	/// Resolve the default font again after the registry changes
+	fn refresh_default_font(&mut self) {
+		self.default_font = self.default_family.as_ref().and_then(|family| {
+			self.resolve_with_fallbacks(
+				&FontFamily::new(family.as_str()),
+				FontWeight::Normal,
+				FontStyle::Normal,
+			)
+		});
+	}
+
+	/// Lay out text with the default font, or approximate metrics without one
+	///
+	/// See [`Font::layout_text`].
+	pub fn layout_text(
+		&self,
+		text: &str,
+		font_size: f32,
+		max_width: Option<f32>,
+		max_lines: Option<usize>,
+	) -> TextLayout {
+		match self.default_font() {
+			Some(font) => font.layout_text(text, font_size, max_width, max_lines),
+			None => Font::new(FontFamily::new(""), font_size)
+				.layout_text(text, font_size, max_width, max_lines),
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	const TUFFY: &[u8] = include_bytes!("../../../assets/fonts/Tuffy.ttf");
+	const TUFFY_COLLECTION: &[u8] = include_bytes!("../../../assets/fonts/TuffyCollection.ttc");
+	const EMOJI: &[u8] = include_bytes!("../../../assets/fonts/EngageEmoji.ttf");
+	const DEJAVU_MONO: &[u8] = include_bytes!("../../../assets/fonts/DejaVuSansMono.ttf");
+
+	/// Tuffy falling back to the emoji font
+	fn tuffy_with_emoji() -> Font {
+		let emoji = Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap();
+		Font::load_from_bytes(TUFFY.to_vec(), 16.0)
+			.unwrap()
+			.with_fallback_fonts([emoji])
+	}
+
+	/// Font without data, measuring 6px per character and 12px per line at 10px
+	fn approximate() -> Font {
+		Font::new(FontFamily::new("Approximate"), 10.0)
+	}
+
+	fn texts(layout: &TextLayout) -> Vec<&str> {
+		layout.lines.iter().map(|line| line.text.as_str()).collect()
+	}
+
 	#[test]
 	fn test_font_weight_value() {
 		assert_eq!(FontWeight::Normal.value(), 400);
@@ -286,7 +1341,7 @@ mod tests {
 		assert!(registry.has_family("Open Sans"));
 		assert!(!registry.has_family("Arial"));
 
-		let retrieved = registry.get("Open Sans", FontWeight::Normal, FontStyle::Normal);
+		let retrieved = registry.get(&family, FontWeight::Normal, FontStyle::Normal);
 		assert!(retrieved.is_some());
 	}
 
@@ -300,4 +1355,486 @@ mod tests {
 		let families = registry.families();
 		assert_eq!(families.len(), 2);
 	}
+
+	#[test]
+	fn test_fallback_metrics() {
+		let font = approximate();
+		let metrics = font.metrics(10.0);
+
+		assert!(!font.is_loaded());
+		assert_eq!(metrics.ascent, 8.0);
+		assert_eq!(metrics.descent, 2.0);
+		assert!((metrics.line_height() - 12.0).abs() < 1e-4);
+		assert_eq!(font.measure("abc", 10.0), 18.0);
+		assert_eq!(font.measure("e\u{301}", 10.0), 6.0);
+	}
+
+	#[test]
+	fn test_loaded_metrics() {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let metrics = font.metrics(20.0);
+
+		assert!(font.is_loaded());
+		assert!(metrics.ascent > 10.0 && metrics.ascent < 20.0);
+		assert!(metrics.descent > 0.0 && metrics.descent < metrics.ascent);
+		assert!(font.advance('W', 20.0) > font.advance('i', 20.0));
+		assert!(font.kerning('A', 'V', 20.0) < 0.0);
+		assert_eq!(
+			font.measure("AV", 20.0),
+			font.advance('A', 20.0) + font.advance('V', 20.0) + font.kerning('A', 'V', 20.0)
+		);
+	}
+
+	#[test]
+	fn test_layout_without_width() {
+		let font = approximate();
+		let layout = font.layout_text("one\ntwo three\r\n\nend", 10.0, None, None);
+
+		assert_eq!(texts(&layout), vec!["one", "two three", "", "end"]);
+		assert_eq!(layout.width(), 54.0);
+		assert!((layout.height() - 48.0).abs() < 1e-4);
+		assert!(!layout.truncated);
+
+		let empty = font.layout_text("", 10.0, None, None);
+		assert_eq!(texts(&empty), vec![""]);
+		assert!((empty.height() - 12.0).abs() < 1e-4);
+	}
+
+	#[test]
+	fn test_word_wrap() {
+		let font = approximate();
+
+		// 60px fits ten characters
+		let layout = font.layout_text("The quick brown fox jumps", 10.0, Some(60.0), None);
+		assert_eq!(texts(&layout), vec!["The quick", "brown fox", "jumps"]);
+		assert!(layout.lines.iter().all(|line| line.width <= 60.0));
+
+		let hyphenated = font.layout_text("well-known words", 10.0, Some(40.0), None);
+		assert_eq!(texts(&hyphenated), vec!["well-", "known", "words"]);
+
+		// No break is allowed at a no-break space
+		let glued = font.layout_text("a b\u{A0}c", 10.0, Some(24.0), None);
+		assert_eq!(texts(&glued), vec!["a", "b\u{A0}c"]);
+	}
+
+	#[test]
+	fn test_long_words_split_between_clusters() {
+		let font = approximate();
+		let layout = font.layout_text("abcdefgh", 10.0, Some(30.0), None);
+		assert_eq!(texts(&layout), vec!["abcde", "fgh"]);
+
+		// Combining accents stay with their base letter
+		let accented = font.layout_text("e\u{301}e\u{301}e\u{301}", 10.0, Some(12.0), None);
+		assert_eq!(texts(&accented), vec!["e\u{301}e\u{301}", "e\u{301}"]);
+
+		// A line always takes at least one cluster
+		let narrow = font.layout_text("ab", 10.0, Some(1.0), None);
+		assert_eq!(texts(&narrow), vec!["a", "b"]);
+	}
+
+	#[test]
+	fn test_cjk_wrap() {
+		let font = approximate();
+		let layout = font.layout_text("日本語の文章です。", 10.0, Some(24.0), None);
+
+		// Breaks between ideographs, but never before the full stop
+		assert_eq!(texts(&layout), vec!["日本語の", "文章で", "す。"]);
+	}
+
+	#[test]
+	fn test_max_lines_ellipsis() {
+		let font = approximate();
+		let layout = font.layout_text("The quick brown fox jumps", 10.0, Some(60.0), Some(2));
+
+		assert!(layout.truncated);
+		assert_eq!(texts(&layout), vec!["The quick", "brown fox\u{2026}"]);
+		assert!(layout.lines[1].width <= 60.0);
+
+		let unbounded = font.layout_text("first\nsecond", 10.0, None, Some(1));
+		assert_eq!(texts(&unbounded), vec!["first\u{2026}"]);
+	}
+
+	#[test]
+	fn test_truncate() {
+		let font = approximate();
+
+		assert_eq!(font.truncate("Short", 10.0, 60.0), "Short");
+		assert_eq!(
+			font.truncate("Much longer text", 10.0, 60.0),
+			"Much long\u{2026}"
+		);
+		// Spaces before the ellipsis are dropped
+		assert_eq!(font.truncate("Much longer", 10.0, 36.0), "Much\u{2026}");
+		assert_eq!(font.truncate("two\nlines", 10.0, 100.0), "two lines");
+	}
+
+	#[test]
+	fn test_break_opportunities() {
+		assert_eq!(break_opportunities("ab cd"), vec![3, 5]);
+		assert_eq!(break_opportunities("a  b"), vec![3, 4]);
+		assert_eq!(break_opportunities("(a) b"), vec![4, 5]);
+		assert_eq!(break_opportunities(""), vec![0]);
+		assert_eq!(break_opportunities("well-known"), vec![5, 10]);
+		// Ideographs break between each other, but not before a full stop
+		assert_eq!(break_opportunities("\u{6F22}\u{5B57}\u{3002}"), vec![3, 9]);
+	}
+
+	#[test]
+	fn test_cluster_boundaries() {
+		assert_eq!(cluster_boundaries("ab"), vec![1, 2]);
+		assert_eq!(cluster_boundaries("e\u{301}x"), vec![3, 4]);
+		// Regional indicator pairs and emoji joined by a zero-width joiner
+		// are single clusters
+		assert_eq!(
+			cluster_boundaries("\u{1F1EB}\u{1F1F7}\u{1F1E9}\u{1F1EA}"),
+			vec![8, 16]
+		);
+		assert_eq!(cluster_boundaries("\u{1F469}\u{200D}\u{1F4BB}"), vec![11]);
+		assert!(cluster_boundaries("").is_empty());
+	}
+
+	#[test]
+	fn test_registry_resolve_and_layout() {
+		let mut registry = FontRegistry::new();
+		assert!(registry.default_font().is_none());
+		assert_eq!(registry.layout_text("abc", 10.0, None, None).width(), 18.0);
+
+		registry.register(Font::new(FontFamily::new("Tuffy"), 16.0));
+		registry.register(Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap());
+		registry.set_default_family("Tuffy");
+
+		let resolved = registry
+			.resolve("Tuffy", FontWeight::Bold, FontStyle::Italic)
+			.unwrap();
+		assert!(resolved.is_loaded());
+		assert_eq!(registry.default_family(), Some("Tuffy"));
+		assert_eq!(
+			registry.layout_text("AV", 20.0, None, None).width(),
+			resolved.measure("AV", 20.0)
+		);
+	}
+
+	#[test]
+	fn test_load_reads_metadata() {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+
+		assert_eq!(font.family.name, "Tuffy");
+		assert_eq!(font.subfamily.as_deref(), Some("Regular"));
+		assert_eq!(font.weight, FontWeight::Medium);
+		assert_eq!(font.style, FontStyle::Normal);
+	}
+
+	#[test]
+	fn test_load_collection() {
+		let faces = Font::load_collection(TUFFY_COLLECTION.to_vec(), 16.0).unwrap();
+
+		assert_eq!(faces.len(), 2);
+		assert!(faces.iter().all(|face| face.family.name == "Tuffy"));
+		assert!(faces.iter().all(Font::is_loaded));
+		assert_eq!(faces[1].subfamily.as_deref(), Some("Bold Italic"));
+		assert_eq!(faces[1].weight, FontWeight::Bold);
+		assert_eq!(faces[1].style, FontStyle::Italic);
+
+		// Faces share one copy of the collection, with their shaping tables
+		let data = |font: &Font| font.data.clone().unwrap();
+		assert!(Arc::ptr_eq(&data(&faces[0]), &data(&faces[1])));
+		let shaper = faces[1].face.as_ref().unwrap().shaper.clone();
+		assert!(Arc::ptr_eq(shaper.borrow_owner(), &data(&faces[1])));
+
+		// A single font file is a collection of one
+		let single = Font::load_collection(TUFFY.to_vec(), 16.0).unwrap();
+		assert_eq!(single.len(), 1);
+
+		// Loading a collection directly gives its first face
+		let first = Font::load_from_bytes(TUFFY_COLLECTION.to_vec(), 16.0).unwrap();
+		assert_eq!(first.weight, FontWeight::Medium);
+	}
+
+	#[test]
+	fn test_parse_subfamily() {
+		assert_eq!(
+			parse_subfamily("Bold Italic"),
+			(FontWeight::Bold, FontStyle::Italic)
+		);
+		assert_eq!(
+			parse_subfamily("SemiBold"),
+			(FontWeight::SemiBold, FontStyle::Normal)
+		);
+		assert_eq!(
+			parse_subfamily("Light Oblique"),
+			(FontWeight::Light, FontStyle::Oblique)
+		);
+		assert_eq!(
+			parse_subfamily("Regular"),
+			(FontWeight::Normal, FontStyle::Normal)
+		);
+	}
+
+	fn face(weight: FontWeight, style: FontStyle) -> Font {
+		Font::with_style(FontFamily::new("Family"), 16.0, weight, style)
+	}
+
+	fn matched(registry: &FontRegistry, weight: FontWeight, style: FontStyle) -> (u16, FontStyle) {
+		let font = registry
+			.get(&FontFamily::new("Family"), weight, style)
+			.unwrap();
+		(font.weight.value(), font.style)
+	}
+
+	#[test]
+	fn test_registry_weight_matching() {
+		let mut registry = FontRegistry::new();
+		for weight in [
+			FontWeight::Light,
+			FontWeight::Medium,
+			FontWeight::SemiBold,
+			FontWeight::Black,
+		] {
+			registry.register(face(weight, FontStyle::Normal));
+		}
+
+		let normal = FontStyle::Normal;
+		assert_eq!(matched(&registry, FontWeight::SemiBold, normal).0, 600);
+		// 400 prefers 500 before lighter weights
+		assert_eq!(matched(&registry, FontWeight::Normal, normal).0, 500);
+		// Light requests look lighter, then heavier
+		assert_eq!(matched(&registry, FontWeight::ExtraLight, normal).0, 300);
+		assert_eq!(matched(&registry, FontWeight::Thin, normal).0, 300);
+		// Bold requests look bolder, then lighter
+		assert_eq!(matched(&registry, FontWeight::Bold, normal).0, 900);
+
+		let mut light = FontRegistry::new();
+		light.register(face(FontWeight::Light, normal));
+		light.register(face(FontWeight::SemiBold, normal));
+		// 500 has nothing up to 500, so lighter wins over heavier
+		assert_eq!(matched(&light, FontWeight::Medium, normal).0, 300);
+		assert_eq!(matched(&light, FontWeight::Bold, normal).0, 600);
+	}
+
+	#[test]
+	fn test_registry_style_matching() {
+		let mut registry = FontRegistry::new();
+		registry.register(face(FontWeight::Normal, FontStyle::Normal));
+		registry.register(face(FontWeight::Bold, FontStyle::Oblique));
+
+		// Style takes precedence over weight
+		assert_eq!(
+			matched(&registry, FontWeight::Normal, FontStyle::Italic),
+			(700, FontStyle::Oblique)
+		);
+		assert_eq!(
+			matched(&registry, FontWeight::Bold, FontStyle::Normal),
+			(400, FontStyle::Normal)
+		);
+
+		registry.register(face(FontWeight::Normal, FontStyle::Italic));
+		assert_eq!(
+			matched(&registry, FontWeight::Bold, FontStyle::Italic),
+			(400, FontStyle::Italic)
+		);
+	}
+
+	#[test]
+	fn test_registry_fallbacks() {
+		let mut registry = FontRegistry::new();
+		registry.register(Font::new(FontFamily::new("Sans"), 16.0));
+		registry.register(Font::new(FontFamily::new("Serif"), 16.0));
+
+		let family = FontFamily::with_fallbacks("Missing", vec!["Unknown", "Serif", "Sans"]);
+		let font = registry
+			.get(&family, FontWeight::Normal, FontStyle::Normal)
+			.unwrap();
+		assert_eq!(font.family.name, "Serif");
+
+		let none = FontFamily::with_fallbacks("Missing", vec!["Unknown"]);
+		assert!(
+			registry
+				.get(&none, FontWeight::Normal, FontStyle::Normal)
+				.is_none()
+		);
+	}
+
+	#[test]
+	fn test_registry_collection_faces() {
+		let mut registry = FontRegistry::new();
+		for font in Font::load_collection(TUFFY_COLLECTION.to_vec(), 16.0).unwrap() {
+			registry.register(font);
+		}
+
+		let family = FontFamily::new("Tuffy");
+		let bold = registry
+			.get(&family, FontWeight::Bold, FontStyle::Italic)
+			.unwrap();
+		assert_eq!(bold.subfamily.as_deref(), Some("Bold Italic"));
+		let regular = registry
+			.get(&family, FontWeight::Normal, FontStyle::Normal)
+			.unwrap();
+		assert_eq!(regular.subfamily.as_deref(), Some("Regular"));
+	}
+
+	#[test]
+	fn test_glyph_coverage() {
+		let tuffy = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let emoji = Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap();
+
+		assert!(tuffy.has_glyph('A'));
+		assert!(!tuffy.has_glyph('\u{1F600}'));
+		assert!(!tuffy.has_color_glyphs());
+		assert_eq!(emoji.family.name, "Engage Emoji");
+		assert!(emoji.has_glyph('\u{1F600}'));
+		assert!(!emoji.has_glyph('A'));
+		assert!(emoji.has_color_glyphs());
+		assert!(!approximate().has_glyph('A'));
+	}
+
+	#[test]
+	fn test_fallback_runs() {
+		let font = tuffy_with_emoji();
+		let text = "Hi \u{1F600}\u{FE0F}!";
+		let runs: Vec<(&str, usize)> = font
+			.runs(text)
+			.iter()
+			.map(|run| (&text[run.range.clone()], run.index))
+			.collect();
+
+		// The space stays with the text before it and the variation selector
+		// with the emoji
+		assert_eq!(runs, vec![("Hi ", 0), ("\u{1F600}\u{FE0F}", 1), ("!", 0)]);
+		assert_eq!(font.font_for('\u{1F600}').family.name, "Engage Emoji");
+		// Characters no font has fall back to the primary font
+		assert_eq!(font.font_for('\u{0627}').family.name, "Tuffy");
+		assert_eq!(font.runs("").len(), 0);
+	}
+
+	#[test]
+	fn test_fallback_measure() {
+		let plain = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let font = tuffy_with_emoji();
+
+		// The emoji advances one em in its own font
+		assert_eq!(font.measure("\u{1F600}", 20.0), 20.0);
+		assert_eq!(font.advance('\u{1F600}', 20.0), 20.0);
+		assert_eq!(
+			font.measure("AV\u{1F600}", 20.0),
+			plain.measure("AV", 20.0) + 20.0
+		);
+		assert_eq!(font.kerning('V', '\u{1F600}', 20.0), 0.0);
+		assert_eq!(
+			font.layout_text("\u{1F600}\u{1F600}", 20.0, None, None)
+				.width(),
+			40.0
+		);
+	}
+
+	#[test]
+	fn test_color_glyph() {
+		let font = tuffy_with_emoji();
+		assert!(font.color_glyph('A', 20.0).is_none());
+
+		let glyph = font.color_glyph('\u{1F600}', 40.0).unwrap();
+		assert_eq!((glyph.width, glyph.height), (40, 40));
+		// The bitmap sits a fifth of its height below the baseline
+		assert_eq!((glyph.left, glyph.top), (0, -32));
+		assert_eq!(glyph.pixels.len(), 40 * 40 * 4);
+
+		let pixel = |x: u32, y: u32| {
+			let index = ((y * glyph.width + x) * 4) as usize;
+			&glyph.pixels[index..index + 4]
+		};
+		assert_eq!(pixel(20, 30), &[255, 204, 0, 255]);
+		assert_eq!(pixel(0, 0)[3], 0);
+
+		// Sizes between strikes are scaled
+		let scaled = font.color_glyph('\u{1F600}', 30.0).unwrap();
+		assert_eq!((scaled.width, scaled.height), (30, 30));
+	}
+
+	#[test]
+	fn test_registry_fallback_chain() {
+		let mut registry = FontRegistry::new();
+		registry.register(Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap());
+		registry.register(Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap());
+		registry.register(Font::new(FontFamily::new("Unloaded"), 16.0));
+
+		let font = registry
+			.resolve_with_fallbacks(
+				&FontFamily::new("Tuffy"),
+				FontWeight::Normal,
+				FontStyle::Normal,
+			)
+			.unwrap();
+		assert_eq!(font.family.name, "Tuffy");
+		assert!(font.data.is_none());
+		let fallbacks: Vec<&str> = font
+			.fallback_fonts()
+			.iter()
+			.map(|font| font.family.name.as_str())
+			.collect();
+		assert_eq!(fallbacks, vec!["Engage Emoji"]);
+
+		// The family's own fallbacks are used when it is not registered
+		let missing = FontFamily::with_fallbacks("Missing", vec!["Engage Emoji"]);
+		let font = registry
+			.resolve_with_fallbacks(&missing, FontWeight::Normal, FontStyle::Normal)
+			.unwrap();
+		assert_eq!(font.family.name, "Engage Emoji");
+		assert_eq!(font.fallback_fonts()[0].family.name, "Tuffy");
+
+		// The default font falls back too
+		registry.set_default_family("Tuffy");
+		let default = registry.default_font().unwrap();
+		assert_eq!(default.fallback_fonts().len(), 1);
+		assert_eq!(
+			registry.layout_text("\u{1F600}", 20.0, None, None).width(),
+			20.0
+		);
+	}
+
+	#[test]
+	fn test_shape_latin() {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let glyphs = font.shape("AV", 32.0, TextDirection::LeftToRight);
+
+		assert_eq!(glyphs.len(), 2);
+		assert_eq!(glyphs[0].cluster, 0);
+		assert_eq!(glyphs[1].cluster, 1);
+		let width: f32 = glyphs.iter().map(|glyph| glyph.advance).sum();
+		assert!((width - font.measure("AV", 32.0)).abs() < 0.01);
+
+		// Right-to-left runs come out in visual order
+		let reversed = font.shape("AV", 32.0, TextDirection::RightToLeft);
+		assert_eq!(reversed[0].cluster, 1);
+		assert_eq!(reversed[1].cluster, 0);
+	}
+
+	#[test]
+	fn test_shape_arabic_contextual_forms() {
+		let font = Font::load_from_bytes(DEJAVU_MONO.to_vec(), 16.0).unwrap();
+		let isolated = font.shape("\u{0628}", 20.0, TextDirection::RightToLeft);
+		let joined = font.shape("\u{0628}\u{0628}", 20.0, TextDirection::RightToLeft);
+
+		// The first beh is drawn on the right in its initial form, the second
+		// on the left in its final form
+		assert_eq!(isolated.len(), 1);
+		assert_eq!(joined.len(), 2);
+		assert_eq!(joined[0].cluster, 2);
+		assert_eq!(joined[1].cluster, 0);
+		assert_ne!(joined[0].glyph, isolated[0].glyph);
+		assert_ne!(joined[1].glyph, isolated[0].glyph);
+		assert_ne!(joined[0].glyph, joined[1].glyph);
+
+		let width: f32 = joined.iter().map(|glyph| glyph.advance).sum();
+		assert!((font.measure("\u{0628}\u{0628}", 20.0) - width).abs() < 0.01);
+	}
+
+	#[test]
+	fn test_needs_shaping() {
+		assert!(!needs_shaping("Hello, world"));
+		assert!(needs_shaping("\u{0645}\u{0631}\u{062D}\u{0628}\u{0627}"));
+		assert!(needs_shaping("\u{05E9}\u{05DC}\u{05D5}\u{05DD}"));
+		assert!(needs_shaping(
+			"\u{0928}\u{092E}\u{0938}\u{094D}\u{0924}\u{0947}"
+		));
+		assert!(needs_shaping("e\u{0301}"));
+	}
 }
@@ -1,9 +1,10 @@
 //! Image format support (PNG, JPEG, WebP, etc.)
 
 use super::MediaError;
+use serde::{Deserialize, Serialize};
 
 /// Supported image formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImageFormat {
 	/// PNG format
 	Png,
@@ -86,7 +87,7 @@ impl ImageFormat {
 }
 
 /// Color type for images
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColorType {
 	/// Grayscale
 	Grayscale,
@@ -97,7 +98,7 @@ pub enum ColorType {
 }
 
 /// Image data representation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageData {
 	/// Image width in pixels
 	pub width: u32,
@@ -197,6 +198,38 @@ impl ImageData {
 		})
 	}
 
+	/// Encode as PNG
+	pub fn encode_png(&self) -> Result<Vec<u8>, MediaError> {
+		use image::ImageEncoder;
+		use image::codecs::png::PngEncoder;
+
+		if self.data.len() != self.size_bytes() {
+			return Err(MediaError::InvalidData(format!(
+				"Expected {} bytes of pixel data, found {}",
+				self.size_bytes(),
+				self.data.len()
+			)));
+		}
+		let color_type = match self.color_type {
+			ColorType::Grayscale => image::ExtendedColorType::L8,
+			ColorType::Rgb => image::ExtendedColorType::Rgb8,
+			ColorType::Rgba => image::ExtendedColorType::Rgba8,
+		};
+
+		let mut png = Vec::new();
+		PngEncoder::new(&mut png)
+			.write_image(&self.data, self.width, self.height, color_type)
+			.map_err(|e| MediaError::SaveFailed(format!("Failed to encode PNG: {}", e)))?;
+		Ok(png)
+	}
+
+	/// Encode as PNG and write to a file path
+	pub fn save_png(&self, path: &str) -> Result<(), MediaError> {
+		let png = self.encode_png()?;
+		std::fs::write(path, png)
+			.map_err(|e| MediaError::SaveFailed(format!("Failed to write image: {}", e)))
+	}
+
 	/// Get bytes per pixel
 	pub fn bytes_per_pixel(&self) -> usize {
 		match self.color_type {
@@ -316,4 +349,23 @@ mod tests {
 		let result = ImageData::load_from_bytes(unknown_bytes);
 		assert!(result.is_err());
 	}
+
+	#[test]
+	fn test_png_round_trip() {
+		let pixels = vec![255, 0, 0, 255, 0, 0, 255, 128];
+		let image = ImageData::new(2, 1, ImageFormat::Png, ColorType::Rgba, pixels.clone());
+
+		let png = image.encode_png().unwrap();
+		assert_eq!(ImageFormat::from_bytes(&png), Some(ImageFormat::Png));
+		let decoded = ImageData::load_from_bytes(png).unwrap();
+		assert_eq!((decoded.width, decoded.height), (2, 1));
+		assert_eq!(decoded.color_type, ColorType::Rgba);
+		assert_eq!(decoded.data, pixels);
+
+		let short = ImageData::new(2, 2, ImageFormat::Png, ColorType::Rgb, vec![0; 3]);
+		assert!(matches!(
+			short.encode_png(),
+			Err(MediaError::InvalidData(_))
+		));
+	}
 }
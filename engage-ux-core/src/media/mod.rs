@@ -1,11 +1,17 @@
 //! Media handling for fonts and images
 //!
-//! Provides support for loading and managing fonts and image formats.
+//! Provides support for loading and managing fonts and image formats, and
+//! for laying out bidirectional text.
 
+pub mod bidi;
 pub mod font;
 pub mod image;
 
-pub use font::{Font, FontFamily, FontStyle, FontWeight};
+pub use bidi::{BidiRun, CaretMovement, TextDirection};
+pub use font::{
+	ColorGlyph, Font, FontFamily, FontMetrics, FontRegistry, FontRun, FontStyle, FontWeight,
+	ShapedGlyph, TextLayout, TextLine,
+};
 pub use image::{ImageData, ImageFormat};
 
 /// Media loading error
@@ -17,6 +23,8 @@ pub enum MediaError {
 	InvalidData(String),
 	/// Loading failed
 	LoadFailed(String),
+	/// Encoding or saving failed
+	SaveFailed(String),
 }
 
 impl std::fmt::Display for MediaError {
@@ -27,6 +35,7 @@ impl std::fmt::Display for MediaError {
 			}
 			MediaError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
 			MediaError::LoadFailed(msg) => write!(f, "Load failed: {}", msg),
+			MediaError::SaveFailed(msg) => write!(f, "Save failed: {}", msg),
 		}
 	}
 }
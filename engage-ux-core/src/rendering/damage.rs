@@ -0,0 +1,204 @@
+//! Damage tracking for partial redraws
+//!
+//! A [`DamageRegion`] collects the parts of a surface that changed since it
+//! was last presented, so a frame only repaints and presents those parts.
+//! Rectangles that overlap, or that would waste little area if drawn as one,
+//! are merged as they are added, and the region never holds more than a
+//! handful of rectangles, so testing a pixel against it stays cheap.
+
+use crate::component::Rect;
+
+/// Most rectangles a region keeps before merging the closest pair
+const MAX_RECTS: usize = 8;
+
+/// How much larger than the area two rectangles cover their union may be
+/// and still be merged into one
+const MERGE_SLACK: f32 = 0.25;
+
+/// The changed parts of a surface, as a short list of rectangles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DamageRegion {
+	rects: Vec<Rect>,
+}
+
+impl DamageRegion {
+	/// Create an empty region, with nothing to repaint
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a region covering one rectangle
+	pub fn from_rect(rect: Rect) -> Self {
+		let mut region = Self::new();
+		region.add(rect);
+		region
+	}
+
+	/// Mark a rectangle as damaged
+	///
+	/// Empty rectangles are ignored. The rectangle absorbs any already in
+	/// the region that it overlaps closely enough.
+	pub fn add(&mut self, rect: Rect) {
+		if rect.is_empty() || !(rect.x.is_finite() && rect.y.is_finite()) {
+			return;
+		}
+
+		let mut rect = rect;
+		while let Some(index) = self.rects.iter().position(|r| worth_merging(r, &rect)) {
+			rect = rect.union(&self.rects.swap_remove(index));
+		}
+		self.rects.push(rect);
+
+		if self.rects.len() > MAX_RECTS {
+			self.merge_closest();
+		}
+	}
+
+	/// Mark everything damaged in another region
+	pub fn add_region(&mut self, other: &DamageRegion) {
+		for rect in &other.rects {
+			self.add(*rect);
+		}
+	}
+
+	/// The damaged rectangles, which may overlap
+	pub fn rects(&self) -> &[Rect] {
+		&self.rects
+	}
+
+	/// Check whether nothing is damaged
+	pub fn is_empty(&self) -> bool {
+		self.rects.is_empty()
+	}
+
+	/// Smallest rectangle holding all the damage
+	pub fn bounds(&self) -> Option<Rect> {
+		self.rects.iter().copied().reduce(|a, b| a.union(&b))
+	}
+
+	/// Check whether a rectangle touches any damaged area
+	pub fn intersects(&self, rect: &Rect) -> bool {
+		self.rects.iter().any(|r| r.intersects(rect))
+	}
+
+	/// Check whether a point lies in a damaged rectangle
+	pub fn contains_point(&self, x: f32, y: f32) -> bool {
+		self.rects.iter().any(|r| r.contains_point(x, y))
+	}
+
+	/// Forget all the damage, for example after presenting a frame
+	pub fn clear(&mut self) {
+		self.rects.clear();
+	}
+
+	/// Take the damage, leaving the region empty
+	pub fn take(&mut self) -> DamageRegion {
+		std::mem::take(self)
+	}
+
+	/// Grow every rectangle out to whole pixels and clip it to a surface of
+	/// the given size
+	pub fn snap_to_pixels(&self, width: u32, height: u32) -> DamageRegion {
+		let (width, height) = (width as f32, height as f32);
+		let mut snapped = DamageRegion::new();
+		for rect in &self.rects {
+			let left = rect.x.floor().clamp(0.0, width);
+			let top = rect.y.floor().clamp(0.0, height);
+			let right = (rect.x + rect.width).ceil().clamp(0.0, width);
+			let bottom = (rect.y + rect.height).ceil().clamp(0.0, height);
+			snapped.add(Rect::new(left, top, right - left, bottom - top));
+		}
+		snapped
+	}
+
+	/// Merge the two rectangles whose union wastes the least area
+	fn merge_closest(&mut self) {
+		let mut best = (0, 1, f32::INFINITY);
+		for i in 0..self.rects.len() {
+			for j in i + 1..self.rects.len() {
+				let waste = wasted_area(&self.rects[i], &self.rects[j]);
+				if waste < best.2 {
+					best = (i, j, waste);
+				}
+			}
+		}
+
+		let (i, j, _) = best;
+		let second = self.rects.swap_remove(j);
+		let first = self.rects.swap_remove(i);
+		self.add(first.union(&second));
+	}
+}
+
+/// Area the union of two rectangles covers that neither of them does
+fn wasted_area(a: &Rect, b: &Rect) -> f32 {
+	let overlap = a.intersection(b).map_or(0.0, |r| r.area());
+	a.union(b).area() - (a.area() + b.area() - overlap)
+}
+
+/// Check whether two rectangles are better drawn as their union
+fn worth_merging(a: &Rect, b: &Rect) -> bool {
+	let overlap = a.intersection(b).map_or(0.0, |r| r.area());
+	let covered = a.area() + b.area() - overlap;
+	a.union(b).area() <= covered * (1.0 + MERGE_SLACK)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_damage_merges_overlapping_rects() {
+		let mut damage = DamageRegion::new();
+		damage.add(Rect::new(0.0, 0.0, 100.0, 20.0));
+		damage.add(Rect::new(0.0, 20.0, 100.0, 20.0));
+		damage.add(Rect::new(10.0, 5.0, 10.0, 10.0));
+		assert_eq!(damage.rects(), &[Rect::new(0.0, 0.0, 100.0, 40.0)]);
+
+		// Ignored when empty
+		damage.add(Rect::new(500.0, 500.0, 0.0, 10.0));
+		assert_eq!(damage.rects().len(), 1);
+	}
+
+	#[test]
+	fn test_damage_keeps_distant_rects_apart() {
+		let mut damage = DamageRegion::new();
+		damage.add(Rect::new(0.0, 0.0, 10.0, 10.0));
+		damage.add(Rect::new(200.0, 200.0, 10.0, 10.0));
+		assert_eq!(damage.rects().len(), 2);
+		assert!(damage.intersects(&Rect::new(205.0, 195.0, 10.0, 10.0)));
+		assert!(!damage.intersects(&Rect::new(50.0, 50.0, 10.0, 10.0)));
+		assert!(damage.contains_point(5.0, 5.0));
+		assert!(!damage.contains_point(100.0, 100.0));
+		assert_eq!(damage.bounds(), Some(Rect::new(0.0, 0.0, 210.0, 210.0)));
+
+		let taken = damage.take();
+		assert_eq!(taken.rects().len(), 2);
+		assert!(damage.is_empty());
+		assert_eq!(damage.bounds(), None);
+	}
+
+	#[test]
+	fn test_damage_rect_limit() {
+		let mut damage = DamageRegion::new();
+		for i in 0..20 {
+			damage.add(Rect::new(i as f32 * 50.0, 0.0, 10.0, 10.0));
+		}
+		assert!(damage.rects().len() <= MAX_RECTS);
+		// Every rectangle added is still covered
+		for i in 0..20 {
+			assert!(damage.contains_point(i as f32 * 50.0 + 5.0, 5.0));
+		}
+	}
+
+	#[test]
+	fn test_damage_snap_to_pixels() {
+		let damage = DamageRegion::from_rect(Rect::new(-4.5, 10.2, 20.0, 5.5));
+		let snapped = damage.snap_to_pixels(12, 14);
+		assert_eq!(snapped.rects(), &[Rect::new(0.0, 10.0, 12.0, 4.0)]);
+
+		// Entirely off the surface
+		let outside = DamageRegion::from_rect(Rect::new(20.0, 20.0, 5.0, 5.0));
+		assert!(outside.snap_to_pixels(12, 14).is_empty());
+	}
+}
@@ -1,10 +1,18 @@
-//! Rendering utilities and SVG support
+//! Rendering utilities, text rasterization and SVG support
 //!
-//! Provides SVG parsing and rendering without script execution for security.
+//! Provides SVG parsing and rendering without script execution for security,
+//! font-based text rasterization shared by the software renderers, and
+//! damage tracking for partial redraws.
 
+pub mod damage;
+pub mod sanitize;
 pub mod svg;
+pub mod text;
 
+pub use damage::DamageRegion;
+pub use sanitize::{SanitizePolicy, SanitizedSvg, SvgSanitizer, SvgViolation, ViolationKind};
 pub use svg::{SvgDocument, SvgElement, SvgParser};
+pub use text::{TextPixel, TextRasterizer};
 
 /// Rendering error types
 #[derive(Debug, Clone, PartialEq)]
@@ -14,9 +22,11 @@ pub enum RenderError {
 	/// Unsupported SVG feature
 	UnsupportedFeature(String),
 	/// Script execution attempted (security violation)
-	ScriptDetected,
+	ScriptDetected(SvgViolation),
 	/// External resource loading not allowed
-	ExternalResourceBlocked(String),
+	ExternalResourceBlocked(SvgViolation),
+	/// Element or attribute outside the sanitizer's allowlist
+	DisallowedContent(SvgViolation),
 }
 
 impl std::fmt::Display for RenderError {
@@ -26,11 +36,18 @@ impl std::fmt::Display for RenderError {
 			RenderError::UnsupportedFeature(feature) => {
 				write!(f, "Unsupported SVG feature: {}", feature)
 			}
-			RenderError::ScriptDetected => {
-				write!(f, "Script execution is not allowed for security reasons")
+			RenderError::ScriptDetected(violation) => {
+				write!(
+					f,
+					"Script execution is not allowed for security reasons: {}",
+					violation
+				)
 			}
-			RenderError::ExternalResourceBlocked(url) => {
-				write!(f, "External resource blocked: {}", url)
+			RenderError::ExternalResourceBlocked(violation) => {
+				write!(f, "External resource blocked: {}", violation)
+			}
+			RenderError::DisallowedContent(violation) => {
+				write!(f, "Content not allowed in SVG: {}", violation)
 			}
 		}
 	}
@@ -47,10 +64,16 @@ mod tests {
 		let error = RenderError::InvalidSvg("Missing closing tag".to_string());
 		assert_eq!(error.to_string(), "Invalid SVG: Missing closing tag");
 
-		let script_error = RenderError::ScriptDetected;
+		let script_error = RenderError::ScriptDetected(SvgViolation {
+			kind: ViolationKind::Script,
+			element: "script".to_string(),
+			attribute: None,
+			line: 1,
+			column: 6,
+		});
 		assert_eq!(
 			script_error.to_string(),
-			"Script execution is not allowed for security reasons"
+			"Script execution is not allowed for security reasons: <script> at line 1, column 6"
 		);
 	}
 }
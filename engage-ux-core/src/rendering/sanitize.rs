@@ -0,0 +1,726 @@
+//! Allowlist-based SVG sanitization
+//!
+//! The sanitizer parses SVG as XML and walks every element and attribute,
+//! keeping only static drawing content: no scripts or event handlers, no
+//! references outside the document, and no elements such as
+//! `<foreignObject>` that embed other languages. Depending on the
+//! [`SanitizePolicy`], the first violation fails the document or every
+//! violating node is removed. Either way the output is re-serialized from
+//! the walked tree, so what is rendered is exactly what was checked.
+
+use super::RenderError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Elements of static SVG that can be drawn without fetching or running
+/// anything
+const ALLOWED_ELEMENTS: &[&str] = &[
+	"svg",
+	"g",
+	"defs",
+	"symbol",
+	"use",
+	"switch",
+	"title",
+	"desc",
+	"style",
+	"image",
+	"path",
+	"rect",
+	"circle",
+	"ellipse",
+	"line",
+	"polyline",
+	"polygon",
+	"text",
+	"tspan",
+	"textPath",
+	"linearGradient",
+	"radialGradient",
+	"stop",
+	"pattern",
+	"clipPath",
+	"mask",
+	"marker",
+	"filter",
+	"feBlend",
+	"feColorMatrix",
+	"feComponentTransfer",
+	"feComposite",
+	"feConvolveMatrix",
+	"feDiffuseLighting",
+	"feDisplacementMap",
+	"feDistantLight",
+	"feDropShadow",
+	"feFlood",
+	"feFuncA",
+	"feFuncB",
+	"feFuncG",
+	"feFuncR",
+	"feGaussianBlur",
+	"feImage",
+	"feMerge",
+	"feMergeNode",
+	"feMorphology",
+	"feOffset",
+	"fePointLight",
+	"feSpecularLighting",
+	"feSpotLight",
+	"feTile",
+	"feTurbulence",
+];
+
+/// Geometry, presentation and filter attributes without a namespace
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+	// Core
+	"id",
+	"class",
+	"style",
+	"lang",
+	"version",
+	"baseProfile",
+	"href",
+	"type",
+	"media",
+	// Geometry
+	"x",
+	"y",
+	"x1",
+	"y1",
+	"x2",
+	"y2",
+	"cx",
+	"cy",
+	"r",
+	"rx",
+	"ry",
+	"fx",
+	"fy",
+	"fr",
+	"width",
+	"height",
+	"d",
+	"points",
+	"pathLength",
+	"viewBox",
+	"preserveAspectRatio",
+	"transform",
+	// Presentation
+	"fill",
+	"fill-opacity",
+	"fill-rule",
+	"stroke",
+	"stroke-width",
+	"stroke-opacity",
+	"stroke-linecap",
+	"stroke-linejoin",
+	"stroke-miterlimit",
+	"stroke-dasharray",
+	"stroke-dashoffset",
+	"opacity",
+	"color",
+	"display",
+	"visibility",
+	"overflow",
+	"clip",
+	"clip-path",
+	"clip-rule",
+	"mask",
+	"mask-type",
+	"filter",
+	"marker",
+	"marker-start",
+	"marker-mid",
+	"marker-end",
+	"paint-order",
+	"vector-effect",
+	"shape-rendering",
+	"image-rendering",
+	"text-rendering",
+	"color-interpolation",
+	"color-interpolation-filters",
+	"isolation",
+	"mix-blend-mode",
+	"stop-color",
+	"stop-opacity",
+	"flood-color",
+	"flood-opacity",
+	"lighting-color",
+	// Text
+	"font",
+	"font-family",
+	"font-size",
+	"font-size-adjust",
+	"font-style",
+	"font-variant",
+	"font-weight",
+	"font-stretch",
+	"font-kerning",
+	"text-anchor",
+	"text-decoration",
+	"dominant-baseline",
+	"alignment-baseline",
+	"baseline-shift",
+	"letter-spacing",
+	"word-spacing",
+	"writing-mode",
+	"direction",
+	"unicode-bidi",
+	"dx",
+	"dy",
+	"rotate",
+	"textLength",
+	"lengthAdjust",
+	"startOffset",
+	"method",
+	"spacing",
+	"side",
+	// Paint servers, clips, masks and markers
+	"gradientUnits",
+	"gradientTransform",
+	"spreadMethod",
+	"offset",
+	"patternUnits",
+	"patternContentUnits",
+	"patternTransform",
+	"clipPathUnits",
+	"maskUnits",
+	"maskContentUnits",
+	"markerUnits",
+	"markerWidth",
+	"markerHeight",
+	"refX",
+	"refY",
+	"orient",
+	// Filters
+	"filterUnits",
+	"primitiveUnits",
+	"in",
+	"in2",
+	"result",
+	"stdDeviation",
+	"edgeMode",
+	"values",
+	"mode",
+	"operator",
+	"k1",
+	"k2",
+	"k3",
+	"k4",
+	"radius",
+	"order",
+	"kernelMatrix",
+	"kernelUnitLength",
+	"divisor",
+	"bias",
+	"targetX",
+	"targetY",
+	"preserveAlpha",
+	"scale",
+	"xChannelSelector",
+	"yChannelSelector",
+	"baseFrequency",
+	"numOctaves",
+	"seed",
+	"stitchTiles",
+	"tableValues",
+	"slope",
+	"intercept",
+	"amplitude",
+	"exponent",
+	"surfaceScale",
+	"specularConstant",
+	"specularExponent",
+	"diffuseConstant",
+	"azimuth",
+	"elevation",
+	"z",
+	"pointsAtX",
+	"pointsAtY",
+	"pointsAtZ",
+	"limitingConeAngle",
+];
+
+/// Raster formats that may be embedded as `data:` URIs when allowed
+const DATA_IMAGE_TYPES: &[&str] = &[
+	"data:image/png",
+	"data:image/jpeg",
+	"data:image/jpg",
+	"data:image/gif",
+	"data:image/webp",
+];
+
+/// What the sanitizer does with content outside its allowlist
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SanitizePolicy {
+	/// Fail on the first violation
+	#[default]
+	Reject,
+	/// Remove violating elements, with their children, and attributes
+	Strip,
+}
+
+/// Why a node violated the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViolationKind {
+	/// A script element, event handler or `javascript:` URL
+	Script,
+	/// A reference to anything outside the document, including `data:`
+	/// URIs that are not allowed
+	ExternalResource,
+	/// An element outside the allowlist
+	DisallowedElement,
+	/// An attribute outside the allowlist
+	DisallowedAttribute,
+}
+
+/// An element or attribute that violated the sanitization policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SvgViolation {
+	pub kind: ViolationKind,
+	/// The offending element, or the element holding the offending attribute
+	pub element: String,
+	/// The offending attribute, with its prefix
+	pub attribute: Option<String>,
+	/// Line of the node in the source, from 1
+	pub line: u32,
+	/// Column of the node in the source, from 1
+	pub column: u32,
+}
+
+impl SvgViolation {
+	/// Convert to the error reported when the policy rejects
+	pub fn into_error(self) -> RenderError {
+		match self.kind {
+			ViolationKind::Script => RenderError::ScriptDetected(self),
+			ViolationKind::ExternalResource => RenderError::ExternalResourceBlocked(self),
+			ViolationKind::DisallowedElement | ViolationKind::DisallowedAttribute => {
+				RenderError::DisallowedContent(self)
+			}
+		}
+	}
+}
+
+impl fmt::Display for SvgViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.attribute {
+			Some(attribute) => write!(f, "attribute `{}` of <{}>", attribute, self.element)?,
+			None => write!(f, "<{}>", self.element)?,
+		}
+		write!(f, " at line {}, column {}", self.line, self.column)
+	}
+}
+
+/// A sanitized document
+#[derive(Debug, Clone)]
+pub struct SanitizedSvg {
+	/// The document re-serialized with only allowed content
+	pub content: String,
+	/// Violations removed under [`SanitizePolicy::Strip`]
+	pub removed: Vec<SvgViolation>,
+}
+
+/// Allowlist-based SVG sanitizer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SvgSanitizer {
+	policy: SanitizePolicy,
+	allow_data_images: bool,
+}
+
+impl SvgSanitizer {
+	/// Create a sanitizer that rejects violations and `data:` URIs
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set what happens to violations
+	pub fn with_policy(mut self, policy: SanitizePolicy) -> Self {
+		self.policy = policy;
+		self
+	}
+
+	/// Allow PNG, JPEG, GIF and WebP images embedded as `data:` URIs
+	pub fn with_data_images(mut self, allow: bool) -> Self {
+		self.allow_data_images = allow;
+		self
+	}
+
+	/// Get the policy
+	pub fn policy(&self) -> SanitizePolicy {
+		self.policy
+	}
+
+	/// Sanitize an SVG document
+	///
+	/// Documents with a DTD are rejected as invalid, which rules out
+	/// entity expansion.
+	pub fn sanitize(&self, content: &str) -> Result<SanitizedSvg, RenderError> {
+		let document = roxmltree::Document::parse(content)
+			.map_err(|e| RenderError::InvalidSvg(format!("Failed to parse XML: {}", e)))?;
+		let mut sanitized = SanitizedSvg {
+			content: String::with_capacity(content.len()),
+			removed: Vec::new(),
+		};
+		self.write_element(document.root_element(), true, &mut sanitized)?;
+		Ok(sanitized)
+	}
+
+	/// Record a violation, failing under [`SanitizePolicy::Reject`]
+	fn violate(
+		&self,
+		violation: SvgViolation,
+		sanitized: &mut SanitizedSvg,
+	) -> Result<(), RenderError> {
+		match self.policy {
+			SanitizePolicy::Reject => Err(violation.into_error()),
+			SanitizePolicy::Strip => {
+				sanitized.removed.push(violation);
+				Ok(())
+			}
+		}
+	}
+
+	/// Write an element and its allowed attributes and children, or skip it
+	/// entirely if it violates the policy
+	fn write_element(
+		&self,
+		node: roxmltree::Node,
+		is_root: bool,
+		sanitized: &mut SanitizedSvg,
+	) -> Result<(), RenderError> {
+		let name = node.tag_name().name();
+		let position = node.document().text_pos_at(node.range().start);
+		let violation =
+			|kind, attribute: Option<String>, position: roxmltree::TextPos| SvgViolation {
+				kind,
+				element: name.to_string(),
+				attribute,
+				line: position.row,
+				column: position.col,
+			};
+
+		if let Some(kind) = self.check_element(node) {
+			return self.violate(violation(kind, None, position), sanitized);
+		}
+
+		sanitized.content.push('<');
+		sanitized.content.push_str(name);
+		if is_root {
+			// Namespaces are restated rather than copied, so documents that
+			// omit them still parse
+			sanitized.content.push_str(&format!(
+				" xmlns=\"{}\" xmlns:xlink=\"{}\"",
+				SVG_NAMESPACE, XLINK_NAMESPACE
+			));
+		}
+		for attribute in node.attributes() {
+			let qualified = match attribute.namespace() {
+				None => attribute.name().to_string(),
+				Some(XLINK_NAMESPACE) => format!("xlink:{}", attribute.name()),
+				Some(XML_NAMESPACE) => format!("xml:{}", attribute.name()),
+				Some(_) => {
+					let prefix = node
+						.lookup_prefix(attribute.namespace().unwrap_or_default())
+						.unwrap_or_default();
+					format!("{}:{}", prefix, attribute.name())
+				}
+			};
+			if let Some(kind) = self.check_attribute(&attribute) {
+				let position = node.document().text_pos_at(attribute.range().start);
+				self.violate(violation(kind, Some(qualified), position), sanitized)?;
+				continue;
+			}
+			sanitized.content.push(' ');
+			sanitized.content.push_str(&qualified);
+			sanitized.content.push_str("=\"");
+			escape_into(&mut sanitized.content, attribute.value(), true);
+			sanitized.content.push('"');
+		}
+
+		if !node.has_children() {
+			sanitized.content.push_str("/>");
+			return Ok(());
+		}
+		sanitized.content.push('>');
+		for child in node.children() {
+			if child.is_element() {
+				self.write_element(child, false, sanitized)?;
+			} else if let Some(text) = child.text()
+				&& child.is_text()
+			{
+				escape_into(&mut sanitized.content, text, false);
+			}
+			// Comments and processing instructions are dropped
+		}
+		sanitized.content.push_str("</");
+		sanitized.content.push_str(name);
+		sanitized.content.push('>');
+		Ok(())
+	}
+
+	/// Check an element's name and, for stylesheets, its contents
+	fn check_element(&self, node: roxmltree::Node) -> Option<ViolationKind> {
+		let name = node.tag_name().name();
+		if name.eq_ignore_ascii_case("script") {
+			return Some(ViolationKind::Script);
+		}
+		let in_svg = matches!(node.tag_name().namespace(), None | Some(SVG_NAMESPACE));
+		if !in_svg || !ALLOWED_ELEMENTS.contains(&name) {
+			return Some(ViolationKind::DisallowedElement);
+		}
+		if name == "style" {
+			let stylesheet: String = node.children().filter_map(|child| child.text()).collect();
+			return self.check_css(&stylesheet);
+		}
+		None
+	}
+
+	/// Check an attribute's name and value
+	fn check_attribute(&self, attribute: &roxmltree::Attribute) -> Option<ViolationKind> {
+		let name = attribute.name();
+		if name.to_ascii_lowercase().starts_with("on") {
+			return Some(ViolationKind::Script);
+		}
+		let allowed = match attribute.namespace() {
+			None => ALLOWED_ATTRIBUTES.contains(&name),
+			Some(XLINK_NAMESPACE) => name == "href",
+			Some(XML_NAMESPACE) => matches!(name, "space" | "lang"),
+			Some(_) => false,
+		};
+		if !allowed {
+			return Some(ViolationKind::DisallowedAttribute);
+		}
+
+		if name == "href" {
+			self.check_reference(attribute.value())
+		} else {
+			self.check_css(attribute.value())
+		}
+	}
+
+	/// Check a reference, which must point into the document
+	fn check_reference(&self, reference: &str) -> Option<ViolationKind> {
+		let reference = normalize(reference);
+		if reference.starts_with('#') {
+			None
+		} else if reference.starts_with("javascript:") {
+			Some(ViolationKind::Script)
+		} else if self.allow_data_images
+			&& DATA_IMAGE_TYPES
+				.iter()
+				.any(|prefix| reference.starts_with(prefix))
+		{
+			None
+		} else {
+			Some(ViolationKind::ExternalResource)
+		}
+	}
+
+	/// Check a stylesheet or attribute value for script and for `url()`s
+	/// that leave the document
+	fn check_css(&self, css: &str) -> Option<ViolationKind> {
+		let css = normalize(css);
+		if css.contains("javascript:") || css.contains("expression(") {
+			return Some(ViolationKind::Script);
+		}
+		if css.contains("@import") {
+			return Some(ViolationKind::ExternalResource);
+		}
+		css.split("url(").skip(1).find_map(|rest| {
+			let target = rest.split(')').next().unwrap_or_default();
+			self.check_reference(target.trim_matches(|c| c == '"' || c == '\''))
+		})
+	}
+}
+
+/// Lowercase a value and remove the whitespace, control characters and
+/// CSS escapes that could disguise a scheme such as `java\script:`
+fn normalize(value: &str) -> String {
+	value
+		.chars()
+		.filter(|c| !c.is_whitespace() && !c.is_control() && *c != '\\')
+		.flat_map(char::to_lowercase)
+		.collect()
+}
+
+/// Append text escaped for XML content or, with `quote`, a double-quoted
+/// attribute value
+fn escape_into(output: &mut String, text: &str, quote: bool) {
+	for c in text.chars() {
+		match c {
+			'&' => output.push_str("&amp;"),
+			'<' => output.push_str("&lt;"),
+			'>' => output.push_str("&gt;"),
+			'"' if quote => output.push_str("&quot;"),
+			_ => output.push(c),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn violation(result: Result<SanitizedSvg, RenderError>) -> SvgViolation {
+		match result {
+			Err(RenderError::ScriptDetected(violation))
+			| Err(RenderError::ExternalResourceBlocked(violation))
+			| Err(RenderError::DisallowedContent(violation)) => violation,
+			other => panic!("expected a violation, got {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_event_handlers() {
+		let sanitizer = SvgSanitizer::new();
+		for handler in ["onfocus", "onbegin", "ONLOAD", "onclick"] {
+			let svg = format!(r#"<svg><rect {}="alert(1)"/></svg>"#, handler);
+			let found = violation(sanitizer.sanitize(&svg));
+			assert_eq!(found.kind, ViolationKind::Script);
+			assert_eq!(found.element, "rect");
+			assert_eq!(found.attribute.as_deref(), Some(handler));
+		}
+	}
+
+	#[test]
+	fn test_disallowed_elements() {
+		let sanitizer = SvgSanitizer::new();
+		let svg = "<svg>\n  <foreignObject><div/></foreignObject>\n</svg>";
+		let found = violation(sanitizer.sanitize(svg));
+		assert_eq!(found.kind, ViolationKind::DisallowedElement);
+		assert_eq!(found.element, "foreignObject");
+		assert_eq!((found.line, found.column), (2, 3));
+
+		let svg = r#"<svg><animate attributeName="href" to="javascript:alert(1)"/></svg>"#;
+		assert_eq!(
+			violation(sanitizer.sanitize(svg)).kind,
+			ViolationKind::DisallowedElement
+		);
+		let svg = r#"<svg><SCRIPT>alert(1)</SCRIPT></svg>"#;
+		assert_eq!(
+			violation(sanitizer.sanitize(svg)).kind,
+			ViolationKind::Script
+		);
+	}
+
+	#[test]
+	fn test_references() {
+		let sanitizer = SvgSanitizer::new();
+		let script = r#"<svg xmlns:xlink="http://www.w3.org/1999/xlink">
+			<use xlink:href=" java&#x09;script:alert(1)"/>
+		</svg>"#;
+		let found = violation(sanitizer.sanitize(script));
+		assert_eq!(found.kind, ViolationKind::Script);
+		assert_eq!(found.attribute.as_deref(), Some("xlink:href"));
+
+		let data = r#"<svg><image href="data:image/png;base64,AAAA"/></svg>"#;
+		assert_eq!(
+			violation(sanitizer.sanitize(data)).kind,
+			ViolationKind::ExternalResource
+		);
+		assert!(
+			SvgSanitizer::new()
+				.with_data_images(true)
+				.sanitize(data)
+				.is_ok()
+		);
+		let svg_data = r#"<svg><image href="data:image/svg+xml,<svg/>"/></svg>"#;
+		assert!(
+			SvgSanitizer::new()
+				.with_data_images(true)
+				.sanitize(svg_data)
+				.is_err()
+		);
+
+		let css = r#"<svg><rect fill="url('https://x/p.svg#a')"/></svg>"#;
+		assert_eq!(
+			violation(sanitizer.sanitize(css)).kind,
+			ViolationKind::ExternalResource
+		);
+		let stylesheet = r#"<svg><style>@import "https://x/a.css";</style></svg>"#;
+		assert_eq!(violation(sanitizer.sanitize(stylesheet)).element, "style");
+	}
+
+	#[test]
+	fn test_allowed_content() {
+		let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">
+			<!-- comment -->
+			<defs><linearGradient id="g"><stop offset="0" stop-color="red"/></linearGradient></defs>
+			<rect width="10" height="10" fill="url(#g)" style="stroke: blue"/>
+			<use href="#g"/>
+			<text xml:space="preserve">see https://x &amp; &lt;more&gt;</text>
+		</svg>"##;
+		let sanitized = SvgSanitizer::new().sanitize(svg).unwrap();
+		assert!(sanitized.removed.is_empty());
+		assert!(!sanitized.content.contains("comment"));
+		assert!(
+			sanitized
+				.content
+				.contains("<text xml:space=\"preserve\">see https://x &amp; &lt;more&gt;</text>")
+		);
+		assert!(sanitized.content.starts_with(
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\""
+		));
+	}
+
+	#[test]
+	fn test_strip_policy() {
+		let svg = r#"<svg xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape">
+			<script>alert(1)</script>
+			<rect width="4" height="4" onclick="alert(1)" inkscape:label="box"/>
+		</svg>"#;
+		let sanitized = SvgSanitizer::new()
+			.with_policy(SanitizePolicy::Strip)
+			.sanitize(svg)
+			.unwrap();
+
+		let removed: Vec<_> = sanitized
+			.removed
+			.iter()
+			.map(|violation| (violation.kind, violation.attribute.as_deref()))
+			.collect();
+		assert_eq!(
+			removed,
+			[
+				(ViolationKind::Script, None),
+				(ViolationKind::Script, Some("onclick")),
+				(ViolationKind::DisallowedAttribute, Some("inkscape:label")),
+			]
+		);
+		assert!(
+			sanitized
+				.content
+				.contains("<rect width=\"4\" height=\"4\"/>")
+		);
+		assert!(!sanitized.content.contains("alert"));
+	}
+
+	#[test]
+	fn test_dtd_rejected() {
+		let svg = r#"<!DOCTYPE svg [<!ENTITY a "aaaa">]><svg><text>&a;</text></svg>"#;
+		assert!(matches!(
+			SvgSanitizer::new().sanitize(svg),
+			Err(RenderError::InvalidSvg(_))
+		));
+	}
+
+	#[test]
+	fn test_violation_display() {
+		let violation = SvgViolation {
+			kind: ViolationKind::Script,
+			element: "rect".to_string(),
+			attribute: Some("onfocus".to_string()),
+			line: 2,
+			column: 9,
+		};
+		assert_eq!(
+			violation.to_string(),
+			"attribute `onfocus` of <rect> at line 2, column 9"
+		);
+	}
+}
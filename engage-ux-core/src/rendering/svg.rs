@@ -1,12 +1,22 @@
 //! SVG parsing and rendering without script execution
 //!
-//! This module provides secure SVG parsing that explicitly blocks:
-//! - Script tags and event handlers
-//! - External resource loading
-//! - Potentially dangerous features
+//! Documents are sanitized by an [`SvgSanitizer`] before parsing, which
+//! blocks:
+//! - Script tags, event handlers and `javascript:` URLs
+//! - External resource loading and `data:` URIs
+//! - Elements and attributes outside its allowlist
+//!
+//! Parsed documents keep the usvg tree, so they can be rasterized at any
+//! size with resvg, and the sanitized markup, which they serialize as
+//! together with the sanitizer settings and what it stripped.
 
 use super::RenderError;
+use super::sanitize::{SanitizePolicy, SvgSanitizer, SvgViolation};
+use crate::color::Color;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
 
 /// SVG element types
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -88,19 +98,110 @@ pub struct SvgDocument {
 	pub height: Option<f32>,
 	/// ViewBox (min-x, min-y, width, height)
 	pub viewbox: Option<(f32, f32, f32, f32)>,
+	/// Parsed tree that is rasterized, shared between clones
+	tree: Option<Arc<usvg::Tree>>,
+	/// Sanitized markup the document was parsed from
+	source: Option<String>,
+	/// Violations the sanitizer stripped
+	removed: Vec<SvgViolation>,
+	/// Sanitizer the document was parsed with, used again when it is
+	/// deserialized
+	sanitizer: SvgSanitizer,
 }
 
 impl SvgDocument {
 	/// Create a new SVG document
+	///
+	/// Documents built from elements rather than parsed cannot be rendered.
 	pub fn new(root: SvgElement) -> Self {
 		Self {
 			root,
 			width: None,
 			height: None,
 			viewbox: None,
+			tree: None,
+			source: None,
+			removed: Vec::new(),
+			sanitizer: SvgSanitizer::new(),
 		}
 	}
 
+	/// Get the sanitized markup the document was parsed from, if it was
+	/// parsed
+	pub fn source(&self) -> Option<&str> {
+		self.source.as_deref()
+	}
+
+	/// Get the violations stripped while parsing under
+	/// [`SanitizePolicy::Strip`]
+	pub fn removed(&self) -> &[SvgViolation] {
+		&self.removed
+	}
+
+	/// Get the parsed usvg tree, if the document was parsed
+	pub fn tree(&self) -> Option<&usvg::Tree> {
+		self.tree.as_deref()
+	}
+
+	/// Rasterize the document into a `width` by `height` premultiplied
+	/// pixmap
+	///
+	/// The document is scaled to fit, keeping its aspect ratio, and centred
+	/// as with the default `preserveAspectRatio`. A tint recolors every
+	/// drawn pixel, keeping its coverage, which suits single-color icons.
+	/// Returns `None` for an empty size or a document that was not parsed.
+	pub fn render_to(
+		&self,
+		width: u32,
+		height: u32,
+		tint: Option<&Color>,
+	) -> Option<tiny_skia::Pixmap> {
+		self.render_region(width, height, (0, 0, width, height), tint)
+	}
+
+	/// Rasterize only the `(x, y, width, height)` region of the document as
+	/// [`Self::render_to`] would draw it at `width` by `height`
+	///
+	/// The pixmap has the region's size, so a document drawn far larger
+	/// than the part of it that is visible costs only that part.
+	pub fn render_region(
+		&self,
+		width: u32,
+		height: u32,
+		(x, y, region_width, region_height): (u32, u32, u32, u32),
+		tint: Option<&Color>,
+	) -> Option<tiny_skia::Pixmap> {
+		let tree = self.tree.as_ref()?;
+		if width == 0 || height == 0 {
+			return None;
+		}
+		let mut pixmap = tiny_skia::Pixmap::new(region_width, region_height)?;
+		let size = tree.size();
+		let scale = (width as f32 / size.width()).min(height as f32 / size.height());
+		let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+			(width as f32 - size.width() * scale) / 2.0 - x as f32,
+			(height as f32 - size.height() * scale) / 2.0 - y as f32,
+		);
+		resvg::render(tree, transform, &mut pixmap.as_mut());
+
+		if let Some(tint) = tint {
+			let [r, g, b, a] = tint.to_rgb().components();
+			for pixel in pixmap.pixels_mut() {
+				let alpha = pixel.alpha() as f32 / 255.0 * a.clamp(0.0, 1.0);
+				let channel = |value: f32| (value.clamp(0.0, 1.0) * alpha * 255.0).round() as u8;
+				if let Some(tinted) = tiny_skia::PremultipliedColorU8::from_rgba(
+					channel(r),
+					channel(g),
+					channel(b),
+					(alpha * 255.0).round() as u8,
+				) {
+					*pixel = tinted;
+				}
+			}
+		}
+		Some(pixmap)
+	}
+
 	/// Parse width from root element
 	pub fn parse_dimensions(&mut self) {
 		if let Some(width_str) = self.root.get_attribute("width")
@@ -130,66 +231,101 @@ impl SvgDocument {
 	}
 }
 
-/// SVG parser with security restrictions
-pub struct SvgParser {
-	/// Whether to block script tags
-	block_scripts: bool,
-	/// Whether to block external resources
-	block_external: bool,
+/// Serialized form of a parsed document
+#[derive(Serialize)]
+struct StoredSvg<'a> {
+	source: &'a str,
+	sanitizer: &'a SvgSanitizer,
+	removed: &'a [SvgViolation],
 }
 
-impl SvgParser {
-	/// Create a new secure SVG parser
-	pub fn new() -> Self {
-		Self {
-			block_scripts: true,
-			block_external: true,
+impl Serialize for SvgDocument {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match &self.source {
+			Some(source) => StoredSvg {
+				source,
+				sanitizer: &self.sanitizer,
+				removed: &self.removed,
+			}
+			.serialize(serializer),
+			None => Err(serde::ser::Error::custom(
+				"SVG document built from elements has no markup to serialize",
+			)),
 		}
 	}
+}
 
-	/// Parse SVG from string
-	pub fn parse(&self, svg_content: &str) -> Result<SvgDocument, RenderError> {
-		// Security check: detect script tags
-		if self.block_scripts && self.contains_script(svg_content) {
-			return Err(RenderError::ScriptDetected);
+/// Markup is sanitized and parsed again as it is deserialized, with the
+/// sanitizer it was first parsed with
+///
+/// Plain markup strings are accepted too, and parsed with the default
+/// sanitizer.
+impl<'de> Deserialize<'de> for SvgDocument {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Format {
+			Markup(String),
+			Stored {
+				source: String,
+				#[serde(default)]
+				sanitizer: SvgSanitizer,
+				#[serde(default)]
+				removed: Vec<SvgViolation>,
+			},
 		}
 
-		// Security check: detect event handlers
-		if self.block_scripts && self.contains_event_handlers(svg_content) {
-			return Err(RenderError::ScriptDetected);
-		}
+		let (source, sanitizer, mut removed) = match Format::deserialize(deserializer)? {
+			Format::Markup(source) => (source, SvgSanitizer::new(), Vec::new()),
+			Format::Stored {
+				source,
+				sanitizer,
+				removed,
+			} => (source, sanitizer, removed),
+		};
+		let mut doc = SvgParser::new()
+			.with_sanitizer(sanitizer)
+			.parse(&source)
+			.map_err(serde::de::Error::custom)?;
+		removed.append(&mut doc.removed);
+		doc.removed = removed;
+		Ok(doc)
+	}
+}
 
-		// Security check: detect external resources
-		if self.block_external && self.contains_external_resources(svg_content) {
-			return Err(RenderError::ExternalResourceBlocked(
-				"External resources not allowed".to_string(),
-			));
-		}
+/// SVG parser with security restrictions
+pub struct SvgParser {
+	sanitizer: SvgSanitizer,
+}
 
-		// Basic SVG parsing (simplified - would use a proper XML parser in production)
-		self.parse_basic_svg(svg_content)
+impl SvgParser {
+	/// Create a new secure SVG parser that rejects documents with
+	/// violations
+	pub fn new() -> Self {
+		Self {
+			sanitizer: SvgSanitizer::new(),
+		}
 	}
 
-	fn contains_script(&self, content: &str) -> bool {
-		content.contains("<script") || content.contains("</script>")
+	/// Sanitize documents with the given sanitizer
+	pub fn with_sanitizer(mut self, sanitizer: SvgSanitizer) -> Self {
+		self.sanitizer = sanitizer;
+		self
 	}
 
-	fn contains_event_handlers(&self, content: &str) -> bool {
-		let event_handlers = [
-			"onclick",
-			"onload",
-			"onmouseover",
-			"onmouseout",
-			"onerror",
-			"onabort",
-		];
-		event_handlers
-			.iter()
-			.any(|handler| content.to_lowercase().contains(handler))
+	/// Set whether violations reject the document or are stripped
+	pub fn with_policy(mut self, policy: SanitizePolicy) -> Self {
+		self.sanitizer = self.sanitizer.with_policy(policy);
+		self
 	}
 
-	fn contains_external_resources(&self, content: &str) -> bool {
-		content.contains("http://") || content.contains("https://")
+	/// Parse SVG from string
+	pub fn parse(&self, svg_content: &str) -> Result<SvgDocument, RenderError> {
+		let sanitized = self.sanitizer.sanitize(svg_content)?;
+		let mut doc = self.parse_basic_svg(&sanitized.content)?;
+		doc.removed = sanitized.removed;
+		doc.sanitizer = self.sanitizer.clone();
+		Ok(doc)
 	}
 
 	fn parse_basic_svg(&self, content: &str) -> Result<SvgDocument, RenderError> {
@@ -211,6 +347,8 @@ impl SvgParser {
 
 		let mut doc = SvgDocument::new(root);
 		doc.parse_dimensions();
+		doc.tree = Some(Arc::new(tree));
+		doc.source = Some(content.to_string());
 
 		Ok(doc)
 	}
@@ -228,8 +366,21 @@ impl SvgParser {
 				usvg::Node::Path(p) => {
 					let mut path_elem = SvgElement::new(SvgElementType::Path);
 					path_elem.set_attribute("id".to_string(), p.id().to_string());
-					// Store path info (actual path data would require more complex conversion)
-					path_elem.set_attribute("stroke-width".to_string(), "1".to_string());
+					path_elem.set_attribute("d".to_string(), path_data(p.data()));
+					path_elem.set_attribute(
+						"fill".to_string(),
+						paint_attribute(p.fill().map(|f| f.paint())),
+					);
+					if let Some(stroke) = p.stroke() {
+						path_elem.set_attribute(
+							"stroke".to_string(),
+							paint_attribute(Some(stroke.paint())),
+						);
+						path_elem.set_attribute(
+							"stroke-width".to_string(),
+							stroke.width().get().to_string(),
+						);
+					}
 					parent.add_child(path_elem);
 				}
 				usvg::Node::Image(_) => {
@@ -240,9 +391,8 @@ impl SvgParser {
 				usvg::Node::Text(t) => {
 					let mut text = SvgElement::new(SvgElementType::Text);
 					text.set_attribute("id".to_string(), t.id().to_string());
-					// Store placeholder text content
-					// Note: usvg's text extraction would require more complex handling
-					let text_content = "text".to_string();
+					let text_content: String =
+						t.chunks().iter().map(|chunk| chunk.text()).collect();
 					if !text_content.is_empty() {
 						text.content = Some(text_content);
 					}
@@ -253,6 +403,40 @@ impl SvgParser {
 	}
 }
 
+/// Path data in SVG `d` attribute syntax, in absolute coordinates
+fn path_data(path: &tiny_skia::Path) -> String {
+	let mut data = String::new();
+	for segment in path.segments() {
+		let _ = match segment {
+			tiny_skia::PathSegment::MoveTo(p) => write!(data, "M{} {} ", p.x, p.y),
+			tiny_skia::PathSegment::LineTo(p) => write!(data, "L{} {} ", p.x, p.y),
+			tiny_skia::PathSegment::QuadTo(p1, p) => {
+				write!(data, "Q{} {} {} {} ", p1.x, p1.y, p.x, p.y)
+			}
+			tiny_skia::PathSegment::CubicTo(p1, p2, p) => write!(
+				data,
+				"C{} {} {} {} {} {} ",
+				p1.x, p1.y, p2.x, p2.y, p.x, p.y
+			),
+			tiny_skia::PathSegment::Close => write!(data, "Z "),
+		};
+	}
+	data.truncate(data.trim_end().len());
+	data
+}
+
+/// A fill or stroke paint as an attribute value: a hex color, `none`, or
+/// the id of a gradient or pattern
+fn paint_attribute(paint: Option<&usvg::Paint>) -> String {
+	match paint {
+		None => "none".to_string(),
+		Some(usvg::Paint::Color(c)) => format!("#{:02X}{:02X}{:02X}", c.red, c.green, c.blue),
+		Some(usvg::Paint::LinearGradient(g)) => format!("url(#{})", g.id()),
+		Some(usvg::Paint::RadialGradient(g)) => format!("url(#{})", g.id()),
+		Some(usvg::Paint::Pattern(p)) => format!("url(#{})", p.id()),
+	}
+}
+
 impl Default for SvgParser {
 	fn default() -> Self {
 		Self::new()
@@ -295,13 +479,62 @@ mod tests {
 		assert_eq!(element.get_attribute("cx"), Some(&"50".to_string()));
 	}
 
+	#[test]
+	fn test_svg_document_serde() {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="10" height="10" fill="red"/></svg>"#;
+		let doc = SvgParser::new().parse(svg).unwrap();
+		assert!(doc.source().is_some());
+
+		let json = serde_json::to_string(&doc).unwrap();
+		let restored: SvgDocument = serde_json::from_str(&json).unwrap();
+		assert_eq!(restored.source(), doc.source());
+		assert_eq!(restored.width, Some(20.0));
+		assert!(restored.tree().is_some());
+
+		// Documents built from elements have no markup
+		let built = SvgDocument::new(SvgElement::new(SvgElementType::Svg));
+		assert!(serde_json::to_string(&built).is_err());
+
+		// Markup is sanitized again on the way in
+		let json = serde_json::to_string(r#"<svg onload="alert(1)"></svg>"#).unwrap();
+		assert!(serde_json::from_str::<SvgDocument>(&json).is_err());
+	}
+
+	#[test]
+	fn test_svg_document_serde_keeps_sanitizer() {
+		// A 1 by 1 PNG
+		let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8DwHwAFBQIAX8jx0gAAAABJRU5ErkJggg==";
+		let svg = format!(
+			r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10" onload="alert(1)"><image width="10" height="10" xlink:href="data:image/png;base64,{png}"/></svg>"#
+		);
+		let parser = SvgParser::new().with_sanitizer(
+			SvgSanitizer::new()
+				.with_policy(SanitizePolicy::Strip)
+				.with_data_images(true),
+		);
+		let doc = parser.parse(&svg).unwrap();
+		assert_eq!(doc.removed().len(), 1);
+		assert!(SvgParser::new().parse(doc.source().unwrap()).is_err());
+
+		let json = serde_json::to_string(&doc).unwrap();
+		let restored: SvgDocument = serde_json::from_str(&json).unwrap();
+		assert_eq!(restored.source(), doc.source());
+		assert_eq!(restored.removed(), doc.removed());
+		assert!(restored.tree().is_some());
+		assert_eq!(
+			serde_json::to_string(&restored).unwrap(),
+			json,
+			"a restored document serializes the same"
+		);
+	}
+
 	#[test]
 	fn test_svg_parser_blocks_scripts() {
 		let parser = SvgParser::new();
 		let svg_with_script = r#"<svg><script>alert('XSS')</script></svg>"#;
 
 		let result = parser.parse(svg_with_script);
-		assert!(matches!(result, Err(RenderError::ScriptDetected)));
+		assert!(matches!(result, Err(RenderError::ScriptDetected(_))));
 	}
 
 	#[test]
@@ -310,7 +543,7 @@ mod tests {
 		let svg_with_onclick = r#"<svg onclick="alert('XSS')"></svg>"#;
 
 		let result = parser.parse(svg_with_onclick);
-		assert!(matches!(result, Err(RenderError::ScriptDetected)));
+		assert!(matches!(result, Err(RenderError::ScriptDetected(_))));
 	}
 
 	#[test]
@@ -325,6 +558,26 @@ mod tests {
 		));
 	}
 
+	#[test]
+	fn test_svg_parser_strips_by_policy() {
+		let svg = r#"<svg width="10" height="10">
+			<foreignObject><div/></foreignObject>
+			<rect width="10" height="10" onfocus="alert(1)"/>
+		</svg>"#;
+		assert!(matches!(
+			SvgParser::new().parse(svg),
+			Err(RenderError::DisallowedContent(_))
+		));
+
+		let doc = SvgParser::new()
+			.with_policy(SanitizePolicy::Strip)
+			.parse(svg)
+			.unwrap();
+		assert_eq!(doc.removed().len(), 2);
+		assert_eq!(doc.root.children.len(), 1);
+		assert!(doc.render_to(10, 10, None).is_some());
+	}
+
 	#[test]
 	fn test_svg_element_type_from_tag_name() {
 		assert_eq!(
@@ -338,6 +591,57 @@ mod tests {
 		assert_eq!(SvgElementType::from_tag_name("unknown"), None);
 	}
 
+	#[test]
+	fn test_svg_parser_keeps_path_details() {
+		let parser = SvgParser::new();
+		let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+			<path d="M0 0 L10 0 L10 10 Z" fill="#ff0000" stroke="#0000ff" stroke-width="3"/>
+		</svg>"##;
+
+		let doc = parser.parse(svg).unwrap();
+		let path = &doc.root.children[0];
+		assert_eq!(path.element_type, SvgElementType::Path);
+		assert_eq!(path.get_attribute("d").unwrap(), "M0 0 L10 0 L10 10 Z");
+		assert_eq!(path.get_attribute("fill").unwrap(), "#FF0000");
+		assert_eq!(path.get_attribute("stroke").unwrap(), "#0000FF");
+		assert_eq!(path.get_attribute("stroke-width").unwrap(), "3");
+	}
+
+	#[test]
+	fn test_svg_render_to() {
+		let parser = SvgParser::new();
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+			<rect width="5" height="10" fill="red"/>
+		</svg>"#;
+		let doc = parser.parse(svg).unwrap();
+
+		// Scaled to fit 20 by 40, centred vertically
+		let pixmap = doc.render_to(20, 40, None).unwrap();
+		let pixel = |x, y| pixmap.pixel(x, y).unwrap();
+		assert_eq!(pixel(5, 20).red(), 255);
+		assert_eq!(pixel(5, 20).alpha(), 255);
+		assert_eq!(pixel(15, 20).alpha(), 0);
+		assert_eq!(pixel(5, 5).alpha(), 0);
+
+		// A region matches the same pixels of the whole raster
+		let part = doc.render_region(20, 40, (4, 18, 4, 4), None).unwrap();
+		assert_eq!((part.width(), part.height()), (4, 4));
+		assert_eq!(part.pixel(1, 2), Some(pixel(5, 20)));
+		assert_eq!(part.pixel(3, 0).unwrap().alpha(), 255);
+
+		let tint = Color::from_hex("#0000FF").unwrap().with_alpha(0.5);
+		let pixmap = doc.render_to(10, 10, Some(&tint)).unwrap();
+		let pixel = pixmap.pixel(2, 5).unwrap();
+		assert_eq!((pixel.red(), pixel.blue(), pixel.alpha()), (0, 128, 128));
+
+		assert!(doc.render_to(0, 10, None).is_none());
+		assert!(
+			SvgDocument::new(doc.root.clone())
+				.render_to(10, 10, None)
+				.is_none()
+		);
+	}
+
 	#[test]
 	fn test_svg_document_dimensions() {
 		let mut root = SvgElement::new(SvgElementType::Svg);
@@ -0,0 +1,377 @@
+//! Text rasterization
+//!
+//! [`TextRasterizer`] lays out a run of text with a font and its fallback
+//! chain and produces anti-aliased coverage for each pixel it touches.
+//! Mixed-direction text is reordered with the Unicode Bidirectional
+//! Algorithm, and each run is shaped with [`Font::shape`], which applies
+//! kerning, ligatures and the contextual forms of scripts such as Arabic. Characters the font has no glyph for
+//! are drawn with the first fallback font that has one, and color bitmap
+//! glyphs such as emoji are drawn in their own colors. Rasterized glyphs are
+//! cached per font, glyph and pixel size, so drawing the same caption every
+//! frame only repeats the layout. Measurement goes through the same [`Font`]
+//! metrics used for layout.
+
+use crate::media::MediaError;
+use crate::media::bidi;
+use crate::media::font::{Font, FontFamily, FontRegistry, FontStyle, FontWeight};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A pixel of rasterized text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPixel {
+	/// Coverage (1-255) of an outline glyph, drawn in the text color
+	Coverage(u8),
+	/// Straight RGBA color of a bitmap glyph, such as an emoji
+	Color([u8; 4]),
+}
+
+/// Pixels of a rasterized glyph
+#[derive(Debug, Clone)]
+enum GlyphBitmap {
+	/// One coverage value per pixel
+	Coverage(Vec<u8>),
+	/// Four straight RGBA bytes per pixel
+	Color(Vec<u8>),
+}
+
+/// A rasterized glyph and the metrics needed to place it
+#[derive(Debug, Clone)]
+struct CachedGlyph {
+	/// Offset of the left edge from the pen position
+	left: i32,
+	/// Offset of the top edge from the baseline
+	top: i32,
+	width: usize,
+	bitmap: GlyphBitmap,
+}
+
+impl CachedGlyph {
+	/// Rasterize a glyph, preferring its color bitmap when the font has one
+	fn rasterize(font: &Font, face: &fontdue::Font, index: u16, font_size: f32) -> Self {
+		if let Some(color) = font.color_glyph_indexed(index, font_size) {
+			return Self {
+				left: color.left,
+				top: color.top,
+				width: color.width as usize,
+				bitmap: GlyphBitmap::Color(color.pixels),
+			};
+		}
+
+		let (metrics, coverage) = face.rasterize_indexed(index, font_size);
+		Self {
+			left: metrics.xmin,
+			top: -metrics.ymin - metrics.height as i32,
+			width: metrics.width,
+			bitmap: GlyphBitmap::Coverage(coverage),
+		}
+	}
+}
+
+/// Rasterizes text into coverage values using a font and its fallbacks
+#[derive(Clone, Default)]
+pub struct TextRasterizer {
+	font: Option<Font>,
+	/// Glyphs by position of their font in the fallback chain, glyph index
+	/// and font size
+	cache: HashMap<(usize, u16, u32), CachedGlyph>,
+}
+
+impl TextRasterizer {
+	/// Create a rasterizer without a font, which draws nothing
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a rasterizer from a loaded font
+	///
+	/// Characters the font has no glyph for are drawn with its
+	/// [fallback fonts](Font::with_fallback_fonts).
+	pub fn from_font(font: &Font) -> Result<Self, MediaError> {
+		if !font.is_loaded() {
+			return Err(MediaError::InvalidData(format!(
+				"Font has no data: {}",
+				font.family.name
+			)));
+		}
+
+		Ok(Self {
+			font: Some(font.without_data()),
+			cache: HashMap::new(),
+		})
+	}
+
+	/// Create a rasterizer from a font registered under `family`
+	///
+	/// The face and the fonts it falls back on are chosen by
+	/// [`FontRegistry::resolve_with_fallbacks`].
+	pub fn from_registry(
+		registry: &FontRegistry,
+		family: &str,
+		weight: FontWeight,
+		style: FontStyle,
+	) -> Result<Self, MediaError> {
+		let font = registry
+			.resolve_with_fallbacks(&FontFamily::new(family), weight, style)
+			.ok_or_else(|| MediaError::LoadFailed(format!("Font not registered: {}", family)))?;
+
+		Self::from_font(&font)
+	}
+
+	/// Check whether a font is loaded
+	pub fn has_font(&self) -> bool {
+		self.font.is_some()
+	}
+
+	/// Get the font used for drawing and measuring
+	pub fn font(&self) -> Option<&Font> {
+		self.font.as_ref()
+	}
+
+	/// Number of glyphs currently cached
+	pub fn cached_glyphs(&self) -> usize {
+		self.cache.len()
+	}
+
+	/// Advance width of a run of text, including kerning and shaping
+	///
+	/// Returns zero without a font, matching what [`TextRasterizer::draw`]
+	/// draws.
+	pub fn measure(&self, text: &str, font_size: f32) -> f32 {
+		self.font
+			.as_ref()
+			.map_or(0.0, |font| font.measure(text, font_size))
+	}
+
+	/// Rasterize a run of text starting at `x` with its baseline at `baseline`
+	///
+	/// `plot` is called with the position of every pixel the text touches
+	/// and either its coverage or, for color glyphs, its color; pixels may
+	/// be outside the target surface.
+	pub fn draw(
+		&mut self,
+		text: &str,
+		x: f32,
+		baseline: f32,
+		font_size: f32,
+		mut plot: impl FnMut(i32, i32, TextPixel),
+	) {
+		let Self { font, cache } = self;
+		let Some(font) = font.as_ref() else {
+			return;
+		};
+		if font_size <= 0.0 {
+			return;
+		}
+
+		let mut pen = x;
+		for bidi_run in bidi::visual_runs(text) {
+			let direction = bidi_run.direction;
+			let bidi_text = &text[bidi_run.range];
+			let mut runs = font.runs(bidi_text);
+			if direction.is_rtl() {
+				runs.reverse();
+			}
+
+			for run in runs {
+				let run_text = &bidi_text[run.range.clone()];
+				let Some(face) = run.font.face() else {
+					pen += run.font.measure(run_text, font_size);
+					continue;
+				};
+
+				for shaped in run.font.shape(run_text, font_size, direction) {
+					let glyph = cache
+						.entry((run.index, shaped.glyph, font_size.to_bits()))
+						.or_insert_with(|| {
+							CachedGlyph::rasterize(run.font, face, shaped.glyph, font_size)
+						});
+					let left = (pen + shaped.x_offset + glyph.left as f32).round() as i32;
+					let top = (baseline - shaped.y_offset).round() as i32 + glyph.top;
+
+					match &glyph.bitmap {
+						GlyphBitmap::Coverage(coverage) => {
+							for (row, line) in coverage.chunks(glyph.width.max(1)).enumerate() {
+								for (column, &value) in line.iter().enumerate() {
+									if value > 0 {
+										plot(
+											left + column as i32,
+											top + row as i32,
+											TextPixel::Coverage(value),
+										);
+									}
+								}
+							}
+						}
+						GlyphBitmap::Color(pixels) => {
+							for (row, line) in pixels.chunks(glyph.width.max(1) * 4).enumerate() {
+								for (column, pixel) in line.chunks_exact(4).enumerate() {
+									if pixel[3] > 0 {
+										plot(
+											left + column as i32,
+											top + row as i32,
+											TextPixel::Color([
+												pixel[0], pixel[1], pixel[2], pixel[3],
+											]),
+										);
+									}
+								}
+							}
+						}
+					}
+
+					pen += shaped.advance;
+				}
+			}
+		}
+	}
+}
+
+impl fmt::Debug for TextRasterizer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TextRasterizer")
+			.field("has_font", &self.has_font())
+			.field("cached_glyphs", &self.cache.len())
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::media::font::FontFamily;
+
+	const TUFFY: &[u8] = include_bytes!("../../../assets/fonts/Tuffy.ttf");
+	const EMOJI: &[u8] = include_bytes!("../../../assets/fonts/EngageEmoji.ttf");
+	const DEJAVU_MONO: &[u8] = include_bytes!("../../../assets/fonts/DejaVuSansMono.ttf");
+
+	fn tuffy() -> Font {
+		Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap()
+	}
+
+	#[test]
+	fn test_without_font_draws_nothing() {
+		let mut text = TextRasterizer::new();
+		let mut plotted = 0;
+		text.draw("Hello", 0.0, 20.0, 16.0, |_, _, _| plotted += 1);
+
+		assert!(!text.has_font());
+		assert_eq!(plotted, 0);
+		assert_eq!(text.measure("Hello", 16.0), 0.0);
+	}
+
+	#[test]
+	fn test_draw_above_baseline() {
+		let mut text = TextRasterizer::from_font(&tuffy()).unwrap();
+		let mut rows = Vec::new();
+		let mut columns = Vec::new();
+		text.draw("H", 10.0, 40.0, 20.0, |x, y, _| {
+			columns.push(x);
+			rows.push(y);
+		});
+
+		// A capital sits on the baseline and starts near the pen position
+		assert!(!rows.is_empty());
+		assert_eq!(*rows.iter().max().unwrap(), 39);
+		assert!(*rows.iter().min().unwrap() < 30);
+		assert!((10..14).contains(columns.iter().min().unwrap()));
+	}
+
+	#[test]
+	fn test_glyph_cache() {
+		let mut text = TextRasterizer::from_font(&tuffy()).unwrap();
+		text.draw("abba", 0.0, 20.0, 16.0, |_, _, _| {});
+		assert_eq!(text.cached_glyphs(), 2);
+
+		text.draw("ab", 0.0, 20.0, 24.0, |_, _, _| {});
+		assert_eq!(text.cached_glyphs(), 4);
+	}
+
+	#[test]
+	fn test_measure_applies_kerning() {
+		let text = TextRasterizer::from_font(&tuffy()).unwrap();
+		let separate = text.measure("A", 32.0) + text.measure("V", 32.0);
+
+		assert!(text.measure("AV", 32.0) < separate);
+		assert!(text.measure("Hello", 16.0) > text.measure("Hell", 16.0));
+	}
+
+	#[test]
+	fn test_from_registry() {
+		let mut registry = FontRegistry::new();
+		registry.register(Font::new(FontFamily::new("Tuffy"), 16.0));
+		let mut bold = tuffy();
+		bold.weight = FontWeight::Bold;
+		registry.register(bold);
+
+		// The exact match has no data, so another face of the family is used
+		let text = TextRasterizer::from_registry(
+			&registry,
+			"Tuffy",
+			FontWeight::Normal,
+			FontStyle::Normal,
+		)
+		.unwrap();
+		assert!(text.has_font());
+
+		let missing = TextRasterizer::from_registry(
+			&registry,
+			"Arial",
+			FontWeight::Normal,
+			FontStyle::Normal,
+		);
+		assert!(matches!(missing, Err(MediaError::LoadFailed(_))));
+	}
+
+	#[test]
+	fn test_draw_color_fallback() {
+		let mut registry = FontRegistry::new();
+		registry.register(tuffy());
+		registry.register(Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap());
+		let mut text = TextRasterizer::from_registry(
+			&registry,
+			"Tuffy",
+			FontWeight::Normal,
+			FontStyle::Normal,
+		)
+		.unwrap();
+
+		let mut outline = Vec::new();
+		let mut color = Vec::new();
+		text.draw("H\u{1F600}", 0.0, 40.0, 20.0, |x, _, pixel| match pixel {
+			TextPixel::Coverage(_) => outline.push(x),
+			TextPixel::Color(rgba) => color.push((x, rgba)),
+		});
+
+		// The emoji is drawn in its own colors after the outline glyph
+		let advance = text.measure("H", 20.0);
+		let emoji_left = advance.round() as i32;
+		assert!(outline.iter().all(|&x| x < emoji_left));
+		assert!(color.iter().all(|&(x, _)| x >= emoji_left));
+		assert!(color.iter().any(|&(_, rgba)| rgba == [255, 204, 0, 255]));
+		assert_eq!(text.cached_glyphs(), 2);
+		assert_eq!(text.measure("H\u{1F600}", 20.0), advance + 20.0);
+	}
+
+	#[test]
+	fn test_draw_bidi_and_shaping() {
+		let font = Font::load_from_bytes(DEJAVU_MONO.to_vec(), 16.0).unwrap();
+		let mut text = TextRasterizer::from_font(&font).unwrap();
+
+		let mut latin = Vec::new();
+		text.draw("x", 0.0, 40.0, 20.0, |x, y, pixel| {
+			latin.push((x, y, pixel))
+		});
+		let mut mixed = Vec::new();
+		text.draw("\u{0628}\u{0628} x", 0.0, 40.0, 20.0, |x, y, pixel| {
+			mixed.push((x, y, pixel))
+		});
+
+		// The paragraph is right-to-left, so the Latin letter is drawn first
+		// on the left, and the two behs are drawn in different joining forms
+		assert!(latin.iter().all(|pixel| mixed.contains(pixel)));
+		assert_eq!(text.cached_glyphs(), 4);
+		let advance = text.measure("x", 20.0);
+		assert!(mixed.iter().any(|&(x, _, _)| x as f32 > advance * 2.0));
+	}
+}
@@ -143,6 +143,7 @@ fn main() {
 
 // Helper drawing functions
 
+#[allow(clippy::too_many_arguments)]
 fn draw_filled_rect(
 	buffer: &mut [u32],
 	width: u32,
@@ -169,6 +170,7 @@ fn draw_filled_rect(
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_rect_outline(
 	buffer: &mut [u32],
 	width: u32,
@@ -262,6 +264,7 @@ fn draw_filled_circle(
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_circle_outline(
 	buffer: &mut [u32],
 	width: u32,
@@ -295,6 +298,7 @@ fn draw_circle_outline(
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_line(
 	buffer: &mut [u32],
 	width: u32,
@@ -0,0 +1,221 @@
+//! Solid colors and gradients that shapes are filled and stroked with
+
+use super::renderer::Color;
+use serde::{Deserialize, Serialize};
+
+/// A color at a position along a gradient
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+	/// Position along the gradient, from 0.0 at its start to 1.0 at its end
+	pub offset: f32,
+	pub color: Color,
+}
+
+impl GradientStop {
+	/// Create a gradient stop
+	pub fn new(offset: f32, color: Color) -> Self {
+		Self { offset, color }
+	}
+}
+
+/// What a shape is painted with
+///
+/// Gradients are given in the same pixel coordinates as the shape and
+/// extend their end colors beyond their first and last stops; gradients
+/// with no length paint their last color, as in SVG. Their stops
+/// are in order of offset; [`Brush::linear`] and [`Brush::radial`] sort
+/// them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Brush {
+	/// A single color
+	Solid(Color),
+	/// Colors blended along the line from `(x1, y1)` to `(x2, y2)`
+	LinearGradient {
+		x1: f32,
+		y1: f32,
+		x2: f32,
+		y2: f32,
+		stops: Vec<GradientStop>,
+	},
+	/// Colors blended outwards from `(cx, cy)` to `radius`
+	RadialGradient {
+		cx: f32,
+		cy: f32,
+		radius: f32,
+		stops: Vec<GradientStop>,
+	},
+}
+
+impl Brush {
+	/// Create a linear gradient from `(x1, y1)` to `(x2, y2)`
+	pub fn linear(x1: f32, y1: f32, x2: f32, y2: f32, stops: Vec<GradientStop>) -> Self {
+		Self::LinearGradient {
+			x1,
+			y1,
+			x2,
+			y2,
+			stops: sorted(stops),
+		}
+	}
+
+	/// Create a radial gradient around `(cx, cy)`
+	pub fn radial(cx: f32, cy: f32, radius: f32, stops: Vec<GradientStop>) -> Self {
+		Self::RadialGradient {
+			cx,
+			cy,
+			radius,
+			stops: sorted(stops),
+		}
+	}
+
+	/// Color the brush paints at a point
+	pub fn color_at(&self, x: f32, y: f32) -> Color {
+		match self {
+			Self::Solid(color) => *color,
+			Self::LinearGradient {
+				x1,
+				y1,
+				x2,
+				y2,
+				stops,
+			} => {
+				let (dx, dy) = (x2 - x1, y2 - y1);
+				let length = dx * dx + dy * dy;
+				let t = if length > 0.0 {
+					((x - x1) * dx + (y - y1) * dy) / length
+				} else {
+					1.0
+				};
+				interpolate(stops, t)
+			}
+			Self::RadialGradient {
+				cx,
+				cy,
+				radius,
+				stops,
+			} => {
+				let t = if *radius > 0.0 {
+					(x - cx).hypot(y - cy) / radius
+				} else {
+					1.0
+				};
+				interpolate(stops, t)
+			}
+		}
+	}
+
+	/// The brush's color when it is a single color
+	pub fn solid_color(&self) -> Option<Color> {
+		match self {
+			Self::Solid(color) => Some(*color),
+			_ => None,
+		}
+	}
+}
+
+impl From<Color> for Brush {
+	fn from(color: Color) -> Self {
+		Self::Solid(color)
+	}
+}
+
+/// Order stops by offset, keeping the order of stops at the same offset
+fn sorted(mut stops: Vec<GradientStop>) -> Vec<GradientStop> {
+	stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+	stops
+}
+
+/// Color of a gradient at `t`, padding with the end colors
+fn interpolate(stops: &[GradientStop], t: f32) -> Color {
+	let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+		return Color::rgba(0.0, 0.0, 0.0, 0.0);
+	};
+	if t <= first.offset {
+		return first.color;
+	}
+	if t >= last.offset {
+		return last.color;
+	}
+
+	let next = stops
+		.iter()
+		.position(|stop| stop.offset > t)
+		.unwrap_or(stops.len() - 1);
+	let (a, b) = (stops[next - 1], stops[next]);
+	let span = b.offset - a.offset;
+	let f = if span > 0.0 {
+		(t - a.offset) / span
+	} else {
+		1.0
+	};
+	let mix = |from: f32, to: f32| from + (to - from) * f;
+	Color::rgba(
+		mix(a.color.r, b.color.r),
+		mix(a.color.g, b.color.g),
+		mix(a.color.b, b.color.b),
+		mix(a.color.a, b.color.a),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_solid() {
+		let brush = Brush::from(Color::rgb(1.0, 0.0, 0.0));
+		assert_eq!(brush.color_at(5.0, 5.0), Color::rgb(1.0, 0.0, 0.0));
+		assert_eq!(brush.solid_color(), Some(Color::rgb(1.0, 0.0, 0.0)));
+	}
+
+	#[test]
+	fn test_linear_gradient() {
+		let brush = Brush::linear(
+			0.0,
+			0.0,
+			100.0,
+			0.0,
+			vec![
+				GradientStop::new(1.0, Color::rgb(1.0, 1.0, 1.0)),
+				GradientStop::new(0.0, Color::rgb(0.0, 0.0, 0.0)),
+			],
+		);
+
+		assert_eq!(brush.color_at(50.0, 30.0), Color::rgb(0.5, 0.5, 0.5));
+		assert_eq!(brush.color_at(-10.0, 0.0), Color::rgb(0.0, 0.0, 0.0));
+		assert_eq!(brush.color_at(200.0, 0.0), Color::rgb(1.0, 1.0, 1.0));
+		assert_eq!(brush.solid_color(), None);
+	}
+
+	#[test]
+	fn test_radial_gradient() {
+		let brush = Brush::radial(
+			50.0,
+			50.0,
+			40.0,
+			vec![
+				GradientStop::new(0.0, Color::rgb(1.0, 0.0, 0.0)),
+				GradientStop::new(0.5, Color::rgb(0.0, 1.0, 0.0)),
+				GradientStop::new(1.0, Color::rgba(0.0, 0.0, 1.0, 0.0)),
+			],
+		);
+
+		assert_eq!(brush.color_at(50.0, 50.0), Color::rgb(1.0, 0.0, 0.0));
+		assert_eq!(brush.color_at(50.0, 70.0), Color::rgb(0.0, 1.0, 0.0));
+		assert_eq!(brush.color_at(50.0, 80.0).a, 0.5);
+		assert_eq!(brush.color_at(50.0, 90.0).a, 0.0);
+	}
+
+	#[test]
+	fn test_degenerate_gradients() {
+		let brush = Brush::linear(0.0, 0.0, 10.0, 0.0, Vec::new());
+		assert_eq!(brush.color_at(5.0, 0.0).a, 0.0);
+
+		let stops = vec![
+			GradientStop::new(0.0, Color::rgb(1.0, 0.0, 0.0)),
+			GradientStop::new(1.0, Color::rgb(0.0, 0.0, 1.0)),
+		];
+		let brush = Brush::linear(5.0, 5.0, 5.0, 5.0, stops);
+		assert_eq!(brush.color_at(0.0, 0.0), Color::rgb(0.0, 0.0, 1.0));
+	}
+}
@@ -0,0 +1,461 @@
+//! Recorded render commands that can be cached and replayed
+//!
+//! A [`DisplayList`] records the commands a component subtree draws, by
+//! painting into it like any other [`RenderContext`]. Replaying the list
+//! draws the same thing again without running paint code; it can be moved
+//! by a translation, and drawing commands outside a clip rectangle can be
+//! culled first. Lists serialize, so recorded drawing can be kept as test
+//! fixtures.
+//!
+//! A [`DisplayListCache`] keeps lists by [`ComponentId`] and drops the ones
+//! the component tree reports as stale, so a list is recorded again after
+//! its component or a descendant changes.
+
+use super::renderer::{self, Rect, RenderCommand, RenderContext};
+use super::transform::Transform;
+use engage_ux_core::component::{ComponentId, ComponentTree};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A recorded list of render commands
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisplayList {
+	commands: Vec<RenderCommand>,
+}
+
+impl DisplayList {
+	/// Create an empty list
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a list from commands that were already recorded
+	pub fn from_commands(commands: Vec<RenderCommand>) -> Self {
+		Self { commands }
+	}
+
+	/// Append a command
+	pub fn push(&mut self, command: RenderCommand) {
+		self.commands.push(command);
+	}
+
+	/// Get the recorded commands
+	pub fn commands(&self) -> &[RenderCommand] {
+		&self.commands
+	}
+
+	/// Number of recorded commands
+	pub fn len(&self) -> usize {
+		self.commands.len()
+	}
+
+	/// Check whether nothing has been recorded
+	pub fn is_empty(&self) -> bool {
+		self.commands.is_empty()
+	}
+
+	/// Forget every recorded command
+	pub fn clear(&mut self) {
+		self.commands.clear();
+	}
+
+	/// Rectangle holding everything the list draws
+	///
+	/// See [`RenderCommand::bounds`].
+	pub fn bounds(&self) -> Option<Rect> {
+		renderer::commands_bounds(&self.commands)
+	}
+
+	/// The list moved by `dx` and `dy`
+	///
+	/// The commands are wrapped in a transform rather than rewritten.
+	pub fn translated(&self, dx: f32, dy: f32) -> DisplayList {
+		if dx == 0.0 && dy == 0.0 {
+			return self.clone();
+		}
+		let mut commands = Vec::with_capacity(self.commands.len() + 2);
+		commands.push(RenderCommand::PushTransform(Transform::from_translate(
+			dx, dy,
+		)));
+		commands.extend(self.commands.iter().cloned());
+		commands.push(RenderCommand::PopTransform);
+		DisplayList { commands }
+	}
+
+	/// The list without the drawing commands that fall entirely outside
+	/// `clip`, in the coordinates the list starts in
+	///
+	/// Clips the list sets itself narrow what is visible further. Commands
+	/// that change state, and `Clear`, are always kept.
+	pub fn culled(&self, clip: Rect) -> DisplayList {
+		let mut transforms = vec![Transform::identity()];
+		let mut visible = vec![Some(clip)];
+		let mut commands = Vec::with_capacity(self.commands.len());
+
+		for command in &self.commands {
+			let transform = transforms.last().copied().unwrap_or_default();
+			let area = visible.last().copied().flatten();
+			match command {
+				RenderCommand::PushTransform(pushed) => {
+					transforms.push(transform.pre_concat(pushed));
+				}
+				RenderCommand::PopTransform if transforms.len() > 1 => {
+					transforms.pop();
+				}
+				RenderCommand::SetClip(rect) => {
+					let rect = transform.map_rect(*rect);
+					visible.push(area.and_then(|area| area.intersection(&rect)));
+				}
+				RenderCommand::RestoreClip if visible.len() > 1 => {
+					visible.pop();
+				}
+				_ => {}
+			}
+
+			let keep = command.bounds().is_none_or(|bounds| {
+				area.is_some_and(|area| area.intersects(&transform.map_rect(bounds)))
+			});
+			if keep {
+				commands.push(command.clone());
+			}
+		}
+		DisplayList { commands }
+	}
+
+	/// Draw the recorded commands into a context
+	pub fn replay(&self, context: &mut dyn RenderContext) {
+		context.execute_batch(&self.commands);
+	}
+
+	/// Draw the recorded commands that reach into `clip` into a context
+	pub fn replay_culled(&self, context: &mut dyn RenderContext, clip: Rect) {
+		self.culled(clip).replay(context);
+	}
+
+	/// Load a list from a JSON string
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+
+	/// Save the list to a JSON string
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		serde_json::to_string_pretty(self)
+	}
+}
+
+/// Painting into a list records the commands
+///
+/// A new frame starts a new recording. The list has no surface of its own,
+/// so its size is zero.
+impl RenderContext for DisplayList {
+	fn execute(&mut self, command: RenderCommand) {
+		self.commands.push(command);
+	}
+
+	fn execute_batch(&mut self, commands: &[RenderCommand]) {
+		self.commands.extend_from_slice(commands);
+	}
+
+	fn begin_frame(&mut self) {
+		self.commands.clear();
+	}
+
+	fn end_frame(&mut self) {}
+
+	fn size(&self) -> (u32, u32) {
+		(0, 0)
+	}
+}
+
+/// Display lists kept by the component they draw
+#[derive(Debug, Default)]
+pub struct DisplayListCache {
+	lists: HashMap<ComponentId, DisplayList>,
+}
+
+impl DisplayListCache {
+	/// Create an empty cache
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Get the list cached for a component
+	pub fn get(&self, id: ComponentId) -> Option<&DisplayList> {
+		self.lists.get(&id)
+	}
+
+	/// Cache a list for a component, returning the one it replaces
+	pub fn insert(&mut self, id: ComponentId, list: DisplayList) -> Option<DisplayList> {
+		self.lists.insert(id, list)
+	}
+
+	/// Get the list cached for a component, recording it first if there is
+	/// none
+	pub fn get_or_record(
+		&mut self,
+		id: ComponentId,
+		record: impl FnOnce(&mut DisplayList),
+	) -> &DisplayList {
+		self.lists.entry(id).or_insert_with(|| {
+			let mut list = DisplayList::new();
+			record(&mut list);
+			list
+		})
+	}
+
+	/// Drop the list cached for a component
+	pub fn invalidate(&mut self, id: ComponentId) -> Option<DisplayList> {
+		self.lists.remove(&id)
+	}
+
+	/// Drop the lists of every component the tree reports as stale since
+	/// it was last asked
+	///
+	/// Components written to outside [`ComponentTree::update`] are only
+	/// stale once passed to [`ComponentTree::invalidate`]. See
+	/// [`ComponentTree::take_stale`].
+	pub fn invalidate_stale(&mut self, tree: &mut ComponentTree) {
+		for id in tree.take_stale() {
+			self.lists.remove(&id);
+		}
+	}
+
+	/// Drop every cached list
+	pub fn clear(&mut self) {
+		self.lists.clear();
+	}
+
+	/// Number of cached lists
+	pub fn len(&self) -> usize {
+		self.lists.len()
+	}
+
+	/// Check whether no lists are cached
+	pub fn is_empty(&self) -> bool {
+		self.lists.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::backends::SoftbufferRenderContext;
+	use crate::backends::image::ImageCommand;
+	use crate::backends::renderer::Color;
+	use engage_ux_core::component::{Component, ComponentProperties};
+	use engage_ux_core::layout::{FlexDirection, FlexItem, FlexLayout, LayoutSolver, Size, Unit};
+	use engage_ux_core::media::ImageData;
+	use engage_ux_core::media::image::{ColorType, ImageFormat};
+	use engage_ux_core::rendering::SvgParser;
+	use std::sync::Arc;
+
+	fn fill(x: f32, y: f32) -> RenderCommand {
+		RenderCommand::FillRect {
+			rect: Rect::new(x, y, 10.0, 10.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		}
+	}
+
+	#[test]
+	fn test_display_list_records_and_replays() {
+		let mut list = DisplayList::new();
+		list.begin_frame();
+		list.execute(fill(0.0, 0.0));
+		list.end_frame();
+		assert_eq!(list.len(), 1);
+
+		let mut context = SoftbufferRenderContext::new(40, 20);
+		list.translated(20.0, 5.0).replay(&mut context);
+		assert_eq!(context.buffer()[0], 0);
+		assert_eq!(context.buffer()[5 * 40 + 20], 0xFFFF0000);
+		assert_eq!(
+			list.translated(20.0, 5.0).bounds(),
+			Some(Rect::new(19.0, 4.0, 12.0, 12.0))
+		);
+
+		// A new frame starts a new recording
+		list.begin_frame();
+		assert!(list.is_empty());
+	}
+
+	#[test]
+	fn test_display_list_culling() {
+		let list = DisplayList::from_commands(vec![
+			fill(0.0, 0.0),
+			fill(100.0, 0.0),
+			RenderCommand::PushTransform(Transform::from_translate(100.0, 0.0)),
+			fill(0.0, 0.0),
+			RenderCommand::PopTransform,
+			// Nothing inside this clip is visible
+			RenderCommand::SetClip(Rect::new(200.0, 0.0, 10.0, 10.0)),
+			fill(0.0, 0.0),
+			RenderCommand::RestoreClip,
+		]);
+
+		let culled = list.culled(Rect::new(0.0, 0.0, 50.0, 50.0));
+		let fills = culled
+			.commands()
+			.iter()
+			.filter(|command| matches!(command, RenderCommand::FillRect { .. }))
+			.count();
+		assert_eq!(fills, 1);
+		// State changes are kept
+		assert_eq!(culled.len(), 5);
+	}
+
+	#[test]
+	fn test_display_list_serialization() {
+		let image = Arc::new(ImageData::new(
+			1,
+			1,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			vec![0, 0, 255, 255],
+		));
+		let svg = SvgParser::new()
+			.parse(r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4"><rect width="4" height="4"/></svg>"#)
+			.unwrap();
+		let list = DisplayList::from_commands(vec![
+			fill(1.0, 2.0),
+			RenderCommand::PushTransform(Transform::from_rotate(0.5)),
+			RenderCommand::Image(ImageCommand::new(image, Rect::new(0.0, 0.0, 4.0, 4.0))),
+			RenderCommand::Svg {
+				document: Arc::new(svg),
+				rect: Rect::new(4.0, 0.0, 4.0, 4.0),
+				tint: Some(Color::rgb(0.0, 1.0, 0.0)),
+			},
+			RenderCommand::PopTransform,
+		]);
+
+		let json = list.to_json().unwrap();
+		let restored = DisplayList::from_json(&json).unwrap();
+		assert_eq!(restored.len(), list.len());
+		assert_eq!(restored.to_json().unwrap(), json);
+
+		// Both draw the same pixels
+		let mut expected = SoftbufferRenderContext::new(12, 8);
+		list.replay(&mut expected);
+		let mut actual = SoftbufferRenderContext::new(12, 8);
+		restored.replay(&mut actual);
+		assert_eq!(actual.buffer(), expected.buffer());
+	}
+
+	struct Node(ComponentProperties);
+
+	impl Component for Node {
+		fn id(&self) -> ComponentId {
+			self.0.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.0
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.0
+		}
+	}
+
+	#[test]
+	fn test_display_list_cache() {
+		let mut tree = ComponentTree::new();
+		tree.insert(None, Node(ComponentProperties::new(1)))
+			.unwrap();
+		tree.insert(Some(1), Node(ComponentProperties::new(2)))
+			.unwrap();
+		tree.insert(None, Node(ComponentProperties::new(3)))
+			.unwrap();
+		tree.take_stale();
+
+		let mut cache = DisplayListCache::new();
+		let mut recorded = 0;
+		for id in [1, 3] {
+			cache.get_or_record(id, |list| {
+				recorded += 1;
+				list.push(fill(0.0, 0.0));
+			});
+		}
+		// Cached lists are not recorded again
+		cache.get_or_record(1, |_| recorded += 1);
+		assert_eq!(recorded, 2);
+
+		// Direct writes leave the lists cached until the component is
+		// invalidated
+		tree.get(3)
+			.unwrap()
+			.try_write()
+			.unwrap()
+			.properties_mut()
+			.enabled = false;
+		cache.invalidate_stale(&mut tree);
+		assert_eq!(cache.len(), 2);
+
+		// Changing a child drops its ancestor's list too
+		tree.update(2, |component| component.properties_mut().enabled = false)
+			.unwrap();
+		cache.invalidate_stale(&mut tree);
+		assert!(cache.get(1).is_none());
+		assert!(cache.get(3).is_some());
+		assert_eq!(cache.len(), 1);
+
+		assert!(cache.invalidate(3).is_some());
+		assert!(cache.is_empty());
+	}
+
+	struct Row(ComponentProperties);
+
+	impl Component for Row {
+		fn id(&self) -> ComponentId {
+			self.0.id
+		}
+
+		fn properties(&self) -> &ComponentProperties {
+			&self.0
+		}
+
+		fn properties_mut(&mut self) -> &mut ComponentProperties {
+			&mut self.0
+		}
+
+		fn flex_layout(&self) -> Option<FlexLayout> {
+			Some(FlexLayout::new(FlexDirection::Row))
+		}
+	}
+
+	#[test]
+	fn test_display_list_cache_after_layout() {
+		let width = |width| FlexItem::new().with_width(Size::Fixed(Unit::pixels(width)));
+		let mut tree = ComponentTree::new();
+		let mut row = ComponentProperties::new(1);
+		row.bounds = engage_ux_core::component::Rect::new(0.0, 0.0, 200.0, 20.0);
+		tree.insert(None, Row(row)).unwrap();
+		for id in [2, 3, 4] {
+			let mut node = ComponentProperties::new(id);
+			node.flex = width(50.0);
+			tree.insert(Some(1), Node(node)).unwrap();
+		}
+		let solver = LayoutSolver::new();
+		solver.layout(&mut tree, 1).unwrap();
+		tree.take_stale();
+
+		let mut cache = DisplayListCache::new();
+		for id in [1, 2, 3, 4] {
+			cache.get_or_record(id, |list| list.push(fill(0.0, 0.0)));
+		}
+
+		// Growing the middle child moves the last one; the first keeps its
+		// list
+		tree.get(3)
+			.unwrap()
+			.try_write()
+			.unwrap()
+			.properties_mut()
+			.flex = width(80.0);
+		solver.layout(&mut tree, 1).unwrap();
+		cache.invalidate_stale(&mut tree);
+		assert!(cache.get(2).is_some());
+		for id in [1, 3, 4] {
+			assert!(cache.get(id).is_none());
+		}
+	}
+}
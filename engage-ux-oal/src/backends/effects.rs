@@ -0,0 +1,369 @@
+//! Blurred shadows and backdrop blur for the software renderers
+//!
+//! Both effects are built on a Gaussian blur, approximated by three
+//! successive box blurs in each direction. Shadows blur the coverage of
+//! their shape; backdrop blur blurs the pixels already drawn behind a shape
+//! and blends them back in where the shape covers.
+
+use super::path::{CornerRadii, FillRule, Path};
+use super::renderer::Rect;
+use super::scanline;
+use super::transform::Transform;
+
+/// Box blurs that approximate one Gaussian blur
+const BOX_PASSES: usize = 3;
+
+/// Coverage of a shape over a region of the surface
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Mask {
+	/// Left edge of the region on the surface
+	pub x: i32,
+	/// Top edge of the region on the surface
+	pub y: i32,
+	pub width: usize,
+	pub height: usize,
+	/// Coverage from 0.0 to 1.0, row by row
+	pub coverage: Vec<f32>,
+}
+
+impl Mask {
+	/// Rasterize a rounded rectangle drawn under a transform into a mask of
+	/// the pixels of a `width` by `height` surface within `padding` pixels
+	/// of it
+	pub fn rounded_rect(
+		rect: Rect,
+		radii: CornerRadii,
+		transform: &Transform,
+		padding: f32,
+		width: u32,
+		height: u32,
+	) -> Option<Self> {
+		let bounds = transform.map_rect(rect);
+		let left = ((bounds.x - padding).floor() as i32).max(0);
+		let top = ((bounds.y - padding).floor() as i32).max(0);
+		let right = ((bounds.x + bounds.width + padding).ceil() as i32).min(width as i32);
+		let bottom = ((bounds.y + bounds.height + padding).ceil() as i32).min(height as i32);
+		if rect.width <= 0.0 || rect.height <= 0.0 || right <= left || bottom <= top {
+			return None;
+		}
+
+		let mut mask = Self {
+			x: left,
+			y: top,
+			width: (right - left) as usize,
+			height: (bottom - top) as usize,
+			coverage: vec![0.0; ((right - left) * (bottom - top)) as usize],
+		};
+		let local = Transform::from_translate(-left as f32, -top as f32).pre_concat(transform);
+		let path = Path::rounded_rect(rect, radii).transformed(&local);
+		scanline::rasterize(
+			&scanline::fill_polygons(&path),
+			FillRule::NonZero,
+			mask.width as u32,
+			mask.height as u32,
+			|x, y, coverage| mask.coverage[y as usize * mask.width + x as usize] = coverage,
+		);
+		Some(mask)
+	}
+
+	/// Mask of a shadow cast by a rounded rectangle, like CSS `box-shadow`
+	///
+	/// The rectangle is moved by the offsets, grown by `spread` on every
+	/// side and blurred with a standard deviation of half `blur_radius`, all
+	/// in the coordinates of the transform it is drawn under.
+	#[allow(clippy::too_many_arguments)]
+	pub fn shadow(
+		rect: Rect,
+		radii: CornerRadii,
+		transform: &Transform,
+		offset_x: f32,
+		offset_y: f32,
+		blur_radius: f32,
+		spread: f32,
+		width: u32,
+		height: u32,
+	) -> Option<Self> {
+		let grow = |radius: f32| {
+			if radius > 0.0 {
+				(radius + spread).max(0.0)
+			} else {
+				0.0
+			}
+		};
+		let shape = Rect::new(
+			rect.x + offset_x - spread,
+			rect.y + offset_y - spread,
+			rect.width + spread * 2.0,
+			rect.height + spread * 2.0,
+		);
+		let radii = CornerRadii::new(
+			grow(radii.top_left),
+			grow(radii.top_right),
+			grow(radii.bottom_right),
+			grow(radii.bottom_left),
+		);
+
+		let blur_radius = blur_radius * transform.scale_factor();
+		let padding = blur_extent(blur_radius);
+		let mut mask = Self::rounded_rect(shape, radii, transform, padding, width, height)?;
+		gaussian_blur(
+			&mut mask.coverage,
+			mask.width,
+			mask.height,
+			sigma(blur_radius),
+		);
+		Some(mask)
+	}
+
+	/// Blur the premultiplied RGBA pixels of the mask's region and mix them
+	/// back into the originals where the mask covers
+	pub fn blur_backdrop(&self, pixels: &mut [[f32; 4]], blur_radius: f32) {
+		let mut blurred = pixels.to_vec();
+		let mut channel = vec![0.0; blurred.len()];
+		for index in 0..4 {
+			for (value, pixel) in channel.iter_mut().zip(blurred.iter()) {
+				*value = pixel[index];
+			}
+			gaussian_blur(&mut channel, self.width, self.height, sigma(blur_radius));
+			for (value, pixel) in channel.iter().zip(blurred.iter_mut()) {
+				pixel[index] = *value;
+			}
+		}
+
+		for ((pixel, blurred), coverage) in pixels.iter_mut().zip(blurred).zip(&self.coverage) {
+			for (original, blurred) in pixel.iter_mut().zip(blurred) {
+				*original += (blurred - *original) * coverage;
+			}
+		}
+	}
+
+	/// Coverage at a position within the mask
+	pub fn at(&self, column: usize, row: usize) -> f32 {
+		self.coverage[row * self.width + column]
+	}
+}
+
+/// Standard deviation of the Gaussian for a CSS blur radius
+fn sigma(blur_radius: f32) -> f32 {
+	blur_radius.max(0.0) / 2.0
+}
+
+/// How far a blur reaches beyond its source, three standard deviations
+pub(crate) fn blur_extent(blur_radius: f32) -> f32 {
+	(sigma(blur_radius) * 3.0).ceil()
+}
+
+/// Blur one channel of a `width` by `height` image in place
+///
+/// Pixels beyond the edges repeat the nearest edge pixel.
+pub(crate) fn gaussian_blur(channel: &mut [f32], width: usize, height: usize, sigma: f32) {
+	if sigma <= 0.0 || width == 0 || height == 0 {
+		return;
+	}
+	let mut scratch = vec![0.0; width.max(height)];
+	for size in box_sizes(sigma) {
+		let radius = (size - 1) / 2;
+		if radius == 0 {
+			continue;
+		}
+		for row in 0..height {
+			let line = &mut channel[row * width..(row + 1) * width];
+			box_blur(line, &mut scratch[..width], radius);
+		}
+		let mut column = vec![0.0; height];
+		for x in 0..width {
+			for (y, value) in column.iter_mut().enumerate() {
+				*value = channel[y * width + x];
+			}
+			box_blur(&mut column, &mut scratch[..height], radius);
+			for (y, value) in column.iter().enumerate() {
+				channel[y * width + x] = *value;
+			}
+		}
+	}
+}
+
+/// Widths of the box blurs whose succession approximates a Gaussian
+fn box_sizes(sigma: f32) -> [usize; BOX_PASSES] {
+	let passes = BOX_PASSES as f32;
+	let ideal = (12.0 * sigma * sigma / passes + 1.0).sqrt();
+	let mut lower = ideal.floor() as usize;
+	if lower.is_multiple_of(2) {
+		lower = lower.saturating_sub(1).max(1);
+	}
+	let upper = lower + 2;
+	let lower_f = lower as f32;
+	let lower_passes = ((12.0 * sigma * sigma
+		- passes * lower_f * lower_f
+		- 4.0 * passes * lower_f
+		- 3.0 * passes)
+		/ (-4.0 * lower_f - 4.0))
+		.round()
+		.max(0.0) as usize;
+
+	let mut sizes = [upper; BOX_PASSES];
+	for size in sizes.iter_mut().take(lower_passes) {
+		*size = lower;
+	}
+	sizes
+}
+
+/// Average each value with its `radius` neighbours on either side
+fn box_blur(line: &mut [f32], scratch: &mut [f32], radius: usize) {
+	let length = line.len();
+	let last = length - 1;
+	let at = |index: isize| line[index.clamp(0, last as isize) as usize];
+	let scale = 1.0 / (radius * 2 + 1) as f32;
+
+	let radius = radius as isize;
+	let mut sum: f32 = (-radius..=radius).map(at).sum();
+	for (index, value) in scratch.iter_mut().enumerate() {
+		*value = sum * scale;
+		let index = index as isize;
+		sum += at(index + radius + 1) - at(index - radius);
+	}
+	line.copy_from_slice(scratch);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_box_sizes() {
+		let sizes = box_sizes(2.0);
+		assert!(sizes.iter().all(|size| size % 2 == 1));
+
+		// Box blurs of these widths have the variance of the Gaussian
+		let variance: f32 = sizes
+			.iter()
+			.map(|&size| ((size * size) as f32 - 1.0) / 12.0)
+			.sum();
+		assert!((variance - 4.0).abs() < 1.0);
+	}
+
+	#[test]
+	fn test_blur_preserves_total() {
+		let mut channel = vec![0.0; 21 * 21];
+		channel[10 * 21 + 10] = 1.0;
+		gaussian_blur(&mut channel, 21, 21, 1.5);
+
+		let total: f32 = channel.iter().sum();
+		assert!((total - 1.0).abs() < 0.001);
+		assert!(channel[10 * 21 + 10] < 0.2);
+		assert!(channel[10 * 21 + 12] > 0.0);
+		// The blur is symmetric
+		assert!((channel[10 * 21 + 8] - channel[8 * 21 + 10]).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_blur_keeps_flat_areas() {
+		let mut channel = vec![0.5; 10 * 4];
+		gaussian_blur(&mut channel, 10, 4, 3.0);
+		assert!(channel.iter().all(|value| (value - 0.5).abs() < 1e-5));
+	}
+
+	#[test]
+	fn test_rounded_rect_mask() {
+		let mask = Mask::rounded_rect(
+			Rect::new(10.0, 10.0, 20.0, 20.0),
+			CornerRadii::uniform(5.0),
+			&Transform::identity(),
+			2.0,
+			100,
+			100,
+		)
+		.unwrap();
+
+		assert_eq!((mask.x, mask.y, mask.width, mask.height), (8, 8, 24, 24));
+		assert_eq!(mask.at(12, 12), 1.0);
+		assert_eq!(mask.at(0, 0), 0.0);
+		assert!(mask.at(2, 2) < 0.5);
+	}
+
+	#[test]
+	fn test_transformed_mask() {
+		// A square turned by 45 degrees about its centre covers a diamond
+		let transform = Transform::from_rotate_at(std::f32::consts::FRAC_PI_4, 50.0, 50.0);
+		let mask = Mask::rounded_rect(
+			Rect::new(40.0, 40.0, 20.0, 20.0),
+			CornerRadii::default(),
+			&transform,
+			0.0,
+			100,
+			100,
+		)
+		.unwrap();
+
+		let at = |x: i32, y: i32| mask.at((x - mask.x) as usize, (y - mask.y) as usize);
+		assert_eq!((mask.x, mask.width), (35, 30));
+		assert_eq!(at(50, 50), 1.0);
+		assert_eq!(at(50, 37), 1.0);
+		assert_eq!(at(37, 37), 0.0);
+	}
+
+	#[test]
+	fn test_shadow_mask() {
+		let mask = Mask::shadow(
+			Rect::new(20.0, 20.0, 40.0, 40.0),
+			CornerRadii::default(),
+			&Transform::identity(),
+			4.0,
+			6.0,
+			8.0,
+			0.0,
+			100,
+			100,
+		)
+		.unwrap();
+
+		// Offset and padded by three standard deviations of the blur
+		assert_eq!((mask.x, mask.y), (12, 14));
+		let centre = mask.at(40 + 4 - 12, 40 + 6 - 14);
+		let edge = mask.at(20 + 4 - 12, 40 + 6 - 14);
+		assert!(centre > 0.99);
+		assert!((0.4..0.6).contains(&edge));
+		assert!(mask.at(0, 0) < 0.01);
+
+		// A shadow entirely off the surface has no mask
+		let hidden = Mask::shadow(
+			Rect::new(-100.0, 0.0, 10.0, 10.0),
+			CornerRadii::default(),
+			&Transform::identity(),
+			0.0,
+			0.0,
+			4.0,
+			0.0,
+			100,
+			100,
+		);
+		assert!(hidden.is_none());
+	}
+
+	#[test]
+	fn test_blur_backdrop() {
+		let mask = Mask::rounded_rect(
+			Rect::new(4.0, 0.0, 4.0, 1.0),
+			CornerRadii::default(),
+			&Transform::identity(),
+			0.0,
+			12,
+			1,
+		)
+		.unwrap();
+		assert_eq!((mask.x, mask.width), (4, 4));
+
+		// A hard edge between black and white within the mask
+		let mut pixels = vec![
+			[0.0, 0.0, 0.0, 1.0],
+			[0.0, 0.0, 0.0, 1.0],
+			[1.0; 4],
+			[1.0; 4],
+		];
+		mask.blur_backdrop(&mut pixels, 2.0);
+		assert!(pixels[1][0] > 0.0 && pixels[1][0] < 0.5);
+		assert!(pixels[2][0] > 0.5 && pixels[2][0] < 1.0);
+		// Alpha stays opaque
+		assert!(pixels.iter().all(|pixel| (pixel[3] - 1.0).abs() < 1e-5));
+	}
+}
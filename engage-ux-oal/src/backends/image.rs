@@ -0,0 +1,807 @@
+//! Drawing decoded bitmaps with fitting, filtering, nine-slice scaling and
+//! tinting
+//!
+//! An [`ImageCommand`] draws part of an [`ImageData`] into a destination
+//! rectangle. [`ImageFit`] decides how the image is scaled into the
+//! rectangle, as CSS `object-fit` does, and [`NineSlice`] keeps the corners
+//! of a frame or button image at their original size while its edges and
+//! centre stretch.
+//!
+//! SVG documents are drawn the same way, after being rasterized at the
+//! size they cover on the surface.
+
+use super::renderer::{Color, Rect};
+use super::transform::Transform;
+use engage_ux_core::media::ImageData;
+use engage_ux_core::media::image::ColorType;
+use engage_ux_core::rendering::SvgDocument;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+
+/// Image fit mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFit {
+	/// Image fills the container, may be cropped
+	Cover,
+	/// Image fits within container, may have empty space
+	Contain,
+	/// Image is stretched to fill container
+	Fill,
+	/// Image maintains original size
+	None,
+	/// Image scales down if larger than container
+	ScaleDown,
+}
+
+impl ImageFit {
+	/// Region of a `width` by `height` image to draw, and where to draw it,
+	/// to fit the image into `dest`
+	///
+	/// Images that do not fill `dest` are centred in it; images that
+	/// overflow it are cropped around their centre.
+	pub fn resolve(self, width: f32, height: f32, dest: Rect) -> (Rect, Rect) {
+		let source = Rect::new(0.0, 0.0, width, height);
+		if width <= 0.0 || height <= 0.0 {
+			return (source, Rect::new(dest.x, dest.y, 0.0, 0.0));
+		}
+
+		let scale = match self {
+			Self::Fill => return (source, dest),
+			Self::Contain => (dest.width / width).min(dest.height / height),
+			Self::Cover => (dest.width / width).max(dest.height / height),
+			Self::None => 1.0,
+			Self::ScaleDown => (dest.width / width).min(dest.height / height).min(1.0),
+		};
+		if scale <= 0.0 {
+			return (source, Rect::new(dest.x, dest.y, 0.0, 0.0));
+		}
+
+		// Overflow on either axis is cropped from the source symmetrically
+		let (drawn_width, drawn_height) = (
+			(width * scale).min(dest.width),
+			(height * scale).min(dest.height),
+		);
+		let (crop_width, crop_height) = (drawn_width / scale, drawn_height / scale);
+		(
+			Rect::new(
+				(width - crop_width) / 2.0,
+				(height - crop_height) / 2.0,
+				crop_width,
+				crop_height,
+			),
+			Rect::new(
+				dest.x + (dest.width - drawn_width) / 2.0,
+				dest.y + (dest.height - drawn_height) / 2.0,
+				drawn_width,
+				drawn_height,
+			),
+		)
+	}
+}
+
+/// How image pixels are sampled when scaled or moved by fractions of a pixel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFilter {
+	/// The nearest pixel, keeping pixel art sharp
+	Nearest,
+	/// A blend of the four nearest pixels
+	#[default]
+	Bilinear,
+	/// A Catmull-Rom blend of the sixteen nearest pixels, sharper than
+	/// bilinear when enlarging
+	Bicubic,
+}
+
+/// Insets from the edges of an image, in image pixels, that divide it into
+/// nine slices
+///
+/// Corners keep their size, the top and bottom edges stretch horizontally,
+/// the left and right edges vertically, and the centre both ways.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NineSlice {
+	pub left: f32,
+	pub top: f32,
+	pub right: f32,
+	pub bottom: f32,
+}
+
+impl NineSlice {
+	/// Create nine-slice insets
+	pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+		Self {
+			left,
+			top,
+			right,
+			bottom,
+		}
+	}
+
+	/// Create the same inset on every side
+	pub fn uniform(inset: f32) -> Self {
+		Self::new(inset, inset, inset, inset)
+	}
+
+	/// Source and destination rectangles of the nine slices, skipping empty
+	/// ones
+	///
+	/// Corners shrink proportionally when the destination is too small for
+	/// them.
+	pub fn slices(&self, source: Rect, dest: Rect) -> Vec<(Rect, Rect)> {
+		let fit = |start: f32, end: f32, space: f32| {
+			let total = start + end;
+			if total > space && total > 0.0 {
+				(start * space / total, end * space / total)
+			} else {
+				(start, end)
+			}
+		};
+		let (src_left, src_right) = fit(self.left.max(0.0), self.right.max(0.0), source.width);
+		let (src_top, src_bottom) = fit(self.top.max(0.0), self.bottom.max(0.0), source.height);
+		let (dst_left, dst_right) = fit(src_left, src_right, dest.width);
+		let (dst_top, dst_bottom) = fit(src_top, src_bottom, dest.height);
+
+		let columns = |x: f32, width: f32, left: f32, right: f32| {
+			[
+				(x, left),
+				(x + left, width - left - right),
+				(x + width - right, right),
+			]
+		};
+		let source_columns = columns(source.x, source.width, src_left, src_right);
+		let source_rows = columns(source.y, source.height, src_top, src_bottom);
+		let dest_columns = columns(dest.x, dest.width, dst_left, dst_right);
+		let dest_rows = columns(dest.y, dest.height, dst_top, dst_bottom);
+
+		let mut slices = Vec::with_capacity(9);
+		for (row, dest_row) in source_rows.iter().zip(dest_rows) {
+			for (column, dest_column) in source_columns.iter().zip(dest_columns) {
+				let source = Rect::new(column.0, row.0, column.1, row.1);
+				let dest = Rect::new(dest_column.0, dest_row.0, dest_column.1, dest_row.1);
+				if source.width > 0.0
+					&& source.height > 0.0
+					&& dest.width > 0.0
+					&& dest.height > 0.0
+				{
+					slices.push((source, dest));
+				}
+			}
+		}
+		slices
+	}
+}
+
+/// Draw a bitmap into a rectangle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageCommand {
+	/// The decoded image
+	pub image: Arc<ImageData>,
+	/// Region of the image to draw, in image pixels; the whole image when
+	/// `None`
+	pub source: Option<Rect>,
+	/// Rectangle to draw into
+	pub dest: Rect,
+	/// How the source region is scaled into `dest`; nine-slice images
+	/// always fill it
+	pub fit: ImageFit,
+	pub filter: ImageFilter,
+	pub nine_slice: Option<NineSlice>,
+	/// Color the image's colors and alpha are multiplied by
+	pub tint: Option<Color>,
+}
+
+impl ImageCommand {
+	/// Draw a whole image stretched into `dest`
+	pub fn new(image: Arc<ImageData>, dest: Rect) -> Self {
+		Self {
+			image,
+			source: None,
+			dest,
+			fit: ImageFit::Fill,
+			filter: ImageFilter::default(),
+			nine_slice: None,
+			tint: None,
+		}
+	}
+
+	/// Draw only a region of the image, such as a sprite in an atlas
+	pub fn with_source(mut self, source: Rect) -> Self {
+		self.source = Some(source);
+		self
+	}
+
+	/// Set how the image is fitted into the destination
+	pub fn with_fit(mut self, fit: ImageFit) -> Self {
+		self.fit = fit;
+		self
+	}
+
+	/// Set how pixels are sampled
+	pub fn with_filter(mut self, filter: ImageFilter) -> Self {
+		self.filter = filter;
+		self
+	}
+
+	/// Scale the image in nine slices
+	pub fn with_nine_slice(mut self, nine_slice: NineSlice) -> Self {
+		self.nine_slice = Some(nine_slice);
+		self
+	}
+
+	/// Multiply the image's colors by a tint
+	pub fn with_tint(mut self, tint: Color) -> Self {
+		self.tint = Some(tint);
+		self
+	}
+
+	/// Source and destination rectangles to draw, after fitting or slicing
+	pub fn regions(&self) -> Vec<(Rect, Rect)> {
+		let (width, height) = (self.image.width as f32, self.image.height as f32);
+		let source = self
+			.source
+			.unwrap_or_else(|| Rect::new(0.0, 0.0, width, height));
+
+		if let Some(nine_slice) = self.nine_slice {
+			return nine_slice.slices(source, self.dest);
+		}
+		let (fitted, dest) = self.fit.resolve(source.width, source.height, self.dest);
+		let source = Rect::new(
+			source.x + fitted.x,
+			source.y + fitted.y,
+			fitted.width,
+			fitted.height,
+		);
+		if dest.width > 0.0 && dest.height > 0.0 {
+			vec![(source, dest)]
+		} else {
+			Vec::new()
+		}
+	}
+}
+
+/// An image decoded into premultiplied RGBA for sampling
+#[derive(Debug)]
+pub(crate) struct Texture {
+	width: usize,
+	height: usize,
+	pixels: Vec<[f32; 4]>,
+}
+
+impl Texture {
+	/// Premultiply the pixels of an image
+	pub fn new(image: &ImageData) -> Self {
+		let (width, height) = (image.width as usize, image.height as usize);
+		let channels = image.bytes_per_pixel();
+		let pixels = image
+			.data
+			.chunks_exact(channels)
+			.take(width * height)
+			.map(|pixel| {
+				let value = |index: usize| pixel[index] as f32 / 255.0;
+				let [r, g, b, a] = match image.color_type {
+					ColorType::Grayscale => [value(0), value(0), value(0), 1.0],
+					ColorType::Rgb => [value(0), value(1), value(2), 1.0],
+					ColorType::Rgba => [value(0), value(1), value(2), value(3)],
+				};
+				[r * a, g * a, b * a, a]
+			})
+			.collect::<Vec<_>>();
+		// Short pixel data leaves the remaining pixels transparent
+		let mut texture = Self {
+			width,
+			height,
+			pixels,
+		};
+		texture.pixels.resize(width * height, [0.0; 4]);
+		texture
+	}
+
+	/// Wrap premultiplied RGBA bytes, such as a rendered pixmap's
+	pub fn from_premultiplied(width: u32, height: u32, data: &[u8]) -> Self {
+		let mut pixels = data
+			.chunks_exact(4)
+			.map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]].map(|c| c as f32 / 255.0))
+			.collect::<Vec<_>>();
+		pixels.resize(width as usize * height as usize, [0.0; 4]);
+		Self {
+			width: width as usize,
+			height: height as usize,
+			pixels,
+		}
+	}
+
+	/// Pixel at a column and row, clamped to `bounds`
+	fn pixel(&self, column: i32, row: i32, bounds: (i32, i32, i32, i32)) -> [f32; 4] {
+		let column = column.clamp(bounds.0, bounds.2) as usize;
+		let row = row.clamp(bounds.1, bounds.3) as usize;
+		self.pixels[row * self.width + column]
+	}
+
+	/// Sample the texture at a point in image pixels, never reading pixels
+	/// outside `region`
+	pub fn sample(&self, x: f32, y: f32, filter: ImageFilter, region: Rect) -> [f32; 4] {
+		if self.width == 0 || self.height == 0 {
+			return [0.0; 4];
+		}
+		let last = |start: f32, size: f32, count: usize| {
+			((start + size).ceil() as i32 - 1).clamp(0, count as i32 - 1)
+		};
+		let bounds = (
+			(region.x.floor() as i32).clamp(0, self.width as i32 - 1),
+			(region.y.floor() as i32).clamp(0, self.height as i32 - 1),
+			last(region.x, region.width, self.width),
+			last(region.y, region.height, self.height),
+		);
+
+		match filter {
+			ImageFilter::Nearest => self.pixel(x.floor() as i32, y.floor() as i32, bounds),
+			ImageFilter::Bilinear => {
+				let (x, y) = (x - 0.5, y - 0.5);
+				let (column, row) = (x.floor() as i32, y.floor() as i32);
+				let (fx, fy) = (x - column as f32, y - row as f32);
+				let mut result = [0.0; 4];
+				for (dx, dy, weight) in [
+					(0, 0, (1.0 - fx) * (1.0 - fy)),
+					(1, 0, fx * (1.0 - fy)),
+					(0, 1, (1.0 - fx) * fy),
+					(1, 1, fx * fy),
+				] {
+					let pixel = self.pixel(column + dx, row + dy, bounds);
+					for (value, channel) in result.iter_mut().zip(pixel) {
+						*value += channel * weight;
+					}
+				}
+				result
+			}
+			ImageFilter::Bicubic => {
+				let (x, y) = (x - 0.5, y - 0.5);
+				let (column, row) = (x.floor() as i32, y.floor() as i32);
+				let (wx, wy) = (catmull_rom(x - column as f32), catmull_rom(y - row as f32));
+				let mut result = [0.0; 4];
+				for (j, weight_y) in wy.iter().enumerate() {
+					for (i, weight_x) in wx.iter().enumerate() {
+						let pixel = self.pixel(column + i as i32 - 1, row + j as i32 - 1, bounds);
+						for (value, channel) in result.iter_mut().zip(pixel) {
+							*value += channel * weight_x * weight_y;
+						}
+					}
+				}
+				// Overshoot can push colors past their alpha
+				let alpha = result[3].clamp(0.0, 1.0);
+				[
+					result[0].clamp(0.0, alpha),
+					result[1].clamp(0.0, alpha),
+					result[2].clamp(0.0, alpha),
+					alpha,
+				]
+			}
+		}
+	}
+}
+
+/// Catmull-Rom weights of the four pixels around a fraction `t` of the
+/// way between the middle two
+fn catmull_rom(t: f32) -> [f32; 4] {
+	let (t2, t3) = (t * t, t * t * t);
+	[
+		(-t3 + 2.0 * t2 - t) / 2.0,
+		(3.0 * t3 - 5.0 * t2 + 2.0) / 2.0,
+		(-3.0 * t3 + 4.0 * t2 + t) / 2.0,
+		(t3 - t2) / 2.0,
+	]
+}
+
+/// Rasterize an image command drawn under a transform onto a `width` by
+/// `height` surface
+///
+/// `plot` is called with premultiplied RGBA for every pixel whose centre
+/// falls inside a destination region.
+pub(crate) fn rasterize(
+	command: &ImageCommand,
+	transform: &Transform,
+	width: u32,
+	height: u32,
+	plot: impl FnMut(i32, i32, [f32; 4]),
+) {
+	let texture = Texture::new(&command.image);
+	let tint = command.tint.map(|tint| {
+		let a = tint.a.clamp(0.0, 1.0);
+		[tint.r * a, tint.g * a, tint.b * a, a].map(|channel| channel.clamp(0.0, 1.0))
+	});
+	draw_texture(
+		&texture,
+		&command.regions(),
+		command.filter,
+		tint,
+		transform,
+		(width, height),
+		plot,
+	);
+}
+
+/// Most rasters an [`SvgRasterCache`] keeps
+const SVG_CACHE_CAPACITY: usize = 16;
+
+/// SVG documents already rasterized, so drawing one again at the same size
+/// and tint does not render it again
+#[derive(Debug, Default)]
+pub(crate) struct SvgRasterCache {
+	/// Most recently used first
+	entries: VecDeque<SvgRaster>,
+}
+
+/// The visible part of a document rasterized at a size and tint
+#[derive(Debug)]
+struct SvgRaster {
+	document: Weak<SvgDocument>,
+	size: (u32, u32),
+	/// `(x, y, width, height)` of the raster that was rendered
+	region: (u32, u32, u32, u32),
+	tint: Option<Color>,
+	texture: Texture,
+}
+
+impl SvgRasterCache {
+	/// Create an empty cache
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Get the raster of `region` of a document drawn at `size`, rendering
+	/// it if it is not cached
+	fn get_or_render(
+		&mut self,
+		document: &Arc<SvgDocument>,
+		size: (u32, u32),
+		region: (u32, u32, u32, u32),
+		tint: Option<Color>,
+	) -> Option<&Texture> {
+		let cached = self.entries.iter().position(|entry| {
+			entry.document.as_ptr() == Arc::as_ptr(document)
+				&& entry.size == size
+				&& entry.region == region
+				&& entry.tint == tint
+		});
+		let entry = match cached {
+			Some(index) => self.entries.remove(index)?,
+			None => {
+				let core_tint = tint
+					.map(|tint| engage_ux_core::color::Color::rgb(tint.r, tint.g, tint.b, tint.a));
+				let pixmap = document.render_region(size.0, size.1, region, core_tint.as_ref())?;
+				SvgRaster {
+					document: Arc::downgrade(document),
+					size,
+					region,
+					tint,
+					texture: Texture::from_premultiplied(
+						pixmap.width(),
+						pixmap.height(),
+						pixmap.data(),
+					),
+				}
+			}
+		};
+
+		// Rasters of dropped documents can never be drawn again
+		self.entries
+			.retain(|entry| entry.document.strong_count() > 0);
+		self.entries.truncate(SVG_CACHE_CAPACITY - 1);
+		self.entries.push_front(entry);
+		self.entries.front().map(|entry| &entry.texture)
+	}
+}
+
+/// Rasterize an SVG document fitted into `rect` under a transform
+///
+/// The document is rendered at the size `rect` covers on the surface, so it
+/// stays sharp when scaled, and then drawn like an image. Only the part
+/// inside `visible`, the area of the surface that can be drawn into, is
+/// rendered, and the raster is kept in `cache`.
+pub(crate) fn rasterize_svg(
+	document: &Arc<SvgDocument>,
+	rect: Rect,
+	tint: Option<Color>,
+	transform: &Transform,
+	visible: Rect,
+	cache: &mut SvgRasterCache,
+	plot: impl FnMut(i32, i32, [f32; 4]),
+) {
+	let scale = transform.scale_factor();
+	let size = (
+		(rect.width * scale).ceil().max(0.0) as u32,
+		(rect.height * scale).ceil().max(0.0) as u32,
+	);
+	if size.0 == 0 || size.1 == 0 {
+		return;
+	}
+	let Some(local) = transform
+		.invert()
+		.and_then(|inverse| inverse.map_rect(visible).intersection(&rect))
+	else {
+		return;
+	};
+
+	// Raster pixels covering the visible part, with a pixel of margin so
+	// filtering at its edges reads the same neighbours as the whole raster
+	let (scale_x, scale_y) = (size.0 as f32 / rect.width, size.1 as f32 / rect.height);
+	let left = ((local.x - rect.x) * scale_x - 1.0).floor().max(0.0);
+	let top = ((local.y - rect.y) * scale_y - 1.0).floor().max(0.0);
+	let right = ((local.x + local.width - rect.x) * scale_x + 1.0)
+		.ceil()
+		.min(size.0 as f32);
+	let bottom = ((local.y + local.height - rect.y) * scale_y + 1.0)
+		.ceil()
+		.min(size.1 as f32);
+	if right <= left || bottom <= top {
+		return;
+	}
+	let region = (
+		left as u32,
+		top as u32,
+		(right - left) as u32,
+		(bottom - top) as u32,
+	);
+	let Some(texture) = cache.get_or_render(document, size, region, tint) else {
+		return;
+	};
+
+	let source = Rect::new(0.0, 0.0, right - left, bottom - top);
+	let dest = Rect::new(
+		rect.x + left / scale_x,
+		rect.y + top / scale_y,
+		(right - left) / scale_x,
+		(bottom - top) / scale_y,
+	);
+	draw_texture(
+		texture,
+		&[(source, dest)],
+		ImageFilter::Bilinear,
+		None,
+		transform,
+		(
+			(visible.x + visible.width).ceil().max(0.0) as u32,
+			(visible.y + visible.height).ceil().max(0.0) as u32,
+		),
+		plot,
+	);
+}
+
+/// Draw source regions of a texture into destination rectangles,
+/// multiplying by a premultiplied tint
+fn draw_texture(
+	texture: &Texture,
+	regions: &[(Rect, Rect)],
+	filter: ImageFilter,
+	tint: Option<[f32; 4]>,
+	transform: &Transform,
+	(width, height): (u32, u32),
+	mut plot: impl FnMut(i32, i32, [f32; 4]),
+) {
+	let Some(inverse) = transform.invert() else {
+		return;
+	};
+
+	for &(source, dest) in regions {
+		let bounds = transform.map_rect(dest);
+		let x_start = (bounds.x.floor() as i32).max(0);
+		let y_start = (bounds.y.floor() as i32).max(0);
+		let x_end = ((bounds.x + bounds.width).ceil() as i32).min(width as i32);
+		let y_end = ((bounds.y + bounds.height).ceil() as i32).min(height as i32);
+		let (scale_x, scale_y) = (source.width / dest.width, source.height / dest.height);
+
+		for y in y_start..y_end {
+			for x in x_start..x_end {
+				let (lx, ly) = inverse.map_point(x as f32 + 0.5, y as f32 + 0.5);
+				if lx < dest.x
+					|| ly < dest.y || lx >= dest.x + dest.width
+					|| ly >= dest.y + dest.height
+				{
+					continue;
+				}
+				let u = source.x + (lx - dest.x) * scale_x;
+				let v = source.y + (ly - dest.y) * scale_y;
+				let mut pixel = texture.sample(u, v, filter, source);
+				if let Some(tint) = tint {
+					for (channel, factor) in pixel.iter_mut().zip(tint) {
+						*channel *= factor;
+					}
+				}
+				if pixel[3] > 0.0 {
+					plot(x, y, pixel);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use engage_ux_core::media::ImageFormat;
+
+	/// A 2 by 2 image: red, green / blue, transparent white
+	fn checker() -> ImageData {
+		ImageData::new(
+			2,
+			2,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			vec![
+				255, 0, 0, 255, 0, 255, 0, 255, //
+				0, 0, 255, 255, 255, 255, 255, 0,
+			],
+		)
+	}
+
+	#[test]
+	fn test_fit_contain_and_cover() {
+		let dest = Rect::new(0.0, 0.0, 200.0, 100.0);
+
+		let (source, drawn) = ImageFit::Contain.resolve(100.0, 100.0, dest);
+		assert_eq!(source, Rect::new(0.0, 0.0, 100.0, 100.0));
+		assert_eq!(drawn, Rect::new(50.0, 0.0, 100.0, 100.0));
+
+		let (source, drawn) = ImageFit::Cover.resolve(100.0, 100.0, dest);
+		assert_eq!(source, Rect::new(0.0, 25.0, 100.0, 50.0));
+		assert_eq!(drawn, dest);
+
+		let (source, drawn) = ImageFit::Fill.resolve(100.0, 100.0, dest);
+		assert_eq!((source.width, drawn), (100.0, dest));
+	}
+
+	#[test]
+	fn test_fit_none_and_scale_down() {
+		let dest = Rect::new(10.0, 10.0, 100.0, 100.0);
+
+		// Natural size, centred and cropped to the destination
+		let (source, drawn) = ImageFit::None.resolve(300.0, 50.0, dest);
+		assert_eq!(source, Rect::new(100.0, 0.0, 100.0, 50.0));
+		assert_eq!(drawn, Rect::new(10.0, 35.0, 100.0, 50.0));
+
+		// Small images are not enlarged, large ones shrink to fit
+		let (_, drawn) = ImageFit::ScaleDown.resolve(50.0, 50.0, dest);
+		assert_eq!(drawn, Rect::new(35.0, 35.0, 50.0, 50.0));
+		let (_, drawn) = ImageFit::ScaleDown.resolve(400.0, 200.0, dest);
+		assert_eq!(drawn, Rect::new(10.0, 35.0, 100.0, 50.0));
+	}
+
+	#[test]
+	fn test_nine_slice() {
+		let slices = NineSlice::uniform(10.0).slices(
+			Rect::new(0.0, 0.0, 30.0, 30.0),
+			Rect::new(0.0, 0.0, 100.0, 50.0),
+		);
+		assert_eq!(slices.len(), 9);
+		// Corners keep their size, the centre stretches
+		assert_eq!(
+			slices[0],
+			(
+				Rect::new(0.0, 0.0, 10.0, 10.0),
+				Rect::new(0.0, 0.0, 10.0, 10.0)
+			)
+		);
+		assert_eq!(
+			slices[8],
+			(
+				Rect::new(20.0, 20.0, 10.0, 10.0),
+				Rect::new(90.0, 40.0, 10.0, 10.0)
+			)
+		);
+		assert_eq!(slices[4].1, Rect::new(10.0, 10.0, 80.0, 30.0));
+
+		// Corners shrink to share a destination too small for them
+		let slices = NineSlice::uniform(10.0).slices(
+			Rect::new(0.0, 0.0, 30.0, 30.0),
+			Rect::new(0.0, 0.0, 10.0, 10.0),
+		);
+		assert_eq!(slices.len(), 4);
+		assert_eq!(slices[0].1, Rect::new(0.0, 0.0, 5.0, 5.0));
+	}
+
+	#[test]
+	fn test_texture_premultiplied() {
+		let image = ImageData::new(
+			1,
+			1,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			vec![255, 0, 0, 128],
+		);
+		let texture = Texture::new(&image);
+		let [r, g, _, a] = texture.pixels[0];
+		assert!((r - a).abs() < 1e-6 && g == 0.0);
+
+		let grey = ImageData::new(1, 1, ImageFormat::Png, ColorType::Grayscale, vec![51]);
+		assert_eq!(Texture::new(&grey).pixels[0], [0.2, 0.2, 0.2, 1.0]);
+	}
+
+	#[test]
+	fn test_filters() {
+		let texture = Texture::new(&checker());
+		let whole = Rect::new(0.0, 0.0, 2.0, 2.0);
+
+		assert_eq!(
+			texture.sample(0.9, 0.9, ImageFilter::Nearest, whole),
+			[1.0, 0.0, 0.0, 1.0]
+		);
+		// Halfway between red and green
+		let mixed = texture.sample(1.0, 0.5, ImageFilter::Bilinear, whole);
+		assert_eq!(mixed, [0.5, 0.5, 0.0, 1.0]);
+		// Pixel centres are reproduced exactly
+		let centre = texture.sample(0.5, 1.5, ImageFilter::Bicubic, whole);
+		assert!((centre[2] - 1.0).abs() < 1e-6 && centre[0].abs() < 1e-6);
+		// Sampling stays inside the region
+		let red = texture.sample(
+			1.0,
+			0.5,
+			ImageFilter::Bilinear,
+			Rect::new(0.0, 0.0, 1.0, 1.0),
+		);
+		assert_eq!(red, [1.0, 0.0, 0.0, 1.0]);
+	}
+
+	#[test]
+	fn test_rasterize_tinted() {
+		let command = ImageCommand::new(Arc::new(checker()), Rect::new(0.0, 0.0, 4.0, 4.0))
+			.with_filter(ImageFilter::Nearest)
+			.with_tint(Color::rgba(1.0, 1.0, 1.0, 0.5));
+
+		let mut pixels = Vec::new();
+		rasterize(&command, &Transform::identity(), 10, 10, |x, y, pixel| {
+			pixels.push((x, y, pixel))
+		});
+		// The transparent quarter is skipped
+		assert_eq!(pixels.len(), 12);
+		assert_eq!(pixels[0], (0, 0, [0.5, 0.0, 0.0, 0.5]));
+		assert!(pixels.iter().all(|&(x, y, _)| x < 2 || y < 2));
+	}
+
+	#[test]
+	fn test_rasterize_svg_cached_and_clamped() {
+		let document = Arc::new(
+			engage_ux_core::rendering::SvgParser::new()
+				.parse(
+					r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+						<rect width="10" height="10" fill="red"/>
+					</svg>"#,
+				)
+				.unwrap(),
+		);
+		let mut cache = SvgRasterCache::new();
+		let visible = Rect::new(0.0, 0.0, 10.0, 10.0);
+		let draw = |cache: &mut SvgRasterCache, rect: Rect, tint: Option<Color>| {
+			let mut pixels = Vec::new();
+			rasterize_svg(
+				&document,
+				rect,
+				tint,
+				&Transform::identity(),
+				visible,
+				cache,
+				|x, y, pixel| pixels.push((x, y, pixel)),
+			);
+			pixels
+		};
+
+		let first = draw(&mut cache, Rect::new(0.0, 0.0, 10.0, 10.0), None);
+		assert_eq!(first.len(), 100);
+		assert_eq!(
+			draw(&mut cache, Rect::new(0.0, 0.0, 10.0, 10.0), None),
+			first
+		);
+		assert_eq!(cache.entries.len(), 1);
+
+		draw(
+			&mut cache,
+			Rect::new(0.0, 0.0, 10.0, 10.0),
+			Some(Color::rgb(0.0, 0.0, 1.0)),
+		);
+		assert_eq!(cache.entries.len(), 2);
+
+		// Only the visible corner of a huge document is rasterized
+		let pixels = draw(&mut cache, Rect::new(-5.0, -5.0, 1.0e6, 1.0e6), None);
+		assert_eq!(pixels.len(), 100);
+		assert_eq!(cache.entries[0].size, (1_000_000, 1_000_000));
+		assert_eq!(cache.entries[0].region, (4, 4, 12, 12));
+	}
+}
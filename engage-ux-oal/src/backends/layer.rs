@@ -0,0 +1,131 @@
+//! Offscreen layers composited with group opacity and blend modes
+//!
+//! Commands between [`RenderCommand::PushLayer`] and
+//! [`RenderCommand::PopLayer`] draw into a transparent layer the size of
+//! the surface. Popping the layer composites it onto what is below with the
+//! layer's opacity and [`BlendMode`], so overlapping shapes in the group
+//! fade together instead of showing through each other.
+//!
+//! [`RenderCommand::PushLayer`]: super::RenderCommand::PushLayer
+//! [`RenderCommand::PopLayer`]: super::RenderCommand::PopLayer
+
+use serde::{Deserialize, Serialize};
+
+/// How a layer's colors combine with the colors below it, as in CSS
+/// `mix-blend-mode`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendMode {
+	/// Draw the layer over what is below
+	#[default]
+	Normal,
+	/// Multiply the colors, always darkening
+	Multiply,
+	/// Invert, multiply and invert again, always lightening
+	Screen,
+	/// Multiply dark colors below and screen light ones
+	Overlay,
+	/// Keep the darker of the two colors
+	Darken,
+	/// Keep the lighter of the two colors
+	Lighten,
+	/// Multiply or screen depending on the layer's colors
+	HardLight,
+	/// Subtract the darker color from the lighter one
+	Difference,
+	/// Like difference, with lower contrast
+	Exclusion,
+}
+
+impl BlendMode {
+	/// Blend one color channel of the layer over one of the backdrop, both
+	/// from 0.0 to 1.0
+	pub fn blend_channel(self, backdrop: f32, source: f32) -> f32 {
+		let multiply = |a: f32, b: f32| a * b;
+		let screen = |a: f32, b: f32| a + b - a * b;
+		let hard_light = |backdrop: f32, source: f32| {
+			if source <= 0.5 {
+				multiply(backdrop, source * 2.0)
+			} else {
+				screen(backdrop, source * 2.0 - 1.0)
+			}
+		};
+
+		match self {
+			Self::Normal => source,
+			Self::Multiply => multiply(backdrop, source),
+			Self::Screen => screen(backdrop, source),
+			Self::Overlay => hard_light(source, backdrop),
+			Self::Darken => backdrop.min(source),
+			Self::Lighten => backdrop.max(source),
+			Self::HardLight => hard_light(backdrop, source),
+			Self::Difference => (backdrop - source).abs(),
+			Self::Exclusion => backdrop + source - 2.0 * backdrop * source,
+		}
+	}
+}
+
+/// Composite a straight RGBA pixel of a layer over one below it
+///
+/// Follows the W3C compositing model: where the backdrop is transparent
+/// the layer keeps its own colors, and the result is drawn source-over.
+pub(crate) fn composite(
+	source: [f32; 4],
+	backdrop: [f32; 4],
+	opacity: f32,
+	mode: BlendMode,
+) -> [f32; 4] {
+	let source_alpha = source[3] * opacity.clamp(0.0, 1.0);
+	let backdrop_alpha = backdrop[3];
+	let alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+	if alpha <= 0.0 {
+		return [0.0; 4];
+	}
+
+	let mut result = [0.0, 0.0, 0.0, alpha];
+	for channel in 0..3 {
+		let (cs, cb) = (source[channel], backdrop[channel]);
+		let mixed = (1.0 - backdrop_alpha) * cs + backdrop_alpha * mode.blend_channel(cb, cs);
+		result[channel] =
+			(source_alpha * mixed + backdrop_alpha * cb * (1.0 - source_alpha)) / alpha;
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_blend_channel() {
+		assert_eq!(BlendMode::Normal.blend_channel(0.2, 0.6), 0.6);
+		assert_eq!(BlendMode::Multiply.blend_channel(0.5, 0.5), 0.25);
+		assert_eq!(BlendMode::Screen.blend_channel(0.5, 0.5), 0.75);
+		assert_eq!(BlendMode::Darken.blend_channel(0.2, 0.6), 0.2);
+		assert_eq!(BlendMode::Lighten.blend_channel(0.2, 0.6), 0.6);
+		assert!((BlendMode::Difference.blend_channel(0.2, 0.6) - 0.4).abs() < 1e-6);
+		// Overlay is hard light with the layers swapped
+		assert_eq!(
+			BlendMode::Overlay.blend_channel(0.25, 0.75),
+			BlendMode::HardLight.blend_channel(0.75, 0.25)
+		);
+	}
+
+	#[test]
+	fn test_composite_opacity() {
+		let red = [1.0, 0.0, 0.0, 1.0];
+		let white = [1.0, 1.0, 1.0, 1.0];
+		let result = composite(red, white, 0.5, BlendMode::Normal);
+		assert_eq!(result, [1.0, 0.5, 0.5, 1.0]);
+
+		// Over a transparent backdrop the layer keeps its color
+		let result = composite(red, [0.0; 4], 0.5, BlendMode::Multiply);
+		assert_eq!(result, [1.0, 0.0, 0.0, 0.5]);
+	}
+
+	#[test]
+	fn test_composite_multiply() {
+		let grey = [0.5, 0.5, 0.5, 1.0];
+		let result = composite(grey, [0.5, 1.0, 0.0, 1.0], 1.0, BlendMode::Multiply);
+		assert_eq!(result, [0.25, 0.5, 0.0, 1.0]);
+	}
+}
@@ -3,9 +3,17 @@
 //! This module provides the architecture for platform-specific rendering,
 //! window management, input handling, and accessibility.
 
+pub mod brush;
+pub mod display_list;
+mod effects;
+pub mod image;
+pub mod layer;
+pub mod path;
 pub mod renderer;
+mod scanline;
 pub mod screen_reader;
 pub mod softbuffer_renderer;
+pub mod transform;
 pub mod window_backend;
 pub mod winit_window;
 
@@ -24,9 +32,15 @@ pub mod screen_reader_android;
 #[cfg(target_os = "ios")]
 pub mod screen_reader_ios;
 
+pub use brush::{Brush, GradientStop};
+pub use display_list::{DisplayList, DisplayListCache};
+pub use image::{ImageCommand, ImageFilter, ImageFit, NineSlice};
+pub use layer::BlendMode;
+pub use path::{CornerRadii, FillRule, LineCap, LineJoin, Path, PathCommand, StrokeStyle};
 pub use renderer::{RenderBackend, RenderCommand, RenderContext};
 pub use screen_reader::{ScreenReaderBackend, StubScreenReader};
-pub use softbuffer_renderer::SoftbufferRenderer;
+pub use softbuffer_renderer::{SoftbufferRenderContext, SoftbufferRenderer};
+pub use transform::Transform;
 pub use window_backend::{WindowBackend, WindowBackendEvent, WindowBounds, WindowState};
 pub use winit_window::WinitWindowBackend;
 
@@ -37,7 +51,7 @@ pub mod tiny_skia_renderer;
 pub mod linux_accessibility;
 
 #[cfg(target_os = "linux")]
-pub use tiny_skia_renderer::TinySkiaRenderer;
+pub use tiny_skia_renderer::{TinySkiaRenderContext, TinySkiaRenderer};
 
 #[cfg(target_os = "linux")]
 pub use linux_accessibility::{
@@ -228,4 +242,44 @@ mod tests {
 		let _screen_reader = factory.create_screen_reader();
 		// Test passes if backends can be created without panic
 	}
+
+	/// Offscreen contexts of every software backend on this platform
+	fn software_contexts(width: u32, height: u32) -> Vec<(&'static str, Box<dyn RenderContext>)> {
+		#[allow(unused_mut)]
+		let mut contexts: Vec<(&'static str, Box<dyn RenderContext>)> = vec![(
+			"softbuffer",
+			Box::new(SoftbufferRenderContext::new(width, height)),
+		)];
+		#[cfg(target_os = "linux")]
+		contexts.push((
+			"tiny-skia",
+			Box::new(TinySkiaRenderContext::new(width, height)),
+		));
+		contexts
+	}
+
+	#[test]
+	fn test_read_pixels() {
+		for (name, mut context) in software_contexts(4, 2) {
+			context.execute(RenderCommand::FillRect {
+				rect: renderer::Rect::new(0.0, 0.0, 2.0, 2.0),
+				color: renderer::Color::rgba(1.0, 0.0, 0.0, 0.5),
+			});
+
+			let image = context.read_pixels().unwrap();
+			assert_eq!((image.width, image.height), (4, 2), "{name}");
+			// Straight alpha, so the color survives the half coverage
+			let red = image.get_pixel(1, 1).unwrap();
+			assert_eq!(red[0], 255, "{name}");
+			assert!((127..=128).contains(&red[3]), "{name}");
+			assert_eq!(image.get_pixel(3, 0).unwrap(), &[0, 0, 0, 0], "{name}");
+
+			let path = std::env::temp_dir().join(format!("engage_ux_{name}_read_pixels.png"));
+			let path = path.to_str().unwrap();
+			context.save_png(path).unwrap();
+			let saved = engage_ux_core::media::ImageData::load_from_file(path).unwrap();
+			std::fs::remove_file(path).unwrap();
+			assert_eq!(saved.data, image.data, "{name}");
+		}
+	}
 }
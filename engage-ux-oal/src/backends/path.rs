@@ -0,0 +1,737 @@
+//! Vector paths and stroke styles for render commands
+//!
+//! A [`Path`] is built from move, line, quadratic and cubic Bézier, arc and
+//! close commands. Renderers that draw curves natively take the commands
+//! as they are, with arcs converted to cubic Béziers; software renderers
+//! flatten paths into polylines first.
+
+use super::renderer::Rect;
+use super::transform::Transform;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+
+/// Largest arc drawn with a single cubic Bézier
+const MAX_ARC_SEGMENT: f32 = FRAC_PI_2;
+
+/// Most line segments a single curve is flattened into
+const MAX_CURVE_SEGMENTS: usize = 256;
+
+/// A single drawing command of a [`Path`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PathCommand {
+	/// Start a new subpath at a point
+	MoveTo { x: f32, y: f32 },
+	/// Draw a straight line to a point
+	LineTo { x: f32, y: f32 },
+	/// Draw a quadratic Bézier through a control point to a point
+	QuadTo { x1: f32, y1: f32, x: f32, y: f32 },
+	/// Draw a cubic Bézier through two control points to a point
+	CubicTo {
+		x1: f32,
+		y1: f32,
+		x2: f32,
+		y2: f32,
+		x: f32,
+		y: f32,
+	},
+	/// Draw an arc of a circle, joined to the current point by a line
+	///
+	/// Angles are in radians from the positive x axis; positive sweeps run
+	/// clockwise on screen.
+	Arc {
+		cx: f32,
+		cy: f32,
+		radius: f32,
+		start: f32,
+		sweep: f32,
+	},
+	/// Close the subpath with a line back to its start
+	Close,
+}
+
+/// An outline made of lines and curves
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Path {
+	commands: Vec<PathCommand>,
+}
+
+impl Path {
+	/// Create an empty path
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a path outlining a rectangle
+	pub fn rect(rect: Rect) -> Self {
+		Self::new()
+			.move_to(rect.x, rect.y)
+			.line_to(rect.x + rect.width, rect.y)
+			.line_to(rect.x + rect.width, rect.y + rect.height)
+			.line_to(rect.x, rect.y + rect.height)
+			.close()
+	}
+
+	/// Create a path outlining a rectangle with rounded corners
+	///
+	/// Radii too large for the rectangle are scaled down together, as in
+	/// CSS, so adjacent corners meet without overlapping.
+	pub fn rounded_rect(rect: Rect, radii: CornerRadii) -> Self {
+		let radii = radii.clamped(rect.width, rect.height);
+		let (left, top) = (rect.x, rect.y);
+		let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+
+		Self::new()
+			.move_to(left + radii.top_left, top)
+			.line_to(right - radii.top_right, top)
+			.corner(
+				right - radii.top_right,
+				top + radii.top_right,
+				radii.top_right,
+				-FRAC_PI_2,
+			)
+			.line_to(right, bottom - radii.bottom_right)
+			.corner(
+				right - radii.bottom_right,
+				bottom - radii.bottom_right,
+				radii.bottom_right,
+				0.0,
+			)
+			.line_to(left + radii.bottom_left, bottom)
+			.corner(
+				left + radii.bottom_left,
+				bottom - radii.bottom_left,
+				radii.bottom_left,
+				FRAC_PI_2,
+			)
+			.line_to(left, top + radii.top_left)
+			.corner(
+				left + radii.top_left,
+				top + radii.top_left,
+				radii.top_left,
+				std::f32::consts::PI,
+			)
+			.close()
+	}
+
+	/// Add a quarter-circle corner, skipping square corners
+	fn corner(self, cx: f32, cy: f32, radius: f32, start: f32) -> Self {
+		if radius > 0.0 {
+			self.arc(cx, cy, radius, start, FRAC_PI_2)
+		} else {
+			self
+		}
+	}
+
+	/// Start a new subpath at a point
+	pub fn move_to(mut self, x: f32, y: f32) -> Self {
+		self.commands.push(PathCommand::MoveTo { x, y });
+		self
+	}
+
+	/// Draw a straight line to a point
+	pub fn line_to(mut self, x: f32, y: f32) -> Self {
+		self.commands.push(PathCommand::LineTo { x, y });
+		self
+	}
+
+	/// Draw a quadratic Bézier through a control point to a point
+	pub fn quad_to(mut self, x1: f32, y1: f32, x: f32, y: f32) -> Self {
+		self.commands.push(PathCommand::QuadTo { x1, y1, x, y });
+		self
+	}
+
+	/// Draw a cubic Bézier through two control points to a point
+	pub fn cubic_to(mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) -> Self {
+		self.commands.push(PathCommand::CubicTo {
+			x1,
+			y1,
+			x2,
+			y2,
+			x,
+			y,
+		});
+		self
+	}
+
+	/// Draw an arc of a circle, see [`PathCommand::Arc`]
+	pub fn arc(mut self, cx: f32, cy: f32, radius: f32, start: f32, sweep: f32) -> Self {
+		self.commands.push(PathCommand::Arc {
+			cx,
+			cy,
+			radius,
+			start,
+			sweep,
+		});
+		self
+	}
+
+	/// Close the current subpath
+	pub fn close(mut self) -> Self {
+		self.commands.push(PathCommand::Close);
+		self
+	}
+
+	/// Get the commands making up the path
+	pub fn commands(&self) -> &[PathCommand] {
+		&self.commands
+	}
+
+	/// Check whether the path has no commands
+	pub fn is_empty(&self) -> bool {
+		self.commands.is_empty()
+	}
+
+	/// Smallest rectangle holding every point and control point of the
+	/// path, and so the whole outline
+	///
+	/// `None` for a path with no points.
+	pub fn bounds(&self) -> Option<Rect> {
+		let mut points = Vec::new();
+		for command in self.curves() {
+			match command {
+				PathCommand::MoveTo { x, y } | PathCommand::LineTo { x, y } => {
+					points.push((x, y));
+				}
+				PathCommand::QuadTo { x1, y1, x, y } => {
+					points.extend([(x1, y1), (x, y)]);
+				}
+				PathCommand::CubicTo {
+					x1,
+					y1,
+					x2,
+					y2,
+					x,
+					y,
+				} => {
+					points.extend([(x1, y1), (x2, y2), (x, y)]);
+				}
+				PathCommand::Arc { .. } | PathCommand::Close => {}
+			}
+		}
+
+		let (&first, rest) = points.split_first()?;
+		let (mut left, mut top, mut right, mut bottom) = (first.0, first.1, first.0, first.1);
+		for &(x, y) in rest {
+			left = left.min(x);
+			top = top.min(y);
+			right = right.max(x);
+			bottom = bottom.max(y);
+		}
+		Some(Rect::new(left, top, right - left, bottom - top))
+	}
+
+	/// The path mapped through a transform
+	///
+	/// Arcs become cubic Béziers, which stay exact under scaling and
+	/// shearing where circular arcs would not.
+	pub fn transformed(&self, transform: &Transform) -> Self {
+		let map = |x: f32, y: f32| transform.map_point(x, y);
+		let commands = self
+			.curves()
+			.into_iter()
+			.map(|command| match command {
+				PathCommand::MoveTo { x, y } => {
+					let (x, y) = map(x, y);
+					PathCommand::MoveTo { x, y }
+				}
+				PathCommand::LineTo { x, y } => {
+					let (x, y) = map(x, y);
+					PathCommand::LineTo { x, y }
+				}
+				PathCommand::QuadTo { x1, y1, x, y } => {
+					let ((x1, y1), (x, y)) = (map(x1, y1), map(x, y));
+					PathCommand::QuadTo { x1, y1, x, y }
+				}
+				PathCommand::CubicTo {
+					x1,
+					y1,
+					x2,
+					y2,
+					x,
+					y,
+				} => {
+					let ((x1, y1), (x2, y2), (x, y)) = (map(x1, y1), map(x2, y2), map(x, y));
+					PathCommand::CubicTo {
+						x1,
+						y1,
+						x2,
+						y2,
+						x,
+						y,
+					}
+				}
+				command => command,
+			})
+			.collect();
+		Self { commands }
+	}
+
+	/// The path's commands with every arc replaced by a line to its start
+	/// and cubic Béziers along it
+	pub(crate) fn curves(&self) -> Vec<PathCommand> {
+		let mut curves = Vec::with_capacity(self.commands.len());
+		let mut has_point = false;
+		for &command in &self.commands {
+			match command {
+				PathCommand::Arc {
+					cx,
+					cy,
+					radius,
+					start,
+					sweep,
+				} => {
+					let (x, y) = (cx + radius * start.cos(), cy + radius * start.sin());
+					curves.push(if has_point {
+						PathCommand::LineTo { x, y }
+					} else {
+						PathCommand::MoveTo { x, y }
+					});
+					arc_to_cubics(cx, cy, radius, start, sweep, &mut curves);
+				}
+				command => curves.push(command),
+			}
+			has_point = command != PathCommand::Close;
+		}
+		curves
+	}
+
+	/// Approximate the path with polylines, one per subpath, each point
+	/// within `tolerance` pixels of the true outline
+	pub(crate) fn flatten(&self, tolerance: f32) -> Vec<Polyline> {
+		let tolerance = tolerance.max(0.01);
+		let mut polylines = Vec::new();
+		let mut current = Polyline::default();
+		let mut pen = (0.0, 0.0);
+
+		for command in self.curves() {
+			match command {
+				PathCommand::MoveTo { x, y } => {
+					if current.points.len() > 1 {
+						polylines.push(std::mem::take(&mut current));
+					}
+					current = Polyline {
+						points: vec![(x, y)],
+						closed: false,
+					};
+					pen = (x, y);
+				}
+				PathCommand::LineTo { x, y } => {
+					current.start_at(pen);
+					current.points.push((x, y));
+					pen = (x, y);
+				}
+				PathCommand::QuadTo { x1, y1, x, y } => {
+					current.start_at(pen);
+					let (x0, y0) = pen;
+					let deviation =
+						((x0 - 2.0 * x1 + x).hypot(y0 - 2.0 * y1 + y) / tolerance / 4.0).sqrt();
+					let segments = (deviation.ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS);
+					for step in 1..=segments {
+						let t = step as f32 / segments as f32;
+						let u = 1.0 - t;
+						current.points.push((
+							u * u * x0 + 2.0 * u * t * x1 + t * t * x,
+							u * u * y0 + 2.0 * u * t * y1 + t * t * y,
+						));
+					}
+					pen = (x, y);
+				}
+				PathCommand::CubicTo {
+					x1,
+					y1,
+					x2,
+					y2,
+					x,
+					y,
+				} => {
+					current.start_at(pen);
+					let (x0, y0) = pen;
+					// Wang's formula for the segments needed to stay within tolerance
+					let dd = (x0 - 2.0 * x1 + x2)
+						.hypot(y0 - 2.0 * y1 + y2)
+						.max((x1 - 2.0 * x2 + x).hypot(y1 - 2.0 * y2 + y));
+					let segments = ((0.75 * dd / tolerance).sqrt().ceil() as usize)
+						.clamp(1, MAX_CURVE_SEGMENTS);
+					for step in 1..=segments {
+						let t = step as f32 / segments as f32;
+						let u = 1.0 - t;
+						let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+						current.points.push((
+							a * x0 + b * x1 + c * x2 + d * x,
+							a * y0 + b * y1 + c * y2 + d * y,
+						));
+					}
+					pen = (x, y);
+				}
+				PathCommand::Close => {
+					if let Some(&start) = current.points.first() {
+						current.closed = true;
+						polylines.push(std::mem::take(&mut current));
+						// A new subpath drawn without a move starts where this one did
+						current.points.push(start);
+						pen = start;
+					}
+				}
+				PathCommand::Arc { .. } => unreachable!("arcs are converted to curves"),
+			}
+		}
+		if current.points.len() > 1 {
+			polylines.push(current);
+		}
+		polylines
+	}
+}
+
+/// Append cubic Béziers approximating an arc that starts at the pen
+fn arc_to_cubics(
+	cx: f32,
+	cy: f32,
+	radius: f32,
+	start: f32,
+	sweep: f32,
+	out: &mut Vec<PathCommand>,
+) {
+	if radius <= 0.0 || sweep == 0.0 {
+		return;
+	}
+	let segments = (sweep.abs() / MAX_ARC_SEGMENT).ceil().max(1.0) as usize;
+	let step = sweep / segments as f32;
+	// Distance of the control points along the tangents, as a fraction of the radius
+	let handle = 4.0 / 3.0 * (step / 4.0).tan();
+
+	let mut angle = start;
+	for _ in 0..segments {
+		let next = angle + step;
+		let (sin_a, cos_a) = angle.sin_cos();
+		let (sin_b, cos_b) = next.sin_cos();
+		out.push(PathCommand::CubicTo {
+			x1: cx + radius * (cos_a - handle * sin_a),
+			y1: cy + radius * (sin_a + handle * cos_a),
+			x2: cx + radius * (cos_b + handle * sin_b),
+			y2: cy + radius * (sin_b - handle * cos_b),
+			x: cx + radius * cos_b,
+			y: cy + radius * sin_b,
+		});
+		angle = next;
+	}
+}
+
+/// A flattened subpath
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Polyline {
+	pub points: Vec<(f32, f32)>,
+	/// The subpath was closed, joining its last point to its first
+	pub closed: bool,
+}
+
+impl Polyline {
+	/// Start an empty polyline at the pen position
+	fn start_at(&mut self, pen: (f32, f32)) {
+		if self.points.is_empty() {
+			self.points.push(pen);
+		}
+	}
+}
+
+/// Radii of the four corners of a rounded rectangle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CornerRadii {
+	pub top_left: f32,
+	pub top_right: f32,
+	pub bottom_right: f32,
+	pub bottom_left: f32,
+}
+
+impl CornerRadii {
+	/// Create radii for each corner, clockwise from the top left
+	pub fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+		Self {
+			top_left,
+			top_right,
+			bottom_right,
+			bottom_left,
+		}
+	}
+
+	/// Use the same radius for every corner
+	pub fn uniform(radius: f32) -> Self {
+		Self::new(radius, radius, radius, radius)
+	}
+
+	/// Check whether every corner is square
+	pub fn is_zero(&self) -> bool {
+		self.top_left <= 0.0
+			&& self.top_right <= 0.0
+			&& self.bottom_right <= 0.0
+			&& self.bottom_left <= 0.0
+	}
+
+	/// Radii scaled down together so that adjacent corners of a
+	/// `width` by `height` rectangle do not overlap
+	pub fn clamped(self, width: f32, height: f32) -> Self {
+		let radii = Self::new(
+			self.top_left.max(0.0),
+			self.top_right.max(0.0),
+			self.bottom_right.max(0.0),
+			self.bottom_left.max(0.0),
+		);
+		let ratio = |side: f32, a: f32, b: f32| if a + b > side { side / (a + b) } else { 1.0 };
+		let scale = ratio(width, radii.top_left, radii.top_right)
+			.min(ratio(width, radii.bottom_left, radii.bottom_right))
+			.min(ratio(height, radii.top_left, radii.bottom_left))
+			.min(ratio(height, radii.top_right, radii.bottom_right))
+			.max(0.0);
+		Self::new(
+			radii.top_left * scale,
+			radii.top_right * scale,
+			radii.bottom_right * scale,
+			radii.bottom_left * scale,
+		)
+	}
+}
+
+/// Rule deciding which points are inside a filled path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillRule {
+	/// Inside where the outline winds around the point a non-zero number of times
+	#[default]
+	NonZero,
+	/// Inside where a ray from the point crosses the outline an odd number of times
+	EvenOdd,
+}
+
+/// Shape drawn at the open ends of a stroke
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineCap {
+	/// The stroke ends exactly at the end point
+	#[default]
+	Butt,
+	/// A half circle is added beyond the end point
+	Round,
+	/// A half square is added beyond the end point
+	Square,
+}
+
+/// Shape drawn where two segments of a stroke meet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineJoin {
+	/// The outer edges are extended to a point, up to the miter limit
+	#[default]
+	Miter,
+	/// The corner is rounded
+	Round,
+	/// The corner is cut off
+	Bevel,
+}
+
+/// How the outline of a path is stroked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrokeStyle {
+	/// Width of the stroke in pixels
+	pub width: f32,
+	pub cap: LineCap,
+	pub join: LineJoin,
+	/// Longest miter, as a multiple of the width, before a join is beveled
+	pub miter_limit: f32,
+	/// Alternating lengths of dashes and gaps; empty for a solid stroke
+	pub dash: Vec<f32>,
+	/// Distance into the dash pattern at which the stroke starts
+	pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+	/// Create a solid stroke with butt caps and miter joins
+	pub fn new(width: f32) -> Self {
+		Self {
+			width,
+			cap: LineCap::Butt,
+			join: LineJoin::Miter,
+			miter_limit: 4.0,
+			dash: Vec::new(),
+			dash_offset: 0.0,
+		}
+	}
+
+	/// Set the shape of the stroke's open ends
+	pub fn with_cap(mut self, cap: LineCap) -> Self {
+		self.cap = cap;
+		self
+	}
+
+	/// Set the shape of the stroke's corners
+	pub fn with_join(mut self, join: LineJoin) -> Self {
+		self.join = join;
+		self
+	}
+
+	/// Set the longest miter, as a multiple of the width
+	pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+		self.miter_limit = miter_limit;
+		self
+	}
+
+	/// Dash the stroke with alternating dash and gap lengths
+	pub fn with_dash(mut self, dash: Vec<f32>, offset: f32) -> Self {
+		self.dash = dash;
+		self.dash_offset = offset;
+		self
+	}
+
+	/// Check whether the stroke has a usable dash pattern
+	///
+	/// Patterns with negative lengths or no length at all draw solid.
+	pub fn is_dashed(&self) -> bool {
+		!self.dash.is_empty()
+			&& self.dash.iter().all(|&length| length >= 0.0)
+			&& self.dash.iter().sum::<f32>() > 0.0
+	}
+}
+
+impl Default for StrokeStyle {
+	fn default() -> Self {
+		Self::new(1.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn near(a: (f32, f32), b: (f32, f32)) -> bool {
+		(a.0 - b.0).abs() < 0.01 && (a.1 - b.1).abs() < 0.01
+	}
+
+	#[test]
+	fn test_path_bounds() {
+		assert_eq!(Path::new().bounds(), None);
+
+		let path = Path::new()
+			.move_to(10.0, 20.0)
+			.quad_to(40.0, 0.0, 30.0, 30.0)
+			.line_to(5.0, 25.0);
+		assert_eq!(path.bounds(), Some(Rect::new(5.0, 0.0, 35.0, 30.0)));
+
+		// Arcs are held by the control points of their curves
+		let circle = Path::new().arc(50.0, 50.0, 10.0, 0.0, std::f32::consts::TAU);
+		let bounds = circle.bounds().unwrap();
+		assert!(bounds.x <= 40.0 && bounds.y <= 40.0);
+		assert!(bounds.x + bounds.width >= 60.0 && bounds.y + bounds.height >= 60.0);
+	}
+
+	#[test]
+	fn test_flatten_lines() {
+		let path = Path::rect(Rect::new(0.0, 0.0, 10.0, 5.0))
+			.move_to(20.0, 20.0)
+			.line_to(30.0, 20.0);
+		let polylines = path.flatten(0.25);
+
+		assert_eq!(polylines.len(), 2);
+		assert!(polylines[0].closed);
+		assert_eq!(
+			polylines[0].points,
+			vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (0.0, 5.0)]
+		);
+		assert!(!polylines[1].closed);
+		assert_eq!(polylines[1].points, vec![(20.0, 20.0), (30.0, 20.0)]);
+	}
+
+	#[test]
+	fn test_flatten_curves_within_tolerance() {
+		let path = Path::new().arc(50.0, 50.0, 40.0, 0.0, std::f32::consts::TAU);
+		let polylines = path.flatten(0.1);
+
+		assert_eq!(polylines.len(), 1);
+		let points = &polylines[0].points;
+		assert!(points.len() > 16);
+		assert!(near(points[0], (90.0, 50.0)));
+		assert!(near(*points.last().unwrap(), (90.0, 50.0)));
+		assert!(
+			points
+				.iter()
+				.all(|&(x, y)| ((x - 50.0).hypot(y - 50.0) - 40.0).abs() < 0.2)
+		);
+	}
+
+	#[test]
+	fn test_arc_direction() {
+		// A positive sweep from the right runs down the screen first
+		let curves = Path::new()
+			.move_to(0.0, 0.0)
+			.arc(0.0, 0.0, 10.0, 0.0, FRAC_PI_2)
+			.curves();
+
+		assert_eq!(curves[1], PathCommand::LineTo { x: 10.0, y: 0.0 });
+		match curves[2] {
+			PathCommand::CubicTo { x, y, .. } => assert!(near((x, y), (0.0, 10.0))),
+			command => panic!("Expected a cubic, got {:?}", command),
+		}
+	}
+
+	#[test]
+	fn test_transformed() {
+		let path = Path::new()
+			.move_to(0.0, 0.0)
+			.line_to(10.0, 0.0)
+			.arc(0.0, 0.0, 10.0, 0.0, FRAC_PI_2)
+			.close()
+			.transformed(&Transform::from_translate(5.0, 5.0).scale(2.0, 1.0));
+
+		let commands = path.commands();
+		assert_eq!(commands[0], PathCommand::MoveTo { x: 5.0, y: 5.0 });
+		assert_eq!(commands[1], PathCommand::LineTo { x: 25.0, y: 5.0 });
+		// The arc is scaled into an elliptical curve
+		match commands[3] {
+			PathCommand::CubicTo { x, y, .. } => assert!(near((x, y), (5.0, 15.0))),
+			command => panic!("Expected a cubic, got {:?}", command),
+		}
+		assert_eq!(commands.last(), Some(&PathCommand::Close));
+	}
+
+	#[test]
+	fn test_rounded_rect() {
+		let path = Path::rounded_rect(
+			Rect::new(0.0, 0.0, 100.0, 50.0),
+			CornerRadii::new(10.0, 0.0, 20.0, 0.0),
+		);
+		let points = &path.flatten(0.1)[0].points;
+
+		assert!(near(points[0], (10.0, 0.0)));
+		assert!(points.contains(&(100.0, 0.0)));
+		assert!(points.contains(&(0.0, 50.0)));
+		assert!(
+			points
+				.iter()
+				.all(|&(x, y)| (0.0..=100.0).contains(&x) && (0.0..=50.0).contains(&y))
+		);
+		// The rounded corners cut across the rectangle's corners
+		assert!(points.iter().all(|&point| !near(point, (0.0, 0.0))));
+		assert!(points.iter().all(|&point| !near(point, (100.0, 50.0))));
+	}
+
+	#[test]
+	fn test_corner_radii_clamped() {
+		let radii = CornerRadii::uniform(40.0).clamped(60.0, 100.0);
+		assert_eq!(radii, CornerRadii::uniform(30.0));
+
+		let radii = CornerRadii::new(10.0, -5.0, 10.0, 10.0).clamped(100.0, 100.0);
+		assert_eq!(radii, CornerRadii::new(10.0, 0.0, 10.0, 10.0));
+		assert!(CornerRadii::default().is_zero());
+	}
+
+	#[test]
+	fn test_stroke_style() {
+		let stroke = StrokeStyle::new(2.0)
+			.with_cap(LineCap::Round)
+			.with_join(LineJoin::Bevel)
+			.with_dash(vec![4.0, 2.0], 1.0);
+
+		assert_eq!(stroke.cap, LineCap::Round);
+		assert_eq!(stroke.join, LineJoin::Bevel);
+		assert!(stroke.is_dashed());
+		assert!(
+			!StrokeStyle::new(1.0)
+				.with_dash(vec![0.0, 0.0], 0.0)
+				.is_dashed()
+		);
+	}
+}
@@ -1,12 +1,25 @@
 //! Rendering backend abstraction
 //!
 //! Provides a platform-independent rendering interface that can be implemented
-//! for different graphics APIs (Direct2D, Core Graphics, Cairo, etc.)
-
+//! for different graphics APIs (Direct2D, Core Graphics, Cairo, etc.), and
+//! [`DisplayList`]s that record commands for replay.
+
+use super::brush::Brush;
+pub use super::display_list::{DisplayList, DisplayListCache};
+use super::effects;
+use super::image::ImageCommand;
+use super::layer::BlendMode;
+use super::path::{CornerRadii, FillRule, Path, StrokeStyle};
+use super::transform::Transform;
+use engage_ux_core::component::Rect as CoreRect;
+use engage_ux_core::media::{ImageData, MediaError};
+use engage_ux_core::rendering::{DamageRegion, SvgDocument};
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
 
 /// Color for rendering
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Color {
 	pub r: f32,
 	pub g: f32,
@@ -24,8 +37,21 @@ impl Color {
 	}
 }
 
+impl From<&engage_ux_core::color::Color> for Color {
+	fn from(color: &engage_ux_core::color::Color) -> Self {
+		let [r, g, b, a] = color.to_rgb().components();
+		Self::rgba(r, g, b, a)
+	}
+}
+
+impl From<engage_ux_core::color::Color> for Color {
+	fn from(color: engage_ux_core::color::Color) -> Self {
+		Self::from(&color)
+	}
+}
+
 /// Rectangle for rendering
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
 	pub x: f32,
 	pub y: f32,
@@ -42,10 +68,49 @@ impl Rect {
 			height,
 		}
 	}
+
+	/// Smallest rectangle holding both rectangles
+	pub fn union(&self, other: &Rect) -> Rect {
+		CoreRect::from(*self).union(&(*other).into()).into()
+	}
+
+	/// The area two rectangles share, if any
+	pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+		CoreRect::from(*self)
+			.intersection(&(*other).into())
+			.map(Rect::from)
+	}
+
+	/// Check whether two rectangles share any area
+	pub fn intersects(&self, other: &Rect) -> bool {
+		self.intersection(other).is_some()
+	}
+
+	/// The rectangle grown by `amount` on every side
+	pub fn outset(&self, amount: f32) -> Rect {
+		Rect::new(
+			self.x - amount,
+			self.y - amount,
+			self.width + amount * 2.0,
+			self.height + amount * 2.0,
+		)
+	}
+}
+
+impl From<Rect> for CoreRect {
+	fn from(rect: Rect) -> Self {
+		Self::new(rect.x, rect.y, rect.width, rect.height)
+	}
+}
+
+impl From<CoreRect> for Rect {
+	fn from(rect: CoreRect) -> Self {
+		Self::new(rect.x, rect.y, rect.width, rect.height)
+	}
 }
 
 /// Text alignment
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextAlign {
 	Left,
 	Center,
@@ -53,7 +118,7 @@ pub enum TextAlign {
 }
 
 /// Rendering commands
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RenderCommand {
 	/// Clear the screen with a color
 	Clear(Color),
@@ -65,7 +130,7 @@ pub enum RenderCommand {
 		color: Color,
 		width: f32,
 	},
-	/// Draw text
+	/// Draw text, with `y` at the baseline and `x` at the anchor given by `align`
 	Text {
 		text: String,
 		x: f32,
@@ -91,10 +156,203 @@ pub enum RenderCommand {
 		color: Color,
 		filled: bool,
 	},
+	/// Fill a rectangle with rounded corners
+	FillRoundedRect {
+		rect: Rect,
+		radii: CornerRadii,
+		brush: Brush,
+	},
+	/// Stroke the outline of a rectangle with rounded corners
+	StrokeRoundedRect {
+		rect: Rect,
+		radii: CornerRadii,
+		brush: Brush,
+		width: f32,
+	},
+	/// Fill the inside of a path
+	FillPath {
+		path: Path,
+		brush: Brush,
+		rule: FillRule,
+	},
+	/// Stroke the outline of a path
+	StrokePath {
+		path: Path,
+		brush: Brush,
+		stroke: StrokeStyle,
+	},
+	/// Draw the blurred shadow of a rectangle with rounded corners, like CSS
+	/// `box-shadow`
+	///
+	/// The shadow is the rectangle moved by the offsets and grown by `spread`
+	/// on every side, blurred by a Gaussian with a standard deviation of half
+	/// `blur_radius`.
+	Shadow {
+		rect: Rect,
+		radii: CornerRadii,
+		color: Color,
+		offset_x: f32,
+		offset_y: f32,
+		blur_radius: f32,
+		spread: f32,
+	},
+	/// Blur what has already been drawn inside a rectangle with rounded
+	/// corners, like CSS `backdrop-filter: blur()`
+	BackdropBlur {
+		rect: Rect,
+		radii: CornerRadii,
+		blur_radius: f32,
+	},
+	/// Draw a bitmap, fitted, filtered, sliced and tinted as the command
+	/// describes
+	Image(ImageCommand),
+	/// Draw an SVG document fitted into a rectangle, keeping its aspect
+	/// ratio
+	///
+	/// A tint recolors every drawn pixel, for single-color icons.
+	Svg {
+		document: Arc<SvgDocument>,
+		rect: Rect,
+		tint: Option<Color>,
+	},
 	/// Set clip region
+	///
+	/// The rectangle is in the coordinates of the current transform, so a
+	/// rotated or skewed transform clips to a rotated or skewed region.
 	SetClip(Rect),
 	/// Restore previous clip
 	RestoreClip,
+	/// Transform everything drawn until the matching `PopTransform`
+	///
+	/// The transform applies in the local coordinates of the transforms
+	/// already pushed.
+	PushTransform(Transform),
+	/// Restore the transform in place before the last `PushTransform`
+	PopTransform,
+	/// Draw into an offscreen layer until the matching `PopLayer`
+	PushLayer { opacity: f32, blend_mode: BlendMode },
+	/// Composite the last pushed layer onto what is below it with its
+	/// opacity and blend mode
+	PopLayer,
+}
+
+impl RenderCommand {
+	/// Rectangle holding everything the command draws, in the coordinates
+	/// of the current transform
+	///
+	/// The rectangle may be larger than what is drawn, never smaller, except
+	/// for text, whose extent is estimated from its length and font size.
+	/// `None` for commands that draw nothing themselves, such as clips,
+	/// transforms and layers, and for `Clear`, which covers everything.
+	pub fn bounds(&self) -> Option<Rect> {
+		// Anti-aliased edges reach up to a pixel beyond the geometry
+		const FRINGE: f32 = 1.0;
+
+		match self {
+			RenderCommand::FillRect { rect, .. } | RenderCommand::FillRoundedRect { rect, .. } => {
+				Some(rect.outset(FRINGE))
+			}
+			RenderCommand::StrokeRect { rect, width, .. }
+			| RenderCommand::StrokeRoundedRect { rect, width, .. } => {
+				Some(rect.outset(width / 2.0 + FRINGE))
+			}
+			RenderCommand::Text {
+				text,
+				x,
+				y,
+				font_size,
+				align,
+				..
+			} => {
+				// Glyphs rarely advance more than an em or reach further
+				// than an em above and half an em below the baseline
+				let width = text.chars().count() as f32 * font_size;
+				let left = match align {
+					TextAlign::Left => *x,
+					TextAlign::Center => x - width / 2.0,
+					TextAlign::Right => x - width,
+				};
+				Some(Rect::new(left, y - font_size, width, font_size * 1.5).outset(FRINGE))
+			}
+			RenderCommand::Line {
+				x1,
+				y1,
+				x2,
+				y2,
+				width,
+				..
+			} => {
+				let rect = Rect::new(x1.min(*x2), y1.min(*y2), (x2 - x1).abs(), (y2 - y1).abs());
+				Some(rect.outset(width.max(1.0) / 2.0 + FRINGE))
+			}
+			RenderCommand::Circle { x, y, radius, .. } => {
+				let rect = Rect::new(x - radius, y - radius, radius * 2.0, radius * 2.0);
+				Some(rect.outset(FRINGE))
+			}
+			RenderCommand::FillPath { path, .. } => path.bounds().map(|rect| rect.outset(FRINGE)),
+			RenderCommand::StrokePath { path, stroke, .. } => {
+				// Miters and square caps reach furthest from the outline
+				let reach = stroke.width / 2.0 * stroke.miter_limit.max(std::f32::consts::SQRT_2);
+				path.bounds().map(|rect| rect.outset(reach + FRINGE))
+			}
+			RenderCommand::Shadow {
+				rect,
+				offset_x,
+				offset_y,
+				blur_radius,
+				spread,
+				..
+			} => {
+				let moved = Rect::new(
+					rect.x + offset_x,
+					rect.y + offset_y,
+					rect.width,
+					rect.height,
+				);
+				Some(moved.outset(spread + effects::blur_extent(*blur_radius) + FRINGE))
+			}
+			RenderCommand::BackdropBlur { rect, .. } => Some(rect.outset(FRINGE)),
+			RenderCommand::Image(command) => Some(command.dest.outset(FRINGE)),
+			RenderCommand::Svg { rect, .. } => Some(rect.outset(FRINGE)),
+			RenderCommand::Clear(_)
+			| RenderCommand::SetClip(_)
+			| RenderCommand::RestoreClip
+			| RenderCommand::PushTransform(_)
+			| RenderCommand::PopTransform
+			| RenderCommand::PushLayer { .. }
+			| RenderCommand::PopLayer => None,
+		}
+	}
+}
+
+/// Rectangle holding everything a list of commands draws, in the
+/// coordinates the list starts in
+///
+/// Follows the transforms the commands push and pop; see
+/// [`RenderCommand::bounds`].
+pub fn commands_bounds(commands: &[RenderCommand]) -> Option<Rect> {
+	let mut transforms = vec![Transform::identity()];
+	let mut bounds: Option<Rect> = None;
+	for command in commands {
+		let current = transforms.last().copied().unwrap_or_default();
+		match command {
+			RenderCommand::PushTransform(transform) => {
+				transforms.push(current.pre_concat(transform));
+			}
+			RenderCommand::PopTransform => {
+				if transforms.len() > 1 {
+					transforms.pop();
+				}
+			}
+			command => {
+				if let Some(rect) = command.bounds() {
+					let rect = current.map_rect(rect);
+					bounds = Some(bounds.map_or(rect, |bounds| bounds.union(&rect)));
+				}
+			}
+		}
+	}
+	bounds
 }
 
 /// Rendering context for a frame
@@ -117,6 +375,41 @@ pub trait RenderContext {
 
 	/// Get the size of the render target
 	fn size(&self) -> (u32, u32);
+
+	/// Restrict painting, until the frame ends, to a damaged region in
+	/// surface pixels
+	///
+	/// Call after [`RenderContext::begin_frame`], which lifts the
+	/// restriction. Pixels outside the region keep what the previous frame
+	/// drew, even for `Clear`. Contexts that cannot restrict painting
+	/// ignore the region and repaint whatever they are given.
+	fn set_damage(&mut self, _damage: &DamageRegion) {}
+
+	/// The region the current frame is restricted to, grown to whole
+	/// pixels, for presenting only the parts of the surface that changed
+	///
+	/// `None` when the whole frame is painted.
+	fn damage(&self) -> Option<&DamageRegion> {
+		None
+	}
+
+	/// Read back what has been drawn as straight (not premultiplied) RGBA8,
+	/// row by row
+	///
+	/// Returns `None` for contexts that cannot read back their target.
+	fn read_pixels(&self) -> Option<ImageData> {
+		None
+	}
+
+	/// Save what has been drawn as a PNG file, for screenshots from
+	/// headless runs
+	fn save_png(&self, path: &str) -> Result<(), MediaError> {
+		self.read_pixels()
+			.ok_or_else(|| {
+				MediaError::SaveFailed("Render context cannot read back pixels".to_string())
+			})?
+			.save_png(path)
+	}
 }
 
 /// Platform-specific rendering backend
@@ -209,6 +502,55 @@ mod tests {
 		context.begin_frame();
 		context.execute(RenderCommand::Clear(Color::rgb(0.0, 0.0, 0.0)));
 		context.end_frame();
+		assert!(context.read_pixels().is_none());
+		assert!(matches!(
+			context.save_png("stub.png"),
+			Err(MediaError::SaveFailed(_))
+		));
+	}
+
+	#[test]
+	fn test_core_conversions() {
+		let color = Color::from(engage_ux_core::color::Color::from_hex("#FF0000").unwrap());
+		assert_eq!(color, Color::rgb(1.0, 0.0, 0.0));
+
+		let rect = Rect::from(engage_ux_core::component::Rect::new(1.0, 2.0, 3.0, 4.0));
+		assert_eq!(rect, Rect::new(1.0, 2.0, 3.0, 4.0));
+	}
+
+	#[test]
+	fn test_command_bounds() {
+		let fill = RenderCommand::FillRect {
+			rect: Rect::new(10.0, 10.0, 20.0, 20.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		};
+		assert_eq!(fill.bounds(), Some(Rect::new(9.0, 9.0, 22.0, 22.0)));
+		assert_eq!(RenderCommand::PopTransform.bounds(), None);
+
+		// Shadows reach beyond their rectangle by the offset and blur
+		let shadow = RenderCommand::Shadow {
+			rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+			radii: CornerRadii::uniform(0.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+			offset_x: 0.0,
+			offset_y: 4.0,
+			blur_radius: 4.0,
+			spread: 0.0,
+		};
+		assert_eq!(shadow.bounds(), Some(Rect::new(-7.0, -3.0, 24.0, 24.0)));
+
+		// Transforms pushed in the list move what follows them
+		let commands = [
+			fill.clone(),
+			RenderCommand::PushTransform(Transform::from_translate(100.0, 0.0)),
+			fill,
+			RenderCommand::PopTransform,
+		];
+		assert_eq!(
+			commands_bounds(&commands),
+			Some(Rect::new(9.0, 9.0, 122.0, 22.0))
+		);
+		assert_eq!(commands_bounds(&[RenderCommand::RestoreClip]), None);
 	}
 
 	#[test]
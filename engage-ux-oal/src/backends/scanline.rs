@@ -0,0 +1,484 @@
+//! Software scan conversion for renderers without a vector backend
+//!
+//! Paths are flattened into polygons and filled a row at a time, sampling
+//! each row at several heights and covering partial pixels horizontally,
+//! which anti-aliases edges in both directions. Strokes are turned into
+//! polygons for every segment, join and cap, all wound the same way so
+//! the non-zero rule merges them where they overlap.
+
+use super::path::{FillRule, LineCap, LineJoin, Path, Polyline, StrokeStyle};
+
+/// Greatest distance in pixels between a curve and its flattened outline
+pub(crate) const TOLERANCE: f32 = 0.25;
+
+/// Sample rows per pixel row
+const SUBSAMPLES: usize = 4;
+
+/// A closed polygon, its last point joined to its first
+pub(crate) type Polygon = Vec<(f32, f32)>;
+
+/// Polygons covering the inside of a path
+pub(crate) fn fill_polygons(path: &Path) -> Vec<Polygon> {
+	path.flatten(TOLERANCE)
+		.into_iter()
+		.map(|polyline| polyline.points)
+		.collect()
+}
+
+/// Rasterize polygons into a `width` by `height` surface
+///
+/// `plot` is called once for every pixel the polygons touch, with the
+/// fraction of the pixel they cover.
+pub(crate) fn rasterize(
+	polygons: &[Polygon],
+	rule: FillRule,
+	width: u32,
+	height: u32,
+	mut plot: impl FnMut(i32, i32, f32),
+) {
+	// Edges as (x at top, top, bottom, dx per y, winding direction)
+	let mut edges = Vec::new();
+	for polygon in polygons {
+		for (index, &(x0, y0)) in polygon.iter().enumerate() {
+			let (x1, y1) = polygon[(index + 1) % polygon.len()];
+			if y0 == y1 || !(x0.is_finite() && y0.is_finite() && x1.is_finite() && y1.is_finite()) {
+				continue;
+			}
+			let (top, bottom, x_top, x_bottom, winding) = if y0 < y1 {
+				(y0, y1, x0, x1, 1)
+			} else {
+				(y1, y0, x1, x0, -1)
+			};
+			edges.push((
+				x_top,
+				top,
+				bottom,
+				(x_bottom - x_top) / (bottom - top),
+				winding,
+			));
+		}
+	}
+	if edges.is_empty() || width == 0 || height == 0 {
+		return;
+	}
+
+	let top = edges
+		.iter()
+		.map(|edge| edge.1)
+		.fold(f32::INFINITY, f32::min);
+	let bottom = edges
+		.iter()
+		.map(|edge| edge.2)
+		.fold(f32::NEG_INFINITY, f32::max);
+	let first_row = (top.floor().max(0.0) as u32).min(height);
+	let last_row = (bottom.ceil().max(0.0) as u32).min(height);
+
+	let mut coverage = vec![0.0f32; width as usize];
+	let mut crossings: Vec<(f32, i32)> = Vec::new();
+	let weight = 1.0 / SUBSAMPLES as f32;
+	for row in first_row..last_row {
+		let mut touched = false;
+		for sample in 0..SUBSAMPLES {
+			let y = row as f32 + (sample as f32 + 0.5) * weight;
+			crossings.clear();
+			crossings.extend(
+				edges
+					.iter()
+					.filter(|edge| edge.1 <= y && y < edge.2)
+					.map(|edge| (edge.0 + (y - edge.1) * edge.3, edge.4)),
+			);
+			crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+			let mut winding = 0;
+			for pair in crossings.windows(2) {
+				winding += pair[0].1;
+				let inside = match rule {
+					FillRule::NonZero => winding != 0,
+					FillRule::EvenOdd => winding % 2 != 0,
+				};
+				if inside {
+					add_span(&mut coverage, pair[0].0, pair[1].0, weight);
+					touched = true;
+				}
+			}
+		}
+
+		if touched {
+			for (x, value) in coverage.iter_mut().enumerate() {
+				if *value > 0.0 {
+					plot(x as i32, row as i32, value.min(1.0));
+					*value = 0.0;
+				}
+			}
+		}
+	}
+}
+
+/// Add coverage for a horizontal span of one sample row
+fn add_span(coverage: &mut [f32], start: f32, end: f32, weight: f32) {
+	let width = coverage.len() as f32;
+	let (start, end) = (start.clamp(0.0, width), end.clamp(0.0, width));
+	if start >= end {
+		return;
+	}
+	let (first, last) = (start.floor() as usize, end.floor() as usize);
+	if first == last {
+		coverage[first] += (end - start) * weight;
+		return;
+	}
+	coverage[first] += (first as f32 + 1.0 - start) * weight;
+	for value in &mut coverage[first + 1..last] {
+		*value += weight;
+	}
+	if last < coverage.len() {
+		coverage[last] += (end - last as f32) * weight;
+	}
+}
+
+/// Polygons covering the stroke of a path, to be filled with
+/// [`FillRule::NonZero`]
+pub(crate) fn stroke_polygons(path: &Path, style: &StrokeStyle) -> Vec<Polygon> {
+	if style.width <= 0.0 {
+		return Vec::new();
+	}
+	let mut polylines = path.flatten(TOLERANCE);
+	if style.is_dashed() {
+		polylines = dash(&polylines, &style.dash, style.dash_offset);
+	}
+
+	let half = style.width / 2.0;
+	let mut polygons = Vec::new();
+	for polyline in &polylines {
+		let mut points = polyline.points.clone();
+		points.dedup();
+		if polyline.closed && points.len() > 1 && points.first() == points.last() {
+			points.pop();
+		}
+
+		if points.len() == 1 {
+			// A zero-length subpath is drawn as its caps
+			let (x, y) = points[0];
+			match style.cap {
+				LineCap::Butt => {}
+				LineCap::Round => polygons.push(circle(x, y, half)),
+				LineCap::Square => polygons.push(vec![
+					(x - half, y - half),
+					(x + half, y - half),
+					(x + half, y + half),
+					(x - half, y + half),
+				]),
+			}
+			continue;
+		}
+
+		let closed = polyline.closed && points.len() > 2;
+		let count = points.len();
+		let segments = if closed { count } else { count - 1 };
+		for index in 0..segments {
+			let (p, q) = (points[index], points[(index + 1) % count]);
+			let n = normal(p, q, half);
+			polygons.push(vec![
+				(p.0 + n.0, p.1 + n.1),
+				(q.0 + n.0, q.1 + n.1),
+				(q.0 - n.0, q.1 - n.1),
+				(p.0 - n.0, p.1 - n.1),
+			]);
+		}
+
+		let joins = if closed { 0..count } else { 1..count - 1 };
+		for index in joins {
+			let previous = points[(index + count - 1) % count];
+			let (vertex, next) = (points[index], points[(index + 1) % count]);
+			if let Some(polygon) = join(previous, vertex, next, half, style) {
+				polygons.push(polygon);
+			}
+		}
+
+		if !closed {
+			polygons.extend(cap(points[1], points[0], half, style.cap));
+			polygons.extend(cap(points[count - 2], points[count - 1], half, style.cap));
+		}
+	}
+
+	for polygon in &mut polygons {
+		if signed_area(polygon) < 0.0 {
+			polygon.reverse();
+		}
+	}
+	polygons
+}
+
+/// Unit direction from `p` to `q`
+fn direction(p: (f32, f32), q: (f32, f32)) -> (f32, f32) {
+	let length = (q.0 - p.0).hypot(q.1 - p.1);
+	((q.0 - p.0) / length, (q.1 - p.1) / length)
+}
+
+/// Normal of the segment from `p` to `q`, `half` pixels long
+fn normal(p: (f32, f32), q: (f32, f32), half: f32) -> (f32, f32) {
+	let (dx, dy) = direction(p, q);
+	(-dy * half, dx * half)
+}
+
+/// Polygon filling the outside of the corner at `vertex`
+fn join(
+	previous: (f32, f32),
+	vertex: (f32, f32),
+	next: (f32, f32),
+	half: f32,
+	style: &StrokeStyle,
+) -> Option<Polygon> {
+	let (d0, d1) = (direction(previous, vertex), direction(vertex, next));
+	let cross = d0.0 * d1.1 - d0.1 * d1.0;
+	let dot = d0.0 * d1.0 + d0.1 * d1.1;
+	if cross.abs() < 1e-4 && dot > 0.0 {
+		return None;
+	}
+	if style.join == LineJoin::Round {
+		return Some(circle(vertex.0, vertex.1, half));
+	}
+
+	// The outer side of the corner is opposite the way the stroke turns
+	let side = if cross > 0.0 { -1.0 } else { 1.0 };
+	let (n0, n1) = ((-d0.1 * side, d0.0 * side), (-d1.1 * side, d1.0 * side));
+	let outer0 = (vertex.0 + n0.0 * half, vertex.1 + n0.1 * half);
+	let outer1 = (vertex.0 + n1.0 * half, vertex.1 + n1.1 * half);
+
+	if style.join == LineJoin::Miter && dot > -1.0 + 1e-4 {
+		// Ratio of the miter's length to the stroke's width
+		let ratio = 1.0 / ((1.0 + dot) / 2.0).sqrt();
+		if ratio <= style.miter_limit {
+			let scale = half / (1.0 + dot);
+			let tip = (
+				vertex.0 + (n0.0 + n1.0) * scale,
+				vertex.1 + (n0.1 + n1.1) * scale,
+			);
+			return Some(vec![vertex, outer0, tip, outer1]);
+		}
+	}
+	Some(vec![vertex, outer0, outer1])
+}
+
+/// Polygon added beyond the end point `end` of a segment from `from`
+fn cap(from: (f32, f32), end: (f32, f32), half: f32, cap: LineCap) -> Option<Polygon> {
+	match cap {
+		LineCap::Butt => None,
+		LineCap::Round => Some(circle(end.0, end.1, half)),
+		LineCap::Square => {
+			let (dx, dy) = direction(from, end);
+			let n = (-dy * half, dx * half);
+			let out = (end.0 + dx * half, end.1 + dy * half);
+			Some(vec![
+				(end.0 + n.0, end.1 + n.1),
+				(out.0 + n.0, out.1 + n.1),
+				(out.0 - n.0, out.1 - n.1),
+				(end.0 - n.0, end.1 - n.1),
+			])
+		}
+	}
+}
+
+/// Polygon approximating a circle
+fn circle(cx: f32, cy: f32, radius: f32) -> Polygon {
+	let segments = ((radius * 2.0).ceil() as usize).clamp(8, 128);
+	(0..segments)
+		.map(|index| {
+			let angle = index as f32 / segments as f32 * std::f32::consts::TAU;
+			(cx + radius * angle.cos(), cy + radius * angle.sin())
+		})
+		.collect()
+}
+
+/// Twice the signed area of a polygon, positive when wound clockwise on
+/// screen
+fn signed_area(polygon: &[(f32, f32)]) -> f32 {
+	let mut area = 0.0;
+	for (index, &(x0, y0)) in polygon.iter().enumerate() {
+		let (x1, y1) = polygon[(index + 1) % polygon.len()];
+		area += x0 * y1 - x1 * y0;
+	}
+	area
+}
+
+/// Split polylines into the dashes of a dash pattern
+fn dash(polylines: &[Polyline], pattern: &[f32], offset: f32) -> Vec<Polyline> {
+	let total: f32 = pattern.iter().sum();
+	let mut dashes = Vec::new();
+
+	for polyline in polylines {
+		let mut points = polyline.points.clone();
+		if polyline.closed
+			&& let Some(&first) = points.first()
+		{
+			points.push(first);
+		}
+
+		// Every subpath starts at the same point of the pattern
+		let mut index = 0;
+		let mut remaining = pattern[0];
+		let mut phase = offset.rem_euclid(total);
+		while phase > 0.0 {
+			if phase < remaining {
+				remaining -= phase;
+				break;
+			}
+			phase -= remaining;
+			index = (index + 1) % pattern.len();
+			remaining = pattern[index];
+		}
+
+		let mut current: Vec<(f32, f32)> = Vec::new();
+		for segment in points.windows(2) {
+			let (mut p, q) = (segment[0], segment[1]);
+			let mut length = (q.0 - p.0).hypot(q.1 - p.1);
+			while length > 0.0 {
+				let step = remaining.min(length);
+				let t = step / length;
+				let point = (p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t);
+				if index % 2 == 0 {
+					if current.is_empty() {
+						current.push(p);
+					}
+					current.push(point);
+				}
+				p = point;
+				length -= step;
+				remaining -= step;
+				if remaining <= 0.0 {
+					if !current.is_empty() {
+						dashes.push(Polyline {
+							points: std::mem::take(&mut current),
+							closed: false,
+						});
+					}
+					index = (index + 1) % pattern.len();
+					remaining = pattern[index];
+				}
+			}
+		}
+		if current.len() > 1 {
+			dashes.push(Polyline {
+				points: current,
+				closed: false,
+			});
+		}
+	}
+	dashes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::backends::renderer::Rect;
+
+	fn covered(polygons: &[Polygon], rule: FillRule, size: u32) -> Vec<Vec<f32>> {
+		let mut pixels = vec![vec![0.0; size as usize]; size as usize];
+		rasterize(polygons, rule, size, size, |x, y, coverage| {
+			pixels[y as usize][x as usize] = coverage;
+		});
+		pixels
+	}
+
+	#[test]
+	fn test_rasterize_rect() {
+		let polygons = fill_polygons(&Path::rect(Rect::new(2.0, 2.0, 4.0, 3.5)));
+		let pixels = covered(&polygons, FillRule::NonZero, 10);
+
+		assert_eq!(pixels[2][2], 1.0);
+		assert_eq!(pixels[4][5], 1.0);
+		assert_eq!(pixels[5][3], 0.5);
+		assert_eq!(pixels[2][6], 0.0);
+		assert_eq!(pixels[1][3], 0.0);
+	}
+
+	#[test]
+	fn test_rasterize_partial_columns() {
+		let polygons = fill_polygons(&Path::rect(Rect::new(1.25, 0.0, 2.0, 1.0)));
+		let pixels = covered(&polygons, FillRule::NonZero, 4);
+
+		assert_eq!(pixels[0], vec![0.0, 0.75, 1.0, 0.25]);
+	}
+
+	#[test]
+	fn test_fill_rules() {
+		// Two nested squares wound the same way
+		let path = Path::rect(Rect::new(0.0, 0.0, 10.0, 10.0)).move_to(3.0, 3.0);
+		let path = path
+			.line_to(7.0, 3.0)
+			.line_to(7.0, 7.0)
+			.line_to(3.0, 7.0)
+			.close();
+		let polygons = fill_polygons(&path);
+
+		assert_eq!(covered(&polygons, FillRule::NonZero, 10)[5][5], 1.0);
+		assert_eq!(covered(&polygons, FillRule::EvenOdd, 10)[5][5], 0.0);
+		assert_eq!(covered(&polygons, FillRule::EvenOdd, 10)[1][1], 1.0);
+	}
+
+	#[test]
+	fn test_stroke_caps() {
+		let path = Path::new().move_to(4.0, 10.0).line_to(16.0, 10.0);
+		let butt = covered(
+			&stroke_polygons(&path, &StrokeStyle::new(4.0)),
+			FillRule::NonZero,
+			20,
+		);
+		let square = covered(
+			&stroke_polygons(&path, &StrokeStyle::new(4.0).with_cap(LineCap::Square)),
+			FillRule::NonZero,
+			20,
+		);
+
+		assert_eq!(butt[9][10], 1.0);
+		assert_eq!(butt[7][10], 0.0);
+		assert_eq!(butt[9][3], 0.0);
+		assert_eq!(square[9][3], 1.0);
+		assert_eq!(square[9][17], 1.0);
+	}
+
+	#[test]
+	fn test_stroke_joins() {
+		let path = Path::new()
+			.move_to(4.0, 4.0)
+			.line_to(14.0, 4.0)
+			.line_to(14.0, 14.0);
+		let draw = |join| {
+			let style = StrokeStyle::new(4.0).with_join(join);
+			covered(&stroke_polygons(&path, &style), FillRule::NonZero, 20)
+		};
+
+		// The outer corner is square with a miter and cut off with a bevel
+		assert_eq!(draw(LineJoin::Miter)[2][15], 1.0);
+		assert!(draw(LineJoin::Bevel)[2][15] < 0.5);
+		assert!(draw(LineJoin::Round)[2][15] < 1.0);
+	}
+
+	#[test]
+	fn test_stroke_dashes() {
+		let path = Path::new().move_to(0.0, 5.0).line_to(20.0, 5.0);
+		let style = StrokeStyle::new(2.0).with_dash(vec![4.0, 2.0], 0.0);
+		let pixels = covered(&stroke_polygons(&path, &style), FillRule::NonZero, 20);
+		let row: Vec<bool> = pixels[4].iter().map(|&coverage| coverage > 0.5).collect();
+
+		assert_eq!(
+			row,
+			vec![
+				true, true, true, true, false, false, true, true, true, true, false, false, true,
+				true, true, true, false, false, true, true,
+			]
+		);
+	}
+
+	#[test]
+	fn test_dash_offset() {
+		let line = Polyline {
+			points: vec![(0.0, 0.0), (10.0, 0.0)],
+			closed: false,
+		};
+		let dashes = dash(&[line], &[4.0, 2.0], 5.0);
+
+		// The line starts one pixel before the end of the first gap
+		assert_eq!(dashes[0].points, vec![(1.0, 0.0), (5.0, 0.0)]);
+		assert_eq!(dashes[1].points, vec![(7.0, 0.0), (10.0, 0.0)]);
+	}
+}
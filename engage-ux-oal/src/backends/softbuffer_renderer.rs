@@ -1,17 +1,45 @@
 //! Softbuffer-based renderer implementation for all platforms
 //!
 //! This provides a safe, cross-platform software renderer using the softbuffer crate.
+//! Paths, rounded rectangles and gradients are scan converted in software
+//! with anti-aliased edges. Under a transform other than the identity,
+//! rectangles, lines and circles are drawn as paths too. A frame restricted
+//! to a damaged region only touches, and only presents, the pixels inside
+//! it.
 
-use super::renderer::{Color, Rect, RenderBackend, RenderCommand, RenderContext};
+use super::brush::Brush;
+use super::effects::{self, Mask};
+use super::image::{self, ImageCommand};
+use super::layer::{self, BlendMode};
+use super::path::{CornerRadii, FillRule, Path, StrokeStyle};
+use super::renderer::{Color, Rect, RenderBackend, RenderCommand, RenderContext, TextAlign};
+use super::scanline::{self, Polygon};
+use super::transform::{self, Transform};
+use engage_ux_core::media::image::ColorType;
+use engage_ux_core::media::{ImageData, ImageFormat};
+use engage_ux_core::rendering::{DamageRegion, SvgDocument, TextPixel, TextRasterizer};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::num::NonZeroU32;
+use std::sync::Arc;
 
 /// Softbuffer-based renderer that works across all platforms
 #[derive(Debug)]
-pub struct SoftbufferRenderer;
+pub struct SoftbufferRenderer {
+	text: TextRasterizer,
+}
 
 impl SoftbufferRenderer {
 	/// Create a new softbuffer renderer
 	pub fn new() -> Self {
-		Self
+		Self {
+			text: TextRasterizer::new(),
+		}
+	}
+
+	/// Draw text in contexts created by this renderer with the given rasterizer
+	pub fn with_text(mut self, text: TextRasterizer) -> Self {
+		self.text = text;
+		self
 	}
 }
 
@@ -23,7 +51,7 @@ impl Default for SoftbufferRenderer {
 
 impl RenderBackend for SoftbufferRenderer {
 	fn create_context(&mut self, width: u32, height: u32) -> Box<dyn RenderContext> {
-		Box::new(SoftbufferRenderContext::new(width, height))
+		Box::new(SoftbufferRenderContext::new(width, height).with_text(self.text.clone()))
 	}
 
 	fn name(&self) -> &str {
@@ -35,22 +63,116 @@ impl RenderBackend for SoftbufferRenderer {
 	}
 }
 
-/// Softbuffer render context
-struct SoftbufferRenderContext {
+/// Softbuffer render context drawing into an ARGB pixel buffer
+#[derive(Debug)]
+pub struct SoftbufferRenderContext {
 	width: u32,
 	height: u32,
 	buffer: Vec<u32>,
-	clip_stack: Vec<Rect>,
+	clip_stack: Vec<Clip>,
+	transform_stack: Vec<Transform>,
+	layer_stack: Vec<Layer>,
+	text: TextRasterizer,
+	/// Pixels the current frame may touch, `None` for all of them
+	damage: Option<DamageRegion>,
+	/// Rasters of SVG documents drawn recently
+	svg_cache: image::SvgRasterCache,
+}
+
+/// A clip rectangle and the transform it was set under
+#[derive(Debug, Clone, Copy)]
+struct Clip {
+	rect: Rect,
+	/// Maps surface pixels into the rectangle's coordinates, `None` when the
+	/// transform collapsed the rectangle and nothing can be drawn
+	inverse: Option<Transform>,
+}
+
+/// The pixels below an offscreen layer and how the layer is composited
+/// onto them
+#[derive(Debug)]
+struct Layer {
+	below: Vec<u32>,
+	opacity: f32,
+	blend_mode: BlendMode,
 }
 
 impl SoftbufferRenderContext {
-	fn new(width: u32, height: u32) -> Self {
+	/// Create a context with a zeroed buffer of the given size
+	pub fn new(width: u32, height: u32) -> Self {
 		let size = (width * height) as usize;
 		Self {
 			width,
 			height,
 			buffer: vec![0; size],
 			clip_stack: Vec::new(),
+			transform_stack: Vec::new(),
+			layer_stack: Vec::new(),
+			text: TextRasterizer::new(),
+			damage: None,
+			svg_cache: image::SvgRasterCache::new(),
+		}
+	}
+
+	/// Set the rasterizer used for text commands
+	pub fn with_text(mut self, text: TextRasterizer) -> Self {
+		self.text = text;
+		self
+	}
+
+	/// Get the pixel buffer, row by row, with one ARGB value per pixel
+	pub fn buffer(&self) -> &[u32] {
+		&self.buffer
+	}
+
+	/// Copy the frame into a window surface's buffer and present it
+	///
+	/// When the surface buffer still holds the previous frame, only the
+	/// damaged rectangles are copied and presented; otherwise the whole
+	/// frame is. The surface should have been resized to this context's
+	/// size.
+	pub fn present<D, W>(
+		&self,
+		mut target: softbuffer::Buffer<'_, D, W>,
+	) -> Result<(), softbuffer::SoftBufferError>
+	where
+		D: HasDisplayHandle,
+		W: HasWindowHandle,
+	{
+		let width = self.width as usize;
+		match self.damage.as_ref() {
+			Some(damage) if target.age() == 1 => {
+				let mut rects = Vec::with_capacity(damage.rects().len());
+				for rect in damage.rects() {
+					let (x, y) = (rect.x as usize, rect.y as usize);
+					let (w, h) = (rect.width as usize, rect.height as usize);
+					for row in y..y + h {
+						let start = row * width + x;
+						if let (Some(dst), Some(src)) = (
+							target.get_mut(start..start + w),
+							self.buffer.get(start..start + w),
+						) {
+							dst.copy_from_slice(src);
+						}
+					}
+					if let (Some(w), Some(h)) =
+						(NonZeroU32::new(w as u32), NonZeroU32::new(h as u32))
+					{
+						rects.push(softbuffer::Rect {
+							x: x as u32,
+							y: y as u32,
+							width: w,
+							height: h,
+						});
+					}
+				}
+				target.present_with_damage(&rects)
+			}
+			_ => {
+				let len = target.len().min(self.buffer.len());
+				target[..len].copy_from_slice(&self.buffer[..len]);
+				target.present()
+			}
 		}
 	}
 
@@ -64,28 +186,133 @@ impl SoftbufferRenderContext {
 		(a << 24) | (r << 16) | (g << 8) | b
 	}
 
+	/// Convert a u32 pixel to straight RGBA channels
+	fn pixel_to_rgba(pixel: u32) -> [f32; 4] {
+		let channel = |shift: u32| ((pixel >> shift) & 0xFF) as f32 / 255.0;
+		[channel(16), channel(8), channel(0), channel(24)]
+	}
+
 	/// Check if a point is within the current clip region
+	///
+	/// Clips set under a transform test the centre of the pixel.
 	fn is_clipped(&self, x: i32, y: i32) -> bool {
 		if let Some(clip) = self.clip_stack.last() {
-			let x_f = x as f32;
-			let y_f = y as f32;
-			x_f < clip.x
-				|| x_f >= clip.x + clip.width
-				|| y_f < clip.y
-				|| y_f >= clip.y + clip.height
+			let Some(inverse) = clip.inverse else {
+				return true;
+			};
+			let (x_f, y_f) = if inverse.is_identity() {
+				(x as f32, y as f32)
+			} else {
+				inverse.map_point(x as f32 + 0.5, y as f32 + 0.5)
+			};
+			let rect = clip.rect;
+			x_f < rect.x
+				|| x_f >= rect.x + rect.width
+				|| y_f < rect.y
+				|| y_f >= rect.y + rect.height
 		} else {
 			false
 		}
 	}
 
+	/// Check if a pixel is inside the region the frame may touch
+	fn is_damaged(&self, x: i32, y: i32) -> bool {
+		self.damage
+			.as_ref()
+			.is_none_or(|damage| damage.contains_point(x as f32 + 0.5, y as f32 + 0.5))
+	}
+
+	/// Current transform
+	fn transform(&self) -> Transform {
+		self.transform_stack.last().copied().unwrap_or_default()
+	}
+
 	/// Set a pixel in the buffer
 	fn set_pixel(&mut self, x: i32, y: i32, pixel: u32) {
-		if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
-			if !self.is_clipped(x, y) {
-				let index = (y as u32 * self.width + x as u32) as usize;
-				if index < self.buffer.len() {
-					self.buffer[index] = pixel;
+		if x >= 0
+			&& y >= 0 && (x as u32) < self.width
+			&& (y as u32) < self.height
+			&& !self.is_clipped(x, y)
+			&& self.is_damaged(x, y)
+		{
+			let index = (y as u32 * self.width + x as u32) as usize;
+			if index < self.buffer.len() {
+				self.buffer[index] = pixel;
+			}
+		}
+	}
+
+	/// Blend a color over a pixel with the given coverage
+	fn blend_pixel(&mut self, x: i32, y: i32, color: Color, coverage: u8) {
+		if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+			return;
+		}
+
+		let index = (y as u32 * self.width + x as u32) as usize;
+		let [dst_r, dst_g, dst_b, dst_a] = Self::pixel_to_rgba(self.buffer[index]);
+		let a = color.a.clamp(0.0, 1.0) * coverage as f32 / 255.0;
+		let alpha = a + dst_a * (1.0 - a);
+		if alpha <= 0.0 {
+			return;
+		}
+		// Colors are stored unpremultiplied, so weigh each by its alpha
+		let channel =
+			|src: f32, dst: f32| (src.clamp(0.0, 1.0) * a + dst * dst_a * (1.0 - a)) / alpha;
+		let blended = Color::rgba(
+			channel(color.r, dst_r),
+			channel(color.g, dst_g),
+			channel(color.b, dst_b),
+			alpha,
+		);
+		self.set_pixel(x, y, Self::color_to_pixel(blended));
+	}
+
+	/// Draw a run of text with its baseline at `y`
+	fn draw_text(
+		&mut self,
+		text: &str,
+		x: f32,
+		y: f32,
+		font_size: f32,
+		color: Color,
+		align: TextAlign,
+	) {
+		let x = match align {
+			TextAlign::Left => x,
+			TextAlign::Center => x - self.text.measure(text, font_size) / 2.0,
+			TextAlign::Right => x - self.text.measure(text, font_size),
+		};
+
+		let mut pixels = Vec::new();
+		let alpha = color.a.clamp(0.0, 1.0);
+		self.text.draw(text, x, y, font_size, |px, py, pixel| {
+			let [r, g, b, a] = match pixel {
+				TextPixel::Coverage(coverage) => {
+					[color.r, color.g, color.b, coverage as f32 / 255.0]
+				}
+				// Color glyphs keep their own color but fade with the text
+				TextPixel::Color([r, g, b, a]) => {
+					[r, g, b, a].map(|channel| channel as f32 / 255.0)
 				}
+			};
+			let a = a * alpha;
+			pixels.push((px, py, [r * a, g * a, b * a, a]));
+		});
+
+		let (width, height) = (self.width, self.height);
+		let mut blended = Vec::with_capacity(pixels.len());
+		transform::transform_pixels(
+			&pixels,
+			&self.transform(),
+			width,
+			height,
+			|px, py, pixel| {
+				blended.push((px, py, pixel));
+			},
+		);
+		for (px, py, [r, g, b, a]) in blended {
+			if a > 0.0 {
+				self.blend_pixel(px, py, Color::rgba(r / a, g / a, b / a, a), 255);
 			}
 		}
 	}
@@ -201,10 +428,221 @@ impl SoftbufferRenderContext {
 		}
 	}
 
-	/// Clear the buffer with a color
+	/// Fill polygons with a brush, blending partially covered pixels
+	///
+	/// The polygons are in surface coordinates; gradients are sampled in the
+	/// coordinates of the current transform.
+	fn fill_polygons(&mut self, polygons: &[Polygon], rule: FillRule, brush: &Brush) {
+		let Some(inverse) = self.transform().invert() else {
+			return;
+		};
+		let (width, height) = (self.width, self.height);
+		scanline::rasterize(polygons, rule, width, height, |x, y, coverage| {
+			let coverage = (coverage * 255.0).round() as u8;
+			if coverage > 0 {
+				let (bx, by) = inverse.map_point(x as f32 + 0.5, y as f32 + 0.5);
+				self.blend_pixel(x, y, brush.color_at(bx, by), coverage);
+			}
+		});
+	}
+
+	/// Fill the inside of a path
+	fn fill_path(&mut self, path: &Path, brush: &Brush, rule: FillRule) {
+		let transform = self.transform();
+		let polygons = if transform.is_identity() {
+			scanline::fill_polygons(path)
+		} else {
+			scanline::fill_polygons(&path.transformed(&transform))
+		};
+		self.fill_polygons(&polygons, rule, brush);
+	}
+
+	/// Stroke the outline of a path
+	///
+	/// The stroke is outlined before it is transformed, so its width scales
+	/// and shears with the path.
+	fn stroke_path(&mut self, path: &Path, brush: &Brush, stroke: &StrokeStyle) {
+		let transform = self.transform();
+		let mut polygons = scanline::stroke_polygons(path, stroke);
+		if !transform.is_identity() {
+			for point in polygons.iter_mut().flatten() {
+				*point = transform.map_point(point.0, point.1);
+			}
+		}
+		self.fill_polygons(&polygons, FillRule::NonZero, brush);
+	}
+
+	/// Blend a shadow over the buffer
+	#[allow(clippy::too_many_arguments)]
+	fn draw_shadow(
+		&mut self,
+		rect: Rect,
+		radii: CornerRadii,
+		color: Color,
+		offset_x: f32,
+		offset_y: f32,
+		blur_radius: f32,
+		spread: f32,
+	) {
+		let Some(mask) = Mask::shadow(
+			rect,
+			radii,
+			&self.transform(),
+			offset_x,
+			offset_y,
+			blur_radius,
+			spread,
+			self.width,
+			self.height,
+		) else {
+			return;
+		};
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				let coverage = (mask.at(column, row) * 255.0).round() as u8;
+				if coverage > 0 {
+					let (x, y) = (mask.x + column as i32, mask.y + row as i32);
+					self.blend_pixel(x, y, color, coverage);
+				}
+			}
+		}
+	}
+
+	/// Draw a bitmap under the current transform
+	fn draw_image(&mut self, command: &ImageCommand) {
+		let mut pixels = Vec::new();
+		image::rasterize(
+			command,
+			&self.transform(),
+			self.width,
+			self.height,
+			|x, y, pixel| pixels.push((x, y, pixel)),
+		);
+		for (x, y, [r, g, b, a]) in pixels {
+			self.blend_pixel(x, y, Color::rgba(r / a, g / a, b / a, a), 255);
+		}
+	}
+
+	/// Draw an SVG document under the current transform
+	fn draw_svg(&mut self, document: &Arc<SvgDocument>, rect: Rect, tint: Option<Color>) {
+		let surface = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+		let visible = match self.clip_stack.last() {
+			Some(clip) => clip
+				.inverse
+				.and_then(|inverse| inverse.invert())
+				.and_then(|transform| transform.map_rect(clip.rect).intersection(&surface)),
+			None => Some(surface),
+		};
+		let Some(visible) = visible else {
+			return;
+		};
+		let transform = self.transform();
+		let mut pixels = Vec::new();
+		image::rasterize_svg(
+			document,
+			rect,
+			tint,
+			&transform,
+			visible,
+			&mut self.svg_cache,
+			|x, y, pixel| pixels.push((x, y, pixel)),
+		);
+		for (x, y, [r, g, b, a]) in pixels {
+			self.blend_pixel(x, y, Color::rgba(r / a, g / a, b / a, a), 255);
+		}
+	}
+
+	/// Blur the buffer inside a rounded rectangle
+	///
+	/// Pixels are blurred premultiplied so transparent ones do not darken
+	/// their neighbours.
+	fn backdrop_blur(&mut self, rect: Rect, radii: CornerRadii, blur_radius: f32) {
+		let transform = self.transform();
+		let blur_radius = blur_radius * transform.scale_factor();
+		let padding = effects::blur_extent(blur_radius);
+		let Some(mask) =
+			Mask::rounded_rect(rect, radii, &transform, padding, self.width, self.height)
+		else {
+			return;
+		};
+		let index = |column: usize, row: usize| {
+			(mask.y as usize + row) * self.width as usize + mask.x as usize + column
+		};
+
+		let mut pixels = Vec::with_capacity(mask.width * mask.height);
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				let pixel = self.buffer[index(column, row)];
+				let channel = |shift: u32| ((pixel >> shift) & 0xFF) as f32 / 255.0;
+				let a = channel(24);
+				pixels.push([channel(16) * a, channel(8) * a, channel(0) * a, a]);
+			}
+		}
+		mask.blur_backdrop(&mut pixels, blur_radius);
+
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				if mask.at(column, row) <= 0.0 {
+					continue;
+				}
+				let [r, g, b, a] = pixels[row * mask.width + column];
+				let color = if a > 0.0 {
+					Color::rgba(r / a, g / a, b / a, a)
+				} else {
+					Color::rgba(0.0, 0.0, 0.0, 0.0)
+				};
+				let (x, y) = (mask.x + column as i32, mask.y + row as i32);
+				self.set_pixel(x, y, Self::color_to_pixel(color));
+			}
+		}
+	}
+
+	/// Start drawing into a transparent layer
+	fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+		let layer = vec![0; self.buffer.len()];
+		let below = std::mem::replace(&mut self.buffer, layer);
+		self.layer_stack.push(Layer {
+			below,
+			opacity,
+			blend_mode,
+		});
+	}
+
+	/// Composite the current layer onto the pixels below it
+	fn pop_layer(&mut self) {
+		let Some(layer) = self.layer_stack.pop() else {
+			return;
+		};
+		let content = std::mem::replace(&mut self.buffer, layer.below);
+		for (dst, src) in self.buffer.iter_mut().zip(content) {
+			if src >> 24 == 0 {
+				continue;
+			}
+			let [r, g, b, a] = layer::composite(
+				Self::pixel_to_rgba(src),
+				Self::pixel_to_rgba(*dst),
+				layer.opacity,
+				layer.blend_mode,
+			);
+			*dst = Self::color_to_pixel(Color::rgba(r, g, b, a));
+		}
+	}
+
+	/// Clear the buffer, or only its damaged rectangles, with a color
 	fn clear(&mut self, color: Color) {
 		let pixel = Self::color_to_pixel(color);
-		self.buffer.fill(pixel);
+		let Some(damage) = self.damage.as_ref() else {
+			self.buffer.fill(pixel);
+			return;
+		};
+		let width = self.width as usize;
+		for rect in damage.rects() {
+			let (x, w) = (rect.x as usize, rect.width as usize);
+			for row in rect.y as usize..(rect.y + rect.height) as usize {
+				let start = row * width + x;
+				self.buffer[start..start + w].fill(pixel);
+			}
+		}
 	}
 }
 
@@ -215,12 +653,26 @@ impl RenderContext for SoftbufferRenderContext {
 				self.clear(color);
 			}
 			RenderCommand::FillRect { rect, color } => {
-				let pixel = Self::color_to_pixel(color);
-				self.fill_rect(rect, pixel);
+				if self.transform().is_identity() {
+					self.fill_rect(rect, Self::color_to_pixel(color));
+				} else {
+					self.fill_path(&Path::rect(rect), &color.into(), FillRule::NonZero);
+				}
 			}
 			RenderCommand::StrokeRect { rect, color, width } => {
-				let pixel = Self::color_to_pixel(color);
-				self.stroke_rect(rect, pixel, width as i32);
+				if self.transform().is_identity() {
+					self.stroke_rect(rect, Self::color_to_pixel(color), width as i32);
+				} else {
+					// Keep the stroke inside the rectangle, as unrotated
+					let half = width / 2.0;
+					let inner = Rect::new(
+						rect.x + half,
+						rect.y + half,
+						rect.width - width,
+						rect.height - width,
+					);
+					self.stroke_path(&Path::rect(inner), &color.into(), &StrokeStyle::new(width));
+				}
 			}
 			RenderCommand::Line {
 				x1,
@@ -228,10 +680,16 @@ impl RenderContext for SoftbufferRenderContext {
 				x2,
 				y2,
 				color,
-				width: _,
+				width,
 			} => {
-				let pixel = Self::color_to_pixel(color);
-				self.draw_line(x1 as i32, y1 as i32, x2 as i32, y2 as i32, pixel);
+				if self.transform().is_identity() {
+					let pixel = Self::color_to_pixel(color);
+					self.draw_line(x1 as i32, y1 as i32, x2 as i32, y2 as i32, pixel);
+				} else {
+					let path = Path::new().move_to(x1, y1).line_to(x2, y2);
+					let stroke = StrokeStyle::new(width.max(1.0));
+					self.stroke_path(&path, &color.into(), &stroke);
+				}
 			}
 			RenderCommand::Circle {
 				x,
@@ -240,46 +698,180 @@ impl RenderContext for SoftbufferRenderContext {
 				color,
 				filled,
 			} => {
-				let pixel = Self::color_to_pixel(color);
-				self.draw_circle(x as i32, y as i32, radius as i32, pixel, filled);
+				if self.transform().is_identity() {
+					let pixel = Self::color_to_pixel(color);
+					self.draw_circle(x as i32, y as i32, radius as i32, pixel, filled);
+				} else {
+					let path = Path::new()
+						.arc(x, y, radius, 0.0, std::f32::consts::TAU)
+						.close();
+					if filled {
+						self.fill_path(&path, &color.into(), FillRule::NonZero);
+					} else {
+						self.stroke_path(&path, &color.into(), &StrokeStyle::new(1.0));
+					}
+				}
 			}
 			RenderCommand::Text {
-				text: _,
-				x: _,
-				y: _,
-				font_size: _,
-				color: _,
-				align: _,
+				text,
+				x,
+				y,
+				font_size,
+				color,
+				align,
 			} => {
-				// Text rendering would require font rasterization
-				// For now, this is a no-op. Full implementation would use fontdue crate
+				self.draw_text(&text, x, y, font_size, color, align);
+			}
+			RenderCommand::FillRoundedRect { rect, radii, brush } => {
+				self.fill_path(&Path::rounded_rect(rect, radii), &brush, FillRule::NonZero);
+			}
+			RenderCommand::StrokeRoundedRect {
+				rect,
+				radii,
+				brush,
+				width,
+			} => {
+				self.stroke_path(
+					&Path::rounded_rect(rect, radii),
+					&brush,
+					&StrokeStyle::new(width),
+				);
+			}
+			RenderCommand::FillPath { path, brush, rule } => {
+				self.fill_path(&path, &brush, rule);
+			}
+			RenderCommand::StrokePath {
+				path,
+				brush,
+				stroke,
+			} => {
+				self.stroke_path(&path, &brush, &stroke);
+			}
+			RenderCommand::Shadow {
+				rect,
+				radii,
+				color,
+				offset_x,
+				offset_y,
+				blur_radius,
+				spread,
+			} => {
+				self.draw_shadow(rect, radii, color, offset_x, offset_y, blur_radius, spread);
+			}
+			RenderCommand::BackdropBlur {
+				rect,
+				radii,
+				blur_radius,
+			} => {
+				self.backdrop_blur(rect, radii, blur_radius);
+			}
+			RenderCommand::Image(command) => {
+				self.draw_image(&command);
+			}
+			RenderCommand::Svg {
+				document,
+				rect,
+				tint,
+			} => {
+				self.draw_svg(&document, rect, tint);
 			}
 			RenderCommand::SetClip(rect) => {
-				self.clip_stack.push(rect);
+				let inverse = self.transform().invert();
+				self.clip_stack.push(Clip { rect, inverse });
 			}
 			RenderCommand::RestoreClip => {
 				self.clip_stack.pop();
 			}
+			RenderCommand::PushTransform(transform) => {
+				let combined = self.transform().pre_concat(&transform);
+				self.transform_stack.push(combined);
+			}
+			RenderCommand::PopTransform => {
+				self.transform_stack.pop();
+			}
+			RenderCommand::PushLayer {
+				opacity,
+				blend_mode,
+			} => {
+				self.push_layer(opacity, blend_mode);
+			}
+			RenderCommand::PopLayer => {
+				self.pop_layer();
+			}
 		}
 	}
 
 	fn begin_frame(&mut self) {
-		// No-op for software renderer
+		self.damage = None;
 	}
 
 	fn end_frame(&mut self) {
-		// In a real implementation, this would present the buffer to the screen
-		// For now, we just keep the buffer in memory
+		// The frame stays in the buffer until it is presented
 	}
 
 	fn size(&self) -> (u32, u32) {
 		(self.width, self.height)
 	}
+
+	fn set_damage(&mut self, damage: &DamageRegion) {
+		self.damage = Some(damage.snap_to_pixels(self.width, self.height));
+	}
+
+	fn damage(&self) -> Option<&DamageRegion> {
+		self.damage.as_ref()
+	}
+
+	fn read_pixels(&self) -> Option<ImageData> {
+		let data = self
+			.buffer
+			.iter()
+			.flat_map(|&pixel| {
+				let [a, r, g, b] = pixel.to_be_bytes();
+				[r, g, b, a]
+			})
+			.collect();
+		Some(ImageData::new(
+			self.width,
+			self.height,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			data,
+		))
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::backends::brush::GradientStop;
+	use engage_ux_core::media::Font;
+
+	const TUFFY: &[u8] = include_bytes!("../../../assets/fonts/Tuffy.ttf");
+	const EMOJI: &[u8] = include_bytes!("../../../assets/fonts/EngageEmoji.ttf");
+
+	/// A 2 by 1 image, red then blue
+	fn red_blue() -> std::sync::Arc<engage_ux_core::media::ImageData> {
+		use engage_ux_core::media::{ImageData, ImageFormat, image::ColorType};
+		std::sync::Arc::new(ImageData::new(
+			2,
+			1,
+			ImageFormat::Png,
+			ColorType::Rgb,
+			vec![255, 0, 0, 0, 0, 255],
+		))
+	}
+
+	/// A 10 by 10 SVG document with its left half filled red
+	fn half_red_svg() -> std::sync::Arc<engage_ux_core::rendering::SvgDocument> {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+			<rect width="5" height="10" fill="red"/>
+		</svg>"#;
+		std::sync::Arc::new(
+			engage_ux_core::rendering::SvgParser::new()
+				.parse(svg)
+				.unwrap(),
+		)
+	}
 
 	#[test]
 	fn test_softbuffer_renderer_creation() {
@@ -350,4 +942,435 @@ mod tests {
 		context.execute(RenderCommand::RestoreClip);
 		context.end_frame();
 	}
+
+	#[test]
+	fn test_softbuffer_text() {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let mut context = SoftbufferRenderContext::new(100, 40)
+			.with_text(TextRasterizer::from_font(&font).unwrap());
+
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::Text {
+			text: "Hi".to_string(),
+			x: 50.0,
+			y: 30.0,
+			font_size: 20.0,
+			color: Color::rgb(0.0, 0.0, 0.0),
+			align: TextAlign::Center,
+		});
+
+		let dark: Vec<usize> = context
+			.buffer()
+			.iter()
+			.enumerate()
+			.filter(|(_, pixel)| **pixel & 0xFF < 128)
+			.map(|(index, _)| index)
+			.collect();
+		assert!(!dark.is_empty());
+
+		// Centred around x = 50, entirely above the baseline
+		let columns = dark.iter().map(|index| index % 100);
+		assert!(columns.clone().min().unwrap() < 50 && columns.max().unwrap() > 50);
+		assert!(dark.iter().all(|index| index / 100 < 30));
+		assert!(context.buffer().contains(&0xFF000000));
+	}
+
+	#[test]
+	fn test_softbuffer_color_emoji() {
+		let emoji = Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap();
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0)
+			.unwrap()
+			.with_fallback_fonts([emoji]);
+		let mut context = SoftbufferRenderContext::new(100, 40)
+			.with_text(TextRasterizer::from_font(&font).unwrap());
+
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::Text {
+			text: "\u{1F600}".to_string(),
+			x: 10.0,
+			y: 30.0,
+			font_size: 20.0,
+			color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+			align: TextAlign::Left,
+		});
+
+		// Half-transparent text fades the emoji over the white background
+		let channels = |pixel: u32| ((pixel >> 16) & 0xFF, (pixel >> 8) & 0xFF, pixel & 0xFF);
+		assert!(context.buffer().iter().any(|&pixel| {
+			let (r, g, b) = channels(pixel);
+			r == 255 && (229..=230).contains(&g) && (127..=128).contains(&b)
+		}));
+	}
+
+	fn channels(pixel: u32) -> (u32, u32, u32, u32) {
+		(
+			(pixel >> 24) & 0xFF,
+			(pixel >> 16) & 0xFF,
+			(pixel >> 8) & 0xFF,
+			pixel & 0xFF,
+		)
+	}
+
+	#[test]
+	fn test_softbuffer_rounded_rect() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::FillRoundedRect {
+			rect: Rect::new(10.0, 10.0, 80.0, 80.0),
+			radii: CornerRadii::new(20.0, 0.0, 20.0, 0.0),
+			brush: Brush::Solid(Color::rgb(1.0, 0.0, 0.0)),
+		});
+
+		let pixel = |x: usize, y: usize| context.buffer()[y * 100 + x];
+		assert_eq!(pixel(50, 50), 0xFFFF0000);
+		// Rounded corners are cut away, square ones are filled
+		assert_eq!(pixel(11, 11), 0);
+		assert_eq!(pixel(88, 88), 0);
+		assert_eq!(pixel(88, 11), 0xFFFF0000);
+		assert_eq!(pixel(11, 88), 0xFFFF0000);
+
+		// Curved edges are anti-aliased
+		assert!(context.buffer().iter().any(|&pixel| {
+			let (a, ..) = channels(pixel);
+			a > 0 && a < 255
+		}));
+	}
+
+	#[test]
+	fn test_softbuffer_stroke_rounded_rect() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::StrokeRoundedRect {
+			rect: Rect::new(10.0, 10.0, 80.0, 80.0),
+			radii: CornerRadii::uniform(10.0),
+			brush: Brush::Solid(Color::rgb(0.0, 0.0, 1.0)),
+			width: 2.0,
+		});
+
+		let pixel = |x: usize, y: usize| context.buffer()[y * 100 + x];
+		assert_eq!(pixel(50, 10), 0xFF0000FF);
+		assert_eq!(pixel(50, 50), 0);
+		assert_eq!(pixel(10, 10), 0);
+	}
+
+	#[test]
+	fn test_softbuffer_radial_gradient() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::FillPath {
+			path: Path::rect(Rect::new(0.0, 0.0, 100.0, 100.0)),
+			brush: Brush::radial(
+				50.0,
+				50.0,
+				50.0,
+				vec![
+					GradientStop::new(0.0, Color::rgb(1.0, 1.0, 1.0)),
+					GradientStop::new(1.0, Color::rgb(0.0, 0.0, 0.0)),
+				],
+			),
+			rule: FillRule::NonZero,
+		});
+
+		let (_, centre, ..) = channels(context.buffer()[50 * 100 + 50]);
+		let (_, middle, ..) = channels(context.buffer()[50 * 100 + 75]);
+		let (_, corner, ..) = channels(context.buffer()[0]);
+		assert!(centre > 250);
+		assert!((120..=135).contains(&middle));
+		assert_eq!(corner, 0);
+	}
+
+	#[test]
+	fn test_softbuffer_stroke_path() {
+		let mut context = SoftbufferRenderContext::new(40, 20);
+		context.execute(RenderCommand::StrokePath {
+			path: Path::new().move_to(0.0, 10.0).line_to(40.0, 10.0),
+			brush: Brush::Solid(Color::rgb(0.0, 0.0, 0.0)),
+			stroke: StrokeStyle::new(4.0).with_dash(vec![5.0, 5.0], 0.0),
+		});
+
+		let alpha = |x: usize, y: usize| channels(context.buffer()[y * 40 + x]).0;
+		assert_eq!(alpha(2, 10), 255);
+		assert_eq!(alpha(7, 10), 0);
+		assert_eq!(alpha(12, 10), 255);
+		assert_eq!(alpha(2, 5), 0);
+	}
+
+	#[test]
+	fn test_softbuffer_path_clipped() {
+		let mut context = SoftbufferRenderContext::new(40, 40);
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 20.0, 40.0)));
+		context.execute(RenderCommand::FillPath {
+			path: Path::new().arc(20.0, 20.0, 15.0, 0.0, std::f32::consts::TAU),
+			brush: Brush::Solid(Color::rgb(0.0, 0.0, 0.0)),
+			rule: FillRule::EvenOdd,
+		});
+
+		let alpha = |x: usize, y: usize| channels(context.buffer()[y * 40 + x]).0;
+		assert_eq!(alpha(15, 20), 255);
+		assert_eq!(alpha(25, 20), 0);
+	}
+
+	#[test]
+	fn test_softbuffer_shadow() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::Shadow {
+			rect: Rect::new(20.0, 20.0, 40.0, 40.0),
+			radii: CornerRadii::uniform(4.0),
+			color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+			offset_x: 10.0,
+			offset_y: 10.0,
+			blur_radius: 8.0,
+			spread: 0.0,
+		});
+
+		let red = |x: usize, y: usize| channels(context.buffer()[y * 100 + x]).1;
+		// Darkest under the moved rectangle, fading out across its edge
+		assert!((126..=128).contains(&red(50, 50)));
+		assert!(red(30, 50) > red(50, 50) && red(30, 50) < 255);
+		assert!(red(24, 50) > red(30, 50));
+		assert_eq!(red(10, 50), 255);
+		assert_eq!(red(95, 95), 255);
+	}
+
+	#[test]
+	fn test_softbuffer_backdrop_blur() {
+		let mut context = SoftbufferRenderContext::new(40, 20);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 20.0, 20.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::BackdropBlur {
+			rect: Rect::new(10.0, 0.0, 20.0, 20.0),
+			radii: CornerRadii::default(),
+			blur_radius: 6.0,
+		});
+
+		let pixel = |x: usize| channels(context.buffer()[10 * 40 + x]);
+		// The hard edge is softened inside the rectangle only
+		let (a, r, ..) = pixel(19);
+		assert_eq!(a, 255);
+		assert!(r > 0 && r < 128);
+		assert!(pixel(20).1 > 128 && pixel(20).1 < 255);
+		assert_eq!(pixel(5).1, 0);
+		assert_eq!(pixel(35).1, 255);
+	}
+
+	#[test]
+	fn test_softbuffer_image() {
+		use crate::backends::image::{ImageCommand, ImageFilter, ImageFit};
+
+		let mut context = SoftbufferRenderContext::new(40, 10);
+		context.execute(RenderCommand::Image(
+			ImageCommand::new(red_blue(), Rect::new(0.0, 0.0, 40.0, 10.0))
+				.with_fit(ImageFit::Contain)
+				.with_filter(ImageFilter::Nearest),
+		));
+		let pixel = |x: usize| channels(context.buffer()[5 * 40 + x]);
+		// Contained in the middle 20 pixels, each image pixel 10 wide
+		assert_eq!(pixel(5).0, 0);
+		assert_eq!(pixel(12), (255, 255, 0, 0));
+		assert_eq!(pixel(25), (255, 0, 0, 255));
+		assert_eq!(pixel(35).0, 0);
+
+		// Tinting multiplies the colors, bilinear filtering blends them
+		let mut context = SoftbufferRenderContext::new(40, 10);
+		context.execute(RenderCommand::Image(
+			ImageCommand::new(red_blue(), Rect::new(0.0, 0.0, 40.0, 10.0))
+				.with_tint(Color::rgb(0.5, 1.0, 1.0)),
+		));
+		let pixel = |x: usize| channels(context.buffer()[5 * 40 + x]);
+		let (a, r, g, _) = pixel(0);
+		assert!((a, g) == (255, 0) && (127..=128).contains(&r));
+		let (_, r, _, b) = pixel(20);
+		assert!(r > 0 && r < 128 && b > 0 && b < 255);
+	}
+
+	#[test]
+	fn test_softbuffer_svg() {
+		let mut context = SoftbufferRenderContext::new(40, 20);
+		context.execute(RenderCommand::Svg {
+			document: half_red_svg(),
+			rect: Rect::new(0.0, 0.0, 40.0, 20.0),
+			tint: None,
+		});
+		let pixel = |x: usize| channels(context.buffer()[10 * 40 + x]);
+		// Fitted into the middle 20 pixels
+		assert_eq!(pixel(5).0, 0);
+		assert_eq!(pixel(15), (255, 255, 0, 0));
+		assert_eq!(pixel(25).0, 0);
+
+		context.execute(RenderCommand::Svg {
+			document: half_red_svg(),
+			rect: Rect::new(0.0, 0.0, 40.0, 20.0),
+			tint: Some(Color::rgb(0.0, 0.0, 1.0)),
+		});
+		assert_eq!(channels(context.buffer()[10 * 40 + 15]), (255, 0, 0, 255));
+	}
+
+	#[test]
+	fn test_softbuffer_transform() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::PushTransform(
+			Transform::from_translate(50.0, 50.0).rotate(std::f32::consts::FRAC_PI_4),
+		));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(-20.0, -20.0, 40.0, 40.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::PopTransform);
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 4.0, 4.0),
+			color: Color::rgb(0.0, 0.0, 1.0),
+		});
+
+		let pixel = |x: usize, y: usize| context.buffer()[y * 100 + x];
+		// The square is turned into a diamond around (50, 50)
+		assert_eq!(pixel(50, 50), 0xFFFF0000);
+		assert_eq!(pixel(50, 25), 0xFFFF0000);
+		assert_eq!(pixel(32, 32), 0);
+		// Popping restores untransformed drawing
+		assert_eq!(pixel(1, 1), 0xFF0000FF);
+	}
+
+	#[test]
+	fn test_softbuffer_transformed_clip() {
+		let mut context = SoftbufferRenderContext::new(100, 100);
+		context.execute(RenderCommand::PushTransform(Transform::from_rotate_at(
+			std::f32::consts::FRAC_PI_4,
+			50.0,
+			50.0,
+		)));
+		context.execute(RenderCommand::SetClip(Rect::new(30.0, 30.0, 40.0, 40.0)));
+		context.execute(RenderCommand::PopTransform);
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 100.0, 100.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::RestoreClip);
+
+		let alpha = |x: usize, y: usize| channels(context.buffer()[y * 100 + x]).0;
+		// The clip stays rotated after the transform is popped
+		assert_eq!(alpha(50, 25), 255);
+		assert_eq!(alpha(32, 32), 0);
+		assert_eq!(alpha(68, 68), 0);
+	}
+
+	#[test]
+	fn test_softbuffer_scaled_text() {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		let mut context = SoftbufferRenderContext::new(200, 80)
+			.with_text(TextRasterizer::from_font(&font).unwrap());
+		let ink = |context: &SoftbufferRenderContext| {
+			context
+				.buffer()
+				.iter()
+				.filter(|&&pixel| pixel >> 24 > 128)
+				.count()
+		};
+
+		let text = RenderCommand::Text {
+			text: "Hi".to_string(),
+			x: 10.0,
+			y: 30.0,
+			font_size: 16.0,
+			color: Color::rgb(0.0, 0.0, 0.0),
+			align: TextAlign::Left,
+		};
+		context.execute(text.clone());
+		let plain = ink(&context);
+
+		let mut context = SoftbufferRenderContext::new(200, 80)
+			.with_text(TextRasterizer::from_font(&font).unwrap());
+		context.execute(RenderCommand::PushTransform(Transform::from_scale(
+			2.0, 2.0,
+		)));
+		context.execute(text);
+		let scaled = ink(&context);
+
+		// Four times the area, without gaps between resampled pixels
+		assert!(scaled > plain * 3 && scaled < plain * 5);
+	}
+
+	#[test]
+	fn test_softbuffer_layer_opacity() {
+		let mut context = SoftbufferRenderContext::new(40, 20);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::PushLayer {
+			opacity: 0.5,
+			blend_mode: BlendMode::Normal,
+		});
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 30.0, 20.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(10.0, 0.0, 30.0, 20.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::PopLayer);
+
+		let pixel = |x: usize| channels(context.buffer()[10 * 40 + x]);
+		// The overlap fades as one group rather than doubling up
+		assert_eq!(pixel(5), pixel(20));
+		let (a, r, g, _) = pixel(20);
+		assert_eq!((a, r), (255, 255));
+		assert!((127..=128).contains(&g));
+	}
+
+	#[test]
+	fn test_softbuffer_layer_blend_mode() {
+		let mut context = SoftbufferRenderContext::new(10, 10);
+		context.execute(RenderCommand::Clear(Color::rgb(0.0, 1.0, 1.0)));
+		context.execute(RenderCommand::PushLayer {
+			opacity: 1.0,
+			blend_mode: BlendMode::Multiply,
+		});
+		context.execute(RenderCommand::FillRoundedRect {
+			rect: Rect::new(0.0, 0.0, 5.0, 10.0),
+			radii: CornerRadii::default(),
+			brush: Brush::Solid(Color::rgba(1.0, 1.0, 0.0, 1.0)),
+		});
+		context.execute(RenderCommand::PopLayer);
+
+		// Cyan multiplied by yellow is green; uncovered pixels are kept
+		assert_eq!(context.buffer()[5 * 10 + 2], 0xFF00FF00);
+		assert_eq!(context.buffer()[5 * 10 + 7], 0xFF00FFFF);
+	}
+
+	#[test]
+	fn test_softbuffer_damage() {
+		let mut context = SoftbufferRenderContext::new(10, 10);
+		context.begin_frame();
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 0.0, 0.0)));
+		context.end_frame();
+		assert!(context.damage().is_none());
+
+		// Only the damaged pixels are cleared and drawn into
+		context.begin_frame();
+		context.set_damage(&DamageRegion::from_rect(
+			engage_ux_core::component::Rect::new(2.2, 2.0, 3.5, 3.0),
+		));
+		context.execute(RenderCommand::Clear(Color::rgb(0.0, 0.0, 1.0)));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 4.0, 10.0, 6.0),
+			color: Color::rgb(0.0, 1.0, 0.0),
+		});
+		context.end_frame();
+
+		let damage = context.damage().unwrap();
+		assert_eq!(
+			damage.rects(),
+			&[engage_ux_core::component::Rect::new(2.0, 2.0, 4.0, 3.0)]
+		);
+		assert_eq!(context.buffer()[2 * 10 + 2], 0xFF0000FF);
+		assert_eq!(context.buffer()[2 * 10 + 5], 0xFF0000FF);
+		assert_eq!(context.buffer()[4 * 10 + 3], 0xFF00FF00);
+		assert_eq!(context.buffer()[2 * 10 + 6], 0xFFFF0000);
+		assert_eq!(context.buffer()[6 * 10 + 3], 0xFFFF0000);
+
+		// A new frame lifts the restriction
+		context.begin_frame();
+		assert!(context.damage().is_none());
+		context.execute(RenderCommand::Clear(Color::rgb(0.0, 0.0, 1.0)));
+		assert_eq!(context.buffer()[9 * 10 + 9], 0xFF0000FF);
+	}
 }
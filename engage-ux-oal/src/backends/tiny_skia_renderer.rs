@@ -2,17 +2,41 @@
 //!
 //! This provides a high-quality 2D graphics renderer using tiny-skia,
 //! which is particularly suitable for Linux systems requiring Cairo-like capabilities.
+//! Paths, rounded rectangles and gradients map directly onto tiny-skia's
+//! paths and shaders, and transforms and layers onto its transforms and
+//! pixmap compositing. A damaged region restricts drawing through the same
+//! mask as the clip.
 
-use super::renderer::{Color, Rect, RenderBackend, RenderCommand, RenderContext};
+use super::brush::Brush;
+use super::effects::{self, Mask};
+use super::image::{self, ImageCommand};
+use super::layer::BlendMode;
+use super::path::{CornerRadii, FillRule, LineCap, LineJoin, Path, PathCommand, StrokeStyle};
+use super::renderer::{Color, Rect, RenderBackend, RenderCommand, RenderContext, TextAlign};
+use super::transform::{self, Transform};
+use engage_ux_core::media::image::ColorType;
+use engage_ux_core::media::{ImageData, ImageFormat};
+use engage_ux_core::rendering::{DamageRegion, SvgDocument, TextPixel, TextRasterizer};
+use std::sync::Arc;
 
 /// Tiny-skia-based renderer optimized for Linux
 #[derive(Debug)]
-pub struct TinySkiaRenderer;
+pub struct TinySkiaRenderer {
+	text: TextRasterizer,
+}
 
 impl TinySkiaRenderer {
 	/// Create a new tiny-skia renderer
 	pub fn new() -> Self {
-		Self
+		Self {
+			text: TextRasterizer::new(),
+		}
+	}
+
+	/// Draw text in contexts created by this renderer with the given rasterizer
+	pub fn with_text(mut self, text: TextRasterizer) -> Self {
+		self.text = text;
+		self
 	}
 }
 
@@ -24,7 +48,7 @@ impl Default for TinySkiaRenderer {
 
 impl RenderBackend for TinySkiaRenderer {
 	fn create_context(&mut self, width: u32, height: u32) -> Box<dyn RenderContext> {
-		Box::new(TinySkiaRenderContext::new(width, height))
+		Box::new(TinySkiaRenderContext::new(width, height).with_text(self.text.clone()))
 	}
 
 	fn name(&self) -> &str {
@@ -36,25 +60,66 @@ impl RenderBackend for TinySkiaRenderer {
 	}
 }
 
-/// Tiny-skia render context
-struct TinySkiaRenderContext {
+/// Tiny-skia render context drawing into an offscreen pixmap
+#[derive(Debug)]
+pub struct TinySkiaRenderContext {
 	width: u32,
 	height: u32,
 	pixmap: Option<tiny_skia::Pixmap>,
-	clip_stack: Vec<Rect>,
+	clip_stack: Vec<(Rect, Transform)>,
+	transform_stack: Vec<Transform>,
+	layer_stack: Vec<Layer>,
+	text: TextRasterizer,
+	/// Pixels the current frame may touch, `None` for all of them
+	damage: Option<DamageRegion>,
+	/// Rasters of SVG documents drawn recently
+	svg_cache: image::SvgRasterCache,
+	/// Coverage of the damaged region, built when the damage is set
+	damage_mask: Option<tiny_skia::Mask>,
+	/// Coverage drawing is limited to: the current clip within the damaged
+	/// region, rebuilt when either changes
+	clip_mask: Option<tiny_skia::Mask>,
+}
+
+/// The pixmap below an offscreen layer and how the layer is composited
+/// onto it
+#[derive(Debug)]
+struct Layer {
+	below: Option<tiny_skia::Pixmap>,
+	opacity: f32,
+	blend_mode: BlendMode,
 }
 
 impl TinySkiaRenderContext {
-	fn new(width: u32, height: u32) -> Self {
+	/// Create a context with a transparent pixmap of the given size
+	pub fn new(width: u32, height: u32) -> Self {
 		let pixmap = tiny_skia::Pixmap::new(width, height);
 		Self {
 			width,
 			height,
 			pixmap,
 			clip_stack: Vec::new(),
+			transform_stack: Vec::new(),
+			layer_stack: Vec::new(),
+			text: TextRasterizer::new(),
+			damage: None,
+			svg_cache: image::SvgRasterCache::new(),
+			damage_mask: None,
+			clip_mask: None,
 		}
 	}
 
+	/// Set the rasterizer used for text commands
+	pub fn with_text(mut self, text: TextRasterizer) -> Self {
+		self.text = text;
+		self
+	}
+
+	/// Get the pixmap drawn into, if the size was valid
+	pub fn pixmap(&self) -> Option<&tiny_skia::Pixmap> {
+		self.pixmap.as_ref()
+	}
+
 	/// Convert our Color type to tiny-skia Color
 	fn to_skia_color(color: Color) -> tiny_skia::Color {
 		tiny_skia::Color::from_rgba(
@@ -71,81 +136,300 @@ impl TinySkiaRenderContext {
 		tiny_skia::Rect::from_xywh(rect.x, rect.y, rect.width, rect.height)
 	}
 
-	/// Get the current clip mask
-	#[allow(dead_code)]
-	fn get_clip_mask(&self) -> Option<tiny_skia::Mask> {
-		if let Some(clip) = self.clip_stack.last() {
-			if let Some(rect) = Self::to_skia_rect(*clip) {
-				let mut mask = tiny_skia::Mask::new(self.width, self.height)?;
-				mask.fill_path(
-					&tiny_skia::PathBuilder::from_rect(rect),
-					tiny_skia::FillRule::Winding,
-					false,
-					tiny_skia::Transform::identity(),
-				);
-				Some(mask)
+	/// Convert a transform to a tiny-skia transform
+	fn to_skia_transform(transform: Transform) -> tiny_skia::Transform {
+		tiny_skia::Transform::from_row(
+			transform.sx,
+			transform.ky,
+			transform.kx,
+			transform.sy,
+			transform.tx,
+			transform.ty,
+		)
+	}
+
+	/// Current transform
+	fn transform(&self) -> Transform {
+		self.transform_stack.last().copied().unwrap_or_default()
+	}
+
+	/// Current transform for tiny-skia drawing calls
+	fn skia_transform(&self) -> tiny_skia::Transform {
+		Self::to_skia_transform(self.transform())
+	}
+
+	/// Rebuild the clip mask from the current clip and the damage mask
+	///
+	/// The clip rectangle is drawn with the transform it was set under, so
+	/// rotated clips have anti-aliased edges.
+	fn update_clip_mask(&mut self) {
+		let clip = self
+			.clip_stack
+			.last()
+			.and_then(|(clip, transform)| Some((Self::to_skia_rect(*clip)?, *transform)));
+		let Some((rect, transform)) = clip else {
+			self.clip_mask = self.damage_mask.clone();
+			return;
+		};
+
+		self.clip_mask = tiny_skia::Mask::new(self.width, self.height).map(|mut clip_mask| {
+			clip_mask.fill_path(
+				&tiny_skia::PathBuilder::from_rect(rect),
+				tiny_skia::FillRule::Winding,
+				!transform.is_translate(),
+				Self::to_skia_transform(transform),
+			);
+			if let Some(damage_mask) = self.damage_mask.as_ref() {
+				for (clip, damaged) in clip_mask.data_mut().iter_mut().zip(damage_mask.data()) {
+					*clip = (*clip).min(*damaged);
+				}
+			}
+			clip_mask
+		});
+	}
+
+	/// Blend premultiplied RGBA pixels over the pixmap, within the current
+	/// clip
+	fn blend_pixels(&mut self, pixels: &[(i32, i32, [f32; 4])]) {
+		let mask = self.clip_mask.as_ref();
+		let Some(pixmap) = self.pixmap.as_mut() else {
+			return;
+		};
+		let (width, height) = (self.width as i32, self.height as i32);
+		let target = pixmap.pixels_mut();
+
+		for &(px, py, pixel) in pixels {
+			if px < 0 || py < 0 || px >= width || py >= height {
+				continue;
+			}
+			let index = (py * width + px) as usize;
+			let coverage = mask.map_or(1.0, |mask| mask.data()[index] as f32 / 255.0);
+			let [r, g, b, a] = pixel.map(|channel| channel.clamp(0.0, 1.0) * coverage);
+			if a <= 0.0 {
+				continue;
+			}
+
+			let dst = target[index];
+			let blend = |src: f32, dst: u8| (src * 255.0 + dst as f32 * (1.0 - a)).round() as u8;
+			let alpha = blend(a, dst.alpha());
+			// Rounding can leave a color channel just above alpha
+			if let Some(blended) = tiny_skia::PremultipliedColorU8::from_rgba(
+				blend(r, dst.red()).min(alpha),
+				blend(g, dst.green()).min(alpha),
+				blend(b, dst.blue()).min(alpha),
+				alpha,
+			) {
+				target[index] = blended;
+			}
+		}
+	}
+
+	/// Convert a path to a tiny-skia path, if it has any segments
+	fn to_skia_path(path: &Path) -> Option<tiny_skia::Path> {
+		let mut builder = tiny_skia::PathBuilder::new();
+		for command in path.curves() {
+			match command {
+				PathCommand::MoveTo { x, y } => builder.move_to(x, y),
+				PathCommand::LineTo { x, y } => builder.line_to(x, y),
+				PathCommand::QuadTo { x1, y1, x, y } => builder.quad_to(x1, y1, x, y),
+				PathCommand::CubicTo {
+					x1,
+					y1,
+					x2,
+					y2,
+					x,
+					y,
+				} => builder.cubic_to(x1, y1, x2, y2, x, y),
+				PathCommand::Close => builder.close(),
+				PathCommand::Arc { .. } => unreachable!("arcs are converted to curves"),
+			}
+		}
+		builder.finish()
+	}
+
+	/// Convert a brush to a tiny-skia shader
+	///
+	/// Gradients tiny-skia cannot draw, such as those with no length, paint
+	/// a single color the way the software renderer does.
+	fn to_skia_shader(brush: &Brush) -> tiny_skia::Shader<'static> {
+		let stops = |stops: &[super::brush::GradientStop]| {
+			stops
+				.iter()
+				.map(|stop| {
+					tiny_skia::GradientStop::new(stop.offset, Self::to_skia_color(stop.color))
+				})
+				.collect::<Vec<_>>()
+		};
+		let shader = match brush {
+			Brush::Solid(color) => {
+				return tiny_skia::Shader::SolidColor(Self::to_skia_color(*color));
+			}
+			Brush::LinearGradient {
+				x1,
+				y1,
+				x2,
+				y2,
+				stops: gradient,
+			} => tiny_skia::LinearGradient::new(
+				tiny_skia::Point::from_xy(*x1, *y1),
+				tiny_skia::Point::from_xy(*x2, *y2),
+				stops(gradient),
+				tiny_skia::SpreadMode::Pad,
+				tiny_skia::Transform::identity(),
+			),
+			Brush::RadialGradient {
+				cx,
+				cy,
+				radius,
+				stops: gradient,
+			} => tiny_skia::RadialGradient::new(
+				tiny_skia::Point::from_xy(*cx, *cy),
+				tiny_skia::Point::from_xy(*cx, *cy),
+				*radius,
+				stops(gradient),
+				tiny_skia::SpreadMode::Pad,
+				tiny_skia::Transform::identity(),
+			),
+		};
+		shader.unwrap_or_else(|| {
+			tiny_skia::Shader::SolidColor(Self::to_skia_color(brush.color_at(0.0, 0.0)))
+		})
+	}
+
+	/// Create an anti-aliased paint for a brush
+	fn to_skia_paint(brush: &Brush) -> tiny_skia::Paint<'static> {
+		tiny_skia::Paint {
+			shader: Self::to_skia_shader(brush),
+			anti_alias: true,
+			..Default::default()
+		}
+	}
+
+	/// Convert a stroke style to a tiny-skia stroke
+	fn to_skia_stroke(stroke: &StrokeStyle) -> tiny_skia::Stroke {
+		tiny_skia::Stroke {
+			width: stroke.width,
+			miter_limit: stroke.miter_limit,
+			line_cap: match stroke.cap {
+				LineCap::Butt => tiny_skia::LineCap::Butt,
+				LineCap::Round => tiny_skia::LineCap::Round,
+				LineCap::Square => tiny_skia::LineCap::Square,
+			},
+			line_join: match stroke.join {
+				LineJoin::Miter => tiny_skia::LineJoin::Miter,
+				LineJoin::Round => tiny_skia::LineJoin::Round,
+				LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+			},
+			dash: if stroke.is_dashed() {
+				tiny_skia::StrokeDash::new(stroke.dash.clone(), stroke.dash_offset)
 			} else {
 				None
-			}
-		} else {
-			None
+			},
 		}
 	}
 
-	/// Execute a clear command
+	/// Execute a path fill command, within the current clip
+	fn execute_fill_path(&mut self, path: &Path, brush: &Brush, rule: FillRule) {
+		let Some(path) = Self::to_skia_path(path) else {
+			return;
+		};
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
+		if let Some(pixmap) = self.pixmap.as_mut() {
+			let rule = match rule {
+				FillRule::NonZero => tiny_skia::FillRule::Winding,
+				FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
+			};
+			pixmap.fill_path(&path, &Self::to_skia_paint(brush), rule, transform, mask);
+		}
+	}
+
+	/// Execute a path stroke command, within the current clip
+	fn execute_stroke_path(&mut self, path: &Path, brush: &Brush, stroke: &StrokeStyle) {
+		if stroke.width <= 0.0 {
+			return;
+		}
+		let Some(path) = Self::to_skia_path(path) else {
+			return;
+		};
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
+		if let Some(pixmap) = self.pixmap.as_mut() {
+			pixmap.stroke_path(
+				&path,
+				&Self::to_skia_paint(brush),
+				&Self::to_skia_stroke(stroke),
+				transform,
+				mask,
+			);
+		}
+	}
+
+	/// Execute a clear command, over only the damaged rectangles if any
 	fn execute_clear(&mut self, color: Color) {
-		if let Some(ref mut pixmap) = self.pixmap {
+		let Some(ref mut pixmap) = self.pixmap else {
+			return;
+		};
+		let Some(damage) = self.damage.as_ref() else {
 			pixmap.fill(Self::to_skia_color(color));
+			return;
+		};
+		let mut paint = tiny_skia::Paint {
+			blend_mode: tiny_skia::BlendMode::Source,
+			anti_alias: false,
+			..Default::default()
+		};
+		paint.set_color(Self::to_skia_color(color));
+		for rect in damage.rects() {
+			if let Some(rect) = Self::to_skia_rect((*rect).into()) {
+				pixmap.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+			}
 		}
 	}
 
 	/// Execute a filled rectangle command
 	fn execute_fill_rect(&mut self, rect: Rect, color: Color) {
-		if let Some(skia_rect) = Self::to_skia_rect(rect) {
-			if let Some(pixmap) = self.pixmap.as_mut() {
-				let mut paint = tiny_skia::Paint::default();
-				paint.set_color(Self::to_skia_color(color));
-				paint.anti_alias = true;
-
-				let path = tiny_skia::PathBuilder::from_rect(skia_rect);
-
-				pixmap.fill_path(
-					&path,
-					&paint,
-					tiny_skia::FillRule::Winding,
-					tiny_skia::Transform::identity(),
-					None,
-				);
-			}
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
+		if let Some(skia_rect) = Self::to_skia_rect(rect)
+			&& let Some(pixmap) = self.pixmap.as_mut()
+		{
+			let mut paint = tiny_skia::Paint::default();
+			paint.set_color(Self::to_skia_color(color));
+			paint.anti_alias = true;
+
+			let path = tiny_skia::PathBuilder::from_rect(skia_rect);
+
+			pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, transform, mask);
 		}
 	}
 
 	/// Execute a stroked rectangle command
 	fn execute_stroke_rect(&mut self, rect: Rect, color: Color, width: f32) {
-		if let Some(skia_rect) = Self::to_skia_rect(rect) {
-			if let Some(pixmap) = self.pixmap.as_mut() {
-				let mut paint = tiny_skia::Paint::default();
-				paint.set_color(Self::to_skia_color(color));
-				paint.anti_alias = true;
-
-				let mut stroke = tiny_skia::Stroke::default();
-				stroke.width = width;
-
-				let path = tiny_skia::PathBuilder::from_rect(skia_rect);
-
-				pixmap.stroke_path(
-					&path,
-					&paint,
-					&stroke,
-					tiny_skia::Transform::identity(),
-					None,
-				);
-			}
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
+		if let Some(skia_rect) = Self::to_skia_rect(rect)
+			&& let Some(pixmap) = self.pixmap.as_mut()
+		{
+			let mut paint = tiny_skia::Paint::default();
+			paint.set_color(Self::to_skia_color(color));
+			paint.anti_alias = true;
+
+			let stroke = tiny_skia::Stroke {
+				width,
+				..Default::default()
+			};
+
+			let path = tiny_skia::PathBuilder::from_rect(skia_rect);
+
+			pixmap.stroke_path(&path, &paint, &stroke, transform, mask);
 		}
 	}
 
 	/// Execute a circle command
 	fn execute_circle(&mut self, x: f32, y: f32, radius: f32, color: Color, filled: bool) {
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
 		if let Some(ref mut pixmap) = self.pixmap {
 			let mut paint = tiny_skia::Paint::default();
 			paint.set_color(Self::to_skia_color(color));
@@ -153,50 +437,272 @@ impl TinySkiaRenderContext {
 
 			if let Some(path) = tiny_skia::PathBuilder::from_circle(x, y, radius) {
 				if filled {
-					pixmap.fill_path(
-						&path,
-						&paint,
-						tiny_skia::FillRule::Winding,
-						tiny_skia::Transform::identity(),
-						None,
-					);
+					pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, transform, mask);
 				} else {
-					let mut stroke = tiny_skia::Stroke::default();
-					stroke.width = 1.0;
-					pixmap.stroke_path(
-						&path,
-						&paint,
-						&stroke,
-						tiny_skia::Transform::identity(),
-						None,
-					);
+					let stroke = tiny_skia::Stroke {
+						width: 1.0,
+						..Default::default()
+					};
+					pixmap.stroke_path(&path, &paint, &stroke, transform, mask);
 				}
 			}
 		}
 	}
 
+	/// Execute a text command
+	///
+	/// Glyph coverage is blended over the pixmap through the current
+	/// transform, skipping pixels outside the current clip.
+	fn execute_text(
+		&mut self,
+		text: &str,
+		x: f32,
+		y: f32,
+		font_size: f32,
+		color: Color,
+		align: TextAlign,
+	) {
+		let x = match align {
+			TextAlign::Left => x,
+			TextAlign::Center => x - self.text.measure(text, font_size) / 2.0,
+			TextAlign::Right => x - self.text.measure(text, font_size),
+		};
+		let alpha = color.a.clamp(0.0, 1.0);
+
+		let mut glyphs = Vec::new();
+		self.text.draw(text, x, y, font_size, |px, py, pixel| {
+			let [r, g, b, a] = match pixel {
+				TextPixel::Coverage(coverage) => [
+					color.r.clamp(0.0, 1.0),
+					color.g.clamp(0.0, 1.0),
+					color.b.clamp(0.0, 1.0),
+					coverage as f32 / 255.0,
+				],
+				// Color glyphs keep their own color but fade with the text
+				TextPixel::Color(rgba) => rgba.map(|channel| channel as f32 / 255.0),
+			};
+			let a = a * alpha;
+			glyphs.push((px, py, [r * a, g * a, b * a, a]));
+		});
+
+		let mut pixels = Vec::with_capacity(glyphs.len());
+		transform::transform_pixels(
+			&glyphs,
+			&self.transform(),
+			self.width,
+			self.height,
+			|px, py, pixel| pixels.push((px, py, pixel)),
+		);
+		self.blend_pixels(&pixels);
+	}
+
+	/// Execute a shadow command
+	///
+	/// The blurred coverage of the shadow is blended over the pixmap,
+	/// skipping pixels outside the current clip.
+	#[allow(clippy::too_many_arguments)]
+	fn execute_shadow(
+		&mut self,
+		rect: Rect,
+		radii: CornerRadii,
+		color: Color,
+		offset_x: f32,
+		offset_y: f32,
+		blur_radius: f32,
+		spread: f32,
+	) {
+		let Some(mask) = Mask::shadow(
+			rect,
+			radii,
+			&self.transform(),
+			offset_x,
+			offset_y,
+			blur_radius,
+			spread,
+			self.width,
+			self.height,
+		) else {
+			return;
+		};
+
+		let [r, g, b, a] = [color.r, color.g, color.b, color.a].map(|c| c.clamp(0.0, 1.0));
+		let mut pixels = Vec::with_capacity(mask.coverage.len());
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				let a = a * mask.at(column, row);
+				if a > 0.0 {
+					let (px, py) = (mask.x + column as i32, mask.y + row as i32);
+					pixels.push((px, py, [r * a, g * a, b * a, a]));
+				}
+			}
+		}
+		self.blend_pixels(&pixels);
+	}
+
+	/// Execute an image command, within the current clip
+	fn execute_image(&mut self, command: &ImageCommand) {
+		let mut pixels = Vec::new();
+		image::rasterize(
+			command,
+			&self.transform(),
+			self.width,
+			self.height,
+			|x, y, pixel| pixels.push((x, y, pixel)),
+		);
+		self.blend_pixels(&pixels);
+	}
+
+	/// Execute an SVG command, within the current clip
+	fn execute_svg(&mut self, document: &Arc<SvgDocument>, rect: Rect, tint: Option<Color>) {
+		let surface = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+		let visible = match self.clip_stack.last() {
+			Some((clip, transform)) => transform.map_rect(*clip).intersection(&surface),
+			None => Some(surface),
+		};
+		let Some(visible) = visible else {
+			return;
+		};
+		let transform = self.transform();
+		let mut pixels = Vec::new();
+		image::rasterize_svg(
+			document,
+			rect,
+			tint,
+			&transform,
+			visible,
+			&mut self.svg_cache,
+			|x, y, pixel| pixels.push((x, y, pixel)),
+		);
+		self.blend_pixels(&pixels);
+	}
+
+	/// Execute a backdrop blur command, within the current clip
+	fn execute_backdrop_blur(&mut self, rect: Rect, radii: CornerRadii, blur_radius: f32) {
+		let transform = self.transform();
+		let blur_radius = blur_radius * transform.scale_factor();
+		let padding = effects::blur_extent(blur_radius);
+		let Some(mut mask) =
+			Mask::rounded_rect(rect, radii, &transform, padding, self.width, self.height)
+		else {
+			return;
+		};
+		let width = self.width as usize;
+
+		// Only the part of the shape inside the clip is blurred
+		if let Some(clip) = self.clip_mask.as_ref() {
+			for row in 0..mask.height {
+				for column in 0..mask.width {
+					let index = (mask.y as usize + row) * width + mask.x as usize + column;
+					mask.coverage[row * mask.width + column] *= clip.data()[index] as f32 / 255.0;
+				}
+			}
+		}
+		let Some(pixmap) = self.pixmap.as_mut() else {
+			return;
+		};
+		let pixels = pixmap.pixels_mut();
+		let index =
+			|column: usize, row: usize| (mask.y as usize + row) * width + mask.x as usize + column;
+
+		// The pixmap is already premultiplied, as the blur needs
+		let mut region = Vec::with_capacity(mask.width * mask.height);
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				let pixel = pixels[index(column, row)];
+				region.push(
+					[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+						.map(|channel| channel as f32 / 255.0),
+				);
+			}
+		}
+		mask.blur_backdrop(&mut region, blur_radius);
+
+		for row in 0..mask.height {
+			for column in 0..mask.width {
+				if mask.at(column, row) <= 0.0 {
+					continue;
+				}
+				let [r, g, b, a] = region[row * mask.width + column]
+					.map(|channel| (channel * 255.0).round() as u8);
+				// Rounding can leave a color channel just above alpha
+				if let Some(blurred) =
+					tiny_skia::PremultipliedColorU8::from_rgba(r.min(a), g.min(a), b.min(a), a)
+				{
+					pixels[index(column, row)] = blurred;
+				}
+			}
+		}
+	}
+
+	/// Start drawing into a transparent layer
+	fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+		let layer = tiny_skia::Pixmap::new(self.width, self.height);
+		let below = std::mem::replace(&mut self.pixmap, layer);
+		self.layer_stack.push(Layer {
+			below,
+			opacity,
+			blend_mode,
+		});
+	}
+
+	/// Composite the current layer onto the pixmap below it
+	fn pop_layer(&mut self) {
+		let Some(layer) = self.layer_stack.pop() else {
+			return;
+		};
+		let content = std::mem::replace(&mut self.pixmap, layer.below);
+		if let (Some(content), Some(pixmap)) = (content, self.pixmap.as_mut()) {
+			let paint = tiny_skia::PixmapPaint {
+				opacity: layer.opacity.clamp(0.0, 1.0),
+				blend_mode: Self::to_skia_blend_mode(layer.blend_mode),
+				quality: tiny_skia::FilterQuality::Nearest,
+			};
+			pixmap.draw_pixmap(
+				0,
+				0,
+				content.as_ref(),
+				&paint,
+				tiny_skia::Transform::identity(),
+				None,
+			);
+		}
+	}
+
+	/// Convert a blend mode to tiny-skia's
+	fn to_skia_blend_mode(mode: BlendMode) -> tiny_skia::BlendMode {
+		match mode {
+			BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
+			BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+			BlendMode::Screen => tiny_skia::BlendMode::Screen,
+			BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+			BlendMode::Darken => tiny_skia::BlendMode::Darken,
+			BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+			BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+			BlendMode::Difference => tiny_skia::BlendMode::Difference,
+			BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+		}
+	}
+
 	/// Execute a line command
 	fn execute_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color, width: f32) {
+		let mask = self.clip_mask.as_ref();
+		let transform = self.skia_transform();
 		if let Some(ref mut pixmap) = self.pixmap {
 			let mut paint = tiny_skia::Paint::default();
 			paint.set_color(Self::to_skia_color(color));
 			paint.anti_alias = true;
 
-			let mut stroke = tiny_skia::Stroke::default();
-			stroke.width = width;
+			let stroke = tiny_skia::Stroke {
+				width,
+				..Default::default()
+			};
 
 			let mut pb = tiny_skia::PathBuilder::new();
 			pb.move_to(x1, y1);
 			pb.line_to(x2, y2);
 
 			if let Some(path) = pb.finish() {
-				pixmap.stroke_path(
-					&path,
-					&paint,
-					&stroke,
-					tiny_skia::Transform::identity(),
-					None,
-				);
+				pixmap.stroke_path(&path, &paint, &stroke, transform, mask);
 			}
 		}
 	}
@@ -233,15 +739,93 @@ impl RenderContext for TinySkiaRenderContext {
 			} => {
 				self.execute_line(x1, y1, x2, y2, color, width);
 			}
-			RenderCommand::Text { .. } => {
-				// Text rendering would require font rasterization
-				// This is a placeholder for future implementation
+			RenderCommand::Text {
+				text,
+				x,
+				y,
+				font_size,
+				color,
+				align,
+			} => {
+				self.execute_text(&text, x, y, font_size, color, align);
+			}
+			RenderCommand::FillRoundedRect { rect, radii, brush } => {
+				self.execute_fill_path(&Path::rounded_rect(rect, radii), &brush, FillRule::NonZero);
+			}
+			RenderCommand::StrokeRoundedRect {
+				rect,
+				radii,
+				brush,
+				width,
+			} => {
+				self.execute_stroke_path(
+					&Path::rounded_rect(rect, radii),
+					&brush,
+					&StrokeStyle::new(width),
+				);
+			}
+			RenderCommand::FillPath { path, brush, rule } => {
+				self.execute_fill_path(&path, &brush, rule);
+			}
+			RenderCommand::StrokePath {
+				path,
+				brush,
+				stroke,
+			} => {
+				self.execute_stroke_path(&path, &brush, &stroke);
+			}
+			RenderCommand::Shadow {
+				rect,
+				radii,
+				color,
+				offset_x,
+				offset_y,
+				blur_radius,
+				spread,
+			} => {
+				self.execute_shadow(rect, radii, color, offset_x, offset_y, blur_radius, spread);
+			}
+			RenderCommand::BackdropBlur {
+				rect,
+				radii,
+				blur_radius,
+			} => {
+				self.execute_backdrop_blur(rect, radii, blur_radius);
+			}
+			RenderCommand::Image(command) => {
+				self.execute_image(&command);
+			}
+			RenderCommand::Svg {
+				document,
+				rect,
+				tint,
+			} => {
+				self.execute_svg(&document, rect, tint);
 			}
 			RenderCommand::SetClip(rect) => {
-				self.clip_stack.push(rect);
+				let transform = self.transform();
+				self.clip_stack.push((rect, transform));
+				self.update_clip_mask();
 			}
 			RenderCommand::RestoreClip => {
 				self.clip_stack.pop();
+				self.update_clip_mask();
+			}
+			RenderCommand::PushTransform(transform) => {
+				let combined = self.transform().pre_concat(&transform);
+				self.transform_stack.push(combined);
+			}
+			RenderCommand::PopTransform => {
+				self.transform_stack.pop();
+			}
+			RenderCommand::PushLayer {
+				opacity,
+				blend_mode,
+			} => {
+				self.push_layer(opacity, blend_mode);
+			}
+			RenderCommand::PopLayer => {
+				self.pop_layer();
 			}
 		}
 	}
@@ -251,6 +835,9 @@ impl RenderContext for TinySkiaRenderContext {
 		if self.pixmap.is_none() {
 			self.pixmap = tiny_skia::Pixmap::new(self.width, self.height);
 		}
+		self.damage = None;
+		self.damage_mask = None;
+		self.update_clip_mask();
 	}
 
 	fn end_frame(&mut self) {
@@ -261,11 +848,96 @@ impl RenderContext for TinySkiaRenderContext {
 	fn size(&self) -> (u32, u32) {
 		(self.width, self.height)
 	}
+
+	fn set_damage(&mut self, damage: &DamageRegion) {
+		let damage = damage.snap_to_pixels(self.width, self.height);
+		self.damage_mask = tiny_skia::Mask::new(self.width, self.height).map(|mut mask| {
+			for rect in damage.rects() {
+				if let Some(rect) = Self::to_skia_rect((*rect).into()) {
+					mask.fill_path(
+						&tiny_skia::PathBuilder::from_rect(rect),
+						tiny_skia::FillRule::Winding,
+						false,
+						tiny_skia::Transform::identity(),
+					);
+				}
+			}
+			mask
+		});
+		self.damage = Some(damage);
+		self.update_clip_mask();
+	}
+
+	fn damage(&self) -> Option<&DamageRegion> {
+		self.damage.as_ref()
+	}
+
+	fn read_pixels(&self) -> Option<ImageData> {
+		let pixmap = self.pixmap.as_ref()?;
+		let data = pixmap
+			.pixels()
+			.iter()
+			.flat_map(|pixel| {
+				let color = pixel.demultiply();
+				[color.red(), color.green(), color.blue(), color.alpha()]
+			})
+			.collect();
+		Some(ImageData::new(
+			self.width,
+			self.height,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			data,
+		))
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::backends::brush::GradientStop;
+	use engage_ux_core::media::Font;
+
+	const TUFFY: &[u8] = include_bytes!("../../../assets/fonts/Tuffy.ttf");
+	const EMOJI: &[u8] = include_bytes!("../../../assets/fonts/EngageEmoji.ttf");
+
+	fn text_context(width: u32, height: u32) -> TinySkiaRenderContext {
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+		TinySkiaRenderContext::new(width, height)
+			.with_text(TextRasterizer::from_font(&font).unwrap())
+	}
+
+	/// Columns containing any opaque-enough pixel
+	fn inked_columns(context: &TinySkiaRenderContext) -> Vec<u32> {
+		let pixmap = context.pixmap().unwrap();
+		(0..pixmap.width())
+			.filter(|&x| (0..pixmap.height()).any(|y| pixmap.pixel(x, y).unwrap().alpha() > 128))
+			.collect()
+	}
+
+	/// A 2 by 1 image, red then blue
+	fn red_blue() -> std::sync::Arc<engage_ux_core::media::ImageData> {
+		use engage_ux_core::media::{ImageData, ImageFormat, image::ColorType};
+		std::sync::Arc::new(ImageData::new(
+			2,
+			1,
+			ImageFormat::Png,
+			ColorType::Rgb,
+			vec![255, 0, 0, 0, 0, 255],
+		))
+	}
+
+	/// A 10 by 10 SVG document with its left half filled red
+	fn half_red_svg() -> std::sync::Arc<engage_ux_core::rendering::SvgDocument> {
+		let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+			<rect width="5" height="10" fill="red"/>
+		</svg>"#;
+		std::sync::Arc::new(
+			engage_ux_core::rendering::SvgParser::new()
+				.parse(svg)
+				.unwrap(),
+		)
+	}
 
 	#[test]
 	fn test_tiny_skia_renderer_creation() {
@@ -350,4 +1022,462 @@ mod tests {
 		context.execute(RenderCommand::RestoreClip);
 		context.end_frame();
 	}
+
+	#[test]
+	fn test_tiny_skia_text() {
+		let mut context = text_context(100, 40);
+
+		context.execute(RenderCommand::Text {
+			text: "Hi".to_string(),
+			x: 10.0,
+			y: 30.0,
+			font_size: 20.0,
+			color: Color::rgb(0.0, 0.0, 1.0),
+			align: TextAlign::Left,
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		let inked = inked_columns(&context);
+		assert!(!inked.is_empty());
+		assert!(inked[0] >= 10 && inked[0] < 14);
+
+		// Nothing is drawn below the baseline for these glyphs
+		assert!((30..40).all(|y| (0..100).all(|x| pixmap.pixel(x, y).unwrap().alpha() == 0)));
+
+		// Coverage only scales the premultiplied blue
+		assert!(pixmap.pixels().iter().all(|pixel| {
+			pixel.red() == 0 && pixel.green() == 0 && pixel.blue() == pixel.alpha()
+		}));
+	}
+
+	#[test]
+	fn test_tiny_skia_text_alignment() {
+		let draw = |align| {
+			let mut context = text_context(200, 40);
+			context.execute(RenderCommand::Text {
+				text: "Wide".to_string(),
+				x: 100.0,
+				y: 30.0,
+				font_size: 20.0,
+				color: Color::rgb(0.0, 0.0, 0.0),
+				align,
+			});
+			inked_columns(&context)
+		};
+
+		let left = draw(TextAlign::Left);
+		let center = draw(TextAlign::Center);
+		let right = draw(TextAlign::Right);
+
+		assert!(left[0] >= 100);
+		assert!(*right.last().unwrap() <= 100);
+		assert!(center[0] < 100 && *center.last().unwrap() > 100);
+	}
+
+	#[test]
+	fn test_tiny_skia_text_clipped() {
+		let mut context = text_context(100, 40);
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 20.0, 40.0)));
+		context.execute(RenderCommand::Text {
+			text: "Clipped".to_string(),
+			x: 10.0,
+			y: 30.0,
+			font_size: 20.0,
+			color: Color::rgb(0.0, 0.0, 0.0),
+			align: TextAlign::Left,
+		});
+
+		assert!(inked_columns(&context).iter().all(|&x| x < 20));
+	}
+
+	#[test]
+	fn test_tiny_skia_color_emoji() {
+		let emoji = Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap();
+		let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0)
+			.unwrap()
+			.with_fallback_fonts([emoji]);
+		let mut context = TinySkiaRenderContext::new(100, 40)
+			.with_text(TextRasterizer::from_font(&font).unwrap());
+
+		context.execute(RenderCommand::Text {
+			text: "A\u{1F600}".to_string(),
+			x: 10.0,
+			y: 30.0,
+			font_size: 20.0,
+			color: Color::rgb(0.0, 0.0, 1.0),
+			align: TextAlign::Left,
+		});
+
+		// The emoji keeps its yellow while the letter is drawn in blue
+		let pixmap = context.pixmap().unwrap();
+		let yellow = pixmap
+			.pixels()
+			.iter()
+			.filter(|pixel| {
+				(pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()) == (255, 204, 0, 255)
+			})
+			.count();
+		let blue = pixmap
+			.pixels()
+			.iter()
+			.filter(|pixel| pixel.red() == 0 && pixel.blue() > 128)
+			.count();
+		assert!(yellow > 100);
+		assert!(blue > 10);
+	}
+
+	#[test]
+	fn test_tiny_skia_rounded_rect() {
+		let mut context = TinySkiaRenderContext::new(100, 100);
+		context.execute(RenderCommand::FillRoundedRect {
+			rect: Rect::new(10.0, 10.0, 80.0, 80.0),
+			radii: CornerRadii::new(20.0, 0.0, 20.0, 0.0),
+			brush: Brush::Solid(Color::rgb(1.0, 0.0, 0.0)),
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		let alpha = |x, y| pixmap.pixel(x, y).unwrap().alpha();
+		assert_eq!(alpha(50, 50), 255);
+		// Rounded corners are cut away, square ones are filled
+		assert_eq!(alpha(11, 11), 0);
+		assert_eq!(alpha(88, 88), 0);
+		assert_eq!(alpha(88, 11), 255);
+		assert_eq!(alpha(11, 88), 255);
+	}
+
+	#[test]
+	fn test_tiny_skia_linear_gradient() {
+		let mut context = TinySkiaRenderContext::new(100, 10);
+		context.execute(RenderCommand::FillPath {
+			path: Path::rect(Rect::new(0.0, 0.0, 100.0, 10.0)),
+			brush: Brush::linear(
+				0.0,
+				0.0,
+				100.0,
+				0.0,
+				vec![
+					GradientStop::new(0.0, Color::rgb(1.0, 0.0, 0.0)),
+					GradientStop::new(1.0, Color::rgb(0.0, 0.0, 1.0)),
+				],
+			),
+			rule: FillRule::NonZero,
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		let left = pixmap.pixel(2, 5).unwrap();
+		let middle = pixmap.pixel(50, 5).unwrap();
+		let right = pixmap.pixel(97, 5).unwrap();
+		assert!(left.red() > 240 && left.blue() < 15);
+		assert!(right.blue() > 240 && right.red() < 15);
+		assert!((118..=138).contains(&middle.red()));
+		assert!((118..=138).contains(&middle.blue()));
+	}
+
+	#[test]
+	fn test_tiny_skia_stroke_path() {
+		let mut context = TinySkiaRenderContext::new(40, 20);
+		context.execute(RenderCommand::StrokePath {
+			path: Path::new().move_to(0.0, 10.0).line_to(40.0, 10.0),
+			brush: Brush::Solid(Color::rgb(0.0, 0.0, 0.0)),
+			stroke: StrokeStyle::new(4.0).with_dash(vec![5.0, 5.0], 0.0),
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		assert_eq!(pixmap.pixel(2, 10).unwrap().alpha(), 255);
+		assert_eq!(pixmap.pixel(7, 10).unwrap().alpha(), 0);
+		assert_eq!(pixmap.pixel(12, 10).unwrap().alpha(), 255);
+		assert_eq!(pixmap.pixel(2, 5).unwrap().alpha(), 0);
+	}
+
+	#[test]
+	fn test_tiny_skia_path_clipped() {
+		let mut context = TinySkiaRenderContext::new(40, 40);
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 20.0, 40.0)));
+		context.execute(RenderCommand::FillPath {
+			path: Path::new().arc(20.0, 20.0, 15.0, 0.0, std::f32::consts::TAU),
+			brush: Brush::Solid(Color::rgb(0.0, 0.0, 0.0)),
+			rule: FillRule::EvenOdd,
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		assert_eq!(pixmap.pixel(15, 20).unwrap().alpha(), 255);
+		assert_eq!(pixmap.pixel(25, 20).unwrap().alpha(), 0);
+	}
+
+	#[test]
+	fn test_tiny_skia_shadow() {
+		let mut context = TinySkiaRenderContext::new(100, 100);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 100.0, 60.0)));
+		context.execute(RenderCommand::Shadow {
+			rect: Rect::new(20.0, 20.0, 40.0, 40.0),
+			radii: CornerRadii::uniform(4.0),
+			color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+			offset_x: 10.0,
+			offset_y: 10.0,
+			blur_radius: 8.0,
+			spread: 0.0,
+		});
+		context.execute(RenderCommand::RestoreClip);
+
+		let pixmap = context.pixmap().unwrap();
+		let red = |x: u32, y: u32| pixmap.pixel(x, y).unwrap().red();
+		assert!((126..=128).contains(&red(50, 50)));
+		assert!(red(30, 50) > red(50, 50) && red(30, 50) < 255);
+		assert_eq!(red(10, 50), 255);
+		// Clipped away below the clip rectangle
+		assert_eq!(red(50, 65), 255);
+	}
+
+	#[test]
+	fn test_tiny_skia_backdrop_blur() {
+		let mut context = TinySkiaRenderContext::new(40, 20);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 20.0, 20.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::BackdropBlur {
+			rect: Rect::new(10.0, 0.0, 20.0, 20.0),
+			radii: CornerRadii::default(),
+			blur_radius: 6.0,
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		let pixel = |x: u32| pixmap.pixel(x, 10).unwrap();
+		assert_eq!(pixel(19).alpha(), 255);
+		assert!(pixel(19).red() > 0 && pixel(19).red() < 128);
+		assert!(pixel(20).red() > 128 && pixel(20).red() < 255);
+		assert_eq!(pixel(5).red(), 0);
+		assert_eq!(pixel(35).red(), 255);
+	}
+
+	#[test]
+	fn test_tiny_skia_image() {
+		use crate::backends::image::{ImageCommand, ImageFilter, ImageFit};
+
+		let mut context = TinySkiaRenderContext::new(40, 10);
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 28.0, 10.0)));
+		context.execute(RenderCommand::Image(
+			ImageCommand::new(red_blue(), Rect::new(0.0, 0.0, 40.0, 10.0))
+				.with_fit(ImageFit::Contain)
+				.with_filter(ImageFilter::Nearest),
+		));
+		let pixmap = context.pixmap().unwrap();
+		let pixel = |x: u32| pixmap.pixel(x, 5).unwrap();
+		// Contained in the middle 20 pixels and clipped on the right
+		assert_eq!(pixel(5).alpha(), 0);
+		assert_eq!((pixel(12).red(), pixel(12).alpha()), (255, 255));
+		assert_eq!((pixel(25).blue(), pixel(25).alpha()), (255, 255));
+		assert_eq!(pixel(29).alpha(), 0);
+
+		// Tinting multiplies the colors and alpha
+		let mut context = TinySkiaRenderContext::new(40, 10);
+		context.execute(RenderCommand::Image(
+			ImageCommand::new(red_blue(), Rect::new(0.0, 0.0, 40.0, 10.0))
+				.with_tint(Color::rgba(1.0, 1.0, 1.0, 0.5)),
+		));
+		let pixel = context.pixmap().unwrap().pixel(0, 5).unwrap();
+		assert_eq!((pixel.red(), pixel.alpha()), (128, 128));
+	}
+
+	#[test]
+	fn test_tiny_skia_svg() {
+		let mut context = TinySkiaRenderContext::new(40, 40);
+		context.execute(RenderCommand::PushTransform(Transform::from_scale(
+			2.0, 2.0,
+		)));
+		context.execute(RenderCommand::Svg {
+			document: half_red_svg(),
+			rect: Rect::new(0.0, 0.0, 20.0, 20.0),
+			tint: Some(Color::rgb(0.0, 0.0, 1.0)),
+		});
+		let pixmap = context.pixmap().unwrap();
+		let pixel = |x: u32| pixmap.pixel(x, 20).unwrap();
+		// Rendered at the scaled size, so the edge stays sharp
+		assert_eq!((pixel(19).blue(), pixel(19).alpha()), (255, 255));
+		assert_eq!(pixel(19).red(), 0);
+		assert_eq!(pixel(20).alpha(), 0);
+	}
+
+	#[test]
+	fn test_tiny_skia_transform() {
+		let mut context = TinySkiaRenderContext::new(100, 100);
+		context.execute(RenderCommand::PushTransform(
+			Transform::from_translate(50.0, 50.0).rotate(std::f32::consts::FRAC_PI_4),
+		));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(-20.0, -20.0, 40.0, 40.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::PopTransform);
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 4.0, 4.0),
+			color: Color::rgb(0.0, 0.0, 1.0),
+		});
+
+		let pixmap = context.pixmap().unwrap();
+		let pixel = |x: u32, y: u32| pixmap.pixel(x, y).unwrap();
+		assert_eq!(pixel(50, 50).red(), 255);
+		assert_eq!(pixel(50, 25).red(), 255);
+		assert_eq!(pixel(32, 32).alpha(), 0);
+		assert_eq!(pixel(1, 1).blue(), 255);
+	}
+
+	#[test]
+	fn test_tiny_skia_transformed_clip() {
+		let mut context = TinySkiaRenderContext::new(100, 100);
+		context.execute(RenderCommand::PushTransform(Transform::from_rotate_at(
+			std::f32::consts::FRAC_PI_4,
+			50.0,
+			50.0,
+		)));
+		context.execute(RenderCommand::SetClip(Rect::new(30.0, 30.0, 40.0, 40.0)));
+		context.execute(RenderCommand::PopTransform);
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 100.0, 100.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::RestoreClip);
+
+		let pixmap = context.pixmap().unwrap();
+		let alpha = |x: u32, y: u32| pixmap.pixel(x, y).unwrap().alpha();
+		assert_eq!(alpha(50, 25), 255);
+		assert_eq!(alpha(32, 32), 0);
+		assert_eq!(alpha(68, 68), 0);
+	}
+
+	#[test]
+	fn test_tiny_skia_scaled_text() {
+		let ink = |transform: Transform| {
+			let mut context = text_context(200, 80);
+			context.execute(RenderCommand::PushTransform(transform));
+			context.execute(RenderCommand::Text {
+				text: "Hi".to_string(),
+				x: 10.0,
+				y: 30.0,
+				font_size: 16.0,
+				color: Color::rgb(0.0, 0.0, 0.0),
+				align: TextAlign::Left,
+			});
+			let pixmap = context.pixmap().unwrap();
+			pixmap
+				.pixels()
+				.iter()
+				.filter(|pixel| pixel.alpha() > 128)
+				.count()
+		};
+
+		let plain = ink(Transform::identity());
+		let scaled = ink(Transform::from_scale(2.0, 2.0));
+		assert!(scaled > plain * 3 && scaled < plain * 5);
+	}
+
+	#[test]
+	fn test_tiny_skia_layer_opacity() {
+		let mut context = TinySkiaRenderContext::new(40, 20);
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+		context.execute(RenderCommand::PushLayer {
+			opacity: 0.5,
+			blend_mode: BlendMode::Normal,
+		});
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 30.0, 20.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(10.0, 0.0, 30.0, 20.0),
+			color: Color::rgb(1.0, 0.0, 0.0),
+		});
+		context.execute(RenderCommand::PopLayer);
+
+		let pixmap = context.pixmap().unwrap();
+		let pixel = |x: u32| pixmap.pixel(x, 10).unwrap();
+		assert_eq!(pixel(5), pixel(20));
+		assert_eq!((pixel(20).alpha(), pixel(20).red()), (255, 255));
+		assert!((127..=128).contains(&pixel(20).green()));
+	}
+
+	#[test]
+	fn test_tiny_skia_layer_blend_mode() {
+		let mut context = TinySkiaRenderContext::new(10, 10);
+		context.execute(RenderCommand::Clear(Color::rgb(0.0, 1.0, 1.0)));
+		context.execute(RenderCommand::PushLayer {
+			opacity: 1.0,
+			blend_mode: BlendMode::Multiply,
+		});
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 5.0, 10.0),
+			color: Color::rgb(1.0, 1.0, 0.0),
+		});
+		context.execute(RenderCommand::PopLayer);
+
+		let pixmap = context.pixmap().unwrap();
+		let rgb = |x: u32| {
+			let pixel = pixmap.pixel(x, 5).unwrap();
+			(pixel.red(), pixel.green(), pixel.blue())
+		};
+		assert_eq!(rgb(2), (0, 255, 0));
+		assert_eq!(rgb(7), (0, 255, 255));
+	}
+
+	#[test]
+	fn test_tiny_skia_damage() {
+		let mut context = TinySkiaRenderContext::new(10, 10);
+		context.begin_frame();
+		context.execute(RenderCommand::Clear(Color::rgb(1.0, 0.0, 0.0)));
+		context.end_frame();
+
+		// Only the damaged pixels are cleared and drawn into
+		context.begin_frame();
+		context.set_damage(&DamageRegion::from_rect(
+			engage_ux_core::component::Rect::new(2.2, 2.0, 3.5, 3.0),
+		));
+		context.execute(RenderCommand::Clear(Color::rgb(0.0, 0.0, 1.0)));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 4.0, 10.0, 6.0),
+			color: Color::rgb(0.0, 1.0, 0.0),
+		});
+		context.end_frame();
+
+		assert_eq!(context.damage().unwrap().rects().len(), 1);
+		let pixmap = context.pixmap().unwrap();
+		let rgb = |x: u32, y: u32| {
+			let pixel = pixmap.pixel(x, y).unwrap();
+			(pixel.red(), pixel.green(), pixel.blue())
+		};
+		assert_eq!(rgb(2, 2), (0, 0, 255));
+		assert_eq!(rgb(5, 2), (0, 0, 255));
+		assert_eq!(rgb(3, 4), (0, 255, 0));
+		assert_eq!(rgb(6, 2), (255, 0, 0));
+		assert_eq!(rgb(3, 6), (255, 0, 0));
+
+		// A clip limits drawing further, and restoring it returns to the
+		// damaged region alone
+		context.begin_frame();
+		context.set_damage(&DamageRegion::from_rect(
+			engage_ux_core::component::Rect::new(0.0, 0.0, 5.0, 10.0),
+		));
+		context.execute(RenderCommand::SetClip(Rect::new(0.0, 0.0, 10.0, 2.0)));
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+			color: Color::rgb(1.0, 1.0, 1.0),
+		});
+		context.execute(RenderCommand::RestoreClip);
+		context.execute(RenderCommand::FillRect {
+			rect: Rect::new(0.0, 8.0, 10.0, 2.0),
+			color: Color::rgb(0.0, 0.0, 0.0),
+		});
+		context.end_frame();
+
+		let pixmap = context.pixmap().unwrap();
+		let rgb = |x: u32, y: u32| {
+			let pixel = pixmap.pixel(x, y).unwrap();
+			(pixel.red(), pixel.green(), pixel.blue())
+		};
+		assert_eq!(rgb(1, 1), (255, 255, 255));
+		assert_eq!(rgb(7, 1), (255, 0, 0));
+		assert_eq!(rgb(1, 9), (0, 0, 0));
+		assert_eq!(rgb(7, 9), (255, 0, 0));
+	}
 }
@@ -0,0 +1,367 @@
+//! Affine transforms for render commands
+//!
+//! A [`Transform`] maps a point `(x, y)` to
+//! `(sx * x + kx * y + tx, ky * x + sy * y + ty)`. Transforms pushed onto a
+//! render context apply in the local coordinates of the ones already
+//! pushed, as in the HTML canvas and other 2D drawing APIs.
+
+use super::renderer::Rect;
+use serde::{Deserialize, Serialize};
+
+/// A 2D affine transform
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+	/// Horizontal scale
+	pub sx: f32,
+	/// Vertical shear, how far y moves per unit of x
+	pub ky: f32,
+	/// Horizontal shear, how far x moves per unit of y
+	pub kx: f32,
+	/// Vertical scale
+	pub sy: f32,
+	/// Horizontal translation
+	pub tx: f32,
+	/// Vertical translation
+	pub ty: f32,
+}
+
+impl Transform {
+	/// The transform that leaves every point where it is
+	pub fn identity() -> Self {
+		Self::from_row(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+	}
+
+	/// Create a transform from its six coefficients
+	pub fn from_row(sx: f32, ky: f32, kx: f32, sy: f32, tx: f32, ty: f32) -> Self {
+		Self {
+			sx,
+			ky,
+			kx,
+			sy,
+			tx,
+			ty,
+		}
+	}
+
+	/// Move points by `(tx, ty)`
+	pub fn from_translate(tx: f32, ty: f32) -> Self {
+		Self::from_row(1.0, 0.0, 0.0, 1.0, tx, ty)
+	}
+
+	/// Scale points away from the origin
+	pub fn from_scale(sx: f32, sy: f32) -> Self {
+		Self::from_row(sx, 0.0, 0.0, sy, 0.0, 0.0)
+	}
+
+	/// Rotate points around the origin by `angle` radians, clockwise on
+	/// screen for positive angles
+	pub fn from_rotate(angle: f32) -> Self {
+		let (sin, cos) = angle.sin_cos();
+		Self::from_row(cos, sin, -sin, cos, 0.0, 0.0)
+	}
+
+	/// Rotate points around `(cx, cy)` by `angle` radians
+	pub fn from_rotate_at(angle: f32, cx: f32, cy: f32) -> Self {
+		Self::from_translate(cx, cy)
+			.rotate(angle)
+			.translate(-cx, -cy)
+	}
+
+	/// Shear points by the angles `ax` from the y axis and `ay` from the x
+	/// axis, in radians, like CSS `skew()`
+	pub fn from_skew(ax: f32, ay: f32) -> Self {
+		Self::from_row(1.0, ay.tan(), ax.tan(), 1.0, 0.0, 0.0)
+	}
+
+	/// Apply `first` before this transform
+	pub fn pre_concat(&self, first: &Transform) -> Self {
+		Self::from_row(
+			self.sx * first.sx + self.kx * first.ky,
+			self.ky * first.sx + self.sy * first.ky,
+			self.sx * first.kx + self.kx * first.sy,
+			self.ky * first.kx + self.sy * first.sy,
+			self.sx * first.tx + self.kx * first.ty + self.tx,
+			self.ky * first.tx + self.sy * first.ty + self.ty,
+		)
+	}
+
+	/// Apply a translation in this transform's local coordinates
+	pub fn translate(self, tx: f32, ty: f32) -> Self {
+		self.pre_concat(&Self::from_translate(tx, ty))
+	}
+
+	/// Apply a scale in this transform's local coordinates
+	pub fn scale(self, sx: f32, sy: f32) -> Self {
+		self.pre_concat(&Self::from_scale(sx, sy))
+	}
+
+	/// Apply a rotation in this transform's local coordinates
+	pub fn rotate(self, angle: f32) -> Self {
+		self.pre_concat(&Self::from_rotate(angle))
+	}
+
+	/// Apply a skew in this transform's local coordinates
+	pub fn skew(self, ax: f32, ay: f32) -> Self {
+		self.pre_concat(&Self::from_skew(ax, ay))
+	}
+
+	/// Map a point through the transform
+	pub fn map_point(&self, x: f32, y: f32) -> (f32, f32) {
+		(
+			self.sx * x + self.kx * y + self.tx,
+			self.ky * x + self.sy * y + self.ty,
+		)
+	}
+
+	/// Smallest axis-aligned rectangle holding a transformed rectangle
+	pub fn map_rect(&self, rect: Rect) -> Rect {
+		let corners = [
+			self.map_point(rect.x, rect.y),
+			self.map_point(rect.x + rect.width, rect.y),
+			self.map_point(rect.x + rect.width, rect.y + rect.height),
+			self.map_point(rect.x, rect.y + rect.height),
+		];
+		let (mut left, mut top) = corners[0];
+		let (mut right, mut bottom) = corners[0];
+		for (x, y) in corners {
+			left = left.min(x);
+			top = top.min(y);
+			right = right.max(x);
+			bottom = bottom.max(y);
+		}
+		Rect::new(left, top, right - left, bottom - top)
+	}
+
+	/// The transform that undoes this one, if it does not collapse the
+	/// plane onto a line or point
+	pub fn invert(&self) -> Option<Self> {
+		let determinant = self.determinant();
+		if determinant.abs() <= f32::EPSILON || !determinant.is_finite() {
+			return None;
+		}
+		let inverse = 1.0 / determinant;
+		Some(Self::from_row(
+			self.sy * inverse,
+			-self.ky * inverse,
+			-self.kx * inverse,
+			self.sx * inverse,
+			(self.kx * self.ty - self.sy * self.tx) * inverse,
+			(self.ky * self.tx - self.sx * self.ty) * inverse,
+		))
+	}
+
+	/// Factor by which the transform scales areas
+	pub fn determinant(&self) -> f32 {
+		self.sx * self.sy - self.kx * self.ky
+	}
+
+	/// Average factor by which the transform scales lengths
+	pub fn scale_factor(&self) -> f32 {
+		self.determinant().abs().sqrt()
+	}
+
+	/// Check whether the transform leaves every point where it is
+	pub fn is_identity(&self) -> bool {
+		*self == Self::identity()
+	}
+
+	/// Check whether the transform only moves points, without scaling,
+	/// rotating or shearing them
+	pub fn is_translate(&self) -> bool {
+		self.sx == 1.0 && self.ky == 0.0 && self.kx == 0.0 && self.sy == 1.0
+	}
+}
+
+impl Default for Transform {
+	fn default() -> Self {
+		Self::identity()
+	}
+}
+
+/// Draw pixels produced in untransformed device space through a transform
+/// onto a `width` by `height` surface
+///
+/// Pixels are premultiplied RGBA. Whole-pixel translations move them as
+/// they are; anything else resamples them bilinearly, so that scaled and
+/// rotated bitmaps such as text have no gaps.
+pub(crate) fn transform_pixels(
+	pixels: &[(i32, i32, [f32; 4])],
+	transform: &Transform,
+	width: u32,
+	height: u32,
+	mut plot: impl FnMut(i32, i32, [f32; 4]),
+) {
+	if transform.is_translate() && transform.tx.fract() == 0.0 && transform.ty.fract() == 0.0 {
+		let (tx, ty) = (transform.tx as i32, transform.ty as i32);
+		for &(x, y, pixel) in pixels {
+			plot(x + tx, y + ty, pixel);
+		}
+		return;
+	}
+	let Some(inverse) = transform.invert() else {
+		return;
+	};
+	let Some(&(first_x, first_y, _)) = pixels.first() else {
+		return;
+	};
+
+	let (mut left, mut top, mut right, mut bottom) = (first_x, first_y, first_x, first_y);
+	for &(x, y, _) in pixels {
+		left = left.min(x);
+		top = top.min(y);
+		right = right.max(x);
+		bottom = bottom.max(y);
+	}
+	let columns = (right - left + 1) as usize;
+	let rows = (bottom - top + 1) as usize;
+	let mut image = vec![[0.0; 4]; columns * rows];
+	for &(x, y, pixel) in pixels {
+		image[(y - top) as usize * columns + (x - left) as usize] = pixel;
+	}
+	let sample = |column: i32, row: i32| {
+		if column < 0 || row < 0 || column >= columns as i32 || row >= rows as i32 {
+			[0.0; 4]
+		} else {
+			image[row as usize * columns + column as usize]
+		}
+	};
+
+	let bounds = transform.map_rect(Rect::new(
+		left as f32,
+		top as f32,
+		columns as f32,
+		rows as f32,
+	));
+	let x_start = (bounds.x.floor() as i32).max(0);
+	let y_start = (bounds.y.floor() as i32).max(0);
+	let x_end = ((bounds.x + bounds.width).ceil() as i32).min(width as i32);
+	let y_end = ((bounds.y + bounds.height).ceil() as i32).min(height as i32);
+	for y in y_start..y_end {
+		for x in x_start..x_end {
+			let (u, v) = inverse.map_point(x as f32 + 0.5, y as f32 + 0.5);
+			let (u, v) = (u - left as f32 - 0.5, v - top as f32 - 0.5);
+			let (column, row) = (u.floor() as i32, v.floor() as i32);
+			let (fx, fy) = (u - column as f32, v - row as f32);
+
+			let mut pixel = [0.0; 4];
+			for (dx, dy, weight) in [
+				(0, 0, (1.0 - fx) * (1.0 - fy)),
+				(1, 0, fx * (1.0 - fy)),
+				(0, 1, (1.0 - fx) * fy),
+				(1, 1, fx * fy),
+			] {
+				let source = sample(column + dx, row + dy);
+				for (channel, value) in pixel.iter_mut().zip(source) {
+					*channel += value * weight;
+				}
+			}
+			if pixel[3] > 0.0 {
+				plot(x, y, pixel);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f32::consts::FRAC_PI_2;
+
+	fn assert_point(actual: (f32, f32), expected: (f32, f32)) {
+		assert!(
+			(actual.0 - expected.0).abs() < 1e-4 && (actual.1 - expected.1).abs() < 1e-4,
+			"{actual:?} != {expected:?}"
+		);
+	}
+
+	#[test]
+	fn test_basic_transforms() {
+		assert_point(Transform::identity().map_point(3.0, 4.0), (3.0, 4.0));
+		assert_point(
+			Transform::from_translate(10.0, 20.0).map_point(3.0, 4.0),
+			(13.0, 24.0),
+		);
+		assert_point(
+			Transform::from_scale(2.0, 3.0).map_point(3.0, 4.0),
+			(6.0, 12.0),
+		);
+		// Clockwise on screen, where y points down
+		assert_point(
+			Transform::from_rotate(FRAC_PI_2).map_point(1.0, 0.0),
+			(0.0, 1.0),
+		);
+		assert_point(
+			Transform::from_skew(std::f32::consts::FRAC_PI_4, 0.0).map_point(0.0, 2.0),
+			(2.0, 2.0),
+		);
+	}
+
+	#[test]
+	fn test_local_coordinates() {
+		// Translate, then scale in the translated coordinates
+		let transform = Transform::from_translate(10.0, 10.0).scale(2.0, 2.0);
+		assert_point(transform.map_point(5.0, 5.0), (20.0, 20.0));
+
+		let rotation = Transform::from_rotate_at(FRAC_PI_2, 50.0, 50.0);
+		assert_point(rotation.map_point(50.0, 50.0), (50.0, 50.0));
+		assert_point(rotation.map_point(60.0, 50.0), (50.0, 60.0));
+	}
+
+	#[test]
+	fn test_invert() {
+		let transform = Transform::from_translate(5.0, -3.0)
+			.rotate(0.7)
+			.scale(2.0, 0.5)
+			.skew(0.2, 0.1);
+		let inverse = transform.invert().unwrap();
+		let (x, y) = transform.map_point(12.0, 34.0);
+		assert_point(inverse.map_point(x, y), (12.0, 34.0));
+		assert_point(
+			inverse.pre_concat(&transform).map_point(1.0, 1.0),
+			(1.0, 1.0),
+		);
+
+		assert!(Transform::from_scale(0.0, 1.0).invert().is_none());
+	}
+
+	#[test]
+	fn test_map_rect() {
+		let rect = Transform::from_rotate_at(FRAC_PI_2, 0.0, 0.0)
+			.map_rect(Rect::new(0.0, 0.0, 20.0, 10.0));
+		assert!((rect.x + 10.0).abs() < 1e-4);
+		assert!((rect.width - 10.0).abs() < 1e-4);
+		assert!((rect.height - 20.0).abs() < 1e-4);
+	}
+
+	#[test]
+	fn test_transform_pixels() {
+		let pixels = [(0, 0, [1.0; 4]), (1, 0, [1.0; 4])];
+
+		let mut moved = Vec::new();
+		transform_pixels(
+			&pixels,
+			&Transform::from_translate(5.0, 2.0),
+			10,
+			10,
+			|x, y, _| moved.push((x, y)),
+		);
+		assert_eq!(moved, vec![(5, 2), (6, 2)]);
+
+		// Doubling the size fills the gaps between the original pixels
+		let mut scaled = Vec::new();
+		transform_pixels(
+			&pixels,
+			&Transform::from_scale(2.0, 2.0),
+			10,
+			10,
+			|x, y, pixel| {
+				if pixel[3] > 0.5 {
+					scaled.push((x, y));
+				}
+			},
+		);
+		assert!(scaled.contains(&(1, 1)));
+		assert!(scaled.contains(&(2, 0)));
+		assert!(!scaled.contains(&(0, 3)));
+	}
+}
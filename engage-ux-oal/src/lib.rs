@@ -23,6 +23,7 @@ pub use window::Window;
 #[cfg(test)]
 mod tests {
 	#[test]
+	#[allow(clippy::assertions_on_constants)]
 	fn oal_modules_exist() {
 		// Basic smoke test to ensure modules compile
 		assert!(true);
@@ -0,0 +1,263 @@
+//! Golden-image snapshot testing
+//!
+//! A snapshot renders commands or a component offscreen with the software
+//! renderer and the bundled Tuffy font, so the same goldens hold on every
+//! platform, and compares the frame with a PNG checked in under
+//! `engage-ux-tests/snapshots/`.
+//!
+//! Pixels whose channels all differ by no more than the tolerance match
+//! outright. Others are compared perceptually, by the weighted YIQ color
+//! distance used by pixelmatch, so differences the eye cannot see, such as
+//! rounding in anti-aliased edges, do not fail a test. On failure the actual
+//! frame is written to the test's temporary directory, with a diff image,
+//! differing pixels in red over a faded copy of the golden, when the sizes
+//! match.
+//!
+//! Run with `ENGAGE_UX_BLESS=1` to write the current frames as the goldens.
+
+use engage_ux_components::paint::Paint;
+use engage_ux_core::media::image::ColorType;
+use engage_ux_core::media::{Font, ImageData, ImageFormat};
+use engage_ux_core::rendering::TextRasterizer;
+use engage_ux_oal::backends::SoftbufferRenderContext;
+use engage_ux_oal::backends::renderer::{Color, RenderCommand, RenderContext};
+use engage_ux_themes::Theme;
+use std::path::PathBuf;
+
+/// Environment variable that re-blesses goldens instead of comparing
+pub const BLESS_VAR: &str = "ENGAGE_UX_BLESS";
+
+const TUFFY: &[u8] = include_bytes!("../assets/fonts/Tuffy.ttf");
+
+/// Largest YIQ distance, between black and white
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// Create an offscreen context that draws text with the bundled font
+pub fn context(width: u32, height: u32) -> SoftbufferRenderContext {
+	let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).expect("bundled font loads");
+	SoftbufferRenderContext::new(width, height)
+		.with_text(TextRasterizer::from_font(&font).expect("bundled font rasterizes"))
+}
+
+/// Result of comparing a frame with its golden
+pub struct Comparison {
+	/// Pixels that differ beyond the tolerance and threshold
+	pub differing: usize,
+	/// The golden faded to grey with differing pixels in red
+	pub diff: ImageData,
+}
+
+/// A named golden image and how closely frames must match it
+pub struct Snapshot {
+	name: String,
+	tolerance: u8,
+	threshold: f32,
+	max_differing: usize,
+}
+
+impl Snapshot {
+	/// Create a snapshot compared with `snapshots/<name>.png`
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			tolerance: 2,
+			threshold: 0.1,
+			max_differing: 0,
+		}
+	}
+
+	/// Set how far each channel may differ, out of 255, and still match
+	/// outright
+	pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+		self.tolerance = tolerance;
+		self
+	}
+
+	/// Set the perceptual threshold from 0.0 to 1.0; smaller is stricter
+	pub fn with_threshold(mut self, threshold: f32) -> Self {
+		self.threshold = threshold.clamp(0.0, 1.0);
+		self
+	}
+
+	/// Set how many pixels may differ before the snapshot fails
+	pub fn with_max_differing(mut self, max_differing: usize) -> Self {
+		self.max_differing = max_differing;
+		self
+	}
+
+	/// Render commands into a fresh `width` by `height` frame and compare it
+	pub fn assert_commands(&self, width: u32, height: u32, commands: &[RenderCommand]) {
+		let mut context = context(width, height);
+		context.begin_frame();
+		context.execute_batch(commands);
+		context.end_frame();
+		self.assert_matches(&context);
+	}
+
+	/// Paint a component over the theme's background and compare the frame
+	pub fn assert_component(&self, component: &dyn Paint, theme: &Theme, width: u32, height: u32) {
+		let mut context = context(width, height);
+		context.begin_frame();
+		context.execute(RenderCommand::Clear(Color::from(&theme.colors.background)));
+		component.render(theme, &mut context);
+		context.end_frame();
+		self.assert_matches(&context);
+	}
+
+	/// Compare what a context has drawn with the golden, or bless it
+	pub fn assert_matches(&self, context: &dyn RenderContext) {
+		let actual = context
+			.read_pixels()
+			.expect("snapshot contexts must support readback");
+		let golden_path = snapshot_dir().join(format!("{}.png", self.name));
+
+		if is_blessing() {
+			std::fs::create_dir_all(snapshot_dir()).expect("create snapshot directory");
+			actual
+				.save_png(golden_path.to_str().unwrap())
+				.expect("write golden");
+			return;
+		}
+
+		let golden = match ImageData::load_from_file(golden_path.to_str().unwrap()) {
+			Ok(golden) => golden,
+			Err(e) => panic!(
+				"No golden for snapshot `{}` at {} ({}); run with {}=1 to record it",
+				self.name,
+				golden_path.display(),
+				e,
+				BLESS_VAR
+			),
+		};
+		if (actual.width, actual.height) != (golden.width, golden.height) {
+			let actual_path = self.write_failure("actual", &actual);
+			panic!(
+				"Snapshot `{}` changed size from {}x{} to {}x{}\n  actual: {}\n\
+				 Run with {}=1 to re-bless it",
+				self.name,
+				golden.width,
+				golden.height,
+				actual.width,
+				actual.height,
+				actual_path.display(),
+				BLESS_VAR
+			);
+		}
+
+		let comparison = self.compare(&actual, &golden);
+		if comparison.differing > self.max_differing {
+			let actual_path = self.write_failure("actual", &actual);
+			let diff_path = self.write_failure("diff", &comparison.diff);
+			panic!(
+				"Snapshot `{}` differs from its golden in {} pixels (at most {} allowed)\n  \
+				 actual: {}\n  diff:   {}\nRun with {}=1 if the change is intended",
+				self.name,
+				comparison.differing,
+				self.max_differing,
+				actual_path.display(),
+				diff_path.display(),
+				BLESS_VAR
+			);
+		}
+	}
+
+	/// Where the `actual` frame or `diff` image of a failed comparison is
+	/// written
+	pub fn failure_path(&self, kind: &str) -> PathBuf {
+		PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+			.join("snapshots")
+			.join(format!("{}.{}.png", self.name, kind))
+	}
+
+	/// Write an image of a failed comparison, returning its path
+	fn write_failure(&self, kind: &str, image: &ImageData) -> PathBuf {
+		let path = self.failure_path(kind);
+		if let Some(dir) = path.parent() {
+			std::fs::create_dir_all(dir).expect("create diff directory");
+		}
+		image
+			.save_png(path.to_str().unwrap())
+			.unwrap_or_else(|e| panic!("write {} image: {}", kind, e));
+		path
+	}
+
+	/// Compare two RGBA frames of the same size
+	pub fn compare(&self, actual: &ImageData, golden: &ImageData) -> Comparison {
+		let max_delta = MAX_YIQ_DELTA * self.threshold * self.threshold;
+		let mut differing = 0;
+		let mut diff = Vec::with_capacity(golden.data.len());
+
+		for (a, g) in rgba_pixels(actual).zip(rgba_pixels(golden)) {
+			let within_tolerance = a
+				.iter()
+				.zip(g)
+				.all(|(a, g)| a.abs_diff(g) <= self.tolerance);
+			if within_tolerance || yiq_delta(a, g) <= max_delta {
+				// Faded grey copy of the golden for context
+				let [y, ..] = yiq(blend_white(g));
+				let faded = (255.0 - (255.0 - y) * 0.1) as u8;
+				diff.extend_from_slice(&[faded, faded, faded, 255]);
+			} else {
+				differing += 1;
+				diff.extend_from_slice(&[255, 0, 0, 255]);
+			}
+		}
+
+		Comparison {
+			differing,
+			diff: ImageData::new(
+				golden.width,
+				golden.height,
+				ImageFormat::Png,
+				ColorType::Rgba,
+				diff,
+			),
+		}
+	}
+}
+
+/// Directory holding the checked-in goldens
+fn snapshot_dir() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots")
+}
+
+/// Whether the bless variable is set to anything but empty or `0`
+fn is_blessing() -> bool {
+	std::env::var(BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Pixels of an image as RGBA, whatever its color type
+fn rgba_pixels(image: &ImageData) -> impl Iterator<Item = [u8; 4]> + '_ {
+	image
+		.data
+		.chunks_exact(image.bytes_per_pixel())
+		.map(|pixel| match pixel {
+			[l] => [*l, *l, *l, 255],
+			[r, g, b] => [*r, *g, *b, 255],
+			[r, g, b, a] => [*r, *g, *b, *a],
+			_ => unreachable!("pixels are one, three or four bytes"),
+		})
+}
+
+/// Composite a pixel over white, so transparency compares as lightness
+fn blend_white([r, g, b, a]: [u8; 4]) -> [f32; 3] {
+	let alpha = a as f32 / 255.0;
+	[r, g, b].map(|channel| 255.0 + (channel as f32 - 255.0) * alpha)
+}
+
+/// Convert RGB from 0 to 255 to YIQ
+fn yiq([r, g, b]: [f32; 3]) -> [f32; 3] {
+	[
+		r * 0.298_895_3 + g * 0.586_622_5 + b * 0.114_482_23,
+		r * 0.595_978 - g * 0.274_176_1 - b * 0.321_801_9,
+		r * 0.211_470_17 - g * 0.522_617_1 + b * 0.311_146_94,
+	]
+}
+
+/// Perceptual distance between two pixels, from 0 to [`MAX_YIQ_DELTA`]
+fn yiq_delta(a: [u8; 4], b: [u8; 4]) -> f32 {
+	let [y1, i1, q1] = yiq(blend_white(a));
+	let [y2, i2, q2] = yiq(blend_white(b));
+	let (y, i, q) = (y1 - y2, i1 - i2, q1 - q2);
+	0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
@@ -102,6 +102,7 @@ fn test_animation_with_easing() {
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_animation_sequence() {
 	// Test sequential animations with delays
 	let mut controller = AnimationController::new();
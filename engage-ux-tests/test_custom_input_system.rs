@@ -68,6 +68,7 @@ fn test_custom_input_event_creation() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_custom_input_data_types() {
 	let mut event = CustomInputEvent::new("device", "test");
 
@@ -354,6 +355,7 @@ fn test_input_handler_unified_interface() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_custom_input_value_types() {
 	let values = vec![
 		CustomInputValue::Bool(true),
@@ -232,6 +232,7 @@ fn test_e2e_window_resize_workflow() {
 
 /// Test platform-specific scaling factors
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_e2e_platform_scaling() {
 	let factory = get_backend_factory();
 	let window = factory.create_window_backend();
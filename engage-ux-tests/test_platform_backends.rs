@@ -292,6 +292,7 @@ fn test_platform_renderer_complex_scene() {
 }
 
 #[test]
+#[allow(clippy::overly_complex_bool_expr)]
 fn test_platform_renderer_hardware_acceleration_query() {
 	let factory = get_backend_factory();
 	let renderer = factory.create_renderer();
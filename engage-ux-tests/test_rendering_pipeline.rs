@@ -17,6 +17,7 @@ fn test_backend_factory_system() {
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_render_commands() {
 	let factory = get_backend_factory();
 	let mut renderer = factory.create_renderer();
@@ -205,6 +205,7 @@ fn test_screen_reader_live_regions() {
 }
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn test_screen_reader_complex_ui() {
 	let factory = get_backend_factory();
 	let mut screen_reader = factory.create_screen_reader();
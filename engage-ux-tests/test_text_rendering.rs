@@ -0,0 +1,140 @@
+//! Headless text rendering tests
+//!
+//! Paints components with captions into offscreen software render contexts
+//! and checks that glyphs land inside the component bounds.
+
+use engage_ux_components::paint::Paint;
+use engage_ux_components::{Button, Label, Text};
+use engage_ux_core::component::{Component, Rect};
+use engage_ux_core::media::font::{Font, FontRegistry, FontStyle, FontWeight};
+use engage_ux_core::rendering::TextRasterizer;
+use engage_ux_oal::backends::SoftbufferRenderContext;
+use engage_ux_oal::backends::renderer::{Color, RenderCommand, RenderContext};
+use engage_ux_themes::Theme;
+
+const TUFFY: &[u8] = include_bytes!("../assets/fonts/Tuffy.ttf");
+const EMOJI: &[u8] = include_bytes!("../assets/fonts/EngageEmoji.ttf");
+
+/// Rasterizer resolved through a font registry, as an application would
+fn rasterizer() -> TextRasterizer {
+	let font = Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap();
+	let mut registry = FontRegistry::new();
+	registry.register(font);
+
+	TextRasterizer::from_registry(&registry, "Tuffy", FontWeight::Normal, FontStyle::Normal)
+		.unwrap()
+}
+
+/// Pixels of an ARGB buffer that differ between two renders
+fn changed_pixels(a: &[u32], b: &[u32], width: usize) -> Vec<(usize, usize)> {
+	a.iter()
+		.zip(b)
+		.enumerate()
+		.filter(|(_, (a, b))| a != b)
+		.map(|(index, _)| (index % width, index / width))
+		.collect()
+}
+
+fn render_softbuffer(component: &dyn Paint, text: TextRasterizer) -> Vec<u32> {
+	let theme = Theme::light();
+	let mut context = SoftbufferRenderContext::new(200, 60).with_text(text);
+	context.begin_frame();
+	context.execute(RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)));
+	component.render(&theme, &mut context);
+	context.end_frame();
+	context.buffer().to_vec()
+}
+
+#[test]
+fn test_label_text_softbuffer() {
+	let mut label = Label::new(1, "Hello");
+	label.set_bounds(Rect::new(20.0, 10.0, 160.0, 40.0));
+
+	let with_text = render_softbuffer(&label, rasterizer());
+	let without_text = render_softbuffer(&label, TextRasterizer::new());
+	let inked = changed_pixels(&with_text, &without_text, 200);
+
+	assert!(!inked.is_empty());
+	assert!(
+		inked
+			.iter()
+			.all(|&(x, y)| (20..180).contains(&x) && (10..50).contains(&y))
+	);
+}
+
+#[test]
+fn test_button_caption_softbuffer() {
+	let mut button = Button::new(1, "OK");
+	button.set_bounds(Rect::new(50.0, 10.0, 100.0, 40.0));
+
+	let with_text = render_softbuffer(&button, rasterizer());
+	let without_text = render_softbuffer(&button, TextRasterizer::new());
+	let inked = changed_pixels(&with_text, &without_text, 200);
+
+	// The caption is centred on the button
+	let left = inked.iter().map(|&(x, _)| x).min().unwrap();
+	let right = inked.iter().map(|&(x, _)| x).max().unwrap();
+	assert!(left > 50 && right < 150);
+	assert!(((left + right) as f32 / 2.0 - 100.0).abs() <= 3.0);
+}
+
+#[test]
+fn test_text_emoji_fallback_softbuffer() {
+	let mut registry = FontRegistry::new();
+	registry.register(Font::load_from_bytes(TUFFY.to_vec(), 16.0).unwrap());
+	registry.register(Font::load_from_bytes(EMOJI.to_vec(), 16.0).unwrap());
+	registry.set_default_family("Tuffy");
+	let text =
+		TextRasterizer::from_registry(&registry, "Tuffy", FontWeight::Normal, FontStyle::Normal)
+			.unwrap();
+
+	let mut caption = Text::new(1, "Hi \u{1F600}");
+	let (width, height) = caption.measure(&registry);
+	caption.set_bounds(Rect::new(20.0, 10.0, width, height));
+	let pixels = render_softbuffer(&caption, text);
+
+	// Latin glyphs come from Tuffy and the emoji from the fallback font,
+	// within the measured bounds
+	let yellow: Vec<(usize, usize)> = pixels
+		.iter()
+		.enumerate()
+		.filter(|(_, pixel)| (*pixel & 0xFFFFFF) == 0xFFCC00)
+		.map(|(index, _)| (index % 200, index / 200))
+		.collect();
+	assert!(!yellow.is_empty());
+	assert!(
+		yellow
+			.iter()
+			.all(|&(x, _)| x >= 20 && (x as f32) < 20.0 + width)
+	);
+	let dark = pixels.iter().filter(|pixel| (*pixel & 0xFF) < 64).count();
+	assert!(dark > 0);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_label_text_tiny_skia() {
+	use engage_ux_oal::backends::TinySkiaRenderContext;
+
+	let theme = Theme::light();
+	let mut label = Label::new(1, "Hello");
+	label.set_bounds(Rect::new(20.0, 10.0, 160.0, 40.0));
+
+	let mut context = TinySkiaRenderContext::new(200, 60).with_text(rasterizer());
+	context.begin_frame();
+	label.render(&theme, &mut context);
+	context.end_frame();
+
+	let pixmap = context.pixmap().unwrap();
+	let inked: Vec<(u32, u32)> = (0..pixmap.width())
+		.flat_map(|x| (0..pixmap.height()).map(move |y| (x, y)))
+		.filter(|&(x, y)| pixmap.pixel(x, y).unwrap().alpha() > 0)
+		.collect();
+
+	assert!(inked.len() > 50);
+	assert!(
+		inked
+			.iter()
+			.all(|&(x, y)| (20..180).contains(&x) && (10..50).contains(&y))
+	);
+}
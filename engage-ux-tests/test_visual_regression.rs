@@ -1,53 +1,27 @@
 //! Visual regression tests for platform-specific rendering
 //!
 //! Tests that verify visual consistency across platforms and prevent
-//! unintended visual changes. Uses snapshot-based testing approach.
+//! unintended visual changes. Frames are compared with golden images in
+//! `snapshots/`; run with `ENGAGE_UX_BLESS=1` to re-bless them.
 
-use engage_ux_components::*;
+mod snapshot;
+
+use engage_ux_components::{Button, Card};
+use engage_ux_core::component::{Component, Rect as Bounds};
+use engage_ux_core::media::image::ColorType;
+use engage_ux_core::media::{ImageData, ImageFormat};
 use engage_ux_oal::backends::{
-	RenderCommand, get_backend_factory,
+	CornerRadii, RenderCommand, RenderContext,
 	renderer::{Color, Rect},
 };
 use engage_ux_themes::Theme;
-
-/// Helper to create a snapshot identifier for cross-platform comparison
-fn snapshot_id(test_name: &str, platform: &str) -> String {
-	format!("{}_{}", test_name, platform)
-}
-
-/// Get current platform identifier
-fn current_platform() -> &'static str {
-	#[cfg(target_os = "windows")]
-	return "windows";
-
-	#[cfg(target_os = "macos")]
-	return "macos";
-
-	#[cfg(target_os = "linux")]
-	return "linux";
-
-	#[cfg(target_os = "android")]
-	return "android";
-
-	#[cfg(target_os = "ios")]
-	return "ios";
-
-	#[cfg(not(any(
-		target_os = "windows",
-		target_os = "macos",
-		target_os = "linux",
-		target_os = "android",
-		target_os = "ios"
-	)))]
-	return "unknown";
-}
+use snapshot::Snapshot;
+use std::panic::AssertUnwindSafe;
 
 /// Test button rendering consistency
 #[test]
 fn test_visual_button_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 200);
+	let mut context = snapshot::context(400, 200);
 
 	context.begin_frame();
 
@@ -89,17 +63,13 @@ fn test_visual_button_rendering() {
 
 	context.end_frame();
 
-	// In a full implementation, would compare with stored snapshot
-	let snapshot = snapshot_id("button_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("button_rendering").assert_matches(&context);
 }
 
 /// Test text input rendering consistency
 #[test]
 fn test_visual_text_input_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 300);
+	let mut context = snapshot::context(400, 300);
 
 	context.begin_frame();
 
@@ -151,16 +121,13 @@ fn test_visual_text_input_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("text_input_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("text_input_rendering").assert_matches(&context);
 }
 
 /// Test checkbox rendering consistency
 #[test]
 fn test_visual_checkbox_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(300, 200);
+	let mut context = snapshot::context(300, 200);
 
 	context.begin_frame();
 
@@ -220,16 +187,13 @@ fn test_visual_checkbox_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("checkbox_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("checkbox_rendering").assert_matches(&context);
 }
 
 /// Test slider rendering consistency
 #[test]
 fn test_visual_slider_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 150);
+	let mut context = snapshot::context(400, 150);
 
 	context.begin_frame();
 
@@ -268,16 +232,13 @@ fn test_visual_slider_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("slider_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("slider_rendering").assert_matches(&context);
 }
 
 /// Test progress indicator rendering consistency
 #[test]
 fn test_visual_progress_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 100);
+	let mut context = snapshot::context(400, 100);
 
 	context.begin_frame();
 
@@ -305,27 +266,28 @@ fn test_visual_progress_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("progress_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("progress_rendering").assert_matches(&context);
 }
 
 /// Test card rendering consistency
 #[test]
 fn test_visual_card_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 300);
+	let mut context = snapshot::context(400, 300);
 
 	context.begin_frame();
 
 	// Background
 	context.execute(RenderCommand::Clear(Color::rgb(0.9, 0.9, 0.9)));
 
-	// Card with shadow (simulated)
-	// Shadow
-	context.execute(RenderCommand::FillRect {
-		rect: Rect::new(53.0, 53.0, 294.0, 194.0),
+	// Card shadow
+	context.execute(RenderCommand::Shadow {
+		rect: Rect::new(50.0, 50.0, 294.0, 194.0),
+		radii: CornerRadii::default(),
 		color: Color::rgba(0.0, 0.0, 0.0, 0.1),
+		offset_x: 0.0,
+		offset_y: 3.0,
+		blur_radius: 6.0,
+		spread: 0.0,
 	});
 
 	// Card background
@@ -349,16 +311,13 @@ fn test_visual_card_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("card_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("card_rendering").assert_matches(&context);
 }
 
 /// Test modal dialog rendering consistency
 #[test]
 fn test_visual_modal_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(800, 600);
+	let mut context = snapshot::context(800, 600);
 
 	context.begin_frame();
 
@@ -397,8 +356,7 @@ fn test_visual_modal_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("modal_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("modal_rendering").assert_matches(&context);
 }
 
 /// Test theme consistency across platforms
@@ -430,9 +388,7 @@ fn test_visual_theme_dark_consistency() {
 /// Test icon rendering consistency
 #[test]
 fn test_visual_icon_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(200, 200);
+	let mut context = snapshot::context(200, 200);
 
 	context.begin_frame();
 
@@ -488,16 +444,13 @@ fn test_visual_icon_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("icon_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("icon_rendering").assert_matches(&context);
 }
 
 /// Test dropdown rendering consistency
 #[test]
 fn test_visual_dropdown_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(300, 250);
+	let mut context = snapshot::context(300, 250);
 
 	context.begin_frame();
 
@@ -565,16 +518,13 @@ fn test_visual_dropdown_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("dropdown_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("dropdown_rendering").assert_matches(&context);
 }
 
 /// Test tab rendering consistency
 #[test]
 fn test_visual_tabs_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(600, 400);
+	let mut context = snapshot::context(600, 400);
 
 	context.begin_frame();
 
@@ -625,16 +575,13 @@ fn test_visual_tabs_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("tabs_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("tabs_rendering").assert_matches(&context);
 }
 
 /// Test toast notification rendering
 #[test]
 fn test_visual_toast_rendering() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(400, 150);
+	let mut context = snapshot::context(400, 150);
 
 	context.begin_frame();
 
@@ -683,16 +630,13 @@ fn test_visual_toast_rendering() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("toast_rendering", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("toast_rendering").assert_matches(&context);
 }
 
 /// Test border radius rendering consistency
 #[test]
 fn test_visual_rounded_corners() {
-	let factory = get_backend_factory();
-	let mut renderer = factory.create_renderer();
-	let mut context = renderer.create_context(300, 200);
+	let mut context = snapshot::context(300, 200);
 
 	context.begin_frame();
 
@@ -714,6 +658,111 @@ fn test_visual_rounded_corners() {
 
 	context.end_frame();
 
-	let snapshot = snapshot_id("rounded_corners", current_platform());
-	println!("Visual snapshot: {}", snapshot);
+	Snapshot::new("rounded_corners").assert_matches(&context);
+}
+
+/// Test a painted button, with its label, against its golden
+#[test]
+fn test_visual_button_component() {
+	let theme = Theme::light();
+	let mut button = Button::new(1, "Engage");
+	button.properties_mut().bounds = Bounds::new(20.0, 20.0, 160.0, 48.0);
+
+	Snapshot::new("button_component").assert_component(&button, &theme, 200, 88);
+}
+
+/// Test a painted card, with its shadow and title, against its golden
+#[test]
+fn test_visual_card_component() {
+	let theme = Theme::dark();
+	let mut card = Card::new(1).with_title("Status");
+	card.properties_mut().bounds = Bounds::new(24.0, 24.0, 240.0, 140.0);
+
+	// Blurred shadows and text edges may round differently across platforms
+	Snapshot::new("card_component")
+		.with_max_differing(16)
+		.assert_component(&card, &theme, 288, 200);
+}
+
+/// Test snapshots of command lists with anti-aliased shapes
+#[test]
+fn test_visual_rounded_path_commands() {
+	Snapshot::new("rounded_path_commands").assert_commands(
+		160,
+		100,
+		&[
+			RenderCommand::Clear(Color::rgb(1.0, 1.0, 1.0)),
+			RenderCommand::FillRoundedRect {
+				rect: Rect::new(20.0, 20.0, 120.0, 60.0),
+				radii: CornerRadii::uniform(16.0),
+				brush: Color::rgb(0.2, 0.4, 0.8).into(),
+			},
+		],
+	);
+}
+
+/// Test the snapshot comparison's tolerance, perceptual threshold and diff
+#[test]
+fn test_snapshot_comparison() {
+	let image = |pixels: &[[u8; 4]]| {
+		ImageData::new(
+			pixels.len() as u32,
+			1,
+			ImageFormat::Png,
+			ColorType::Rgba,
+			pixels.concat(),
+		)
+	};
+	let golden = image(&[[0, 0, 0, 255], [200, 40, 40, 255], [0, 0, 255, 0]]);
+	let snapshot = Snapshot::new("comparison");
+
+	assert_eq!(snapshot.compare(&golden, &golden).differing, 0);
+
+	// Within the per-channel tolerance, or imperceptibly different
+	let close = image(&[[2, 1, 0, 255], [204, 40, 44, 255], [255, 0, 0, 0]]);
+	assert_eq!(snapshot.compare(&close, &golden).differing, 0);
+
+	// A visibly different color, flagged red in the diff
+	let changed = image(&[[0, 0, 0, 255], [40, 40, 200, 255], [0, 0, 255, 0]]);
+	let comparison = snapshot.compare(&changed, &golden);
+	assert_eq!(comparison.differing, 1);
+	assert_eq!(comparison.diff.get_pixel(1, 0).unwrap(), &[255, 0, 0, 255]);
+	assert_ne!(comparison.diff.get_pixel(0, 0).unwrap(), &[255, 0, 0, 255]);
+
+	// A strict threshold catches even small perceptual differences
+	let strict = Snapshot::new("comparison")
+		.with_tolerance(0)
+		.with_threshold(0.0);
+	assert_eq!(strict.compare(&close, &golden).differing, 2);
+}
+
+/// Test that a frame of the wrong size is written out before failing
+#[test]
+fn test_snapshot_size_mismatch_writes_actual() {
+	if std::env::var(snapshot::BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0") {
+		// Blessing would replace the golden instead of comparing
+		return;
+	}
+	let snapshot = Snapshot::new("button_rendering");
+	let _ = std::fs::remove_file(snapshot.failure_path("actual"));
+
+	let mut context = snapshot::context(8, 8);
+	context.execute(RenderCommand::Clear(Color::rgb(1.0, 0.0, 0.0)));
+	let failure = std::panic::catch_unwind(AssertUnwindSafe(|| snapshot.assert_matches(&context)))
+		.unwrap_err();
+
+	let message = failure.downcast_ref::<String>().unwrap();
+	assert!(message.contains("changed size from 400x200 to 8x8"));
+	let actual =
+		ImageData::load_from_file(snapshot.failure_path("actual").to_str().unwrap()).unwrap();
+	assert_eq!((actual.width, actual.height), (8, 8));
+}
+
+/// Test that contexts used for snapshots read back what they drew
+#[test]
+fn test_snapshot_context_readback() {
+	let mut context = snapshot::context(4, 4);
+	context.execute(RenderCommand::Clear(Color::rgb(0.0, 1.0, 0.0)));
+	let pixels = context.read_pixels().unwrap();
+	assert_eq!(pixels.get_pixel(3, 3).unwrap(), &[0, 255, 0, 255]);
 }
@@ -3,8 +3,10 @@
 //! Provides JSON-based theme configuration and management.
 
 use engage_ux_core::color::Color;
+use engage_ux_core::media::FontRegistry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Theme definition with colors, fonts, and styling
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,9 @@ pub struct Theme {
 	/// Component-specific layouts mapped by component ID or name
 	#[serde(default)]
 	pub component_layouts: HashMap<String, engage_ux_core::layout::Layout>,
+	/// Fonts text is measured with, approximated when none are registered
+	#[serde(skip)]
+	pub fonts: Arc<FontRegistry>,
 }
 
 /// Color palette for a theme
@@ -179,6 +184,7 @@ impl Theme {
 				color: Color::from_hex("#6699FF33").unwrap(),
 			},
 			component_layouts: HashMap::new(),
+			fonts: Arc::default(),
 		}
 	}
 
@@ -284,6 +290,7 @@ impl Theme {
 				color: Color::from_hex("#6699FF44").unwrap(),
 			},
 			component_layouts: HashMap::new(),
+			fonts: Arc::default(),
 		}
 	}
 
@@ -331,6 +338,7 @@ impl Theme {
 				color: Color::rgb(0.0, 0.0, 0.0, 0.2),
 			},
 			component_layouts: HashMap::new(),
+			fonts: Arc::default(),
 		}
 	}
 
@@ -378,9 +386,19 @@ impl Theme {
 				color: Color::rgb(0.0, 0.0, 0.0, 0.4),
 			},
 			component_layouts: HashMap::new(),
+			fonts: Arc::default(),
 		}
 	}
 
+	/// Set the fonts text is measured with
+	///
+	/// Fonts are not part of the theme's JSON, so a loaded theme needs them
+	/// set again.
+	pub fn with_fonts(mut self, fonts: Arc<FontRegistry>) -> Self {
+		self.fonts = fonts;
+		self
+	}
+
 	/// Load theme from JSON string
 	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
 		serde_json::from_str(json)
@@ -430,6 +448,37 @@ mod tests {
 		assert_eq!(deserialized.name, theme.name);
 	}
 
+	#[test]
+	fn test_component_layout_grid() {
+		use engage_ux_core::layout::{GridItem, GridLayout, Layout, TrackSize};
+
+		let mut theme = Theme::light();
+		theme.component_layouts.insert(
+			"dashboard".to_string(),
+			Layout::new().with_grid(
+				GridLayout::new(TrackSize::parse_list("240px 1fr").unwrap())
+					.with_gap(8.0)
+					.with_areas(vec!["nav main".into()]),
+			),
+		);
+		theme.component_layouts.insert(
+			"sidebar".to_string(),
+			Layout::new().with_grid_item(GridItem::in_area("nav")),
+		);
+
+		let deserialized = Theme::from_json(&theme.to_json().unwrap()).unwrap();
+		let grid = deserialized.component_layouts["dashboard"]
+			.grid
+			.as_ref()
+			.unwrap();
+		assert_eq!(grid.columns[1], TrackSize::fr(1.0));
+		assert_eq!(grid.area("nav").unwrap().column, 0);
+		assert_eq!(
+			deserialized.component_layouts["sidebar"].grid_item,
+			Some(GridItem::in_area("nav"))
+		);
+	}
+
 	#[test]
 	fn test_spacing() {
 		let theme = Theme::default();